@@ -0,0 +1,44 @@
+use anyhow::{ anyhow, Result };
+
+use crate::cli::ui;
+use crate::cli::CacheAction;
+use axiom::implementations::llm::cache::{ ResponseCache, ResponseCacheConfig };
+
+/// LLM response cache maintenance command
+pub fn execute(action: &CacheAction, config: Option<&ResponseCacheConfig>) -> Result<()> {
+    let default_config = ResponseCacheConfig::default();
+    let cache = ResponseCache::from_config(config.unwrap_or(&default_config));
+
+    match action {
+        CacheAction::Stats => {
+            ui::print_header("LLM Response Cache");
+            let stats = cache.stats();
+
+            ui::print_result("Directory", &cache.dir().display().to_string());
+            ui::print_result("Entries", &stats.entries.to_string());
+            ui::print_result("Expired", &stats.expired.to_string());
+            ui::print_result("Size", &format_bytes(stats.total_bytes));
+            if let (Some(oldest), Some(newest)) = (stats.oldest, stats.newest) {
+                ui::print_result("Oldest", &oldest.to_rfc3339());
+                ui::print_result("Newest", &newest.to_rfc3339());
+            }
+        }
+        CacheAction::Clear { expired } => {
+            let removed = cache
+                .clear(*expired)
+                .map_err(|e| anyhow!("Failed to clear cache {}: {}", cache.dir().display(), e))?;
+            let what = if *expired { "expired cached responses" } else { "cached responses" };
+            ui::print_success(&format!("Removed {} {} from {}", removed, what, cache.dir().display()));
+        }
+    }
+
+    Ok(())
+}
+
+fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1024 * 1024 => format!("{:.1} MiB", (b as f64) / (1024.0 * 1024.0)),
+        b if b >= 1024 => format!("{:.1} KiB", (b as f64) / 1024.0),
+        b => format!("{} B", b),
+    }
+}
@@ -0,0 +1,100 @@
+use anyhow::{ anyhow, Result };
+use std::fs;
+use std::io::IsTerminal;
+use std::path::Path;
+
+use crate::cli::commands::spec::parse_domain;
+use crate::cli::ui;
+use crate::models::common::{ Domain, Language, VerificationSystem };
+use crate::project::{ Project, ProjectManifest, MANIFEST_FILE };
+
+/// Project scaffolding command
+///
+/// Settings that are not given are asked for when running in a terminal and take their
+/// defaults otherwise.
+pub fn execute(
+    directory: &Path,
+    language_str: Option<&str>,
+    verification_system_str: Option<&str>,
+    domain_str: Option<&str>
+) -> Result<()> {
+    ui::print_header("Initializing Axiom Project");
+
+    fs::create_dir_all(directory)?;
+    let root = directory.canonicalize()?;
+    let name = root
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| anyhow!("Cannot name a project after {}", root.display()))?;
+
+    let interactive = std::io::stdin().is_terminal();
+    let language = match language_str {
+        Some(language) => parse_language(language),
+        None if interactive => ui::select_language()?,
+        None => Language::Rust,
+    };
+    let domain = match domain_str {
+        Some(domain) => parse_domain(domain)?,
+        None if interactive => ui::select_domain()?,
+        None => Domain::HighAssuranceSoftware,
+    };
+    let verification_system = match verification_system_str {
+        Some(system) => Some(parse_verification_system(system)),
+        None if interactive => Some(ui::select_verification_system()?),
+        None => None,
+    };
+
+    let manifest = ProjectManifest::new(&name, language, domain, verification_system);
+    let project = Project::init(&root, manifest)?;
+
+    ui::print_result("Project", &project.manifest.project.name);
+    ui::print_result("Language", &format!("{:?}", project.manifest.project.language));
+    ui::print_result(
+        "Verification system",
+        &format!("{:?}", project.manifest.project.verification_system)
+    );
+    ui::print_result("Domain", &project.manifest.project.domain.to_string());
+    ui::print_success(&format!("Created {}", project.manifest_path().display()));
+    ui::print_info(
+        &format!(
+            "Add requirements to {} and run `axiom spec` from anywhere inside the project",
+            project.requirements_dir().display()
+        )
+    );
+    ui::print_info(&format!("LLM settings and tool paths can be set in {}", MANIFEST_FILE));
+
+    Ok(())
+}
+
+pub(crate) fn parse_language(language_str: &str) -> Language {
+    match language_str.to_lowercase().as_str() {
+        "rust" => Language::Rust,
+        "c" => Language::C,
+        "c++" | "cpp" => Language::CPlusPlus,
+        "python" | "py" => Language::Python,
+        "javascript" | "js" => Language::JavaScript,
+        "go" => Language::Go,
+        "haskell" | "hs" => Language::Haskell,
+        "ocaml" | "ml" => Language::OCaml,
+        "java" => Language::Java,
+        "csharp" | "c#" => Language::CSharp,
+        "scala" => Language::Scala,
+        "swift" => Language::Swift,
+        _ => Language::Custom(language_str.to_string()),
+    }
+}
+
+pub(crate) fn parse_verification_system(system_str: &str) -> VerificationSystem {
+    match system_str.to_lowercase().as_str() {
+        "fstar" | "f*" => VerificationSystem::FStar,
+        "dafny" => VerificationSystem::Dafny,
+        "coq" => VerificationSystem::Coq,
+        "isabelle" => VerificationSystem::Isabelle,
+        "lean" => VerificationSystem::Lean,
+        "tla" | "tla+" => VerificationSystem::TLA,
+        "why3" => VerificationSystem::Why3,
+        "z3" => VerificationSystem::Z3,
+        "frama-c" | "framac" => VerificationSystem::FramaC,
+        _ => VerificationSystem::Custom(system_str.to_string()),
+    }
+}
@@ -1,9 +1,10 @@
+pub mod cache;
 pub mod process;
-// pub mod init;
+pub mod init;
 pub mod spec;
 pub mod validate;
 // pub mod implement;
-// pub mod verify;
+pub mod verify;
 // pub mod translate;
 // pub mod list;
 // pub mod check;
@@ -8,16 +8,23 @@ use crate::models::common::{ Domain, Language, VerificationLanguage, Verificatio
 use crate::models::implementation::ImplementationOptions;
 use crate::models::specification::SpecificationOptions;
 use crate::models::verification::VerificationOptions;
+use crate::project::Project;
 use crate::traits::axiom_system::AxiomSystem;
 use crate::traits::specification_generator::ValidationDepth;
 
 /// Process command that runs the entire pipeline from requirements to verified implementation
+///
+/// Inside a project the requirements, languages, domain and verification system default to
+/// the project's, and without `--output` the specification, implementation and results are
+/// saved in its `specs/`, `impl/` and `proofs/` directories.
+#[allow(clippy::too_many_arguments)]
 pub async fn execute<S: AxiomSystem>(
     axiom: &S,
-    requirements_path: &Path,
-    language: Language,
-    domain: Domain,
-    output_dir: &Path,
+    project: Option<&Project>,
+    requirements_path: Option<&Path>,
+    language: Option<Language>,
+    domain: Option<Domain>,
+    output_dir: Option<&Path>,
     verification_system: Option<VerificationSystem>,
     verification_language: Option<VerificationLanguage>,
     interactive: bool
@@ -36,68 +43,116 @@ pub async fn execute<S: AxiomSystem>(
         ui::pause()?;
     }
 
-    // Ensure output directory exists
-    if !output_dir.exists() {
-        fs::create_dir_all(output_dir)?;
+    if let Some(project) = project {
+        ui::print_info(format!("Using project {}", project.manifest_path().display()).as_str());
+    }
+
+    let language = match (language, project) {
+        (Some(language), _) => language,
+        (None, Some(project)) => project.manifest.project.language.clone(),
+        (None, None) => return Err(anyhow!("--language is required outside an Axiom project")),
+    };
+    let domain = match (domain, project) {
+        (Some(domain), _) => domain,
+        (None, Some(project)) => project.manifest.project.domain.clone(),
+        (None, None) => return Err(anyhow!("--domain is required outside an Axiom project")),
+    };
+
+    // Directories for the specification, implementation and results, and the file stem
+    let (spec_dir, impl_dir, results_dir, stem) = match (output_dir, project) {
+        (Some(output_dir), _) => (
+            output_dir.to_path_buf(),
+            output_dir.to_path_buf(),
+            output_dir.to_path_buf(),
+            None,
+        ),
+        (None, Some(project)) => (
+            project.specs_dir(),
+            project.impl_dir(),
+            project.proofs_dir(),
+            Some(project.manifest.project.name.clone()),
+        ),
+        (None, None) => return Err(anyhow!("--output is required outside an Axiom project")),
+    };
+
+    // Ensure the output directories exist
+    for dir in [&spec_dir, &impl_dir, &results_dir] {
+        fs::create_dir_all(dir)?;
     }
 
     // Load requirements
     ui::print_header("Loading Requirements");
-    let requirements = match requirements_path.exists() {
-        true => {
-            let content = fs::read_to_string(requirements_path)?;
+    let requirements = match (requirements_path, project) {
+        (Some(path), _) if path.exists() => {
+            let content = fs::read_to_string(path)?;
             content
                 .lines()
                 .map(|s| s.trim().to_string())
                 .filter(|s| !s.is_empty())
                 .collect()
         }
-        false => {
-            if interactive {
-                ui::get_requirements()?
-            } else {
-                return Err(anyhow!("Requirements file not found: {:?}", requirements_path));
-            }
+        (None, Some(project)) => project.requirements()?,
+        _ if interactive => ui::get_requirements()?,
+        (Some(path), _) => return Err(anyhow!("Requirements file not found: {:?}", path)),
+        (None, None) => {
+            return Err(anyhow!("--requirements is required outside an Axiom project"));
         }
     };
 
     // Display the loaded requirements
     ui::print_info(format!("Loaded {} requirements:", requirements.len()).as_str());
     for (i, req) in requirements.iter().enumerate() {
-        println!("{}. {}", i + 1, req);
+        ui::print_line(&format!("{}. {}", i + 1, req));
     }
 
     if interactive {
         ui::pause()?;
     }
 
-    // Determine verification language
-    let verification_lang = if let Some(lang) = verification_language {
-        lang
-    } else if interactive {
-        ui::select_verification_language()?
-    } else {
-        // Default to F* if not specified
-        VerificationLanguage::FStarLang
+    // Determine the verification system; the specification is written in its language
+    let (verification_sys, verification_lang) = match (verification_system, verification_language) {
+        (Some(sys), Some(lang)) if sys.verification_language() != lang => {
+            return Err(
+                anyhow!("{} checks {} specifications, not {}", sys, sys.verification_language(), lang)
+            );
+        }
+        (Some(sys), _) => {
+            let lang = sys.verification_language();
+            (sys, lang)
+        }
+        (None, Some(lang)) => (lang.verification_system(), lang),
+        (None, None) => {
+            let sys = if let Some(project) = project {
+                project.manifest.project.verification_system.clone()
+            } else if interactive {
+                ui::select_verification_system()?
+            } else {
+                // Default to F* if not specified
+                VerificationSystem::FStar
+            };
+            let lang = sys.verification_language();
+            (sys, lang)
+        }
     };
 
-    // Determine verification system
-    let verification_sys = if let Some(sys) = verification_system {
-        sys
-    } else if interactive {
-        ui::select_verification_system()?
-    } else {
-        // Default to F* if not specified
-        VerificationSystem::FStar
-    };
+    if !axiom.is_verification_system_available(verification_sys.clone())? {
+        ui::print_warning(
+            format!(
+                "{} is not installed or has no registered backend; verification will fail.",
+                verification_sys
+            ).as_str()
+        );
+    }
 
     // Setup specification options
-    let mut spec_options = SpecificationOptions::default();
-    spec_options.verification_language = verification_lang.clone();
+    let spec_options = SpecificationOptions {
+        verification_language: verification_lang.clone(),
+        ..SpecificationOptions::default()
+    };
 
     if interactive {
         // Allow user to choose specification paradigm
-        let paradigm = ui::select_specification_paradigm()?;
+        let _paradigm = ui::select_specification_paradigm()?;
         // The specification options would be updated with the selected paradigm
         // This depends on the actual implementation of SpecificationOptions
     }
@@ -120,23 +175,11 @@ pub async fn execute<S: AxiomSystem>(
     ui::display_specification(&verification_lang, &formal_spec.spec_code);
 
     // Save the specification to a file
-    let spec_filename = match verification_lang {
-        VerificationLanguage::FStarLang => "specification.fst",
-        VerificationLanguage::DafnyLang => "specification.dfy",
-        VerificationLanguage::CoqLang => "specification.v",
-        VerificationLanguage::IsabelleLang => "specification.thy",
-        VerificationLanguage::LeanLang => "specification.lean",
-        VerificationLanguage::TLAPlus => "specification.tla",
-        VerificationLanguage::Why3Lang => "specification.why",
-        VerificationLanguage::Z3SMT => "specification.smt2",
-        VerificationLanguage::ACSL => "specification.c",
-        VerificationLanguage::JML => "specification.java",
-        VerificationLanguage::Liquid => "specification.hs",
-        VerificationLanguage::RustMIRAI => "specification.rs",
-        VerificationLanguage::Custom(_) => "specification.txt",
-    };
-
-    let spec_path = output_dir.join(spec_filename);
+    let spec_path = spec_dir.join(format!(
+        "{}.{}",
+        stem.as_deref().unwrap_or("specification"),
+        verification_lang.spec_file_extension()
+    ));
     fs::write(&spec_path, &formal_spec.spec_code)?;
 
     ui::print_success(format!("Specification saved to {}", spec_path.display()).as_str());
@@ -206,7 +249,7 @@ pub async fn execute<S: AxiomSystem>(
         style_guide: None,
     };
 
-    let implementation = axiom.generate_implementation_from_formal_spec(
+    let mut implementation = axiom.generate_implementation_from_formal_spec(
         &spec.formal_spec,
         language.clone(),
         &implementation_options
@@ -216,26 +259,30 @@ pub async fn execute<S: AxiomSystem>(
 
     // Display the implementation
     ui::print_info("Generated Implementation:");
-    println!("\n{}\n", implementation.source_code);
+    ui::print_line(&format!("\n{}\n", implementation.source_code));
 
     // Save the implementation to a file
-    let impl_filename = match language {
-        Language::Rust => "implementation.rs",
-        Language::C => "implementation.c",
-        Language::CPlusPlus => "implementation.cpp",
-        Language::Python => "implementation.py",
-        Language::JavaScript => "implementation.js",
-        Language::Go => "implementation.go",
-        Language::Haskell => "implementation.hs",
-        Language::OCaml => "implementation.ml",
-        Language::Java => "implementation.java",
-        Language::CSharp => "implementation.cs",
-        Language::Scala => "implementation.scala",
-        Language::Swift => "implementation.swift",
-        Language::Custom(_) => "implementation.txt",
+    let impl_extension = match language {
+        Language::Rust => "rs",
+        Language::C => "c",
+        Language::CPlusPlus => "cpp",
+        Language::Python => "py",
+        Language::JavaScript => "js",
+        Language::Go => "go",
+        Language::Haskell => "hs",
+        Language::OCaml => "ml",
+        Language::Java => "java",
+        Language::CSharp => "cs",
+        Language::Scala => "scala",
+        Language::Swift => "swift",
+        Language::Custom(_) => "txt",
     };
 
-    let impl_path = output_dir.join(impl_filename);
+    let impl_path = impl_dir.join(format!(
+        "{}.{}",
+        stem.as_deref().unwrap_or("implementation"),
+        impl_extension
+    ));
     fs::write(&impl_path, &implementation.source_code)?;
 
     ui::print_success(format!("Implementation saved to {}", impl_path.display()).as_str());
@@ -258,10 +305,11 @@ pub async fn execute<S: AxiomSystem>(
             max_verification_time: Duration::from_secs(600),
             max_proof_depth: None,
             parallel_jobs: None,
+            provers: vec![],
         },
     };
 
-    let verification_result = axiom.verify_against_formal_spec(
+    let mut verification_result = axiom.verify_against_formal_spec(
         &implementation,
         &spec.formal_spec,
         &verification_options
@@ -269,6 +317,26 @@ pub async fn execute<S: AxiomSystem>(
 
     spinner.finish();
 
+    // Feed failures, timeouts and errors back to the generator for a few rounds
+    if verification_result.status != crate::models::verification::VerificationStatus::Verified {
+        ui::print_verification_status(&verification_result.status);
+        ui::print_header("Refining Implementation");
+
+        let spinner = ui::spinner_with_message("Refining implementation with verifier feedback...");
+        let original_code = implementation.source_code.clone();
+        implementation.verification_result = verification_result;
+        implementation = axiom.refine_to_satisfy(&implementation, &spec)?;
+        verification_result = implementation.verification_result.clone();
+        spinner.finish();
+
+        if implementation.source_code != original_code {
+            fs::write(&impl_path, &implementation.source_code)?;
+            ui::print_success(
+                format!("Refined implementation saved to {}", impl_path.display()).as_str()
+            );
+        }
+    }
+
     // Display verification result
     ui::print_verification_status(&verification_result.status);
 
@@ -282,7 +350,7 @@ pub async fn execute<S: AxiomSystem>(
     }
 
     // Save verification results
-    let results_path = output_dir.join("verification_results.txt");
+    let results_path = results_dir.join("verification_results.txt");
     let results_content = format!(
         "Verification Results\n\
          ===================\n\
@@ -3,50 +3,93 @@ use std::fs;
 use std::path::Path;
 
 use crate::cli::ui;
-use crate::models::common::{Domain, VerificationLanguage, SpecificationParadigm};
+use crate::models::common::{Domain, VerificationLanguage};
+use crate::models::schema::to_document_json;
 use crate::models::specification::SpecificationOptions;
+use crate::project::Project;
 use crate::traits::axiom_system::AxiomSystem;
-use crate::traits::specification_generator::ValidationDepth;
 
 /// Specification generation command
+///
+/// Inside a project the requirements, domain and verification language default to the
+/// project's, and the specification is saved in its `specs/` directory. Outside one it is
+/// saved to `output_path`, with the other files next to it. Returns the
+/// specification as a JSON document, printed with `--output-format json`.
+#[allow(clippy::too_many_arguments)]
 pub async fn execute<S: AxiomSystem>(
     axiom: &S,
-    requirements_path: &Path,
-    verification_language_str: &str,
-    domain_str: &str,
+    project: Option<&Project>,
+    requirements_path: Option<&Path>,
+    verification_language_str: Option<&str>,
+    domain_str: Option<&str>,
     output_path: Option<&Path>,
     detail_level: &str,
-) -> Result<()> {
+    candidates: usize,
+) -> Result<String> {
     ui::print_header("Generating Formal Specification");
     
+    if let Some(project) = project {
+        ui::print_info(format!("Using project {}", project.manifest_path().display()).as_str());
+    }
+    
     // Parse verification language
-    let verification_language = parse_verification_language(verification_language_str)?;
+    let verification_language = match (verification_language_str, project) {
+        (Some(language), _) => parse_verification_language(language)?,
+        (None, Some(project)) => project.manifest.verification_language(),
+        (None, None) => VerificationLanguage::FStarLang,
+    };
     
     // Parse domain
-    let domain = parse_domain(domain_str)?;
+    let domain = match (domain_str, project) {
+        (Some(domain), _) => parse_domain(domain)?,
+        (None, Some(project)) => project.manifest.project.domain.clone(),
+        (None, None) => return Err(anyhow!("--domain is required outside an Axiom project")),
+    };
+    
+    // Save into the project's specs directory, or next to the output file outside a project
+    let (project_dir, stem) = match (project, output_path) {
+        (Some(project), _) => (project.specs_dir(), project.manifest.project.name.clone()),
+        (None, Some(output_path)) => (
+            output_path.parent().unwrap_or(Path::new("")).to_path_buf(),
+            output_path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "spec".to_string()),
+        ),
+        (None, None) => return Err(anyhow!("--output is required outside an Axiom project")),
+    };
     
     // Load requirements
     ui::print_info("Loading requirements...");
-    let requirements = match fs::read_to_string(requirements_path) {
-        Ok(content) => content.lines()
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty())
-            .collect::<Vec<String>>(),
-        Err(e) => return Err(anyhow!("Failed to read requirements file: {}", e)),
+    let requirements = match (requirements_path, project) {
+        (Some(path), _) => match fs::read_to_string(path) {
+            Ok(content) => content.lines()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<String>>(),
+            Err(e) => return Err(anyhow!("Failed to read requirements file: {}", e)),
+        },
+        (None, Some(project)) => project.requirements()?,
+        (None, None) => return Err(anyhow!("--requirements is required outside an Axiom project")),
     };
+    if requirements.is_empty() {
+        return Err(anyhow!("No requirements to generate a specification from"));
+    }
     
     ui::print_info(format!("Loaded {} requirements", requirements.len()).as_str());
     
     // Setup specification options
-    let mut spec_options = SpecificationOptions::default();
-    spec_options.verification_language = verification_language.clone();
-    
-    // Set detail level
-    spec_options.detail_level = match detail_level.to_lowercase().as_str() {
-        "minimal" => crate::models::specification::DetailLevel::Minimal,
-        "standard" => crate::models::specification::DetailLevel::Standard,
-        "comprehensive" => crate::models::specification::DetailLevel::Comprehensive,
-        _ => crate::models::specification::DetailLevel::Standard,
+    let spec_options = SpecificationOptions {
+        verification_language: verification_language.clone(),
+        // Set detail level
+        detail_level: match detail_level.to_lowercase().as_str() {
+            "minimal" => crate::models::specification::DetailLevel::Minimal,
+            "standard" => crate::models::specification::DetailLevel::Standard,
+            "comprehensive" => crate::models::specification::DetailLevel::Comprehensive,
+            _ => crate::models::specification::DetailLevel::Standard,
+        },
+        candidates: candidates.max(1),
+        ..SpecificationOptions::default()
     };
     
     // Generate specification
@@ -54,7 +97,7 @@ pub async fn execute<S: AxiomSystem>(
     
     let formal_spec = axiom.generate_formal_specification(
         &requirements,
-        domain,
+        domain.clone(),
         verification_language.clone(),
         &spec_options,
     )?;
@@ -64,40 +107,52 @@ pub async fn execute<S: AxiomSystem>(
     // Display the specification
     ui::display_specification(&verification_language, &formal_spec.spec_code);
     
-    // Create project directory structure
-    let domain_str = domain_str.replace(" ", "_").to_lowercase();
-    let timestamp = chrono::Utc::now().timestamp();
-    let project_name = format!("{}_{}", domain_str, timestamp);
-    let project_dir = std::path::Path::new("projects").join(&project_name);
+    let extension = verification_language.spec_file_extension();
     fs::create_dir_all(&project_dir)?;
     
-    // Determine file extension based on verification language
-    let extension = match verification_language {
-        VerificationLanguage::FStarLang => "fst",
-        VerificationLanguage::DafnyLang => "dfy",
-        VerificationLanguage::CoqLang => "v",
-        VerificationLanguage::IsabelleLang => "thy",
-        VerificationLanguage::LeanLang => "lean",
-        VerificationLanguage::TLAPlus => "tla",
-        VerificationLanguage::Why3Lang => "why",
-        VerificationLanguage::Z3SMT => "smt2",
-        _ => "txt",
-    };
-    
     // Save specification to project directory
-    let spec_path = project_dir.join(format!("spec.{}", extension));
+    let spec_path = match (project, output_path) {
+        (None, Some(output_path)) => output_path.to_path_buf(),
+        _ => project_dir.join(format!("{}.{}", stem, extension)),
+    };
     fs::write(&spec_path, &formal_spec.spec_code)?;
     ui::print_success(format!("Specification saved to {}", spec_path.display()).as_str());
     
     // Save the natural language description
     if let Some(description) = formal_spec.components.get("description") {
-        let description_path = project_dir.join("description.md");
+        let description_path = project_dir.join(format!("{}.md", stem));
         fs::write(&description_path, description)?;
         ui::print_success(format!("Description saved to {}", description_path.display()).as_str());
     }
     
-    // Save to output path if provided
-    if let Some(output_path) = output_path {
+    // Save the specification as a versioned JSON document for other tools
+    let document = to_document_json(&formal_spec)?;
+    fs::write(project_dir.join(format!("{}.json", stem)), &document)?;
+    
+    // Keep every candidate and its score next to the selected specification
+    let drawn = &formal_spec.candidates;
+    if !drawn.is_empty() {
+        let candidates_dir = project_dir.join("candidates");
+        fs::create_dir_all(&candidates_dir)?;
+        for candidate in drawn {
+            let candidate_path = candidates_dir.join(format!("candidate_{}.{}", candidate.index + 1, extension));
+            fs::write(&candidate_path, &candidate.spec_code)?;
+            let score = match &candidate.failure {
+                Some(failure) => format!("failed: {}", failure),
+                None => format!("{} errors, {} warnings", candidate.errors, candidate.warnings),
+            };
+            let marker = if candidate.selected { " (selected)" } else { "" };
+            ui::print_result(
+                &format!("Candidate {}", candidate.index + 1),
+                &format!("{} - {}{}", candidate.model, score, marker),
+            );
+        }
+        fs::write(candidates_dir.join("candidates.json"), serde_json::to_string_pretty(drawn)?)?;
+        ui::print_success(format!("Candidates saved to {}", candidates_dir.display()).as_str());
+    }
+    
+    // Inside a project, also save to the output path if provided
+    if let (Some(_), Some(output_path)) = (project, output_path) {
         fs::write(output_path, &formal_spec.spec_code)?;
         ui::print_success(format!("Specification also saved to {}", output_path.display()).as_str());
     }
@@ -107,10 +162,10 @@ pub async fn execute<S: AxiomSystem>(
     
     ui::print_success("Formal specification generation completed!");
     
-    Ok(())
+    Ok(document)
 }
 
-fn parse_verification_language(language_str: &str) -> Result<VerificationLanguage> {
+pub(crate) fn parse_verification_language(language_str: &str) -> Result<VerificationLanguage> {
     match language_str.to_lowercase().as_str() {
         "fstar" => Ok(VerificationLanguage::FStarLang),
         "dafny" => Ok(VerificationLanguage::DafnyLang),
@@ -128,7 +183,7 @@ fn parse_verification_language(language_str: &str) -> Result<VerificationLanguag
     }
 }
 
-fn parse_domain(domain_str: &str) -> Result<Domain> {
+pub(crate) fn parse_domain(domain_str: &str) -> Result<Domain> {
     match domain_str.to_lowercase().as_str() {
         "crypto" | "cryptography" => Ok(Domain::Cryptography),
         "distributed" | "distributedsystems" => Ok(Domain::DistributedSystems),
@@ -4,65 +4,47 @@ use std::path::Path;
 
 use crate::cli::ui;
 use crate::models::common::Domain;
+use crate::models::schema::to_document_json;
 use crate::models::specification::{ FormalSpecification, Specification, SpecificationMetadata };
+use crate::project::Project;
 use crate::traits::axiom_system::AxiomSystem;
 use crate::traits::specification_generator::ValidationDepth;
 
 /// Specification validation command
+///
+/// With `--project`, or without `--spec`, the specification and requirements of the
+/// project containing the current directory are validated. Returns the validation report
+/// as a JSON document, printed with `--output-format json`.
 pub async fn execute<S: AxiomSystem>(
     axiom: &S,
-    spec_path: &Path,
+    project: Option<&Project>,
+    spec_path: Option<&Path>,
     depth_str: &str,
     requirements_path: Option<&Path>,
     is_project: bool
-) -> Result<()> {
+) -> Result<String> {
     ui::print_header("Validating Formal Specification");
 
     // Parse validation depth
     let validation_depth = parse_validation_depth(depth_str)?;
 
-    // Determine actual spec path and requirements path
-    let (actual_spec_path, actual_req_path) = if is_project {
-        // We're validating a project in the projects directory
-        let project_name = spec_path.to_string_lossy();
-        let project_dir = Path::new("projects").join(&*project_name);
-
-        if !project_dir.exists() {
-            return Err(anyhow!("Project {} not found in projects directory", project_name));
-        }
-
-        // Look for a specification file in the project directory
-        let mut found_spec_path = None;
-        for ext in &["fst", "dfy", "v", "thy", "lean", "tla", "why", "smt2"] {
-            let potential_path = project_dir.join(format!("spec.{}", ext));
-            if potential_path.exists() {
-                found_spec_path = Some(potential_path);
-                break;
+    // Determine the specification and where its requirements come from
+    let (project, actual_spec_path) = match spec_path {
+        Some(path) if !is_project => (None, path.to_path_buf()),
+        _ => {
+            let project = project.ok_or_else(||
+                anyhow!(
+                    "No axiom.toml found in this directory or its parents; pass --spec or run `axiom init`"
+                )
+            )?;
+            let path = project.spec_path(&project.manifest.verification_language());
+            if !path.exists() {
+                return Err(anyhow!("No specification at {}; run `axiom spec` first", path.display()));
             }
+            ui::print_info(&format!("Using project: {}", project.manifest.project.name));
+            ui::print_info(&format!("Using specification: {}", path.display()));
+            (Some(project), path)
         }
-
-        let spec_file_path = found_spec_path.ok_or_else(||
-            anyhow!("No specification file found in project {}", project_name)
-        )?;
-
-        // Look for requirements file
-        let req_file_path = project_dir.join("requirements.txt");
-        let req_path = if req_file_path.exists() {
-            Some(req_file_path)
-        } else {
-            requirements_path.map(|p| p.to_path_buf())
-        };
-
-        ui::print_info(&format!("Using project: {}", project_name));
-        ui::print_info(&format!("Using specification: {}", spec_file_path.display()));
-        if let Some(ref p) = req_path {
-            ui::print_info(&format!("Using requirements: {}", p.display()));
-        }
-
-        (spec_file_path, req_path)
-    } else {
-        // Standard validation of a direct specification file
-        (spec_path.to_path_buf(), requirements_path.map(|p| p.to_path_buf()))
     };
 
     // Load specification
@@ -87,21 +69,22 @@ pub async fn execute<S: AxiomSystem>(
         ui::print_warning("Note: Using F* for verification regardless of file extension.");
     }
 
-    // Load requirements if provided
-    let requirements = if let Some(req_path) = actual_req_path {
-        match fs::read_to_string(req_path) {
-            Ok(content) =>
-                content
-                    .lines()
-                    .map(|s| s.trim().to_string())
-                    .filter(|s| !s.is_empty())
-                    .collect::<Vec<String>>(),
-            Err(e) => {
-                return Err(anyhow!("Failed to read requirements file: {}", e));
+    // Load requirements if provided, or from the project
+    let requirements = match (requirements_path, project) {
+        (Some(req_path), _) =>
+            match fs::read_to_string(req_path) {
+                Ok(content) =>
+                    content
+                        .lines()
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty())
+                        .collect::<Vec<String>>(),
+                Err(e) => {
+                    return Err(anyhow!("Failed to read requirements file: {}", e));
+                }
             }
-        }
-    } else {
-        vec!["Specification validation".to_string()]
+        (None, Some(project)) => project.requirements()?,
+        (None, None) => vec!["Specification validation".to_string()],
     };
 
     // Create a formal specification struct
@@ -110,6 +93,7 @@ pub async fn execute<S: AxiomSystem>(
         spec_code: spec_content.clone(),
         components: std::collections::HashMap::new(),
         dependencies: vec![],
+        candidates: vec![],
     };
 
     // Create a full specification struct
@@ -120,28 +104,7 @@ pub async fn execute<S: AxiomSystem>(
         formal_spec,
         metadata: SpecificationMetadata {
             created_at: chrono::Utc::now(),
-            verification_system: match verification_language {
-                crate::models::common::VerificationLanguage::FStarLang =>
-                    crate::models::common::VerificationSystem::FStar,
-                crate::models::common::VerificationLanguage::DafnyLang =>
-                    crate::models::common::VerificationSystem::Dafny,
-                crate::models::common::VerificationLanguage::CoqLang =>
-                    crate::models::common::VerificationSystem::Coq,
-                crate::models::common::VerificationLanguage::IsabelleLang =>
-                    crate::models::common::VerificationSystem::Isabelle,
-                crate::models::common::VerificationLanguage::LeanLang =>
-                    crate::models::common::VerificationSystem::Lean,
-                crate::models::common::VerificationLanguage::TLAPlus =>
-                    crate::models::common::VerificationSystem::TLA,
-                crate::models::common::VerificationLanguage::Why3Lang =>
-                    crate::models::common::VerificationSystem::Why3,
-                crate::models::common::VerificationLanguage::Z3SMT =>
-                    crate::models::common::VerificationSystem::Z3,
-                _ =>
-                    crate::models::common::VerificationSystem::Custom(
-                        verification_language.to_string()
-                    ),
-            },
+            verification_system: verification_language.verification_system(),
             domain: Domain::Custom("validation".to_string()),
             confidence_score: 0.9,
             is_formally_validated: false,
@@ -172,7 +135,7 @@ pub async fn execute<S: AxiomSystem>(
         if issue.message.contains("automatically fixed") {
             if let Some(fix) = &issue.suggested_fix {
                 fixed_spec_code = Some(fix.clone());
-                ui::print_success(&format!("{}", issue.message));
+                ui::print_success(&issue.message);
             }
         }
     }
@@ -186,7 +149,7 @@ pub async fn execute<S: AxiomSystem>(
             ui::print_info("Saving the automatically fixed specification...");
             
             // Determine the output path
-            let output_path = if is_project {
+            let output_path = if project.is_some() {
                 // If it's a project, update the original file
                 actual_spec_path.clone()
             } else {
@@ -230,10 +193,8 @@ pub async fn execute<S: AxiomSystem>(
         }
 
         // Try to load the description file if we're validating a project
-        if is_project {
-            let project_name = spec_path.to_string_lossy();
-            let project_dir = Path::new("projects").join(&*project_name);
-            let description_path = project_dir.join("description.md");
+        if project.is_some() {
+            let description_path = actual_spec_path.with_extension("md");
 
             if description_path.exists() {
                 match fs::read_to_string(&description_path) {
@@ -307,10 +268,18 @@ pub async fn execute<S: AxiomSystem>(
                 continue;
             }
             
-            let location = if let Some(line) = issue.line_number {
-                format!("Line {}", line)
-            } else {
-                "Unknown location".to_string()
+            let location = match (&issue.span, issue.line_number) {
+                (Some(span), _) => format!(
+                    "Line {}:{}-{}:{}",
+                    span.start_line, span.start_column, span.end_line, span.end_column
+                ),
+                (None, Some(line)) => format!("Line {}", line),
+                (None, None) => "Unknown location".to_string(),
+            };
+            
+            let severity_str = match issue.error_code {
+                Some(code) => format!("{} {}", severity_str, code),
+                None => severity_str.to_string(),
             };
             
             ui::print_info(&format!("{}. [{}] {}: {}", 
@@ -339,30 +308,28 @@ pub async fn execute<S: AxiomSystem>(
             
             // Simple user prompt for saving best effort fix
             let mut input = String::new();
-            if std::io::stdin().read_line(&mut input).is_ok() {
-                if input.trim().to_lowercase() == "y" {
-                    if let Some(fixed_code) = &fix_issue.suggested_fix {
-                        // Determine the output path
-                        let output_path = if is_project {
-                            let mut fixed_path = actual_spec_path.clone();
-                            fixed_path.set_file_name("spec_best_effort_fix.fst");
-                            fixed_path
-                        } else {
-                            let mut fixed_path = actual_spec_path.clone();
-                            let stem = fixed_path.file_stem().unwrap_or_default().to_string_lossy().to_string();
-                            let ext = fixed_path.extension().unwrap_or_default().to_string_lossy().to_string();
-                            fixed_path.set_file_name(format!("{}_best_effort_fix.{}", stem, ext));
-                            fixed_path
-                        };
-                        
-                        // Write the best effort fixed code to the file
-                        match std::fs::write(&output_path, fixed_code) {
-                            Ok(_) => {
-                                ui::print_success(&format!("Best effort fix saved to {}", output_path.display()));
-                            }
-                            Err(e) => {
-                                ui::print_error(&format!("Failed to save best effort fix: {}", e));
-                            }
+            if std::io::stdin().read_line(&mut input).is_ok() && input.trim().to_lowercase() == "y" {
+                if let Some(fixed_code) = &fix_issue.suggested_fix {
+                    // Determine the output path
+                    let output_path = if project.is_some() {
+                        let mut fixed_path = actual_spec_path.clone();
+                        fixed_path.set_file_name("spec_best_effort_fix.fst");
+                        fixed_path
+                    } else {
+                        let mut fixed_path = actual_spec_path.clone();
+                        let stem = fixed_path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+                        let ext = fixed_path.extension().unwrap_or_default().to_string_lossy().to_string();
+                        fixed_path.set_file_name(format!("{}_best_effort_fix.{}", stem, ext));
+                        fixed_path
+                    };
+                    
+                    // Write the best effort fixed code to the file
+                    match std::fs::write(&output_path, fixed_code) {
+                        Ok(_) => {
+                            ui::print_success(&format!("Best effort fix saved to {}", output_path.display()));
+                        }
+                        Err(e) => {
+                            ui::print_error(&format!("Failed to save best effort fix: {}", e));
                         }
                     }
                 }
@@ -372,7 +339,7 @@ pub async fn execute<S: AxiomSystem>(
 
     ui::print_success("Validation completed!");
 
-    Ok(())
+    Ok(to_document_json(&validation_report)?)
 }
 
 fn parse_validation_depth(depth_str: &str) -> Result<ValidationDepth> {
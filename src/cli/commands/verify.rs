@@ -0,0 +1,321 @@
+use anyhow::{ anyhow, Context, Result };
+use std::fs;
+use std::path::{ Path, PathBuf };
+use std::time::Duration;
+
+use axiom::implementations::backends::{ incompatible_implementation, BackendRegistry };
+use crate::cli::commands::init::{ parse_language, parse_verification_system };
+use crate::cli::ui;
+use crate::models::common::{ Domain, Language, ProofLevel, VerificationLanguage, VerificationSystem };
+use crate::models::implementation::Implementation;
+use crate::models::schema::{ from_document_json, to_document_json, DocumentError };
+use crate::models::specification::{ FormalSpecification, Specification, SpecificationMetadata };
+use crate::models::verification::{ VerificationOptions, VerificationResult, VerificationStatus };
+use crate::project::Project;
+use crate::traits::verification_engine::VerificationEngine;
+
+/// File the verification result is written to in the output directory
+pub const RESULTS_FILE: &str = "verification_results.json";
+/// Directory the proof artifacts are copied to in the output directory
+pub const ARTIFACTS_DIR: &str = "artifacts";
+
+/// Implementation verification command
+///
+/// Runs the backend of the chosen verification system on the implementation and
+/// specification, and writes the result and proof artifacts to the output directory.
+/// The returned status decides the exit code, see `exit_code`.
+#[allow(clippy::too_many_arguments)]
+pub fn execute(
+    backends: &BackendRegistry,
+    project: Option<&Project>,
+    implementation_path: &Path,
+    spec_path: Option<&Path>,
+    system_str: Option<&str>,
+    output_dir: Option<&Path>,
+    proof_level_str: &str,
+    timeout_secs: u64,
+    output_format: &str
+) -> Result<VerificationStatus> {
+    ui::print_header("Verifying Implementation");
+
+    let explicit_system = system_str.map(parse_verification_system);
+    let fallback_language = explicit_system
+        .as_ref()
+        .or(project.map(|project| &project.manifest.project.verification_system))
+        .map(VerificationSystem::verification_language);
+
+    let spec_path = match (spec_path, project) {
+        (Some(path), _) => path.to_path_buf(),
+        (None, Some(project)) => project.spec_path(&project.manifest.verification_language()),
+        (None, None) => {
+            return Err(anyhow!("No axiom.toml found in this directory or its parents; pass --spec"));
+        }
+    };
+    ui::print_info(&format!("Loading specification from {}", spec_path.display()));
+    let spec = load_specification(&spec_path, fallback_language, project)?;
+    let system = explicit_system.unwrap_or_else(||
+        spec.formal_spec.verification_language.verification_system()
+    );
+
+    ui::print_info(&format!("Loading implementation from {}", implementation_path.display()));
+    let implementation = load_implementation(implementation_path, &spec)?;
+
+    let engine = backends
+        .engine(&system)
+        .ok_or_else(|| anyhow!("No verification backend is registered for {}", system))?;
+    if spec.formal_spec.verification_language != system.verification_language() {
+        return Err(
+            anyhow!(
+                "{} checks {} specifications, but {} is written in {}",
+                system,
+                system.verification_language(),
+                spec_path.display(),
+                spec.formal_spec.verification_language
+            )
+        );
+    }
+    // Never pass code the backend would have to ignore
+    if !engine.can_verify(&spec, &implementation.language) {
+        return Err(
+            anyhow!(
+                "{} ({}), and {} would only check the specification",
+                incompatible_implementation(&implementation.language, system.verification_language()),
+                implementation_path.display(),
+                system
+            )
+        );
+    }
+
+    let options = VerificationOptions {
+        timeout: Duration::from_secs(timeout_secs),
+        proof_level: parse_proof_level(proof_level_str),
+        ..VerificationOptions::default()
+    };
+
+    ui::print_result("Verification system", &system.to_string());
+    ui::print_result("Proof level", proof_level_str);
+    let spinner = ui::spinner_with_message(&format!("Running {}...", system));
+    let outcome = engine.verify(&implementation, &spec, &options);
+    spinner.finish_and_clear();
+    let mut result = outcome?;
+
+    let output_dir = match (output_dir, project) {
+        (Some(dir), _) => dir.to_path_buf(),
+        (None, Some(project)) => project.proofs_dir(),
+        (None, None) => PathBuf::from("proofs"),
+    };
+    let results_path = save_results(&mut result, &output_dir)?;
+
+    ui::print_verification_status(&result.status);
+    ui::print_result("Time", &format!("{:.2}s", result.verification_time.as_secs_f64()));
+    if !result.goal_results.is_empty() {
+        let proven = result.goal_results
+            .iter()
+            .filter(|goal| goal.outcome.is_valid())
+            .count();
+        ui::print_result("Goals proven", &format!("{}/{}", proven, result.goal_results.len()));
+    }
+    for counterexample in engine.extract_counterexamples(&result)? {
+        ui::print_warning(&format!("Counterexample: {}", counterexample));
+    }
+    ui::print_info(&format!("Results written to {}", results_path.display()));
+
+    if output_format.eq_ignore_ascii_case("json") {
+        println!("{}", to_document_json(&result)?);
+    }
+
+    Ok(result.status)
+}
+
+/// Exit code reporting a verification status, so that the command can gate CI
+///
+/// 0 is verified; 1 and 2 stay reserved for general and usage errors.
+pub fn exit_code(status: &VerificationStatus) -> i32 {
+    match status {
+        VerificationStatus::Verified => 0,
+        VerificationStatus::Failed(_) => 3,
+        VerificationStatus::Unverified => 4,
+        VerificationStatus::Timeout => 5,
+        VerificationStatus::Error(_) => 6,
+    }
+}
+
+/// Read a specification from a source file in a verification language, or from a
+/// `specification` or `formal_specification` JSON document
+fn load_specification(
+    path: &Path,
+    fallback_language: Option<VerificationLanguage>,
+    project: Option<&Project>
+) -> Result<Specification> {
+    let text = fs
+        ::read_to_string(path)
+        .with_context(|| format!("Failed to read specification file {}", path.display()))?;
+
+    let formal_spec = if extension(path) == "json" {
+        match from_document_json::<Specification>(&text) {
+            Ok(spec) => {
+                return Ok(spec);
+            }
+            Err(DocumentError::WrongKind { .. }) =>
+                from_document_json::<FormalSpecification>(&text).with_context(||
+                    format!("{} is not a specification document", path.display())
+                )?,
+            Err(e) => {
+                return Err(anyhow!("Failed to read {}: {}", path.display(), e));
+            }
+        }
+    } else {
+        let verification_language = VerificationLanguage::from_spec_file_extension(
+            &extension(path)
+        )
+            .or(fallback_language)
+            .ok_or_else(||
+                anyhow!(
+                    "Cannot tell the specification language of {}; pass --system",
+                    path.display()
+                )
+            )?;
+        FormalSpecification {
+            verification_language,
+            spec_code: text,
+            components: Default::default(),
+            dependencies: vec![],
+            candidates: vec![],
+        }
+    };
+
+    Ok(Specification {
+        id: file_stem(path),
+        source_requirements: vec![],
+        formal_properties: vec![],
+        metadata: SpecificationMetadata {
+            created_at: chrono::Utc::now(),
+            verification_system: formal_spec.verification_language.verification_system(),
+            domain: project
+                .map(|project| project.manifest.project.domain.clone())
+                .unwrap_or_else(|| Domain::Custom("verification".to_string())),
+            confidence_score: 1.0,
+            is_formally_validated: false,
+        },
+        formal_spec,
+    })
+}
+
+/// Read an implementation from a source file or an `implementation` JSON document
+///
+/// Source files in a verification language are given the language name its backend
+/// checks directly; other files take the language of their extension.
+fn load_implementation(path: &Path, spec: &Specification) -> Result<Implementation> {
+    let text = fs
+        ::read_to_string(path)
+        .with_context(|| format!("Failed to read implementation file {}", path.display()))?;
+
+    let extension = extension(path);
+    if extension == "json" {
+        return from_document_json::<Implementation>(&text).map_err(|e|
+            anyhow!("Failed to read {}: {}", path.display(), e)
+        );
+    }
+
+    let language = match VerificationLanguage::from_spec_file_extension(&extension) {
+        // ACSL annotates C code, which Frama-C checks as C
+        Some(VerificationLanguage::ACSL) => Language::C,
+        Some(verification_language) => Language::Custom(native_language_name(&verification_language)),
+        None => source_language(&extension),
+    };
+
+    Ok(Implementation {
+        id: file_stem(path),
+        specification_id: spec.id.clone(),
+        language,
+        source_code: text,
+        verification_result: VerificationResult {
+            status: VerificationStatus::Unverified,
+            proof_artifacts: vec![],
+            verification_time: Duration::ZERO,
+            resource_usage: Default::default(),
+            goal_results: vec![],
+            counterexamples: vec![],
+        },
+    })
+}
+
+/// Name under which the backends accept code written in their own language
+fn native_language_name(language: &VerificationLanguage) -> String {
+    let name = match language {
+        VerificationLanguage::FStarLang => "fstar",
+        VerificationLanguage::DafnyLang => "dafny",
+        VerificationLanguage::CoqLang => "coq",
+        VerificationLanguage::IsabelleLang => "isabelle",
+        VerificationLanguage::LeanLang => "lean",
+        VerificationLanguage::TLAPlus => "tla",
+        VerificationLanguage::Why3Lang => "why3",
+        VerificationLanguage::Z3SMT => "smt2",
+        other => {
+            return other.to_string();
+        }
+    };
+    name.to_string()
+}
+
+/// Implementation language of a source file extension
+fn source_language(extension: &str) -> Language {
+    match extension {
+        "rs" => Language::Rust,
+        "h" => Language::C,
+        "cc" | "cxx" | "hpp" => Language::CPlusPlus,
+        "cs" => Language::CSharp,
+        other => parse_language(other),
+    }
+}
+
+/// Write the result to `verification_results.json` in the output directory, after copying
+/// its proof artifacts into `artifacts/` and pointing the result at the copies
+fn save_results(result: &mut VerificationResult, output_dir: &Path) -> Result<PathBuf> {
+    let artifacts_dir = output_dir.join(ARTIFACTS_DIR);
+    fs::create_dir_all(&artifacts_dir).with_context(||
+        format!("Failed to create {}", artifacts_dir.display())
+    )?;
+
+    for artifact in &mut result.proof_artifacts {
+        let source = PathBuf::from(&artifact.path);
+        let Some(name) = source.file_name() else {
+            continue;
+        };
+        let target = artifacts_dir.join(name);
+        match fs::copy(&source, &target) {
+            Ok(_) => {
+                artifact.path = target.display().to_string();
+            }
+            Err(e) => ui::print_warning(&format!("Could not copy {}: {}", source.display(), e)),
+        }
+    }
+
+    let results_path = output_dir.join(RESULTS_FILE);
+    fs::write(&results_path, to_document_json(result)?).with_context(||
+        format!("Failed to write {}", results_path.display())
+    )?;
+    Ok(results_path)
+}
+
+fn parse_proof_level(proof_level_str: &str) -> ProofLevel {
+    match proof_level_str.to_lowercase().as_str() {
+        "quick" => ProofLevel::Quick,
+        "standard" => ProofLevel::Standard,
+        "thorough" => ProofLevel::Thorough,
+        "exhaustive" => ProofLevel::Exhaustive,
+        _ => ProofLevel::Custom(proof_level_str.to_string()),
+    }
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
@@ -14,7 +14,7 @@ pub mod ui;
 )]
 pub struct AxiomCli {
     /// Sets the log level (error, warn, info, debug, trace)
-    #[arg(short, long, global = true, default_value = "info")]
+    #[arg(long, global = true, default_value = "info")]
     pub log_level: String,
 
     /// Path to configuration file
@@ -25,15 +25,23 @@ pub struct AxiomCli {
     #[arg(long, global = true, default_value = "text")]
     pub output_format: String,
 
+    /// Always call the LLM instead of reusing cached responses
+    #[arg(long, global = true)]
+    pub no_cache: bool,
+
+    /// Print LLM output as it arrives instead of a live character count
+    #[arg(long, global = true)]
+    pub stream_output: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
-    /// Initialize a new Axiom project
+    /// Initialize a new Axiom project with an axiom.toml manifest
     Init {
-        /// Project directory
+        /// Project directory, created if missing
         #[arg(default_value = ".")]
         directory: PathBuf,
 
@@ -52,17 +60,17 @@ pub enum Commands {
 
     /// Generate a formal specification from requirements
     Spec {
-        /// Path to requirements file (one requirement per line)
+        /// Path to requirements file (one requirement per line); defaults to the project's requirements
         #[arg(short, long)]
-        requirements: PathBuf,
+        requirements: Option<PathBuf>,
 
-        /// Verification language to generate
-        #[arg(short, long, default_value = "fstar")]
-        verification_language: String,
+        /// Verification language to generate; defaults to the project's, or fstar
+        #[arg(short, long)]
+        verification_language: Option<String>,
 
-        /// Domain for the specification
+        /// Domain for the specification; defaults to the project's
         #[arg(short, long)]
-        domain: String,
+        domain: Option<String>,
 
         /// Output file for the specification
         #[arg(short, long)]
@@ -71,13 +79,17 @@ pub enum Commands {
         /// Detailed level for specification generation
         #[arg(long, default_value = "standard")]
         detail_level: String,
+
+        /// Number of candidate specifications to draw and check; the one with the fewest errors is kept
+        #[arg(long, default_value = "1")]
+        candidates: usize,
     },
 
     /// Validate a formal specification
     Validate {
-        /// Path to specification file or project name in projects directory
+        /// Path to specification file; defaults to the project's specification
         #[arg(short, long)]
-        spec: PathBuf,
+        spec: Option<PathBuf>,
 
         /// Validation depth (basic, typecheck, formal)
         #[arg(short, long, default_value = "basic")]
@@ -87,7 +99,7 @@ pub enum Commands {
         #[arg(short, long)]
         requirements: Option<PathBuf>,
         
-        /// Validate a project in the projects directory
+        /// Validate the specification of the project containing the current directory
         #[arg(short, long, default_value = "false")]
         project: bool,
     },
@@ -121,12 +133,12 @@ pub enum Commands {
         #[arg(short, long)]
         implementation: PathBuf,
 
-        /// Path to specification file
+        /// Path to specification file (default: the project's)
         #[arg(short, long)]
-        spec: PathBuf,
+        spec: Option<PathBuf>,
 
-        /// Verification system to use
-        #[arg(short, long)]
+        /// Verification system to use (default: the one checking the specification's language)
+        #[arg(long)]
         system: Option<String>,
 
         /// Output directory for verification results
@@ -140,27 +152,31 @@ pub enum Commands {
         /// Timeout in seconds
         #[arg(short, long, default_value = "300")]
         timeout: u64,
+
+        /// Value of a constant of the TLC model, as NAME=VALUE (repeatable)
+        #[arg(long = "constant", value_name = "NAME=VALUE")]
+        constants: Vec<String>,
     },
 
     /// Process requirements through the entire pipeline
     Process {
-        /// Path to requirements file
+        /// Path to requirements file; defaults to the project's requirements
         #[arg(short, long)]
-        requirements: PathBuf,
+        requirements: Option<PathBuf>,
 
-        /// Target implementation language
+        /// Target implementation language; defaults to the project's
         #[arg(short, long)]
-        language: String,
+        language: Option<String>,
 
-        /// Domain for the specification
+        /// Domain for the specification; defaults to the project's
         #[arg(short, long)]
-        domain: String,
+        domain: Option<String>,
 
-        /// Output directory for all generated files
+        /// Output directory for all generated files; defaults to the project's directories
         #[arg(short, long)]
-        output: PathBuf,
+        output: Option<PathBuf>,
 
-        /// Verification system to use
+        /// Verification system to use; defaults to the project's
         #[arg(short, long)]
         system: Option<String>,
 
@@ -209,4 +225,23 @@ pub enum Commands {
         #[arg(short, long, default_value = "false")]
         install: bool,
     },
+
+    /// Inspect or clear the LLM response cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CacheAction {
+    /// Show the number, age and size of cached responses
+    Stats,
+
+    /// Remove cached responses
+    Clear {
+        /// Only remove responses older than the cache TTL
+        #[arg(long)]
+        expired: bool,
+    },
 }
@@ -1,7 +1,10 @@
 use colored::*;
 use console::Term;
 use dialoguer::{ theme::ColorfulTheme, Confirm, Input, Select };
-use indicatif::{ ProgressBar, ProgressStyle };
+use indicatif::{ ProgressBar, ProgressDrawTarget, ProgressStyle };
+use std::io::Write;
+use std::sync::atomic::{ AtomicBool, AtomicUsize, Ordering };
+use std::sync::Mutex;
 use std::time::Duration;
 use textwrap::wrap;
 
@@ -12,9 +15,29 @@ use crate::models::common::{
     VerificationSystem,
     SpecificationParadigm,
 };
+use axiom::implementations::llm::{ StreamObserver, UsageSummary };
 use crate::models::verification::VerificationStatus;
 use crate::traits::specification_generator::ValidationDepth;
 
+/// Set when `--output-format json` reserves stdout for the command's JSON document
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// `println!` for human-readable output, which goes to stderr in JSON mode
+macro_rules! say {
+    ($($arg:tt)*) => {
+        if JSON_OUTPUT.load(Ordering::SeqCst) {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+
+/// Send human-readable output to stderr from now on, keeping stdout for JSON
+pub fn set_json_output(json: bool) {
+    JSON_OUTPUT.store(json, Ordering::SeqCst);
+}
+
 /// UI theme for consistent appearance
 pub fn get_theme() -> ColorfulTheme {
     ColorfulTheme::default()
@@ -22,9 +45,8 @@ pub fn get_theme() -> ColorfulTheme {
 
 /// Print a section header
 pub fn print_header(title: &str) {
-    let width = Term::stdout().size().1 as usize;
     let title = format!(" {} ", title);
-    println!("\n{}\n", title.bold().white().on_blue());
+    say!("\n{}\n", title.bold().white().on_blue());
 }
 
 /// Print text with proper wrapping
@@ -33,17 +55,17 @@ pub fn print_text(text: &str) {
     for line in text.lines() {
         if line.starts_with('#') {
             // Handle headers
-            println!("{}", line.bold());
+            say!("{}", line.bold());
         } else if line.starts_with('-') {
             // Handle list items
-            println!("{}", line);
+            say!("{}", line);
         } else if line.starts_with("```") {
             // Handle code blocks
-            println!("{}", line);
+            say!("{}", line);
         } else {
             // Wrap normal text
             for wrapped_line in wrap(line, width.saturating_sub(10)) {
-                println!("{}", wrapped_line);
+                say!("{}", wrapped_line);
             }
         }
     }
@@ -56,37 +78,65 @@ pub fn print_error(message: &str) {
 
 /// Print a warning message
 pub fn print_warning(message: &str) {
-    println!("{} {}", "WARNING:".yellow().bold(), message);
+    say!("{} {}", "WARNING:".yellow().bold(), message);
 }
 
 /// Print a success message
 pub fn print_success(message: &str) {
-    println!("{} {}", "SUCCESS:".green().bold(), message);
+    say!("{} {}", "SUCCESS:".green().bold(), message);
 }
 
 /// Print information
 pub fn print_info(message: &str) {
-    println!("{} {}", "INFO:".blue().bold(), message);
+    say!("{} {}", "INFO:".blue().bold(), message);
+}
+
+/// Print a line of plain text
+pub fn print_line(line: &str) {
+    say!("{}", line);
 }
 
 /// Print a formatted result
 pub fn print_result(label: &str, value: &str) {
-    println!("{}: {}", label.bold(), value);
+    say!("{}: {}", label.bold(), value);
 }
 
-/// Create a new progress bar
-pub fn create_progress_bar(length: u64, message: &str) -> ProgressBar {
-    let pb = ProgressBar::new(length);
-    pb.set_style(
-        ProgressStyle::default_bar()
-            .template(
-                "{spinner:.green} [{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {msg}"
-            )
-            .unwrap()
-            .progress_chars("##-")
-    );
-    pb.set_message(message.to_string());
-    pb
+/// Print the JSON document a command produced, with the token usage and cost of its LLM
+/// calls added as `llm_usage`. Commands without a document print just the usage.
+pub fn print_json_document(document: Option<&str>, summary: &UsageSummary) {
+    let mut report = document
+        .and_then(|document| serde_json::from_str::<serde_json::Value>(document).ok())
+        .filter(|document| document.is_object())
+        .unwrap_or_else(|| serde_json::json!({}));
+    report["llm_usage"] = serde_json::json!(summary);
+    println!("{}", serde_json::to_string_pretty(&report).unwrap_or_default());
+}
+
+/// Print the token usage and cost of a run's LLM calls
+pub fn print_usage_summary(summary: &UsageSummary) {
+    if summary.calls == 0 {
+        return;
+    }
+
+    print_header("LLM Usage");
+    print_result("Total", &summary.to_string());
+    for record in &summary.records {
+        let cost = match (record.cached, record.cost_usd) {
+            (true, _) => "cached".to_string(),
+            (false, Some(cost)) => format!("${:.4}", cost),
+            (false, None) => "unpriced".to_string(),
+        };
+        say!(
+            "  {}/{}: {} + {} tokens{}, {} ms, {}",
+            record.provider,
+            record.model,
+            record.usage.prompt_tokens,
+            record.usage.completion_tokens,
+            if record.estimated { " (estimated)" } else { "" },
+            record.latency_ms,
+            cost
+        );
+    }
 }
 
 /// Print verification status with color
@@ -95,7 +145,7 @@ pub fn print_verification_status(status: &VerificationStatus) {
         VerificationStatus::Verified => ("✓ Verified".to_string(), "green"),
         VerificationStatus::Unverified => ("? Unverified".to_string(), "yellow"),
         VerificationStatus::Failed(reasons) => {
-            let mut status = format!("✗ Failed");
+            let mut status = "✗ Failed".to_string();
             if !reasons.is_empty() {
                 status = format!("{} - {}", status, reasons.join(", "));
             }
@@ -106,10 +156,10 @@ pub fn print_verification_status(status: &VerificationStatus) {
     };
 
     match color {
-        "green" => println!("{}", status_str.green().bold()),
-        "yellow" => println!("{}", status_str.yellow().bold()),
-        "red" => println!("{}", status_str.red().bold()),
-        _ => println!("{}", status_str),
+        "green" => say!("{}", status_str.green().bold()),
+        "yellow" => say!("{}", status_str.yellow().bold()),
+        "red" => say!("{}", status_str.red().bold()),
+        _ => say!("{}", status_str),
     }
 }
 
@@ -135,7 +185,7 @@ pub fn select_language() -> std::io::Result<Language> {
         .items(&languages)
         .default(0)
         .interact()
-        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        .map_err(std::io::Error::other)?;
 
     let lang = match selection {
         0 => Language::Rust,
@@ -158,14 +208,14 @@ pub fn select_language() -> std::io::Result<Language> {
 
 /// Interactive selection of a verification system
 pub fn select_verification_system() -> std::io::Result<VerificationSystem> {
-    let systems = vec!["F*", "Dafny", "Coq", "Isabelle", "Lean", "TLA+", "Why3", "Z3"];
+    let systems = vec!["F*", "Dafny", "Coq", "Isabelle", "Lean", "TLA+", "Why3", "Z3", "Frama-C"];
 
     let selection = Select::with_theme(&get_theme())
         .with_prompt("Select verification system")
         .items(&systems)
         .default(0)
         .interact()
-        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        .map_err(std::io::Error::other)?;
 
     let system = match selection {
         0 => VerificationSystem::FStar,
@@ -176,55 +226,13 @@ pub fn select_verification_system() -> std::io::Result<VerificationSystem> {
         5 => VerificationSystem::TLA,
         6 => VerificationSystem::Why3,
         7 => VerificationSystem::Z3,
+        8 => VerificationSystem::FramaC,
         _ => VerificationSystem::Custom(systems[selection].to_string()),
     };
 
     Ok(system)
 }
 
-/// Interactive selection of a verification language
-pub fn select_verification_language() -> std::io::Result<VerificationLanguage> {
-    let languages = vec![
-        "F*",
-        "Dafny",
-        "Coq",
-        "Isabelle",
-        "Lean",
-        "TLA+",
-        "Why3",
-        "Z3 SMT",
-        "ACSL (C)",
-        "JML (Java)",
-        "Liquid Haskell",
-        "MIRAI (Rust)"
-    ];
-
-    let selection = Select::with_theme(&get_theme())
-        .with_prompt("Select verification language")
-        .items(&languages)
-        .default(0)
-        .interact()
-        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
-
-    let lang = match selection {
-        0 => VerificationLanguage::FStarLang,
-        1 => VerificationLanguage::DafnyLang,
-        2 => VerificationLanguage::CoqLang,
-        3 => VerificationLanguage::IsabelleLang,
-        4 => VerificationLanguage::LeanLang,
-        5 => VerificationLanguage::TLAPlus,
-        6 => VerificationLanguage::Why3Lang,
-        7 => VerificationLanguage::Z3SMT,
-        8 => VerificationLanguage::ACSL,
-        9 => VerificationLanguage::JML,
-        10 => VerificationLanguage::Liquid,
-        11 => VerificationLanguage::RustMIRAI,
-        _ => VerificationLanguage::Custom(languages[selection].to_string()),
-    };
-
-    Ok(lang)
-}
-
 /// Interactive selection of a domain
 pub fn select_domain() -> std::io::Result<Domain> {
     let domains = vec![
@@ -243,7 +251,7 @@ pub fn select_domain() -> std::io::Result<Domain> {
         .items(&domains)
         .default(0)
         .interact()
-        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        .map_err(std::io::Error::other)?;
 
     let domain = match selection {
         0 => Domain::Cryptography,
@@ -277,7 +285,7 @@ pub fn select_specification_paradigm() -> std::io::Result<SpecificationParadigm>
         .items(&paradigms)
         .default(0)
         .interact()
-        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        .map_err(std::io::Error::other)?;
 
     let paradigm = match selection {
         0 => SpecificationParadigm::PrePostConditions,
@@ -306,7 +314,7 @@ pub fn select_validation_depth() -> std::io::Result<ValidationDepth> {
         .items(&depths)
         .default(0)
         .interact()
-        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        .map_err(std::io::Error::other)?;
 
     let depth = match selection {
         0 => ValidationDepth::Basic,
@@ -322,13 +330,13 @@ pub fn select_validation_depth() -> std::io::Result<ValidationDepth> {
 pub fn get_requirements() -> std::io::Result<Vec<String>> {
     let mut requirements = Vec::new();
 
-    println!("Enter requirements (one per line, empty line to finish):");
+    say!("Enter requirements (one per line, empty line to finish):");
     loop {
         let req: String = Input::with_theme(&get_theme())
             .with_prompt(format!("Requirement {}", requirements.len() + 1))
             .allow_empty(true)
             .interact()
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+            .map_err(std::io::Error::other)?;
 
         if req.is_empty() {
             break;
@@ -362,8 +370,8 @@ pub fn display_specification(language: &VerificationLanguage, code: &str) {
         VerificationLanguage::Custom(s) => s,
     };
 
-    println!("Language: {}", lang_name.cyan());
-    println!("\n{}\n", code);
+    say!("Language: {}", lang_name.cyan());
+    say!("\n{}\n", code);
 }
 
 /// Confirm an action with the user
@@ -372,7 +380,7 @@ pub fn confirm_action(prompt: &str) -> std::io::Result<bool> {
         .with_prompt(prompt)
         .default(true)
         .interact()
-        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+        .map_err(std::io::Error::other)
 }
 
 /// Display a spinner while waiting for an operation to complete
@@ -386,14 +394,84 @@ pub fn spinner_with_message(message: &str) -> ProgressBar {
     );
     spinner.set_message(message.to_string());
     spinner.enable_steady_tick(Duration::from_millis(100));
+    *ACTIVE_SPINNER.lock().unwrap() = Some((spinner.clone(), message.to_string()));
     spinner
 }
 
+/// Most recent spinner and its message, where streamed LLM output is reported
+static ACTIVE_SPINNER: Mutex<Option<(ProgressBar, String)>> = Mutex::new(None);
+
+fn active_spinner() -> Option<(ProgressBar, String)> {
+    ACTIVE_SPINNER.lock()
+        .unwrap()
+        .clone()
+        .filter(|(spinner, _)| !spinner.is_finished())
+}
+
+/// Shows streaming LLM output on the active spinner, as a live character count or,
+/// with `echo`, as the text itself
+pub struct LlmProgress {
+    echo: bool,
+    received: AtomicUsize,
+}
+
+impl LlmProgress {
+    pub fn new(echo: bool) -> Self {
+        Self { echo, received: AtomicUsize::new(0) }
+    }
+}
+
+impl StreamObserver for LlmProgress {
+    fn on_start(&self, provider: &str, model: &str) {
+        self.received.store(0, Ordering::SeqCst);
+        if !self.echo {
+            return;
+        }
+        let header = format!("--- {} ({}) ---", provider, model);
+        match active_spinner() {
+            Some((spinner, _)) => {
+                spinner.suspend(|| say!("{}", header.dimmed()));
+                // Redrawing the spinner would overwrite the partial line being streamed
+                spinner.set_draw_target(ProgressDrawTarget::hidden());
+            }
+            None => say!("{}", header.dimmed()),
+        }
+    }
+
+    fn on_text(&self, text: &str) {
+        let count = text.chars().count();
+        let received = self.received.fetch_add(count, Ordering::SeqCst) + count;
+        if self.echo {
+            if JSON_OUTPUT.load(Ordering::SeqCst) {
+                eprint!("{}", text);
+                let _ = std::io::stderr().flush();
+            } else {
+                print!("{}", text);
+                let _ = std::io::stdout().flush();
+            }
+        } else if let Some((spinner, message)) = active_spinner() {
+            spinner.set_message(format!("{} {} characters received", message, received));
+        }
+    }
+
+    fn on_finish(&self) {
+        let spinner = active_spinner();
+        if self.echo {
+            say!();
+            if let Some((spinner, _)) = &spinner {
+                spinner.set_draw_target(ProgressDrawTarget::stderr());
+            }
+        } else if let Some((spinner, message)) = spinner {
+            spinner.set_message(message);
+        }
+    }
+}
+
 pub fn pause() -> std::io::Result<()> {
-    println!("\nPress Enter to continue...");
+    say!("\nPress Enter to continue...");
     let _input: String = Input::with_theme(&get_theme())
         .allow_empty(true)
         .interact()
-        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        .map_err(std::io::Error::other)?;
     Ok(())
 }
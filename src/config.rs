@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::time::Duration;
 use crate::models::common::{Language, ResourceLimits, VerificationSystem};
 
@@ -11,9 +11,29 @@ pub struct AxiomConfig {
 }
 
 /// Configuration for external verification tools
+#[derive(Debug, Clone)]
 pub struct ExternalToolsConfig {
     pub tool_paths: HashMap<String, String>,
     pub timeout: Duration,
+    /// Values of the constants TLA+ modules declare, keyed by name, for TLC models
+    pub model_constants: BTreeMap<String, String>,
+}
+
+impl Default for ExternalToolsConfig {
+    fn default() -> Self {
+        Self {
+            tool_paths: HashMap::new(),
+            timeout: Duration::from_secs(300),
+            model_constants: BTreeMap::new(),
+        }
+    }
+}
+
+impl ExternalToolsConfig {
+    /// Resolve the executable for a tool, falling back to the given default name
+    pub fn tool_path(&self, tool: &str, default: &str) -> String {
+        self.tool_paths.get(tool).cloned().unwrap_or_else(|| default.to_string())
+    }
 }
 
 /// Options for the main Axiom system
@@ -50,6 +50,12 @@ pub enum AxiomError {
 
     #[error("Failed to integrate with verification tool: {tool}, reason: {reason}")]
     VerificationToolIntegrationError { tool: String, reason: String },
+
+    #[error("Budget exceeded: {0}")]
+    BudgetExceeded(String),
+
+    #[error("Cancelled by user")]
+    Cancelled,
 }
 
 /// Result type specific to Axiom operations
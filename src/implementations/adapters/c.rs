@@ -0,0 +1,647 @@
+use log::warn;
+
+use crate::errors::{AxiomError, AxiomResult};
+use crate::implementations::adapters::signatures::{matching_paren, split_top_level};
+use crate::implementations::adapters::{
+    contracts_by_function, identifier, signature_problems, Contract, ContractClause,
+    FunctionAdapter, SpecFunction, SpecType,
+};
+use crate::models::common::Language;
+use crate::models::property::Property;
+use crate::traits::language_adapter::LanguageAdapter;
+
+/// Headers declaring the fixed-width integer, `bool` and `size_t` types of prototypes
+const HEADERS: &str = "#include <stdbool.h>\n#include <stddef.h>\n#include <stdint.h>\n";
+
+/// Language adapter for C
+///
+/// Properties become ACSL function contracts, `/*@ requires ...; ensures ...; */` blocks
+/// on the prototypes of the functions of the specification, which Frama-C's WP plugin
+/// proves the implementation against; see `Contract` for how properties are read.
+/// Postconditions may name the return value `result`, which becomes ACSL `\result`.
+/// Sequences are passed as a `const` pointer and a `<name>_len` length. Tests are a
+/// self-contained program of `assert`s over boundary values of the inputs.
+#[derive(Debug, Clone, Default)]
+pub struct CAdapter {
+    functions: Vec<SpecFunction>,
+}
+
+impl CAdapter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Problems that keep the source from matching the specification: unterminated
+    /// comments and annotations, unbalanced braces, and functions that are missing or
+    /// whose signature does not fit
+    ///
+    /// This is a lexical check of declarations at file scope, not a C parser; Frama-C
+    /// reports everything else.
+    pub fn check_source(&self, source: &str) -> Vec<String> {
+        let code = match strip_comments(source) {
+            Ok(code) => code,
+            Err(problem) => return vec![problem],
+        };
+        let declarations = match top_level_declarations(&code) {
+            Ok(declarations) => declarations,
+            Err(problem) => return vec![problem],
+        };
+
+        signature_problems(
+            &self.functions,
+            |name| {
+                declarations
+                    .iter()
+                    .filter(|declaration| declaration.name == name)
+                    .collect()
+            },
+            |function, declaration| signature_mismatches(function, declaration),
+        )
+    }
+}
+
+impl FunctionAdapter for CAdapter {
+    fn functions(&self) -> &[SpecFunction] {
+        &self.functions
+    }
+
+    fn functions_mut(&mut self) -> &mut Vec<SpecFunction> {
+        &mut self.functions
+    }
+}
+
+impl LanguageAdapter for CAdapter {
+    fn language(&self) -> Language {
+        Language::C
+    }
+
+    fn convert_properties(&self, properties: &[Property]) -> AxiomResult<String> {
+        let (contracts, skipped) = self.contracts(properties);
+        let (groups, unattached) = contracts_by_function(&contracts);
+        let mut out = String::from(HEADERS);
+
+        for (function, group) in &groups {
+            out.push('\n');
+            out.push_str(&function_contract(function, group));
+        }
+
+        if !unattached.is_empty() || !skipped.is_empty() {
+            out.push('\n');
+        }
+        for contract in &unattached {
+            out.push_str(&format!(
+                "// {}: no function of the specification to attach `{}` to\n",
+                contract.property.id, contract.condition
+            ));
+        }
+        for property in &skipped {
+            out.push_str(&format!(
+                "// {}: cannot be stated as a contract: {}\n",
+                property.id, property.description
+            ));
+        }
+
+        Ok(out)
+    }
+
+    fn generate_tests(&self, properties: &[Property]) -> AxiomResult<String> {
+        let (contracts, _) = self.contracts(properties);
+        let cases: Vec<(&SpecFunction, String, String)> = contracts
+            .iter()
+            .filter(|contract| contract.clause == ContractClause::Ensures)
+            .filter_map(|contract| assert_case(contract, &contracts))
+            .collect();
+        if cases.is_empty() {
+            return Err(AxiomError::InvalidInput(
+                "No property is a C postcondition of a function whose inputs can be generated"
+                    .to_string(),
+            ));
+        }
+
+        let mut out = format!("#include <assert.h>\n{}#include <stdio.h>\n\n", HEADERS);
+        let mut declared: Vec<&SpecFunction> = Vec::new();
+        for (function, _, _) in &cases {
+            if !declared.contains(function) {
+                declared.push(function);
+                out.push_str(&format!("{};\n", prototype(function).unwrap_or_default()));
+            }
+        }
+        for (_, _, test) in &cases {
+            out.push('\n');
+            out.push_str(test);
+        }
+        out.push_str("\nint main(void)\n{\n");
+        for (_, name, _) in &cases {
+            out.push_str(&format!("    {}();\n", name));
+        }
+        out.push_str("    puts(\"All property tests passed\");\n    return 0;\n}\n");
+        Ok(out)
+    }
+
+    fn validate_source(&self, source: &str) -> AxiomResult<bool> {
+        let problems = self.check_source(source);
+        for problem in &problems {
+            warn!("{}", problem);
+        }
+        Ok(problems.is_empty())
+    }
+}
+
+/// C type used for values of a specification type, if there is one; sequences have
+/// none, they are passed as a pointer and a length
+pub fn c_type(ty: &SpecType) -> Option<String> {
+    match ty {
+        SpecType::Unit => Some("void".to_string()),
+        SpecType::Bool => Some("bool".to_string()),
+        SpecType::Nat => Some("uint64_t".to_string()),
+        SpecType::Int => Some("int64_t".to_string()),
+        SpecType::Unsigned(bits) if *bits <= 64 => Some(format!("uint{}_t", bits)),
+        SpecType::Signed(bits) if *bits <= 64 => Some(format!("int{}_t", bits)),
+        _ => None,
+    }
+}
+
+/// C parameters standing for a parameter of the specification
+fn c_params(name: &str, ty: &SpecType) -> Option<Vec<String>> {
+    match ty {
+        SpecType::Sequence(element) => Some(vec![
+            format!("const {} *{}", c_type(element)?, name),
+            format!("size_t {}_len", name),
+        ]),
+        SpecType::Unit => Some(vec![]),
+        _ => Some(vec![format!("{} {}", c_type(ty)?, name)]),
+    }
+}
+
+/// C prototype of a function, without the final semicolon, if all its types have a C
+/// counterpart
+fn prototype(function: &SpecFunction) -> Option<String> {
+    let mut params = Vec::new();
+    for param in &function.params {
+        params.extend(c_params(&param.name, &param.ty)?);
+    }
+    let params = if params.is_empty() {
+        "void".to_string()
+    } else {
+        params.join(", ")
+    };
+    Some(format!(
+        "{} {}({})",
+        c_type(&function.returns)?,
+        function.name,
+        params
+    ))
+}
+
+/// ACSL contract block and prototype of a function, with its loop invariants as
+/// annotations to copy into the body
+fn function_contract(function: &SpecFunction, group: &[&Contract]) -> String {
+    let mut clauses = Vec::new();
+    for param in &function.params {
+        if matches!(param.ty, SpecType::Sequence(_)) {
+            clauses.push(format!(
+                "requires \\valid_read({} + (0 .. {}_len - 1));",
+                param.name, param.name
+            ));
+        }
+    }
+    for clause in [ContractClause::Requires, ContractClause::Ensures] {
+        for contract in group.iter().filter(|contract| contract.clause == clause) {
+            let keyword = match clause {
+                ContractClause::Requires => "requires",
+                _ => "ensures",
+            };
+            clauses.push(format!(
+                "// {}: {}",
+                contract.property.id, contract.property.description
+            ));
+            clauses.push(format!(
+                "{} {}: {};",
+                keyword,
+                identifier(&contract.property.id),
+                acsl_condition(&contract.condition)
+            ));
+        }
+    }
+
+    let mut out = format!("// Contracts for `{}`\n", function.name);
+    match prototype(function) {
+        Some(prototype) => {
+            // Prototypes only pass scalars and read-only pointers
+            clauses.push("assigns \\nothing;".to_string());
+            out.push_str(&format!("/*@ {}\n  @*/\n", clauses.join("\n  @ ")));
+            out.push_str(&format!("{};\n", prototype));
+        }
+        None => {
+            out.push_str(&format!(
+                "// `{}` has specification types without a C counterpart; its clauses are\n",
+                function.name
+            ));
+            for clause in &clauses {
+                out.push_str(&format!("//   {}\n", clause));
+            }
+        }
+    }
+
+    for contract in group
+        .iter()
+        .filter(|contract| contract.clause == ContractClause::LoopInvariant)
+    {
+        out.push_str(&format!(
+            "// {}: {}; put before the loop in the body of `{}`:\n//   /*@ loop invariant {}: {}; */\n",
+            contract.property.id,
+            contract.property.description,
+            function.name,
+            identifier(&contract.property.id),
+            acsl_condition(&contract.condition)
+        ));
+    }
+    out
+}
+
+/// Condition with the return value `result` spelled as ACSL `\result`
+fn acsl_condition(condition: &str) -> String {
+    let mut out = String::new();
+    let mut rest = condition;
+    while let Some(start) = rest.find(|c: char| c.is_alphanumeric() || c == '_') {
+        let end = rest[start..]
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .map_or(rest.len(), |end| start + end);
+        out.push_str(&rest[..start]);
+        let member = out.ends_with('\\') || out.ends_with('.') || out.ends_with("->");
+        if &rest[start..end] == "result" && !member {
+            out.push_str("\\result");
+        } else {
+            out.push_str(&rest[start..end]);
+        }
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Whether a condition is a plain C expression rather than ACSL only
+fn is_c_expression(condition: &str) -> bool {
+    !condition.contains('\\') && !condition.contains("==>") && !condition.contains("<==>")
+}
+
+/// Boundary values to test a parameter of the specification type with
+fn sample_values(ty: &SpecType) -> Option<Vec<String>> {
+    let values: Vec<String> = match ty {
+        SpecType::Bool => vec!["false".into(), "true".into()],
+        SpecType::Nat => return sample_values(&SpecType::Unsigned(64)),
+        SpecType::Int => vec![
+            "INT64_MIN".into(),
+            "-100".into(),
+            "-1".into(),
+            "0".into(),
+            "1".into(),
+            "100".into(),
+            "INT64_MAX".into(),
+        ],
+        SpecType::Unsigned(bits) if *bits <= 64 => vec![
+            "0".into(),
+            "1".into(),
+            "2".into(),
+            "100".into(),
+            format!("UINT{}_MAX - 1", bits),
+            format!("UINT{}_MAX", bits),
+        ],
+        SpecType::Signed(bits) if *bits <= 64 => vec![
+            format!("INT{}_MIN", bits),
+            "-1".into(),
+            "0".into(),
+            "1".into(),
+            "100".into(),
+            format!("INT{}_MAX", bits),
+        ],
+        _ => return None,
+    };
+    Some(values)
+}
+
+/// Test function asserting a postcondition on every combination of boundary values
+/// that satisfies the preconditions, with its name, for functions that only take
+/// scalars
+fn assert_case<'a>(
+    contract: &Contract<'a>,
+    contracts: &[Contract],
+) -> Option<(&'a SpecFunction, String, String)> {
+    let function = contract.function?;
+    let preconditions: Vec<&str> = contracts
+        .iter()
+        .filter(|other| {
+            other.clause == ContractClause::Requires && other.function == contract.function
+        })
+        .map(|other| other.condition.as_str())
+        .collect();
+    if !is_c_expression(&contract.condition)
+        || !preconditions
+            .iter()
+            .all(|condition| is_c_expression(condition))
+    {
+        return None;
+    }
+    let result_type = c_type(&function.returns)?;
+
+    let name = format!(
+        "test_{}_{}",
+        identifier(&contract.property.id),
+        function.name
+    );
+    let mut body = String::new();
+    let mut indent = "    ".to_string();
+    for param in &function.params {
+        let ty = c_type(&param.ty)?;
+        let values = sample_values(&param.ty)?;
+        body.push_str(&format!(
+            "{}static const {} {}_values[] = {{{}}};\n",
+            indent,
+            ty,
+            param.name,
+            values.join(", ")
+        ));
+        body.push_str(&format!(
+            "{indent}for (size_t {p}_index = 0; {p}_index < sizeof {p}_values / sizeof {p}_values[0]; {p}_index++) {{\n",
+            indent = indent,
+            p = param.name
+        ));
+        indent.push_str("    ");
+        body.push_str(&format!(
+            "{}{} {} = {}_values[{}_index];\n",
+            indent, ty, param.name, param.name, param.name
+        ));
+    }
+
+    let skip = if function.params.is_empty() {
+        "return"
+    } else {
+        "continue"
+    };
+    for condition in preconditions {
+        body.push_str(&format!(
+            "{}if (!({})) {{\n{}    {};\n{}}}\n",
+            indent, condition, indent, skip, indent
+        ));
+    }
+    let args: Vec<&str> = function
+        .params
+        .iter()
+        .map(|param| param.name.as_str())
+        .collect();
+    let call = format!("{}({})", function.name, args.join(", "));
+    if function.returns == SpecType::Unit {
+        body.push_str(&format!("{}{};\n", indent, call));
+    } else {
+        body.push_str(&format!("{}{} result = {};\n", indent, result_type, call));
+    }
+    body.push_str(&format!(
+        "{}assert(({}) && {:?});\n",
+        indent,
+        contract.condition,
+        format!(
+            "{}: {}",
+            contract.property.id, contract.property.description
+        )
+    ));
+    while indent.len() > 4 {
+        indent.truncate(indent.len() - 4);
+        body.push_str(&format!("{}}}\n", indent));
+    }
+
+    let test = format!(
+        "// {}: {}\nstatic void {}(void)\n{{\n{}}}\n",
+        contract.property.id, contract.property.description, name, body
+    );
+    Some((function, name, test))
+}
+
+/// Source with comments removed, keeping line structure, or the problem with an
+/// unterminated comment or annotation
+fn strip_comments(source: &str) -> Result<String, String> {
+    let mut out = String::new();
+    let mut rest = source;
+    loop {
+        let line = rest.find("//");
+        let block = rest.find("/*");
+        match (line, block) {
+            (Some(start), block) if block.is_none_or(|block| start < block) => {
+                out.push_str(&rest[..start]);
+                rest = rest[start..]
+                    .find('\n')
+                    .map_or("", |end| &rest[start + end..]);
+            }
+            (_, Some(start)) => {
+                out.push_str(&rest[..start]);
+                let Some(end) = rest[start + 2..].find("*/") else {
+                    let line = source.len() - rest.len() + start;
+                    let line = source[..line].lines().count().max(1);
+                    return Err(if rest[start..].starts_with("/*@") {
+                        format!("Unterminated ACSL annotation starting on line {}", line)
+                    } else {
+                        format!("Unterminated comment starting on line {}", line)
+                    });
+                };
+                let comment = &rest[start..start + 2 + end + 2];
+                out.push_str(&"\n".repeat(comment.matches('\n').count()));
+                out.push(' ');
+                rest = &rest[start + 2 + end + 2..];
+            }
+            _ => {
+                out.push_str(rest);
+                return Ok(out);
+            }
+        }
+    }
+}
+
+/// Function declared or defined at file scope
+#[derive(Debug)]
+struct CDeclaration {
+    name: String,
+    returns: String,
+    params: Vec<String>,
+}
+
+/// Functions declared or defined at file scope of comment-free C code
+fn top_level_declarations(code: &str) -> Result<Vec<CDeclaration>, String> {
+    // Keep file scope only, with bodies turned into statement ends
+    let mut top = String::new();
+    let mut depth = 0usize;
+    for line in code.lines() {
+        if depth == 0 && line.trim_start().starts_with('#') {
+            continue;
+        }
+        for c in line.chars() {
+            match c {
+                '{' => {
+                    if depth == 0 {
+                        top.push(';');
+                    }
+                    depth += 1;
+                }
+                '}' => {
+                    depth = depth
+                        .checked_sub(1)
+                        .ok_or_else(|| "Unbalanced braces: unexpected `}`".to_string())?;
+                }
+                _ if depth == 0 => top.push(c),
+                _ => {}
+            }
+        }
+        if depth == 0 {
+            top.push('\n');
+        }
+    }
+    if depth > 0 {
+        return Err("Unbalanced braces: missing `}`".to_string());
+    }
+
+    let mut declarations = Vec::new();
+    for statement in top.split(';') {
+        let Some(open) = statement.find('(') else {
+            continue;
+        };
+        let head = &statement[..open];
+        if head.contains('=') {
+            continue;
+        }
+        let Some(close) = matching_paren(&statement[open..]).map(|end| open + end) else {
+            return Err(format!(
+                "Unbalanced parentheses in `{}`",
+                statement.split_whitespace().collect::<Vec<_>>().join(" ")
+            ));
+        };
+        let head = head.trim_end();
+        let name_start = head
+            .rfind(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .map_or(0, |i| i + 1);
+        let name = &head[name_start..];
+        if name.is_empty() {
+            continue;
+        }
+        let returns = head[..name_start]
+            .split_whitespace()
+            .filter(|word| !matches!(*word, "static" | "inline" | "extern"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        if returns.is_empty() {
+            continue;
+        }
+        let params = statement[open + 1..close].trim();
+        let params = if params.is_empty() || params == "void" {
+            vec![]
+        } else {
+            split_top_level(params, ",")
+                .iter()
+                .map(|param| param_type(param))
+                .collect()
+        };
+        declarations.push(CDeclaration {
+            name: name.to_string(),
+            returns,
+            params,
+        });
+    }
+    Ok(declarations)
+}
+
+/// Type of a C parameter such as `const uint64_t *a`, `int a[]` or `size_t`
+fn param_type(param: &str) -> String {
+    let param = param.trim();
+    let (param, array) = match param.find('[') {
+        Some(open) => (param[..open].trim_end(), true),
+        None => (param, false),
+    };
+    let name_start = param
+        .rfind(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .map_or(0, |i| i + 1);
+    let ty = param[..name_start].trim();
+    // Without a name, the last word is part of the type
+    let ty = if type_name(ty).is_empty() { param } else { ty };
+    if array {
+        format!("{} *", ty)
+    } else {
+        ty.to_string()
+    }
+}
+
+/// Type name without qualifiers and pointers
+fn type_name(ty: &str) -> String {
+    ty.replace('*', " ")
+        .split_whitespace()
+        .filter(|word| !matches!(*word, "const" | "volatile" | "restrict"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Ways in which a C declaration does not fit a specification function
+fn signature_mismatches(function: &SpecFunction, declaration: &CDeclaration) -> Vec<String> {
+    // Sequences take a pointer and a length
+    let mut expected: Vec<(String, SpecType)> = Vec::new();
+    for param in &function.params {
+        match &param.ty {
+            SpecType::Sequence(_) => {
+                expected.push((param.name.clone(), param.ty.clone()));
+                expected.push((format!("{}_len", param.name), SpecType::Nat));
+            }
+            SpecType::Unit => {}
+            ty => expected.push((param.name.clone(), ty.clone())),
+        }
+    }
+
+    if declaration.params.len() != expected.len() {
+        return vec![format!(
+            "`{}` takes {} parameter(s), the specification declares {}",
+            function.name,
+            declaration.params.len(),
+            expected.len()
+        )];
+    }
+
+    let mut mismatches: Vec<String> = expected
+        .iter()
+        .zip(&declaration.params)
+        .filter(|((_, spec_type), ty)| !fits(spec_type, ty))
+        .map(|((name, spec_type), _)| {
+            format!(
+                "Parameter `{}` of `{}` should have a C type for {}",
+                name, function.name, spec_type
+            )
+        })
+        .collect();
+    if !fits(&function.returns, &declaration.returns) {
+        mismatches.push(format!(
+            "`{}` should return a C type for {}",
+            function.name, function.returns
+        ));
+    }
+    mismatches
+}
+
+/// Whether values of the specification type can be passed as the C type
+fn fits(spec_type: &SpecType, ty: &str) -> bool {
+    let pointer = ty.contains('*');
+    let name = type_name(ty);
+    let unsigned = name.starts_with("uint")
+        || name.starts_with("unsigned")
+        || matches!(name.as_str(), "size_t" | "uintmax_t" | "uintptr_t");
+    let signed = !unsigned
+        && (name.starts_with("int")
+            || name.starts_with("signed")
+            || name.contains("long")
+            || name.contains("short")
+            || matches!(name.as_str(), "ptrdiff_t" | "ssize_t" | "intmax_t"));
+
+    match spec_type {
+        SpecType::Sequence(_) => pointer,
+        SpecType::Other(_) => true,
+        _ if pointer => false,
+        SpecType::Unit => name == "void",
+        SpecType::Bool => matches!(name.as_str(), "bool" | "_Bool" | "int"),
+        SpecType::Nat => signed || unsigned,
+        SpecType::Int => signed,
+        SpecType::Unsigned(bits) => name == format!("uint{}_t", bits),
+        SpecType::Signed(bits) => name == format!("int{}_t", bits),
+    }
+}
@@ -0,0 +1,199 @@
+pub mod c;
+pub mod rust;
+pub mod signatures;
+
+pub use c::CAdapter;
+pub use rust::RustAdapter;
+pub use signatures::{spec_functions, SpecFunction, SpecParam, SpecType};
+
+use crate::models::property::{Property, PropertyKind};
+use crate::models::specification::FormalSpecification;
+
+/// Language adapter built around the functions of a specification, which it writes
+/// contracts for and checks implementations against
+pub trait FunctionAdapter: Sized {
+    /// Functions the adapter annotates and checks for
+    fn functions(&self) -> &[SpecFunction];
+
+    fn functions_mut(&mut self) -> &mut Vec<SpecFunction>;
+
+    /// Take the functions to annotate and check for from a formal specification
+    fn with_specification(mut self, spec: &FormalSpecification) -> Self {
+        *self.functions_mut() = spec_functions(spec);
+        self
+    }
+
+    /// Add a function to annotate and check for
+    fn with_function(mut self, function: SpecFunction) -> Self {
+        self.functions_mut().push(function);
+        self
+    }
+
+    /// Properties read as contracts, and the properties that cannot be
+    fn contracts<'a>(
+        &'a self,
+        properties: &'a [Property],
+    ) -> (Vec<Contract<'a>>, Vec<&'a Property>) {
+        let mut contracts = Vec::new();
+        let mut skipped = Vec::new();
+        for property in properties {
+            match property_contract(property, self.functions()) {
+                Some(contract) => contracts.push(contract),
+                None => skipped.push(property),
+            }
+        }
+        (contracts, skipped)
+    }
+}
+
+/// Problems with the declarations of `functions` in a source: functions without a
+/// declaration, and the mismatches of the closest declaration when none fits
+///
+/// `declarations` gives the declarations of a name, and `mismatches` the ways in which a
+/// declaration does not fit a function; any declaration with a fitting signature will do.
+pub fn signature_problems<D>(
+    functions: &[SpecFunction],
+    declarations: impl Fn(&str) -> Vec<D>,
+    mismatches: impl Fn(&SpecFunction, &D) -> Vec<String>,
+) -> Vec<String> {
+    let mut problems = Vec::new();
+    for function in functions {
+        let candidates = declarations(&function.name);
+        if candidates.is_empty() {
+            problems.push(format!("Function `{}` is missing", function.name));
+            continue;
+        }
+        let found: Vec<Vec<String>> = candidates
+            .iter()
+            .map(|declaration| mismatches(function, declaration))
+            .collect();
+        if !found.iter().any(Vec::is_empty) {
+            problems.extend(found.into_iter().min_by_key(Vec::len).unwrap_or_default());
+        }
+    }
+    problems
+}
+
+/// Contracts grouped by the function they constrain, in order of appearance, and the
+/// contracts without a function
+pub fn contracts_by_function<'c, 'a>(
+    contracts: &'c [Contract<'a>],
+) -> (
+    Vec<(&'a SpecFunction, Vec<&'c Contract<'a>>)>,
+    Vec<&'c Contract<'a>>,
+) {
+    let mut groups: Vec<(&SpecFunction, Vec<&Contract>)> = Vec::new();
+    let mut unattached = Vec::new();
+    for contract in contracts {
+        let Some(function) = contract.function else {
+            unattached.push(contract);
+            continue;
+        };
+        match groups.iter_mut().find(|(other, _)| *other == function) {
+            Some((_, group)) => group.push(contract),
+            None => groups.push((function, vec![contract])),
+        }
+    }
+    (groups, unattached)
+}
+
+/// Whether a contract constrains the inputs or the result of a function, or holds on
+/// every iteration of a loop in its body
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContractClause {
+    Requires,
+    Ensures,
+    LoopInvariant,
+}
+
+/// A property read as a pre- or postcondition or a loop invariant of a function
+///
+/// The formal definition of such a property is a boolean expression in the target
+/// language, optionally prefixed with the function it constrains and the clause:
+/// `incr: requires x < 100`, `ensures result > x` or `loop invariant 0 <= i <= n`.
+/// Without a clause the property is a postcondition, and without a function it belongs
+/// to the only function of the specification, if there is exactly one. Postconditions
+/// name the return value `result`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contract<'a> {
+    pub property: &'a Property,
+    pub function: Option<&'a SpecFunction>,
+    pub clause: ContractClause,
+    pub condition: String,
+}
+
+/// Read a property as a contract on one of `functions`
+///
+/// Liveness and resource usage properties cannot be stated as contracts, and neither can
+/// properties without a formal definition or naming a function not in `functions`; these
+/// give `None`.
+pub fn property_contract<'a>(
+    property: &'a Property,
+    functions: &'a [SpecFunction],
+) -> Option<Contract<'a>> {
+    if matches!(
+        property.kind,
+        PropertyKind::Liveness | PropertyKind::ResourceUsage
+    ) {
+        return None;
+    }
+
+    let mut definition = property.formal_definition.trim();
+    let mut function_name = None;
+    if let Some((head, rest)) = definition.split_once(':') {
+        let head = head.trim();
+        let is_identifier = !head.is_empty()
+            && head.chars().all(|c| c.is_alphanumeric() || c == '_')
+            && !head.starts_with(|c: char| c.is_ascii_digit());
+        if is_identifier && !rest.starts_with(':') {
+            function_name = Some(head);
+            definition = rest.trim();
+        }
+    }
+
+    let (clause, condition) = if let Some(condition) = definition.strip_prefix("requires ") {
+        (ContractClause::Requires, condition)
+    } else if let Some(condition) = definition.strip_prefix("ensures ") {
+        (ContractClause::Ensures, condition)
+    } else if let Some(condition) = definition.strip_prefix("loop invariant ") {
+        (ContractClause::LoopInvariant, condition)
+    } else {
+        (ContractClause::Ensures, definition)
+    };
+    let condition = condition.trim();
+    if condition.is_empty() {
+        return None;
+    }
+
+    let function = match function_name {
+        Some(name) => Some(functions.iter().find(|function| function.name == name)?),
+        None if functions.len() == 1 => functions.first(),
+        None => None,
+    };
+
+    Some(Contract {
+        property,
+        function,
+        clause,
+        condition: condition.to_string(),
+    })
+}
+
+/// Lowercase identifier built from a property id, for generated test and harness names
+pub fn identifier(id: &str) -> String {
+    let identifier: String = id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("p_{}", identifier)
+    } else {
+        identifier
+    }
+}
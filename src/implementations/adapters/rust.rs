@@ -0,0 +1,457 @@
+use std::collections::HashMap;
+
+use log::warn;
+
+use crate::errors::{AxiomError, AxiomResult};
+use crate::implementations::adapters::{
+    contracts_by_function, identifier, signature_problems, Contract, ContractClause,
+    FunctionAdapter, SpecFunction, SpecType,
+};
+use crate::models::common::Language;
+use crate::models::property::Property;
+use crate::traits::language_adapter::LanguageAdapter;
+
+/// Language adapter for Rust
+///
+/// Properties become Prusti `#[requires]`/`#[ensures]` attributes on stubs of the
+/// functions of the specification, whose bodies the implementation fills in, and Kani
+/// proof harnesses; tests are `proptest` property tests. See `Contract` for how
+/// properties are read. Harnesses and tests call the functions of the specification set
+/// with `with_specification`, which `validate_source` also checks the source against.
+#[derive(Debug, Clone, Default)]
+pub struct RustAdapter {
+    functions: Vec<SpecFunction>,
+}
+
+impl RustAdapter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Problems that keep the source from matching the specification: syntax errors,
+    /// and functions that are missing or whose signature does not fit
+    pub fn check_source(&self, source: &str) -> Vec<String> {
+        let file = match syn::parse_file(source) {
+            Ok(file) => file,
+            Err(e) => return vec![format!("Syntax error: {}", e)],
+        };
+
+        let mut signatures = HashMap::new();
+        collect_signatures(&file.items, &mut signatures);
+
+        signature_problems(
+            &self.functions,
+            |name| signatures.get(name).cloned().unwrap_or_default(),
+            signature_mismatches,
+        )
+    }
+}
+
+impl FunctionAdapter for RustAdapter {
+    fn functions(&self) -> &[SpecFunction] {
+        &self.functions
+    }
+
+    fn functions_mut(&mut self) -> &mut Vec<SpecFunction> {
+        &mut self.functions
+    }
+}
+
+impl LanguageAdapter for RustAdapter {
+    fn language(&self) -> Language {
+        Language::Rust
+    }
+
+    fn convert_properties(&self, properties: &[Property]) -> AxiomResult<String> {
+        let (contracts, skipped) = self.contracts(properties);
+        let (groups, unattached) = contracts_by_function(&contracts);
+        let mut out = String::new();
+        if !groups.is_empty() {
+            out.push_str("use prusti_contracts::*;\n");
+        }
+
+        for (function, group) in &groups {
+            out.push('\n');
+            out.push_str(&function_contract(function, group));
+        }
+
+        if !unattached.is_empty() || !skipped.is_empty() {
+            out.push('\n');
+        }
+        for contract in &unattached {
+            out.push_str(&format!(
+                "// {}: no function of the specification to attach `{}` to\n",
+                contract.property.id, contract.condition
+            ));
+        }
+        for property in &skipped {
+            out.push_str(&format!(
+                "// {}: cannot be stated as a contract: {}\n",
+                property.id, property.description
+            ));
+        }
+
+        let harnesses: Vec<String> = contracts
+            .iter()
+            .filter(|contract| contract.clause == ContractClause::Ensures)
+            .filter_map(|contract| kani_harness(contract, &contracts))
+            .collect();
+        if !harnesses.is_empty() {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str("#[cfg(kani)]\nmod verification {\n    use super::*;\n");
+            for harness in harnesses {
+                out.push('\n');
+                out.push_str(&harness);
+            }
+            out.push_str("}\n");
+        }
+
+        Ok(out)
+    }
+
+    fn generate_tests(&self, properties: &[Property]) -> AxiomResult<String> {
+        let (contracts, _) = self.contracts(properties);
+        let tests: Vec<String> = contracts
+            .iter()
+            .filter(|contract| contract.clause == ContractClause::Ensures)
+            .filter_map(|contract| proptest_case(contract, &contracts))
+            .collect();
+        if tests.is_empty() {
+            return Err(AxiomError::InvalidInput(
+                "No property is a postcondition of a function whose inputs can be generated"
+                    .to_string(),
+            ));
+        }
+
+        let mut out = String::from(
+            "#[cfg(test)]\nmod property_tests {\n    use super::*;\n    use proptest::prelude::*;\n\n    proptest! {\n",
+        );
+        out.push_str(&tests.join("\n"));
+        out.push_str("    }\n}\n");
+        Ok(out)
+    }
+
+    fn validate_source(&self, source: &str) -> AxiomResult<bool> {
+        let problems = self.check_source(source);
+        for problem in &problems {
+            warn!("{}", problem);
+        }
+        Ok(problems.is_empty())
+    }
+}
+
+/// Rust type used for inputs of a specification type, if there is one
+pub fn rust_type(ty: &SpecType) -> Option<String> {
+    match ty {
+        SpecType::Unit => Some("()".to_string()),
+        SpecType::Bool => Some("bool".to_string()),
+        SpecType::Nat => Some("u64".to_string()),
+        SpecType::Int => Some("i64".to_string()),
+        SpecType::Unsigned(bits) => Some(format!("u{}", bits)),
+        SpecType::Signed(bits) => Some(format!("i{}", bits)),
+        SpecType::Sequence(element) => {
+            rust_type(element).map(|element| format!("Vec<{}>", element))
+        }
+        SpecType::Other(_) => None,
+    }
+}
+
+/// Rust signature of a function, if all its types have a Rust counterpart
+fn signature(function: &SpecFunction) -> Option<String> {
+    let params = function
+        .params
+        .iter()
+        .map(|param| Some(format!("{}: {}", param.name, rust_type(&param.ty)?)))
+        .collect::<Option<Vec<String>>>()?;
+    let returns = match &function.returns {
+        SpecType::Unit => String::new(),
+        ty => format!(" -> {}", rust_type(ty)?),
+    };
+    Some(format!(
+        "pub fn {}({}){}",
+        function.name,
+        params.join(", "),
+        returns
+    ))
+}
+
+/// Prusti contract attributes on a stub of a function, with its loop invariants as
+/// `body_invariant!`s to put into the body
+fn function_contract(function: &SpecFunction, group: &[&Contract]) -> String {
+    let mut attributes = Vec::new();
+    for clause in [ContractClause::Requires, ContractClause::Ensures] {
+        for contract in group.iter().filter(|contract| contract.clause == clause) {
+            let attribute = match clause {
+                ContractClause::Requires => "requires",
+                _ => "ensures",
+            };
+            attributes.push(format!(
+                "// {}: {}",
+                contract.property.id, contract.property.description
+            ));
+            attributes.push(format!("#[{}({})]", attribute, contract.condition));
+        }
+    }
+
+    let mut out = format!("// Contracts for `{}`\n", function.name);
+    match signature(function) {
+        Some(signature) => {
+            for attribute in &attributes {
+                out.push_str(&format!("{}\n", attribute));
+            }
+            out.push_str(&format!(
+                "{} {{\n    unimplemented!(\"replace with the implementation of `{}`\")\n}}\n",
+                signature, function.name
+            ));
+        }
+        None => {
+            out.push_str(&format!(
+                "// `{}` has specification types without a Rust counterpart; its attributes are\n",
+                function.name
+            ));
+            for attribute in &attributes {
+                out.push_str(&format!("//   {}\n", attribute));
+            }
+        }
+    }
+
+    // Prusti states loop invariants inside the loop body
+    for contract in group
+        .iter()
+        .filter(|contract| contract.clause == ContractClause::LoopInvariant)
+    {
+        out.push_str(&format!(
+            "// {}: {}; put at the start of the loop body in `{}`:\n//   body_invariant!({});\n",
+            contract.property.id, contract.property.description, function.name, contract.condition
+        ));
+    }
+    out
+}
+
+/// Preconditions of the function a postcondition belongs to
+fn preconditions<'a>(contract: &Contract, contracts: &'a [Contract]) -> Vec<&'a str> {
+    contracts
+        .iter()
+        .filter(|other| {
+            other.clause == ContractClause::Requires && other.function == contract.function
+        })
+        .map(|other| other.condition.as_str())
+        .collect()
+}
+
+fn call(function: &SpecFunction) -> String {
+    let args: Vec<&str> = function
+        .params
+        .iter()
+        .map(|param| param.name.as_str())
+        .collect();
+    format!("{}({})", function.name, args.join(", "))
+}
+
+/// Kani harness checking a postcondition on arbitrary inputs, for functions that only
+/// take scalars
+fn kani_harness(contract: &Contract, contracts: &[Contract]) -> Option<String> {
+    let function = contract.function?;
+    let mut body = String::new();
+    for param in &function.params {
+        if matches!(param.ty, SpecType::Sequence(_)) {
+            return None;
+        }
+        body.push_str(&format!(
+            "        let {}: {} = kani::any();\n",
+            param.name,
+            rust_type(&param.ty)?
+        ));
+    }
+    for condition in preconditions(contract, contracts) {
+        body.push_str(&format!("        kani::assume({});\n", condition));
+    }
+    body.push_str(&format!("        let result = {};\n", call(function)));
+    body.push_str(&format!(
+        "        assert!({}, {:?});\n",
+        contract.condition,
+        format!(
+            "{}: {}",
+            contract.property.id, contract.property.description
+        )
+    ));
+
+    Some(format!(
+        "    /// {}: {}\n    #[kani::proof]\n    fn check_{}() {{\n{}    }}\n",
+        contract.property.id,
+        contract.property.description,
+        identifier(&contract.property.id),
+        body
+    ))
+}
+
+/// `proptest!` case checking a postcondition on generated inputs
+fn proptest_case(contract: &Contract, contracts: &[Contract]) -> Option<String> {
+    let function = contract.function?;
+    let strategies = function
+        .params
+        .iter()
+        .map(|param| {
+            Some(format!(
+                "{} in any::<{}>()",
+                param.name,
+                rust_type(&param.ty)?
+            ))
+        })
+        .collect::<Option<Vec<String>>>()?;
+
+    let mut body = String::new();
+    for condition in preconditions(contract, contracts) {
+        body.push_str(&format!("            prop_assume!({});\n", condition));
+    }
+    body.push_str(&format!("            let result = {};\n", call(function)));
+    body.push_str(&format!(
+        "            prop_assert!({});\n",
+        contract.condition
+    ));
+
+    Some(format!(
+        "        /// {}: {}\n        #[test]\n        fn {}_{}({}) {{\n{}        }}\n",
+        contract.property.id,
+        contract.property.description,
+        identifier(&contract.property.id),
+        function.name,
+        strategies.join(", "),
+        body
+    ))
+}
+
+/// Record the signature of every function, including methods and functions in inline modules
+fn collect_signatures(items: &[syn::Item], signatures: &mut HashMap<String, Vec<syn::Signature>>) {
+    for item in items {
+        match item {
+            syn::Item::Fn(item) => signatures
+                .entry(item.sig.ident.to_string())
+                .or_default()
+                .push(item.sig.clone()),
+            syn::Item::Impl(item) => {
+                for impl_item in &item.items {
+                    if let syn::ImplItem::Fn(method) = impl_item {
+                        signatures
+                            .entry(method.sig.ident.to_string())
+                            .or_default()
+                            .push(method.sig.clone());
+                    }
+                }
+            }
+            syn::Item::Mod(item) => {
+                if let Some((_, items)) = &item.content {
+                    collect_signatures(items, signatures);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Ways in which a Rust signature does not fit a specification function
+fn signature_mismatches(function: &SpecFunction, signature: &syn::Signature) -> Vec<String> {
+    let inputs: Vec<&syn::Type> = signature
+        .inputs
+        .iter()
+        .filter_map(|input| match input {
+            syn::FnArg::Typed(pattern) => Some(pattern.ty.as_ref()),
+            syn::FnArg::Receiver(_) => None,
+        })
+        .collect();
+
+    if inputs.len() != function.params.len() {
+        return vec![format!(
+            "`{}` takes {} parameter(s), the specification declares {}",
+            function.name,
+            inputs.len(),
+            function.params.len()
+        )];
+    }
+
+    let mut mismatches: Vec<String> = function
+        .params
+        .iter()
+        .zip(&inputs)
+        .filter(|(param, ty)| !fits(&param.ty, ty))
+        .map(|(param, _)| {
+            format!(
+                "Parameter `{}` of `{}` should have a Rust type for {}",
+                param.name, function.name, param.ty
+            )
+        })
+        .collect();
+
+    let fits_result = match &signature.output {
+        syn::ReturnType::Default => function.returns == SpecType::Unit,
+        syn::ReturnType::Type(_, ty) => fits(&function.returns, success_type(ty)),
+    };
+    if !fits_result {
+        mismatches.push(format!(
+            "`{}` should return a Rust type for {}",
+            function.name, function.returns
+        ));
+    }
+    mismatches
+}
+
+/// The value type of `Result<T, _>` and `Option<T>`, or the type itself
+fn success_type(ty: &syn::Type) -> &syn::Type {
+    if let syn::Type::Path(path) = ty {
+        if let Some(segment) = path.path.segments.last() {
+            if segment.ident == "Result" || segment.ident == "Option" {
+                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                    if let Some(syn::GenericArgument::Type(inner)) = args.args.first() {
+                        return inner;
+                    }
+                }
+            }
+        }
+    }
+    ty
+}
+
+/// Whether values of the specification type can be passed as the Rust type
+fn fits(spec_type: &SpecType, ty: &syn::Type) -> bool {
+    let ty = match ty {
+        syn::Type::Reference(reference) => return fits(spec_type, &reference.elem),
+        syn::Type::Paren(paren) => return fits(spec_type, &paren.elem),
+        syn::Type::Group(group) => return fits(spec_type, &group.elem),
+        other => other,
+    };
+
+    let name = match ty {
+        syn::Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+            .unwrap_or_default(),
+        syn::Type::Tuple(tuple) if tuple.elems.is_empty() => "()".to_string(),
+        syn::Type::Slice(_) | syn::Type::Array(_) => "[]".to_string(),
+        _ => String::new(),
+    };
+    let signed = matches!(
+        name.as_str(),
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "BigInt"
+    );
+    let unsigned = matches!(
+        name.as_str(),
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "BigUint"
+    );
+
+    match spec_type {
+        SpecType::Unit => name == "()",
+        SpecType::Bool => name == "bool",
+        SpecType::Nat => signed || unsigned,
+        SpecType::Int => signed,
+        SpecType::Unsigned(bits) => {
+            name == format!("u{}", bits) || (*bits == 64 && name == "usize")
+        }
+        SpecType::Signed(bits) => name == format!("i{}", bits) || (*bits == 64 && name == "isize"),
+        SpecType::Sequence(_) => matches!(name.as_str(), "[]" | "Vec" | "VecDeque"),
+        SpecType::Other(_) => true,
+    }
+}
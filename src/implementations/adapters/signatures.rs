@@ -0,0 +1,377 @@
+use std::fmt;
+
+use crate::models::common::VerificationLanguage;
+use crate::models::specification::FormalSpecification;
+
+/// Type of a parameter or result in a specification, as far as adapters care about it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpecType {
+    Unit,
+    Bool,
+    /// Mathematical natural number (`nat`)
+    Nat,
+    /// Mathematical integer (`int`)
+    Int,
+    /// Machine integer of the given width, e.g. F* `U32.t` or Dafny `bv32`
+    Unsigned(u8),
+    Signed(u8),
+    /// Sequence or array of elements
+    Sequence(Box<SpecType>),
+    /// Any other type, by its name in the specification
+    Other(String),
+}
+
+impl fmt::Display for SpecType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpecType::Unit => write!(f, "unit"),
+            SpecType::Bool => write!(f, "bool"),
+            SpecType::Nat => write!(f, "nat"),
+            SpecType::Int => write!(f, "int"),
+            SpecType::Unsigned(bits) => write!(f, "u{}", bits),
+            SpecType::Signed(bits) => write!(f, "i{}", bits),
+            SpecType::Sequence(element) => write!(f, "seq<{}>", element),
+            SpecType::Other(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// Parameter of a function declared by a specification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpecParam {
+    pub name: String,
+    pub ty: SpecType,
+}
+
+/// Executable function declared by a specification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpecFunction {
+    pub name: String,
+    pub params: Vec<SpecParam>,
+    pub returns: SpecType,
+}
+
+/// Effects that may wrap the result type of an F* function
+const FSTAR_EFFECTS: &[&str] = &[
+    "Tot",
+    "GTot",
+    "Pure",
+    "ST",
+    "Stack",
+    "StackInline",
+    "ML",
+    "Dv",
+];
+
+/// Functions the specification declares, in order of declaration
+///
+/// F* `val` declarations and Dafny methods, functions and predicates are recognized;
+/// lemmas and ghost declarations are skipped. Other languages declare no functions.
+pub fn spec_functions(spec: &FormalSpecification) -> Vec<SpecFunction> {
+    match spec.verification_language {
+        VerificationLanguage::FStarLang => fstar_functions(&spec.spec_code),
+        VerificationLanguage::DafnyLang => dafny_functions(&spec.spec_code),
+        _ => vec![],
+    }
+}
+
+/// Parse F* `val name : binder -> ... -> result` declarations, including indented
+/// continuation lines
+fn fstar_functions(code: &str) -> Vec<SpecFunction> {
+    let mut declarations: Vec<String> = Vec::new();
+    let mut current: Option<String> = None;
+    for line in code.lines() {
+        let continues = line.starts_with(char::is_whitespace) && !line.trim().is_empty();
+        match &mut current {
+            Some(declaration) if continues => {
+                declaration.push(' ');
+                declaration.push_str(line.trim());
+            }
+            _ => {
+                declarations.extend(current.take());
+                current = line
+                    .strip_prefix("val ")
+                    .map(|rest| rest.trim().to_string());
+            }
+        }
+    }
+    declarations.extend(current);
+
+    declarations
+        .iter()
+        .filter_map(|declaration| {
+            let (name, signature) = declaration.split_once(':')?;
+            let name = name.trim();
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                return None;
+            }
+
+            let mut segments = split_top_level(signature, "->");
+            let result = segments.pop()?;
+            let result = strip_effect(&result)?;
+
+            let params = segments
+                .iter()
+                .map(|segment| fstar_binder(segment))
+                .filter(|(name, ty)| !name.starts_with('#') && *ty != SpecType::Unit)
+                .enumerate()
+                .map(|(i, (name, ty))| SpecParam {
+                    name: if name.is_empty() {
+                        format!("arg{}", i)
+                    } else {
+                        name
+                    },
+                    ty,
+                })
+                .collect();
+
+            Some(SpecFunction {
+                name: name.to_string(),
+                params,
+                returns: fstar_binder(&result).1,
+            })
+        })
+        .collect()
+}
+
+/// Remove the effect of an F* result type, or `None` for a lemma
+fn strip_effect(result: &str) -> Option<String> {
+    let result = result.trim();
+    let (head, rest) = result
+        .split_once(char::is_whitespace)
+        .unwrap_or((result, ""));
+    if head == "Lemma" {
+        return None;
+    }
+    if !FSTAR_EFFECTS.contains(&head) {
+        return Some(result.to_string());
+    }
+
+    // The result type is the first argument of the effect
+    let rest = rest.trim_start();
+    if rest.starts_with('(') {
+        Some(rest[..matching_paren(rest).map_or(rest.len(), |end| end + 1)].to_string())
+    } else {
+        Some(rest.split_whitespace().next().unwrap_or("unit").to_string())
+    }
+}
+
+/// Name and type of an F* binder such as `x:nat`, `(x:nat{x > 0})` or `nat`
+fn fstar_binder(binder: &str) -> (String, SpecType) {
+    let mut binder = binder.trim();
+    while binder.starts_with('(') && matching_paren(binder) == Some(binder.len() - 1) {
+        binder = binder[1..binder.len() - 1].trim();
+    }
+    let binder = binder.split('{').next().unwrap_or(binder);
+
+    match binder.split_once(':') {
+        Some((name, ty)) if !name.trim().contains(char::is_whitespace) => {
+            (name.trim().to_string(), fstar_type(ty.trim()))
+        }
+        _ => (String::new(), fstar_type(binder.trim())),
+    }
+}
+
+fn fstar_type(ty: &str) -> SpecType {
+    let ty = ty.trim();
+    match ty {
+        "unit" => SpecType::Unit,
+        "bool" => SpecType::Bool,
+        "nat" | "pos" => SpecType::Nat,
+        "int" => SpecType::Int,
+        _ => {
+            if let Some(element) = ty
+                .strip_prefix("seq ")
+                .or_else(|| ty.strip_prefix("Seq.seq "))
+                .or_else(|| ty.strip_prefix("list "))
+            {
+                return SpecType::Sequence(Box::new(fstar_type(element)));
+            }
+            machine_integer(ty.trim_end_matches(".t").rsplit('.').next().unwrap_or(ty))
+                .unwrap_or_else(|| SpecType::Other(ty.to_string()))
+        }
+    }
+}
+
+/// Machine integers named like F* `U32`, `UInt32`, `Int64` or Dafny `bv16`
+fn machine_integer(name: &str) -> Option<SpecType> {
+    let (signed, bits) = if let Some(bits) = name.strip_prefix("UInt") {
+        (false, bits)
+    } else if let Some(bits) = name.strip_prefix("Int") {
+        (true, bits)
+    } else if let Some(bits) = name.strip_prefix('U') {
+        (false, bits)
+    } else if let Some(bits) = name.strip_prefix('I') {
+        (true, bits)
+    } else if let Some(bits) = name.strip_prefix("bv") {
+        (false, bits)
+    } else {
+        return None;
+    };
+
+    match bits.parse::<u8>() {
+        Ok(bits @ (8 | 16 | 32 | 64 | 128)) if signed => Some(SpecType::Signed(bits)),
+        Ok(bits @ (8 | 16 | 32 | 64 | 128)) => Some(SpecType::Unsigned(bits)),
+        _ => None,
+    }
+}
+
+/// Parse Dafny `method`, `function` and `predicate` declarations
+fn dafny_functions(code: &str) -> Vec<SpecFunction> {
+    let line_starts = std::iter::once(0).chain(code.match_indices('\n').map(|(i, _)| i + 1));
+
+    let mut functions = Vec::new();
+    for start in line_starts {
+        let mut text = code[start..].trim_start_matches([' ', '\t']);
+        for modifier in ["static ", "opaque "] {
+            text = text
+                .strip_prefix(modifier)
+                .unwrap_or(text)
+                .trim_start_matches([' ', '\t']);
+        }
+        let (returns_bool, rest) = if let Some(rest) = text.strip_prefix("method ") {
+            (false, rest)
+        } else if let Some(rest) = text.strip_prefix("function ") {
+            (
+                false,
+                rest.trim_start().strip_prefix("method ").unwrap_or(rest),
+            )
+        } else if let Some(rest) = text.strip_prefix("predicate ") {
+            (true, rest)
+        } else {
+            continue;
+        };
+
+        let rest = rest.trim_start();
+        let Some(open) = rest.find('(') else {
+            continue;
+        };
+        let name = rest[..open].split('<').next().unwrap_or("").trim();
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            continue;
+        }
+        let Some(close) = matching_paren(&rest[open..]).map(|end| open + end) else {
+            continue;
+        };
+
+        let after = rest[close + 1..].trim_start();
+        let returns = if returns_bool {
+            SpecType::Bool
+        } else if let Some(results) = after.strip_prefix("returns") {
+            let results = results.trim_start();
+            let results = match matching_paren(results) {
+                Some(end) if results.starts_with('(') => dafny_params(&results[1..end]),
+                _ => vec![],
+            };
+            match results.as_slice() {
+                [] => SpecType::Unit,
+                [single] => single.ty.clone(),
+                several => SpecType::Other(
+                    several
+                        .iter()
+                        .map(|param| param.ty.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ),
+            }
+        } else if let Some(result) = after.strip_prefix(':') {
+            let result = result
+                .split(['\n', '{'])
+                .next()
+                .unwrap_or("")
+                .split_whitespace()
+                .take_while(|word| !matches!(*word, "requires" | "ensures" | "reads" | "decreases"))
+                .collect::<Vec<_>>()
+                .join(" ");
+            dafny_type(&result)
+        } else {
+            SpecType::Unit
+        };
+
+        functions.push(SpecFunction {
+            name: name.to_string(),
+            params: dafny_params(&rest[open + 1..close]),
+            returns,
+        });
+    }
+    functions
+}
+
+fn dafny_params(params: &str) -> Vec<SpecParam> {
+    split_top_level(params, ",")
+        .iter()
+        .filter_map(|param| {
+            let param = param.trim().trim_start_matches("ghost ").trim();
+            let (name, ty) = param.split_once(':')?;
+            Some(SpecParam {
+                name: name.trim().to_string(),
+                ty: dafny_type(ty),
+            })
+        })
+        .collect()
+}
+
+fn dafny_type(ty: &str) -> SpecType {
+    let ty = ty.trim();
+    match ty {
+        "" => SpecType::Unit,
+        "bool" => SpecType::Bool,
+        "nat" => SpecType::Nat,
+        "int" => SpecType::Int,
+        _ => {
+            for prefix in ["seq<", "array<"] {
+                if let Some(element) = ty.strip_prefix(prefix).and_then(|t| t.strip_suffix('>')) {
+                    return SpecType::Sequence(Box::new(dafny_type(element)));
+                }
+            }
+            machine_integer(ty).unwrap_or_else(|| SpecType::Other(ty.to_string()))
+        }
+    }
+}
+
+/// Split on a separator outside of parentheses, brackets and braces, and outside of
+/// angle brackets when splitting on commas
+pub(crate) fn split_top_level(text: &str, separator: &str) -> Vec<String> {
+    let angles = separator == ",";
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    let mut chars = text.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            '<' if angles => depth += 1,
+            '>' if angles => depth -= 1,
+            _ if depth == 0 && text[i..].starts_with(separator) => {
+                parts.push(text[start..i].to_string());
+                start = i + separator.len();
+                // Skip the rest of the separator
+                for _ in 1..separator.chars().count() {
+                    chars.next();
+                }
+            }
+            _ => {}
+        }
+    }
+    parts.push(text[start..].to_string());
+    parts
+}
+
+/// Index of the parenthesis closing the one `text` starts with
+pub(crate) fn matching_paren(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
@@ -0,0 +1,404 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use log::{debug, info, warn};
+
+use crate::config::ExternalToolsConfig;
+use crate::errors::{AxiomError, AxiomResult};
+use crate::implementations::backends::{
+    create_run_dir, incompatible_implementation, probe_tool, run_with_timeout, write_run_file,
+};
+use crate::models::common::{Language, ResourceUsage, VerificationLanguage};
+use crate::models::implementation::Implementation;
+use crate::models::specification::Specification;
+use crate::models::verification::{
+    ArtifactType, GoalOutcome, GoalResult, ProofArtifact, VerificationOptions, VerificationResult,
+    VerificationStatus,
+};
+use crate::traits::verification_engine::VerificationBackendAdapter;
+
+/// Logical path the working directory is bound to with `-Q`
+const LOGICAL_ROOT: &str = "Axiom";
+
+/// Vernacular commands that open a proof or a definition that may be proven interactively
+const DECLARATION_KEYWORDS: &[&str] = &[
+    "Theorem", "Lemma", "Fact", "Remark", "Corollary", "Proposition", "Example", "Definition",
+    "Fixpoint", "Instance", "Program",
+];
+
+/// Proof-checker backend that compiles Coq files with `coqc`
+///
+/// A file that compiles but still contains `Admitted` or `admit` is reported as
+/// `Unverified`, with one `Incomplete` goal per unfinished proof. The executable is
+/// looked up under the `coqc` key of `ExternalToolsConfig::tool_paths` and
+/// defaults to `coqc` on the `PATH`.
+#[derive(Debug, Clone)]
+pub struct CoqBackend {
+    coqc_path: String,
+    timeout: Duration,
+    work_root: PathBuf,
+}
+
+impl CoqBackend {
+    /// Create a backend from the external tools configuration
+    pub fn new(tools: &ExternalToolsConfig) -> Self {
+        Self {
+            coqc_path: tools.tool_path("coqc", "coqc"),
+            timeout: tools.timeout,
+            work_root: std::env::temp_dir().join("axiom_coq"),
+        }
+    }
+
+    /// Set the directory under which per-run working directories are created
+    pub fn with_work_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.work_root = dir.into();
+        self
+    }
+
+    /// Path of the `coqc` executable this backend runs
+    pub fn coqc_path(&self) -> &str {
+        &self.coqc_path
+    }
+}
+
+impl VerificationBackendAdapter for CoqBackend {
+    fn convert_specification(&self, spec: &Specification) -> AxiomResult<String> {
+        if spec.formal_spec.verification_language != VerificationLanguage::CoqLang {
+            return Err(AxiomError::LanguageCompatibilityError {
+                source_lang: spec.formal_spec.verification_language.to_string(),
+                target_lang: VerificationLanguage::CoqLang.to_string(),
+            });
+        }
+
+        Ok(spec.formal_spec.spec_code.clone())
+    }
+
+    fn convert_implementation(&self, implementation: &Implementation) -> AxiomResult<String> {
+        if !self.supports_implementation_language(&implementation.language) {
+            return Err(incompatible_implementation(
+                &implementation.language,
+                VerificationLanguage::CoqLang,
+            ));
+        }
+        Ok(implementation.source_code.clone())
+    }
+
+    fn supports_implementation_language(&self, language: &Language) -> bool {
+        match language {
+            Language::Custom(name) => matches!(name.to_lowercase().as_str(), "coq" | "gallina"),
+            _ => false,
+        }
+    }
+
+    fn execute_verification(
+        &self,
+        converted_spec: &str,
+        converted_impl: &str,
+        options: &VerificationOptions,
+    ) -> AxiomResult<VerificationResult> {
+        let run_dir = create_run_dir(&self.work_root, "run")?;
+        debug!("Coq working directory: {}", run_dir.display());
+
+        let spec_file = run_dir.join("Spec.v");
+        write_run_file(&spec_file, converted_spec)?;
+
+        // The implementation is compiled after the spec so it can `Require Import Spec`
+        let impl_file = if converted_impl.trim().is_empty() {
+            spec_file.clone()
+        } else {
+            let path = run_dir.join("Impl.v");
+            write_run_file(&path, converted_impl)?;
+            path
+        };
+
+        let mut command = self.get_verification_command(&spec_file, &impl_file, options)?;
+        info!("Running coqc in {}", run_dir.display());
+        let output = run_with_timeout(&mut command, "Coq", options.timeout)?;
+
+        let log_path = run_dir.join("coqc.log");
+        write_run_file(&log_path, &output.combined())?;
+
+        let mut result = if output.timed_out {
+            warn!("coqc timed out after {:?}", options.timeout);
+            VerificationResult {
+                status: VerificationStatus::Timeout,
+                proof_artifacts: vec![],
+                verification_time: output.elapsed,
+                resource_usage: ResourceUsage::default(),
+                goal_results: vec![],
+                counterexamples: vec![],
+            }
+        } else {
+            self.parse_verification_output(&output.combined(), output.exit_code.unwrap_or(-1))?
+        };
+
+        // coqc accepts `Admitted` silently, so holes are found in the source
+        let mut goals = proof_goals(converted_spec);
+        goals.extend(proof_goals(converted_impl));
+        if matches!(result.status, VerificationStatus::Verified)
+            && goals.iter().any(|g| g.outcome == GoalOutcome::Incomplete)
+        {
+            result.status = VerificationStatus::Unverified;
+        }
+        result.resource_usage.lemmas_proven = goals.iter().filter(|g| g.outcome.is_valid()).count();
+        result.goal_results = goals;
+
+        result.verification_time = output.elapsed;
+        result.resource_usage.cpu_seconds = output.elapsed.as_secs_f64();
+        result.proof_artifacts.push(ProofArtifact {
+            artifact_type: ArtifactType::Log,
+            path: log_path.display().to_string(),
+            description: "coqc output".to_string(),
+        });
+        result.proof_artifacts.extend(
+            self.extract_artifacts(&run_dir)?
+                .into_iter()
+                .filter(|a| !matches!(a.artifact_type, ArtifactType::Log)),
+        );
+
+        Ok(result)
+    }
+
+    fn extract_artifacts(&self, output_dir: &Path) -> AxiomResult<Vec<ProofArtifact>> {
+        let entries = std::fs::read_dir(output_dir).map_err(|e| {
+            AxiomError::SystemError(format!("Failed to read {}: {}", output_dir.display(), e))
+        })?;
+
+        let mut artifacts = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+            let (artifact_type, description) = match extension {
+                "vo" => (ArtifactType::Proof, "Compiled Coq proof object"),
+                "glob" => (ArtifactType::Custom("glob".to_string()), "Coq cross-reference data"),
+                "log" => (ArtifactType::Log, "coqc output"),
+                _ => continue,
+            };
+            artifacts.push(ProofArtifact {
+                artifact_type,
+                path: path.display().to_string(),
+                description: description.to_string(),
+            });
+        }
+
+        Ok(artifacts)
+    }
+
+    fn check_backend_availability(&self) -> AxiomResult<bool> {
+        Ok(probe_tool(&self.coqc_path, &["--version"]))
+    }
+
+    fn get_backend_version(&self) -> AxiomResult<String> {
+        let output = run_with_timeout(
+            Command::new(&self.coqc_path).arg("--version"),
+            "Coq",
+            self.timeout,
+        )?;
+
+        output
+            .stdout
+            .lines()
+            .map(|line| line.trim())
+            .find(|line| !line.is_empty())
+            .map(|line| line.to_string())
+            .ok_or_else(|| AxiomError::ExternalToolError {
+                tool: "Coq".to_string(),
+                message: "No version information reported".to_string(),
+            })
+    }
+
+    fn install_dependencies(&self) -> AxiomResult<()> {
+        if self.check_backend_availability()? {
+            return Ok(());
+        }
+
+        Err(AxiomError::MissingDependenciesError(format!(
+            "coqc executable '{}' not found. Install Coq with `opam install coq` \
+            or set the 'coqc' tool path in the configuration.",
+            self.coqc_path
+        )))
+    }
+
+    fn get_verification_command(
+        &self,
+        spec_file: &Path,
+        impl_file: &Path,
+        _options: &VerificationOptions,
+    ) -> AxiomResult<Command> {
+        let mut command = Command::new(&self.coqc_path);
+
+        if let Some(dir) = spec_file.parent() {
+            command.current_dir(dir).arg("-Q").arg(dir).arg(LOGICAL_ROOT);
+        }
+
+        command.arg(spec_file);
+        if impl_file != spec_file {
+            command.arg(impl_file);
+        }
+
+        Ok(command)
+    }
+
+    fn parse_verification_output(
+        &self,
+        output: &str,
+        exit_code: i32,
+    ) -> AxiomResult<VerificationResult> {
+        let errors = parse_coq_errors(output);
+
+        let status = if !errors.is_empty() {
+            VerificationStatus::Failed(errors)
+        } else if exit_code == 0 {
+            VerificationStatus::Verified
+        } else {
+            let message = output
+                .lines()
+                .map(|line| line.trim())
+                .find(|line| !line.is_empty())
+                .map(|line| line.to_string())
+                .unwrap_or_else(|| format!("coqc exited with code {}", exit_code));
+            VerificationStatus::Error(message)
+        };
+
+        Ok(VerificationResult {
+            status,
+            proof_artifacts: vec![],
+            verification_time: Duration::ZERO,
+            resource_usage: ResourceUsage::default(),
+            goal_results: vec![],
+            counterexamples: vec![],
+        })
+    }
+}
+
+/// Collect `File "...", line N, characters a-b:` blocks followed by `Error:` into single lines
+pub fn parse_coq_errors(output: &str) -> Vec<String> {
+    let mut errors = Vec::new();
+    let mut location: Option<String> = None;
+    let mut message: Option<String> = None;
+
+    let mut flush = |location: &mut Option<String>, message: &mut Option<String>| {
+        let location = location.take();
+        if let Some(text) = message.take() {
+            match location {
+                Some(loc) => errors.push(format!("{}: {}", loc, text)),
+                None => errors.push(text),
+            }
+        }
+    };
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(loc) = parse_location(trimmed) {
+            flush(&mut location, &mut message);
+            location = Some(loc);
+        } else if let Some(text) = trimmed.strip_prefix("Error:") {
+            message = Some(text.trim().to_string());
+        } else if trimmed.starts_with("Warning:") {
+            // The pending location belongs to a warning, not an error
+            flush(&mut location, &mut message);
+        } else if let Some(text) = message.as_mut() {
+            if !trimmed.is_empty() {
+                text.push(' ');
+                text.push_str(trimmed);
+            }
+        }
+    }
+    flush(&mut location, &mut message);
+
+    errors
+}
+
+/// Parse `File "./Spec.v", line 12, characters 4-20:` into `Spec.v:12:4-20`
+fn parse_location(line: &str) -> Option<String> {
+    let rest = line.strip_prefix("File \"")?;
+    let (file, rest) = rest.split_once('"')?;
+    let line_no = rest.split("line ").nth(1)?.split(',').next()?.trim();
+    let characters = rest
+        .split("characters ")
+        .nth(1)
+        .map(|c| c.trim_end_matches(':').trim())
+        .unwrap_or("");
+    let file = file.trim_start_matches("./");
+
+    Some(if characters.is_empty() {
+        format!("{}:{}", file, line_no)
+    } else {
+        format!("{}:{}:{}", file, line_no, characters)
+    })
+}
+
+/// One goal per theorem-like declaration, marked `Incomplete` if its proof was admitted
+pub fn proof_goals(source: &str) -> Vec<GoalResult> {
+    let code = strip_comments(source);
+    let mut goals: Vec<GoalResult> = Vec::new();
+    let mut current: Option<String> = None;
+    let mut has_admit = false;
+
+    let mut words = code
+        .split(|c: char| c.is_whitespace())
+        .filter(|w| !w.is_empty())
+        .peekable();
+    while let Some(word) = words.next() {
+        if DECLARATION_KEYWORDS.contains(&word) {
+            if let Some(name) = words.peek() {
+                let name = name.trim_end_matches(|c: char| !(c.is_alphanumeric() || c == '_' || c == '\''));
+                current = Some(name.to_string());
+                has_admit = false;
+            }
+            continue;
+        }
+
+        let outcome = match word {
+            "admit." => {
+                has_admit = true;
+                continue;
+            }
+            "Abort." => {
+                current = None;
+                continue;
+            }
+            "Admitted." => GoalOutcome::Incomplete,
+            "Qed." | "Defined." if has_admit => GoalOutcome::Incomplete,
+            "Qed." | "Defined." => GoalOutcome::Valid,
+            _ => continue,
+        };
+        if let Some(name) = current.take() {
+            goals.push(GoalResult {
+                name,
+                outcome,
+                duration: Duration::ZERO,
+                resource_count: None,
+                prover: None,
+            });
+        }
+    }
+
+    goals
+}
+
+/// Remove `(* ... *)` comments, which may nest
+fn strip_comments(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut depth = 0usize;
+    let mut chars = source.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('(', Some('*')) => {
+                chars.next();
+                depth += 1;
+            }
+            ('*', Some(')')) if depth > 0 => {
+                chars.next();
+                depth -= 1;
+                out.push(' ');
+            }
+            _ if depth > 0 => {}
+            _ => out.push(c),
+        }
+    }
+
+    out
+}
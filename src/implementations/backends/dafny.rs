@@ -0,0 +1,448 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use log::{debug, info, warn};
+
+use crate::config::ExternalToolsConfig;
+use crate::errors::{AxiomError, AxiomResult};
+use crate::implementations::backends::{
+    create_run_dir, incompatible_implementation, probe_tool, run_with_timeout, write_run_file,
+};
+use crate::models::common::{Language, ProofLevel, ResourceUsage, VerificationLanguage};
+use crate::models::implementation::Implementation;
+use crate::models::specification::Specification;
+use crate::models::verification::{
+    ArtifactType, GoalOutcome, GoalResult, ProofArtifact, VerificationOptions, VerificationResult,
+    VerificationStatus,
+};
+use crate::traits::verification_engine::VerificationBackendAdapter;
+
+/// File name of the per-method CSV log written by `dafny verify`
+const CSV_LOG_NAME: &str = "verification.csv";
+
+/// Verification backend that checks Dafny programs with `dafny verify`
+///
+/// The executable is looked up under the `dafny` key of
+/// `ExternalToolsConfig::tool_paths` and defaults to `dafny` on the `PATH`.
+/// Per-method outcomes are read from the CSV log Dafny writes with `--log-format`.
+#[derive(Debug, Clone)]
+pub struct DafnyBackend {
+    dafny_path: String,
+    timeout: Duration,
+    work_root: PathBuf,
+}
+
+impl DafnyBackend {
+    /// Create a backend from the external tools configuration
+    pub fn new(tools: &ExternalToolsConfig) -> Self {
+        Self {
+            dafny_path: tools.tool_path("dafny", "dafny"),
+            timeout: tools.timeout,
+            work_root: std::env::temp_dir().join("axiom_dafny"),
+        }
+    }
+
+    /// Set the directory under which per-run working directories are created
+    pub fn with_work_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.work_root = dir.into();
+        self
+    }
+
+    /// Path of the Dafny executable this backend runs
+    pub fn dafny_path(&self) -> &str {
+        &self.dafny_path
+    }
+
+    /// Per-method time limit in seconds for each proof level, `None` for no limit
+    fn time_limit(proof_level: &ProofLevel) -> Option<u64> {
+        match proof_level {
+            ProofLevel::Quick => Some(10),
+            ProofLevel::Standard => Some(30),
+            ProofLevel::Thorough => Some(120),
+            ProofLevel::Exhaustive => None,
+            ProofLevel::Custom(value) => value.trim().parse().ok(),
+        }
+    }
+
+    /// Combine the verifier's textual output with the per-method goals from its log
+    fn merge_goal_results(result: &mut VerificationResult, goals: Vec<GoalResult>) {
+        if goals.is_empty() {
+            return;
+        }
+
+        result.resource_usage.lemmas_proven = goals.iter().filter(|g| g.outcome.is_valid()).count();
+
+        // Dafny reports time-outs per method, so the text output alone can look like a plain failure
+        let failed: Vec<String> = goals
+            .iter()
+            .filter(|g| !g.outcome.is_valid())
+            .map(|g| format!("{}: {:?}", g.name, g.outcome))
+            .collect();
+        let only_timeouts = !failed.is_empty()
+            && goals
+                .iter()
+                .filter(|g| !g.outcome.is_valid())
+                .all(|g| matches!(g.outcome, GoalOutcome::Timeout | GoalOutcome::OutOfResource));
+
+        match &result.status {
+            VerificationStatus::Verified if !failed.is_empty() => {
+                result.status = VerificationStatus::Failed(failed);
+            }
+            VerificationStatus::Failed(reasons) if reasons.is_empty() && only_timeouts => {
+                result.status = VerificationStatus::Timeout;
+            }
+            VerificationStatus::Failed(reasons) if reasons.is_empty() => {
+                result.status = VerificationStatus::Failed(failed);
+            }
+            _ => {}
+        }
+
+        result.goal_results = goals;
+    }
+}
+
+impl VerificationBackendAdapter for DafnyBackend {
+    fn convert_specification(&self, spec: &Specification) -> AxiomResult<String> {
+        if spec.formal_spec.verification_language != VerificationLanguage::DafnyLang {
+            return Err(AxiomError::LanguageCompatibilityError {
+                source_lang: spec.formal_spec.verification_language.to_string(),
+                target_lang: VerificationLanguage::DafnyLang.to_string(),
+            });
+        }
+
+        Ok(spec.formal_spec.spec_code.clone())
+    }
+
+    fn convert_implementation(&self, implementation: &Implementation) -> AxiomResult<String> {
+        if !self.supports_implementation_language(&implementation.language) {
+            return Err(incompatible_implementation(
+                &implementation.language,
+                VerificationLanguage::DafnyLang,
+            ));
+        }
+        Ok(implementation.source_code.clone())
+    }
+
+    fn supports_implementation_language(&self, language: &Language) -> bool {
+        match language {
+            Language::Custom(name) => name.eq_ignore_ascii_case("dafny"),
+            _ => false,
+        }
+    }
+
+    fn execute_verification(
+        &self,
+        converted_spec: &str,
+        converted_impl: &str,
+        options: &VerificationOptions,
+    ) -> AxiomResult<VerificationResult> {
+        let run_dir = create_run_dir(&self.work_root, "run")?;
+        debug!("Dafny working directory: {}", run_dir.display());
+
+        let spec_file = run_dir.join("spec.dfy");
+        write_run_file(&spec_file, converted_spec)?;
+
+        let impl_file = if converted_impl.trim().is_empty() {
+            spec_file.clone()
+        } else {
+            let path = run_dir.join("impl.dfy");
+            write_run_file(&path, converted_impl)?;
+            path
+        };
+
+        let mut command = self.get_verification_command(&spec_file, &impl_file, options)?;
+        info!("Running dafny verify in {}", run_dir.display());
+        let output = run_with_timeout(&mut command, "Dafny", options.timeout)?;
+
+        let log_path = run_dir.join("dafny.log");
+        write_run_file(&log_path, &output.combined())?;
+
+        let mut result = if output.timed_out {
+            warn!("Dafny timed out after {:?}", options.timeout);
+            VerificationResult {
+                status: VerificationStatus::Timeout,
+                proof_artifacts: vec![],
+                verification_time: output.elapsed,
+                resource_usage: ResourceUsage::default(),
+                goal_results: vec![],
+                counterexamples: vec![],
+            }
+        } else {
+            self.parse_verification_output(&output.combined(), output.exit_code.unwrap_or(-1))?
+        };
+
+        let csv_path = run_dir.join(CSV_LOG_NAME);
+        if let Ok(csv) = std::fs::read_to_string(&csv_path) {
+            Self::merge_goal_results(&mut result, parse_csv_log(&csv));
+        }
+
+        result.verification_time = output.elapsed;
+        result.resource_usage.cpu_seconds = output.elapsed.as_secs_f64();
+        result.proof_artifacts.push(ProofArtifact {
+            artifact_type: ArtifactType::Log,
+            path: log_path.display().to_string(),
+            description: "Dafny verifier output".to_string(),
+        });
+        result.proof_artifacts.extend(
+            self.extract_artifacts(&run_dir)?
+                .into_iter()
+                .filter(|a| a.path != log_path.display().to_string()),
+        );
+
+        Ok(result)
+    }
+
+    fn extract_artifacts(&self, output_dir: &Path) -> AxiomResult<Vec<ProofArtifact>> {
+        let entries = std::fs::read_dir(output_dir).map_err(|e| {
+            AxiomError::SystemError(format!("Failed to read {}: {}", output_dir.display(), e))
+        })?;
+
+        let mut artifacts = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+            let (artifact_type, description) = match extension {
+                "csv" => (ArtifactType::Log, "Dafny per-method verification log (CSV)"),
+                "json" => (ArtifactType::Log, "Dafny per-method verification log (JSON)"),
+                "log" => (ArtifactType::Log, "Dafny verifier output"),
+                "bpl" => (ArtifactType::Model, "Boogie program generated by Dafny"),
+                _ => continue,
+            };
+            artifacts.push(ProofArtifact {
+                artifact_type,
+                path: path.display().to_string(),
+                description: description.to_string(),
+            });
+        }
+
+        Ok(artifacts)
+    }
+
+    fn check_backend_availability(&self) -> AxiomResult<bool> {
+        Ok(probe_tool(&self.dafny_path, &["--version"]))
+    }
+
+    fn get_backend_version(&self) -> AxiomResult<String> {
+        let output = run_with_timeout(
+            Command::new(&self.dafny_path).arg("--version"),
+            "Dafny",
+            self.timeout,
+        )?;
+
+        output
+            .stdout
+            .lines()
+            .map(|line| line.trim())
+            .find(|line| !line.is_empty())
+            .map(|line| line.to_string())
+            .ok_or_else(|| AxiomError::ExternalToolError {
+                tool: "Dafny".to_string(),
+                message: "No version information reported".to_string(),
+            })
+    }
+
+    fn install_dependencies(&self) -> AxiomResult<()> {
+        if self.check_backend_availability()? {
+            return Ok(());
+        }
+
+        Err(AxiomError::MissingDependenciesError(format!(
+            "Dafny executable '{}' not found. Install Dafny with `dotnet tool install --global dafny` \
+            or set the 'dafny' tool path in the configuration.",
+            self.dafny_path
+        )))
+    }
+
+    fn get_verification_command(
+        &self,
+        spec_file: &Path,
+        impl_file: &Path,
+        options: &VerificationOptions,
+    ) -> AxiomResult<Command> {
+        let mut command = Command::new(&self.dafny_path);
+        command.arg("verify");
+
+        if let Some(dir) = spec_file.parent() {
+            command.current_dir(dir).arg("--log-format").arg(format!(
+                "csv;LogFileName={}",
+                dir.join(CSV_LOG_NAME).display()
+            ));
+        }
+        if let Some(limit) = Self::time_limit(&options.proof_level) {
+            command.arg("--verification-time-limit").arg(limit.to_string());
+        }
+        if let Some(jobs) = options.resource_limits.parallel_jobs {
+            command.arg("--cores").arg(jobs.to_string());
+        }
+
+        command.arg(spec_file);
+        if impl_file != spec_file {
+            command.arg(impl_file);
+        }
+
+        Ok(command)
+    }
+
+    fn parse_verification_output(
+        &self,
+        output: &str,
+        exit_code: i32,
+    ) -> AxiomResult<VerificationResult> {
+        let errors: Vec<String> = output
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| line.contains("): Error") || line.contains(": Error:"))
+            .map(|line| line.to_string())
+            .collect();
+        let summary = output.lines().find_map(parse_summary_line);
+
+        let status = match (exit_code, summary) {
+            (0, _) if errors.is_empty() => VerificationStatus::Verified,
+            _ if !errors.is_empty() => VerificationStatus::Failed(errors),
+            // Exit code 4 signals verification errors, possibly all time-outs without error lines
+            (4, _) => VerificationStatus::Failed(vec![]),
+            (_, Some((_, failed))) if failed > 0 => VerificationStatus::Failed(vec![]),
+            _ => {
+                let message = output
+                    .lines()
+                    .map(|line| line.trim())
+                    .find(|line| !line.is_empty())
+                    .map(|line| line.to_string())
+                    .unwrap_or_else(|| format!("Dafny exited with code {}", exit_code));
+                VerificationStatus::Error(message)
+            }
+        };
+
+        Ok(VerificationResult {
+            status,
+            proof_artifacts: vec![],
+            verification_time: Duration::ZERO,
+            resource_usage: ResourceUsage {
+                lemmas_proven: summary.map(|(verified, _)| verified).unwrap_or(0),
+                ..ResourceUsage::default()
+            },
+            goal_results: vec![],
+            counterexamples: vec![],
+        })
+    }
+}
+
+/// Parse `Dafny program verifier finished with N verified, M errors` into `(N, M)`
+fn parse_summary_line(line: &str) -> Option<(usize, usize)> {
+    let rest = line.trim().strip_prefix("Dafny program verifier finished with ")?;
+    let mut verified = 0;
+    let mut failed = 0;
+    for part in rest.split(',') {
+        let mut words = part.split_whitespace();
+        let count: usize = match words.next().and_then(|n| n.parse().ok()) {
+            Some(count) => count,
+            None => continue,
+        };
+        match words.next() {
+            Some(kind) if kind.starts_with("verified") => verified = count,
+            Some(kind) if kind.starts_with("error") => failed += count,
+            Some(kind) if kind.starts_with("time") || kind.starts_with("out") => failed += count,
+            _ => {}
+        }
+    }
+    Some((verified, failed))
+}
+
+/// Parse the CSV log written by `dafny verify --log-format csv` into per-method goals
+///
+/// The log has a header row naming the `TestResult.DisplayName`, `TestResult.Outcome`,
+/// `TestResult.Duration` and `TestResult.ResourceCount` columns, in any order.
+pub fn parse_csv_log(csv: &str) -> Vec<GoalResult> {
+    let mut lines = csv.lines().filter(|line| !line.trim().is_empty());
+    let header = match lines.next() {
+        Some(header) => split_csv_row(header),
+        None => return vec![],
+    };
+    let column = |name: &str| header.iter().position(|h| h.trim() == name);
+    let (name_col, outcome_col) = match (column("TestResult.DisplayName"), column("TestResult.Outcome")) {
+        (Some(name), Some(outcome)) => (name, outcome),
+        _ => return vec![],
+    };
+    let duration_col = column("TestResult.Duration");
+    let resource_col = column("TestResult.ResourceCount");
+
+    lines
+        .map(split_csv_row)
+        .filter_map(|row| {
+            let field = |col: Option<usize>| col.and_then(|c| row.get(c)).map(|f| f.trim());
+            Some(GoalResult {
+                name: method_name(field(Some(name_col))?),
+                outcome: parse_outcome(field(Some(outcome_col))?),
+                duration: field(duration_col).and_then(parse_duration).unwrap_or_default(),
+                resource_count: field(resource_col).and_then(|r| r.parse().ok()),
+                prover: None,
+            })
+        })
+        .collect()
+}
+
+/// Map a Dafny/Boogie outcome name onto a goal outcome
+fn parse_outcome(outcome: &str) -> GoalOutcome {
+    match outcome.trim() {
+        "Passed" | "Correct" | "Valid" => GoalOutcome::Valid,
+        "Failed" | "Errors" | "Invalid" => GoalOutcome::Invalid,
+        "TimedOut" | "TimeOut" => GoalOutcome::Timeout,
+        "OutOfResource" | "OutOfMemory" => GoalOutcome::OutOfResource,
+        "Inconclusive" => GoalOutcome::Unknown,
+        other => GoalOutcome::Error(other.to_string()),
+    }
+}
+
+/// Strip Boogie's implementation prefixes from a display name
+///
+/// `Impl$$_module.__default.Abs (correctness)` becomes `Abs (correctness)`.
+fn method_name(display_name: &str) -> String {
+    let (qualified, kind) = match display_name.split_once(' ') {
+        Some((qualified, kind)) => (qualified, Some(kind.trim())),
+        None => (display_name, None),
+    };
+    let qualified = qualified.rsplit("$$").next().unwrap_or(qualified);
+    let qualified = qualified.strip_prefix("_module.").unwrap_or(qualified);
+    let qualified = qualified.strip_prefix("__default.").unwrap_or(qualified);
+
+    match kind {
+        Some(kind) if !kind.is_empty() => format!("{} {}", qualified, kind),
+        _ => qualified.to_string(),
+    }
+}
+
+/// Parse a .NET `hh:mm:ss.fffffff` time span
+fn parse_duration(value: &str) -> Option<Duration> {
+    let mut parts = value.trim().split(':');
+    let hours: u64 = parts.next()?.parse().ok()?;
+    let minutes: u64 = parts.next()?.parse().ok()?;
+    let seconds: f64 = parts.next()?.parse().ok()?;
+    if parts.next().is_some() || seconds < 0.0 {
+        return None;
+    }
+    Some(Duration::from_secs(hours * 3600 + minutes * 60) + Duration::from_secs_f64(seconds))
+}
+
+/// Split a CSV row, honouring double-quoted fields
+fn split_csv_row(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields
+}
@@ -0,0 +1,102 @@
+use crate::errors::{AxiomError, AxiomResult};
+use crate::models::common::{Language, VerificationLanguage, VerificationSystem};
+use crate::models::implementation::Implementation;
+use crate::models::property::Property;
+use crate::models::specification::Specification;
+use crate::models::verification::{VerificationOptions, VerificationResult};
+use crate::traits::verification_engine::{
+    VerificationBackendAdapter, VerificationComplexity, VerificationEngine,
+};
+
+/// Verification engine running a registered backend adapter
+///
+/// Obtained from `BackendRegistry::engine`. Verification converts the specification and
+/// implementation with the backend and runs its tool once on the result.
+pub struct BackendEngine<'a> {
+    system: VerificationSystem,
+    backend: &'a (dyn VerificationBackendAdapter + Send + Sync),
+}
+
+impl<'a> BackendEngine<'a> {
+    pub fn new(
+        system: VerificationSystem,
+        backend: &'a (dyn VerificationBackendAdapter + Send + Sync),
+    ) -> Self {
+        Self { system, backend }
+    }
+
+    fn unsupported(&self, operation: &str) -> AxiomError {
+        AxiomError::VerificationError(format!(
+            "The {} backend does not support {}",
+            self.system, operation
+        ))
+    }
+}
+
+impl VerificationEngine for BackendEngine<'_> {
+    fn verify(
+        &self,
+        implementation: &Implementation,
+        spec: &Specification,
+        options: &VerificationOptions,
+    ) -> AxiomResult<VerificationResult> {
+        let converted_spec = self.backend.convert_specification(spec)?;
+        let converted_impl = self.backend.convert_implementation(implementation)?;
+        self.backend
+            .execute_verification(&converted_spec, &converted_impl, options)
+    }
+
+    fn can_verify(&self, spec: &Specification, language: &Language) -> bool {
+        spec.formal_spec.verification_language == self.system.verification_language()
+            && self.backend.supports_implementation_language(language)
+    }
+
+    fn verification_system(&self) -> VerificationSystem {
+        self.system.clone()
+    }
+
+    fn supports_formal_proofs(&self, language: VerificationLanguage) -> bool {
+        language == self.system.verification_language()
+    }
+
+    fn generate_verification_conditions(
+        &self,
+        _spec: &Specification,
+        _implementation: &Implementation,
+    ) -> AxiomResult<Vec<String>> {
+        Err(self.unsupported("generating verification conditions"))
+    }
+
+    fn extract_counterexamples(
+        &self,
+        verification_result: &VerificationResult,
+    ) -> AxiomResult<Vec<String>> {
+        Ok(verification_result
+            .counterexamples
+            .iter()
+            .map(|c| c.to_string())
+            .collect())
+    }
+
+    fn verify_property(
+        &self,
+        _implementation: &Implementation,
+        _property: &Property,
+        _options: &VerificationOptions,
+    ) -> AxiomResult<bool> {
+        Err(self.unsupported("checking single properties"))
+    }
+
+    fn estimate_verification_complexity(
+        &self,
+        _spec: &Specification,
+        _implementation: &Implementation,
+    ) -> AxiomResult<VerificationComplexity> {
+        Err(self.unsupported("complexity estimates"))
+    }
+
+    fn cancel_verification(&self) -> AxiomResult<()> {
+        // The tool runs synchronously inside `verify` and is killed there on timeout
+        Err(self.unsupported("cancellation"))
+    }
+}
@@ -0,0 +1,478 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use log::{debug, info, warn};
+
+use crate::config::ExternalToolsConfig;
+use crate::errors::{AxiomError, AxiomResult};
+use crate::implementations::backends::{
+    create_run_dir, incompatible_implementation, probe_tool, run_with_timeout, write_run_file,
+};
+use crate::models::common::{Language, ProofLevel, ResourceUsage, VerificationLanguage};
+use crate::models::implementation::Implementation;
+use crate::models::specification::Specification;
+use crate::models::verification::{
+    ArtifactType, GoalOutcome, GoalResult, ProofArtifact, VerificationOptions, VerificationResult,
+    VerificationStatus,
+};
+use crate::traits::verification_engine::VerificationBackendAdapter;
+
+/// Header the specification is written to; the implementation includes it
+const SPEC_HEADER: &str = "axiom_spec.h";
+/// File the implementation is written to
+const IMPL_FILE: &str = "axiom_impl.c";
+
+/// Verification backend that proves ACSL-annotated C code with the Frama-C WP plugin
+///
+/// The specification is a C header whose prototypes carry ACSL contracts, and the
+/// implementation is C code defining those functions. Frama-C merges the contracts of
+/// the header into the definitions and `frama-c -wp` proves every resulting goal,
+/// including runtime-error guards unless `with_rte(false)` is set; a run that generates
+/// no goal for a supplied implementation is `Unverified`. Provers are taken
+/// from `ResourceLimits::provers`; WP picks its default otherwise. The executable is
+/// looked up under the `frama-c` key of `ExternalToolsConfig::tool_paths` and defaults
+/// to `frama-c` on the `PATH`.
+#[derive(Debug, Clone)]
+pub struct FramaCBackend {
+    frama_c_path: String,
+    timeout: Duration,
+    work_root: PathBuf,
+    rte: bool,
+}
+
+impl FramaCBackend {
+    /// Create a backend from the external tools configuration
+    pub fn new(tools: &ExternalToolsConfig) -> Self {
+        Self {
+            frama_c_path: tools.tool_path("frama-c", "frama-c"),
+            timeout: tools.timeout,
+            work_root: std::env::temp_dir().join("axiom_frama_c"),
+            rte: true,
+        }
+    }
+
+    /// Set the directory under which per-run working directories are created
+    pub fn with_work_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.work_root = dir.into();
+        self
+    }
+
+    /// Also prove the absence of runtime errors (`-wp-rte`)
+    pub fn with_rte(mut self, rte: bool) -> Self {
+        self.rte = rte;
+        self
+    }
+
+    /// Path of the Frama-C executable this backend runs
+    pub fn frama_c_path(&self) -> &str {
+        &self.frama_c_path
+    }
+
+    /// Per-goal prover time limit in seconds for each proof level
+    fn goal_time_limit(proof_level: &ProofLevel) -> u64 {
+        match proof_level {
+            ProofLevel::Quick => 2,
+            ProofLevel::Standard => 10,
+            ProofLevel::Thorough => 30,
+            ProofLevel::Exhaustive => 120,
+            ProofLevel::Custom(value) => value.trim().parse().unwrap_or(10),
+        }
+    }
+
+    /// Overall status of a run from its goals and the `Proved goals` summary
+    fn status_of(goals: &[GoalResult], summary: Option<(usize, usize)>) -> VerificationStatus {
+        let unproven: Vec<&GoalResult> = goals.iter().filter(|g| !g.outcome.is_valid()).collect();
+        let describe = |g: &&GoalResult| match &g.prover {
+            Some(prover) => format!("{}: {:?} ({})", g.name, g.outcome, prover),
+            None => format!("{}: {:?}", g.name, g.outcome),
+        };
+
+        if let Some(failure) = unproven
+            .iter()
+            .find(|g| matches!(g.outcome, GoalOutcome::Error(_)))
+        {
+            return VerificationStatus::Error(describe(failure));
+        }
+        // WP never refutes a goal: a goal it cannot prove is reported as unknown
+        let failed: Vec<String> = unproven
+            .iter()
+            .filter(|g| !matches!(g.outcome, GoalOutcome::Timeout | GoalOutcome::OutOfResource))
+            .map(describe)
+            .collect();
+        if !failed.is_empty() {
+            return VerificationStatus::Failed(failed);
+        }
+        if !unproven.is_empty() {
+            return VerificationStatus::Timeout;
+        }
+
+        match summary {
+            Some((proved, total)) if proved < total => VerificationStatus::Failed(vec![format!(
+                "{} of {} goals were not proved",
+                total - proved,
+                total
+            )]),
+            _ => VerificationStatus::Verified,
+        }
+    }
+}
+
+impl VerificationBackendAdapter for FramaCBackend {
+    fn convert_specification(&self, spec: &Specification) -> AxiomResult<String> {
+        if spec.formal_spec.verification_language != VerificationLanguage::ACSL {
+            return Err(AxiomError::LanguageCompatibilityError {
+                source_lang: spec.formal_spec.verification_language.to_string(),
+                target_lang: VerificationLanguage::ACSL.to_string(),
+            });
+        }
+
+        Ok(spec.formal_spec.spec_code.clone())
+    }
+
+    fn convert_implementation(&self, implementation: &Implementation) -> AxiomResult<String> {
+        if !self.supports_implementation_language(&implementation.language) {
+            return Err(incompatible_implementation(
+                &implementation.language,
+                VerificationLanguage::ACSL,
+            ));
+        }
+        Ok(implementation.source_code.clone())
+    }
+
+    fn supports_implementation_language(&self, language: &Language) -> bool {
+        match language {
+            Language::C => true,
+            Language::Custom(name) => matches!(name.to_lowercase().as_str(), "c" | "acsl"),
+            _ => false,
+        }
+    }
+
+    fn execute_verification(
+        &self,
+        converted_spec: &str,
+        converted_impl: &str,
+        options: &VerificationOptions,
+    ) -> AxiomResult<VerificationResult> {
+        let run_dir = create_run_dir(&self.work_root, "run")?;
+        debug!("Frama-C working directory: {}", run_dir.display());
+
+        let spec_file = run_dir.join(SPEC_HEADER);
+        write_run_file(&spec_file, converted_spec)?;
+        let include = format!("#include \"{}\"", SPEC_HEADER);
+        let impl_code = if converted_impl.contains(&include) {
+            converted_impl.to_string()
+        } else {
+            format!("{}\n\n{}", include, converted_impl)
+        };
+        let impl_file = run_dir.join(IMPL_FILE);
+        write_run_file(&impl_file, &impl_code)?;
+
+        let mut command = self.get_verification_command(&spec_file, &impl_file, options)?;
+        info!("Running frama-c -wp");
+        let output = run_with_timeout(&mut command, "Frama-C", options.timeout)?;
+
+        let log_path = run_dir.join("frama-c.log");
+        write_run_file(&log_path, &output.combined())?;
+
+        let mut result = if output.timed_out {
+            warn!("Frama-C timed out after {:?}", options.timeout);
+            VerificationResult {
+                status: VerificationStatus::Timeout,
+                proof_artifacts: vec![],
+                verification_time: output.elapsed,
+                resource_usage: ResourceUsage::default(),
+                goal_results: vec![],
+                counterexamples: vec![],
+            }
+        } else {
+            self.parse_verification_output(&output.combined(), output.exit_code.unwrap_or(-1))?
+        };
+
+        // A run without goals proved nothing about the implementation, e.g. because it
+        // defines none of the annotated functions
+        if matches!(result.status, VerificationStatus::Verified)
+            && !converted_impl.trim().is_empty()
+            && result.goal_results.is_empty()
+            && result.resource_usage.lemmas_proven == 0
+        {
+            warn!("Frama-C generated no proof goals for the implementation");
+            result.status = VerificationStatus::Unverified;
+        }
+
+        result.verification_time = output.elapsed;
+        result.resource_usage.cpu_seconds = output.elapsed.as_secs_f64();
+        result.proof_artifacts.push(ProofArtifact {
+            artifact_type: ArtifactType::Log,
+            path: log_path.display().to_string(),
+            description: "frama-c -wp output".to_string(),
+        });
+        result.proof_artifacts.extend(
+            self.extract_artifacts(&run_dir)?
+                .into_iter()
+                .filter(|a| !matches!(a.artifact_type, ArtifactType::Log)),
+        );
+
+        Ok(result)
+    }
+
+    fn extract_artifacts(&self, output_dir: &Path) -> AxiomResult<Vec<ProofArtifact>> {
+        let entries = std::fs::read_dir(output_dir).map_err(|e| {
+            AxiomError::SystemError(format!("Failed to read {}: {}", output_dir.display(), e))
+        })?;
+
+        let mut artifacts = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let (artifact_type, description) = if path.is_dir() {
+                // `-wp-out` keeps the generated proof obligations
+                (ArtifactType::Proof, "WP proof obligations")
+            } else {
+                match path.extension().and_then(|e| e.to_str()) {
+                    Some("log") => (ArtifactType::Log, "frama-c -wp output"),
+                    _ => continue,
+                }
+            };
+            artifacts.push(ProofArtifact {
+                artifact_type,
+                path: path.display().to_string(),
+                description: description.to_string(),
+            });
+        }
+
+        Ok(artifacts)
+    }
+
+    fn check_backend_availability(&self) -> AxiomResult<bool> {
+        Ok(probe_tool(&self.frama_c_path, &["-version"]))
+    }
+
+    fn get_backend_version(&self) -> AxiomResult<String> {
+        let output = run_with_timeout(
+            Command::new(&self.frama_c_path).arg("-version"),
+            "Frama-C",
+            self.timeout,
+        )?;
+
+        output
+            .stdout
+            .lines()
+            .map(|line| line.trim())
+            .find(|line| !line.is_empty())
+            .map(|line| line.to_string())
+            .ok_or_else(|| AxiomError::ExternalToolError {
+                tool: "Frama-C".to_string(),
+                message: "No version information reported".to_string(),
+            })
+    }
+
+    fn install_dependencies(&self) -> AxiomResult<()> {
+        if self.check_backend_availability()? {
+            return Ok(());
+        }
+
+        Err(AxiomError::MissingDependenciesError(format!(
+            "Frama-C executable '{}' not found. Install Frama-C and a prover with \
+            `opam install frama-c alt-ergo`, run `why3 config detect`, or set the 'frama-c' \
+            tool path in the configuration.",
+            self.frama_c_path
+        )))
+    }
+
+    fn get_verification_command(
+        &self,
+        _spec_file: &Path,
+        impl_file: &Path,
+        options: &VerificationOptions,
+    ) -> AxiomResult<Command> {
+        let mut command = Command::new(&self.frama_c_path);
+
+        if let Some(dir) = impl_file.parent() {
+            command.current_dir(dir).arg("-wp-out").arg(dir.join("wp"));
+        }
+        command.arg("-wp");
+        if self.rte {
+            command.arg("-wp-rte");
+        }
+        command
+            .arg("-wp-timeout")
+            .arg(Self::goal_time_limit(&options.proof_level).to_string());
+        if !options.resource_limits.provers.is_empty() {
+            command
+                .arg("-wp-prover")
+                .arg(options.resource_limits.provers.join(","));
+        }
+        command.arg(impl_file);
+
+        Ok(command)
+    }
+
+    fn parse_verification_output(
+        &self,
+        output: &str,
+        exit_code: i32,
+    ) -> AxiomResult<VerificationResult> {
+        let (goals, summary) = parse_wp_output(output);
+        let errors = frama_c_errors(output);
+
+        let status = if !errors.is_empty() {
+            VerificationStatus::Failed(errors)
+        } else if goals.is_empty() && summary.is_none() {
+            if exit_code == 0 {
+                // Nothing to prove, e.g. a specification without any definitions
+                VerificationStatus::Verified
+            } else {
+                VerificationStatus::Error(format!("frama-c exited with code {}", exit_code))
+            }
+        } else {
+            Self::status_of(&goals, summary)
+        };
+
+        let lemmas_proven = match summary {
+            Some((proved, _)) => proved,
+            None => goals.iter().filter(|g| g.outcome.is_valid()).count(),
+        };
+
+        Ok(VerificationResult {
+            status,
+            proof_artifacts: vec![],
+            verification_time: Duration::ZERO,
+            resource_usage: ResourceUsage {
+                lemmas_proven,
+                ..ResourceUsage::default()
+            },
+            goal_results: goals,
+            counterexamples: vec![],
+        })
+    }
+}
+
+/// Parse `frama-c -wp` output into one result per reported goal, and the
+/// `Proved goals: N / M` summary if present
+///
+/// Both per-goal layouts are understood:
+///
+/// ```text
+/// [wp] [Alt-Ergo 2.5.2] Goal typed_incr_ensures : Valid (Qed:2ms) (12ms) (24)
+/// [wp] [Timeout] typed_incr_assert_rte_unsigned_overflow (Qed 1ms) (Alt-Ergo)
+/// ```
+///
+/// Recent versions only list the goals they could not prove; the summary accounts for
+/// the others.
+pub fn parse_wp_output(output: &str) -> (Vec<GoalResult>, Option<(usize, usize)>) {
+    let mut goals = Vec::new();
+    let mut summary = None;
+
+    for line in output.lines() {
+        let Some(rest) = line.trim().strip_prefix("[wp]") else {
+            continue;
+        };
+        let rest = rest.trim();
+
+        if let Some(counts) = rest.strip_prefix("Proved goals:") {
+            if let Some((proved, total)) = counts.split_once('/') {
+                if let (Ok(proved), Ok(total)) =
+                    (proved.trim().parse(), total.trim().parse::<usize>())
+                {
+                    summary = Some((proved, total));
+                }
+            }
+            continue;
+        }
+
+        let Some((bracket, after)) = rest.strip_prefix('[').and_then(|rest| rest.split_once(']'))
+        else {
+            continue;
+        };
+        let after = after.trim();
+
+        let goal = if let Some(goal) = after.strip_prefix("Goal ") {
+            // `[Prover] Goal name : Result (...)`
+            let Some((name, result)) = goal.split_once(" : ") else {
+                continue;
+            };
+            let Some(outcome) = parse_outcome(result) else {
+                continue;
+            };
+            GoalResult {
+                name: name.trim().to_string(),
+                outcome,
+                duration: parse_duration(result),
+                resource_count: None,
+                prover: Some(bracket.to_string()),
+            }
+        } else {
+            // `[Result] name (...) (Prover)`
+            let Some(outcome) = parse_outcome(bracket) else {
+                continue;
+            };
+            let name = after.split_whitespace().next().unwrap_or("");
+            if name.is_empty() {
+                continue;
+            }
+            let prover = after
+                .rsplit_once('(')
+                .and_then(|(_, prover)| prover.strip_suffix(')'))
+                .filter(|prover| {
+                    !prover.starts_with("Qed") && !prover.starts_with(|c: char| c.is_ascii_digit())
+                })
+                .map(|prover| prover.to_string());
+            GoalResult {
+                name: name.to_string(),
+                outcome,
+                duration: parse_duration(after),
+                resource_count: None,
+                prover,
+            }
+        };
+        goals.push(goal);
+    }
+
+    (goals, summary)
+}
+
+/// Map a WP goal status onto a goal outcome
+fn parse_outcome(result: &str) -> Option<GoalOutcome> {
+    let word = result.split([' ', '(', ']']).next().unwrap_or("");
+    let outcome = match word {
+        "Valid" => GoalOutcome::Valid,
+        "Unknown" => GoalOutcome::Unknown,
+        "Timeout" => GoalOutcome::Timeout,
+        "Stepout" => GoalOutcome::OutOfResource,
+        "Failed" | "Failure" => GoalOutcome::Error(result.trim().to_string()),
+        _ => return None,
+    };
+    Some(outcome)
+}
+
+/// Longest `(12ms)`/`(1.5s)` measurement in a goal line
+fn parse_duration(text: &str) -> Duration {
+    text.split(['(', ')', ' '])
+        .filter_map(|part| {
+            let part = part.trim_start_matches("Qed:");
+            if let Some(ms) = part.strip_suffix("ms") {
+                ms.parse::<f64>().ok().map(|ms| ms / 1000.0)
+            } else {
+                part.strip_suffix('s').and_then(|s| s.parse::<f64>().ok())
+            }
+        })
+        .fold(Duration::ZERO, |longest, seconds| {
+            longest.max(Duration::from_secs_f64(seconds.max(0.0)))
+        })
+}
+
+/// Collect kernel errors, such as C or ACSL syntax and typing errors, that stop WP
+/// before any goal is generated
+fn frama_c_errors(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| {
+            line.starts_with("[kernel] User Error:")
+                || line.starts_with("[kernel] Failure:")
+                || line.starts_with("[kernel:annot-error]")
+                || line.contains("] Error:")
+        })
+        .map(|line| line.to_string())
+        .collect()
+}
@@ -0,0 +1,322 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use log::{debug, info, warn};
+
+use crate::config::ExternalToolsConfig;
+use crate::errors::{AxiomError, AxiomResult};
+use crate::implementations::backends::fstar_diagnostics::{describe_issue, parse_fstar_diagnostics};
+use crate::implementations::backends::{
+    create_run_dir, incompatible_implementation, probe_tool, run_with_timeout, write_run_file,
+};
+use crate::models::common::{Language, ProofLevel, ResourceUsage, VerificationLanguage};
+use crate::models::implementation::Implementation;
+use crate::models::specification::{IssueSeverity, Specification};
+use crate::models::verification::{
+    ArtifactType, ProofArtifact, VerificationOptions, VerificationResult, VerificationStatus,
+};
+use crate::traits::verification_engine::VerificationBackendAdapter;
+
+/// Module name used when a specification does not declare one
+const DEFAULT_MODULE_NAME: &str = "AxiomSpec";
+
+/// Verification backend that checks F* code with the `fstar.exe` executable
+///
+/// The executable is looked up under the `fstar` key of
+/// `ExternalToolsConfig::tool_paths` and defaults to `fstar.exe` on the `PATH`.
+/// A `z3` entry, if present, is passed to F* through `--smt`.
+#[derive(Debug, Clone)]
+pub struct FStarBackend {
+    fstar_path: String,
+    z3_path: Option<String>,
+    timeout: Duration,
+    work_root: PathBuf,
+    lax: bool,
+}
+
+impl FStarBackend {
+    /// Create a backend from the external tools configuration
+    pub fn new(tools: &ExternalToolsConfig) -> Self {
+        Self {
+            fstar_path: tools.tool_path("fstar", "fstar.exe"),
+            z3_path: tools.tool_paths.get("z3").cloned(),
+            timeout: tools.timeout,
+            work_root: std::env::temp_dir().join("axiom_fstar"),
+            lax: false,
+        }
+    }
+
+    /// Set the directory under which per-run working directories are created
+    pub fn with_work_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.work_root = dir.into();
+        self
+    }
+
+    /// Only type check (`--lax`) instead of discharging proof obligations
+    pub fn with_lax(mut self, lax: bool) -> Self {
+        self.lax = lax;
+        self
+    }
+
+    /// Path of the F* executable this backend runs
+    pub fn fstar_path(&self) -> &str {
+        &self.fstar_path
+    }
+
+    /// Time limit for a run of F*, from the external tools configuration
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Z3 resource limit used for each proof level
+    fn z3_rlimit(proof_level: &ProofLevel) -> u32 {
+        match proof_level {
+            ProofLevel::Quick => 5,
+            ProofLevel::Standard => 20,
+            ProofLevel::Thorough => 60,
+            ProofLevel::Exhaustive => 200,
+            ProofLevel::Custom(value) => value.trim().parse().unwrap_or(20),
+        }
+    }
+
+    /// Extract the module name declared by F* source code
+    fn module_name(code: &str) -> Option<String> {
+        code.lines()
+            .map(|line| line.trim())
+            .find_map(|line| line.strip_prefix("module "))
+            .and_then(|rest| rest.split_whitespace().next())
+            .filter(|name| !name.contains('='))
+            .map(|name| name.to_string())
+    }
+
+    /// Make sure the code declares a module, returning the module name and source to write
+    pub(crate) fn with_module_header(code: &str, default_name: &str) -> (String, String) {
+        match Self::module_name(code) {
+            Some(name) => (name, code.to_string()),
+            None => (default_name.to_string(), format!("module {}\n\n{}", default_name, code)),
+        }
+    }
+}
+
+impl VerificationBackendAdapter for FStarBackend {
+    fn convert_specification(&self, spec: &Specification) -> AxiomResult<String> {
+        if spec.formal_spec.verification_language != VerificationLanguage::FStarLang {
+            return Err(AxiomError::LanguageCompatibilityError {
+                source_lang: spec.formal_spec.verification_language.to_string(),
+                target_lang: VerificationLanguage::FStarLang.to_string(),
+            });
+        }
+
+        Ok(spec.formal_spec.spec_code.clone())
+    }
+
+    fn convert_implementation(&self, implementation: &Implementation) -> AxiomResult<String> {
+        if !self.supports_implementation_language(&implementation.language) {
+            return Err(incompatible_implementation(
+                &implementation.language,
+                VerificationLanguage::FStarLang,
+            ));
+        }
+        Ok(implementation.source_code.clone())
+    }
+
+    fn supports_implementation_language(&self, language: &Language) -> bool {
+        match language {
+            Language::Custom(name) => matches!(name.to_lowercase().as_str(), "fstar" | "f*"),
+            _ => false,
+        }
+    }
+
+    fn execute_verification(
+        &self,
+        converted_spec: &str,
+        converted_impl: &str,
+        options: &VerificationOptions,
+    ) -> AxiomResult<VerificationResult> {
+        let run_dir = create_run_dir(&self.work_root, "run")?;
+        debug!("F* working directory: {}", run_dir.display());
+
+        let (module, spec_code) = Self::with_module_header(converted_spec, DEFAULT_MODULE_NAME);
+        let spec_file = run_dir.join(format!("{}.fst", module));
+        write_run_file(&spec_file, &spec_code)?;
+
+        let impl_file = if converted_impl.trim().is_empty() {
+            spec_file.clone()
+        } else {
+            let (impl_module, impl_code) =
+                Self::with_module_header(converted_impl, &format!("{}Impl", module));
+            let path = run_dir.join(format!("{}.fst", impl_module));
+            write_run_file(&path, &impl_code)?;
+            path
+        };
+
+        let mut command = self.get_verification_command(&spec_file, &impl_file, options)?;
+        info!("Running F* on module {}", module);
+        let output = run_with_timeout(&mut command, "F*", options.timeout)?;
+
+        let log_path = run_dir.join("fstar.log");
+        write_run_file(&log_path, &output.combined())?;
+
+        let mut result = if output.timed_out {
+            warn!("F* timed out after {:?}", options.timeout);
+            VerificationResult {
+                status: VerificationStatus::Timeout,
+                proof_artifacts: vec![],
+                verification_time: output.elapsed,
+                resource_usage: ResourceUsage::default(),
+                goal_results: vec![],
+                counterexamples: vec![],
+            }
+        } else {
+            self.parse_verification_output(&output.combined(), output.exit_code.unwrap_or(-1))?
+        };
+
+        result.verification_time = output.elapsed;
+        result.resource_usage.cpu_seconds = output.elapsed.as_secs_f64();
+        result.proof_artifacts.push(ProofArtifact {
+            artifact_type: ArtifactType::Log,
+            path: log_path.display().to_string(),
+            description: format!("F* output for module {}", module),
+        });
+        result.proof_artifacts.extend(
+            self.extract_artifacts(&run_dir)?
+                .into_iter()
+                .filter(|a| !matches!(a.artifact_type, ArtifactType::Log)),
+        );
+
+        Ok(result)
+    }
+
+    fn extract_artifacts(&self, output_dir: &Path) -> AxiomResult<Vec<ProofArtifact>> {
+        let entries = std::fs::read_dir(output_dir).map_err(|e| {
+            AxiomError::SystemError(format!("Failed to read {}: {}", output_dir.display(), e))
+        })?;
+
+        let mut artifacts = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+            let (artifact_type, description) = match extension {
+                "checked" => (ArtifactType::Proof, "F* checked module cache"),
+                "hints" => (ArtifactType::Proof, "F* SMT hints"),
+                "smt2" => (ArtifactType::Model, "SMT queries sent to Z3"),
+                "log" => (ArtifactType::Log, "F* output"),
+                _ => continue,
+            };
+            artifacts.push(ProofArtifact {
+                artifact_type,
+                path: path.display().to_string(),
+                description: description.to_string(),
+            });
+        }
+
+        Ok(artifacts)
+    }
+
+    fn check_backend_availability(&self) -> AxiomResult<bool> {
+        Ok(probe_tool(&self.fstar_path, &["--version"]))
+    }
+
+    fn get_backend_version(&self) -> AxiomResult<String> {
+        let output = run_with_timeout(
+            Command::new(&self.fstar_path).arg("--version"),
+            "F*",
+            self.timeout,
+        )?;
+
+        output
+            .stdout
+            .lines()
+            .map(|line| line.trim())
+            .find(|line| !line.is_empty())
+            .map(|line| line.to_string())
+            .ok_or_else(|| AxiomError::ExternalToolError {
+                tool: "F*".to_string(),
+                message: "No version information reported".to_string(),
+            })
+    }
+
+    fn install_dependencies(&self) -> AxiomResult<()> {
+        if self.check_backend_availability()? {
+            return Ok(());
+        }
+
+        Err(AxiomError::MissingDependenciesError(format!(
+            "F* executable '{}' not found. Install F* from https://fstar-lang.org \
+            or set the 'fstar' tool path in the configuration.",
+            self.fstar_path
+        )))
+    }
+
+    fn get_verification_command(
+        &self,
+        spec_file: &Path,
+        impl_file: &Path,
+        options: &VerificationOptions,
+    ) -> AxiomResult<Command> {
+        let mut command = Command::new(&self.fstar_path);
+
+        if let Some(dir) = spec_file.parent() {
+            command.current_dir(dir).arg("--include").arg(dir);
+        }
+        if self.lax {
+            command.arg("--lax");
+        }
+        command
+            .arg("--z3rlimit")
+            .arg(Self::z3_rlimit(&options.proof_level).to_string());
+        if let Some(z3) = &self.z3_path {
+            command.arg("--smt").arg(z3);
+        }
+
+        command.arg(spec_file);
+        if impl_file != spec_file {
+            command.arg(impl_file);
+        }
+
+        Ok(command)
+    }
+
+    fn parse_verification_output(
+        &self,
+        output: &str,
+        exit_code: i32,
+    ) -> AxiomResult<VerificationResult> {
+        let errors: Vec<String> = parse_fstar_diagnostics(output)
+            .iter()
+            .filter(|issue| issue.severity == IssueSeverity::Error)
+            .map(describe_issue)
+            .collect();
+        let verified_modules = output
+            .lines()
+            .filter(|line| line.trim_start().starts_with("Verified module:"))
+            .count();
+
+        let status = if exit_code == 0 && errors.is_empty() {
+            VerificationStatus::Verified
+        } else if !errors.is_empty() {
+            VerificationStatus::Failed(errors)
+        } else {
+            let message = output
+                .lines()
+                .map(|line| line.trim())
+                .find(|line| !line.is_empty())
+                .map(|line| line.to_string())
+                .unwrap_or_else(|| format!("F* exited with code {}", exit_code));
+            VerificationStatus::Error(message)
+        };
+
+        Ok(VerificationResult {
+            status,
+            proof_artifacts: vec![],
+            verification_time: Duration::ZERO,
+            resource_usage: ResourceUsage {
+                lemmas_proven: verified_modules,
+                ..ResourceUsage::default()
+            },
+            goal_results: vec![],
+            counterexamples: vec![],
+        })
+    }
+}
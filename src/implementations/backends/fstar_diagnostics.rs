@@ -0,0 +1,202 @@
+//! Parser for the diagnostics F* prints on stderr
+//!
+//! F* reports problems in two layouts depending on its version:
+//!
+//! ```text
+//! Test.fst(12,4-12,20): (Error 189) Expected expression of type int; got bool
+//!
+//! * Error 19 at Test.fst(4,2-4,14):
+//!   - Assertion failed
+//!   - The SMT solver could not prove the query.
+//! ```
+//!
+//! Both are turned into `ValidationIssue`s carrying the source range and error number.
+
+use crate::models::specification::{IssueSeverity, SourceSpan, ValidationIssue};
+
+/// Broad category of an F* error, used to steer automatic fixing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FStarErrorCategory {
+    /// A referenced identifier, function or predicate is not defined
+    MissingDefinition,
+    /// The source could not be parsed
+    Syntax,
+    /// Type mismatch or ill-formed type
+    Type,
+    /// The SMT solver could not discharge a proof obligation
+    Proof,
+    Other,
+}
+
+impl FStarErrorCategory {
+    /// Categorize an issue by its F* error number, falling back to the message text
+    pub fn of(issue: &ValidationIssue) -> Self {
+        match issue.error_code {
+            Some(72) => return Self::MissingDefinition,
+            Some(168) => return Self::Syntax,
+            Some(189) => return Self::Type,
+            Some(19) => return Self::Proof,
+            _ => {}
+        }
+
+        let msg = issue.message.to_lowercase();
+        if (msg.contains("undefined") || msg.contains("not found"))
+            && (msg.contains("function") || msg.contains("predicate") || msg.contains("identifier"))
+        {
+            Self::MissingDefinition
+        } else if msg.contains("syntax") || msg.contains("expected") || msg.contains("missing") {
+            Self::Syntax
+        } else if msg.contains("type") {
+            Self::Type
+        } else if msg.contains("assertion") || msg.contains("could not prove") {
+            Self::Proof
+        } else {
+            Self::Other
+        }
+    }
+}
+
+/// Parse raw F* output into validation issues, one per reported diagnostic
+pub fn parse_fstar_diagnostics(output: &str) -> Vec<ValidationIssue> {
+    let mut issues: Vec<ValidationIssue> = Vec::new();
+    let mut current: Option<ValidationIssue> = None;
+
+    for line in output.lines() {
+        if let Some(issue) = parse_header(line) {
+            issues.extend(current.take());
+            current = Some(issue);
+            continue;
+        }
+
+        let trimmed = line.trim();
+        if trimmed.is_empty() || is_summary_line(trimmed) {
+            issues.extend(current.take());
+            continue;
+        }
+
+        // Continuation of the current diagnostic (indented text or `- ` bullets)
+        if let Some(issue) = current.as_mut() {
+            let text = trimmed.trim_start_matches("- ").trim();
+            if issue.message.is_empty() {
+                issue.message = text.to_string();
+            } else {
+                issue.message.push_str("; ");
+                issue.message.push_str(text);
+            }
+        }
+    }
+    issues.extend(current);
+
+    issues
+}
+
+/// Render an issue in the classic single-line F* layout
+pub fn describe_issue(issue: &ValidationIssue) -> String {
+    let location = match &issue.span {
+        Some(span) => format!(
+            "{}({},{}-{},{}): ",
+            span.file.as_deref().unwrap_or("<input>"),
+            span.start_line,
+            span.start_column,
+            span.end_line,
+            span.end_column
+        ),
+        None => String::new(),
+    };
+    let kind = match issue.severity {
+        IssueSeverity::Error => "Error",
+        IssueSeverity::Warning => "Warning",
+        IssueSeverity::Info => "Info",
+    };
+    let code = issue.error_code.map(|c| format!(" {}", c)).unwrap_or_default();
+
+    format!("{}({}{}) {}", location, kind, code, issue.message)
+}
+
+/// Name of the identifier an "Identifier not found" error refers to
+pub fn missing_identifier(issue: &ValidationIssue) -> Option<String> {
+    let start = issue.message.find("Identifier not found: [")? + "Identifier not found: [".len();
+    let end = issue.message[start..].find(']')?;
+    let name = issue.message[start..start + end].trim();
+    Some(name.rsplit('.').next().unwrap_or(name).to_string())
+}
+
+/// Parse the first line of a diagnostic in either layout
+fn parse_header(line: &str) -> Option<ValidationIssue> {
+    let trimmed = line.trim();
+
+    // New layout: `* Error 19 at Test.fst(4,2-4,14):`
+    if let Some(rest) = trimmed.strip_prefix("* ") {
+        let (kind, rest) = rest.split_once(' ')?;
+        let severity = severity_of(kind)?;
+        let (code, location) = match rest.split_once(" at ") {
+            Some((code, location)) => (code.trim().parse().ok(), Some(location)),
+            None => (rest.trim_end_matches(':').trim().parse().ok(), None),
+        };
+        let span = location.and_then(|l| parse_location(l.trim_end_matches(':')));
+        return Some(new_issue(severity, code, span, String::new()));
+    }
+
+    // Classic layout: `Test.fst(12,4-12,20): (Error 189) message`
+    let marker = trimmed.find(": (")?;
+    let span = parse_location(&trimmed[..marker]);
+    let rest = &trimmed[marker + 3..];
+    let close = rest.find(')')?;
+    let mut parts = rest[..close].split_whitespace();
+    let severity = severity_of(parts.next()?)?;
+    let code = parts.next().and_then(|c| c.parse().ok());
+    let message = rest[close + 1..].trim().to_string();
+
+    Some(new_issue(severity, code, span, message))
+}
+
+/// Parse `file(start_line,start_col-end_line,end_col)`
+fn parse_location(location: &str) -> Option<SourceSpan> {
+    let open = location.rfind('(')?;
+    let close = location.rfind(')')?;
+    let (start, end) = location[open + 1..close].split_once('-')?;
+    let (start_line, start_column) = start.split_once(',')?;
+    let (end_line, end_column) = end.split_once(',')?;
+    let file = location[..open].trim();
+
+    Some(SourceSpan {
+        file: if file.is_empty() { None } else { Some(file.to_string()) },
+        start_line: start_line.trim().parse().ok()?,
+        start_column: start_column.trim().parse().ok()?,
+        end_line: end_line.trim().parse().ok()?,
+        end_column: end_column.trim().parse().ok()?,
+    })
+}
+
+fn severity_of(kind: &str) -> Option<IssueSeverity> {
+    match kind {
+        "Error" => Some(IssueSeverity::Error),
+        "Warning" => Some(IssueSeverity::Warning),
+        "Info" => Some(IssueSeverity::Info),
+        _ => None,
+    }
+}
+
+/// Lines F* prints around diagnostics that are not part of them
+fn is_summary_line(line: &str) -> bool {
+    line.starts_with("Verified module:")
+        || line.starts_with("All verification conditions")
+        || (line.contains("error") && line.contains("reported"))
+}
+
+fn new_issue(
+    severity: IssueSeverity,
+    error_code: Option<u32>,
+    span: Option<SourceSpan>,
+    message: String,
+) -> ValidationIssue {
+    ValidationIssue {
+        severity,
+        message,
+        related_property: None,
+        line_number: span.as_ref().map(|s| s.start_line),
+        span,
+        error_code,
+        suggested_fix: None,
+    }
+}
@@ -0,0 +1,384 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use log::{debug, info, warn};
+
+use crate::config::ExternalToolsConfig;
+use crate::errors::{AxiomError, AxiomResult};
+use crate::implementations::backends::{
+    create_run_dir, incompatible_implementation, probe_tool, run_with_timeout, write_run_file,
+};
+use crate::models::common::{Language, ProofLevel, ResourceUsage, VerificationLanguage};
+use crate::models::implementation::Implementation;
+use crate::models::specification::Specification;
+use crate::models::verification::{
+    ArtifactType, GoalOutcome, GoalResult, ProofArtifact, VerificationOptions, VerificationResult,
+    VerificationStatus,
+};
+use crate::traits::verification_engine::VerificationBackendAdapter;
+
+/// Commands that introduce a named declaration whose body may contain `sorry`
+const DECLARATION_KEYWORDS: &[&str] = &["theorem", "lemma", "def", "instance", "abbrev"];
+
+/// Proof-checker backend that elaborates Lean 4 files with `lake env lean`
+///
+/// Running through `lake env` makes the dependencies of a Lake project (such as
+/// Mathlib) visible; the project is taken from the `lean_project` entry of
+/// `ExternalToolsConfig::tool_paths`. A file that elaborates but still uses `sorry`
+/// is reported as `Unverified`. The `lake` executable is looked up under the `lake`
+/// key and defaults to `lake` on the `PATH`.
+#[derive(Debug, Clone)]
+pub struct LeanBackend {
+    lake_path: String,
+    project_dir: Option<PathBuf>,
+    timeout: Duration,
+    work_root: PathBuf,
+}
+
+impl LeanBackend {
+    /// Create a backend from the external tools configuration
+    pub fn new(tools: &ExternalToolsConfig) -> Self {
+        Self {
+            lake_path: tools.tool_path("lake", "lake"),
+            project_dir: tools.tool_paths.get("lean_project").map(PathBuf::from),
+            timeout: tools.timeout,
+            work_root: std::env::temp_dir().join("axiom_lean"),
+        }
+    }
+
+    /// Set the directory under which per-run working directories are created
+    pub fn with_work_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.work_root = dir.into();
+        self
+    }
+
+    /// Path of the `lake` executable this backend runs
+    pub fn lake_path(&self) -> &str {
+        &self.lake_path
+    }
+
+    /// Heartbeat budget per declaration for each proof level, 0 meaning unlimited
+    fn max_heartbeats(proof_level: &ProofLevel) -> u64 {
+        match proof_level {
+            ProofLevel::Quick => 100_000,
+            ProofLevel::Standard => 200_000,
+            ProofLevel::Thorough => 800_000,
+            ProofLevel::Exhaustive => 0,
+            ProofLevel::Custom(value) => value.trim().parse().unwrap_or(200_000),
+        }
+    }
+
+    /// Merge specification and implementation into one file, hoisting `import`s to the top
+    fn combine_sources(spec: &str, implementation: &str) -> String {
+        let (imports, body): (Vec<&str>, Vec<&str>) = spec
+            .lines()
+            .chain(std::iter::once(""))
+            .chain(implementation.lines())
+            .partition(|line| line.starts_with("import "));
+
+        let mut unique_imports: Vec<&str> = Vec::new();
+        for import in imports {
+            if !unique_imports.contains(&import) {
+                unique_imports.push(import);
+            }
+        }
+
+        let mut combined = unique_imports.join("\n");
+        if !combined.is_empty() {
+            combined.push_str("\n\n");
+        }
+        combined.push_str(body.join("\n").trim());
+        combined.push('\n');
+        combined
+    }
+}
+
+impl VerificationBackendAdapter for LeanBackend {
+    fn convert_specification(&self, spec: &Specification) -> AxiomResult<String> {
+        if spec.formal_spec.verification_language != VerificationLanguage::LeanLang {
+            return Err(AxiomError::LanguageCompatibilityError {
+                source_lang: spec.formal_spec.verification_language.to_string(),
+                target_lang: VerificationLanguage::LeanLang.to_string(),
+            });
+        }
+
+        Ok(spec.formal_spec.spec_code.clone())
+    }
+
+    fn convert_implementation(&self, implementation: &Implementation) -> AxiomResult<String> {
+        if !self.supports_implementation_language(&implementation.language) {
+            return Err(incompatible_implementation(
+                &implementation.language,
+                VerificationLanguage::LeanLang,
+            ));
+        }
+        Ok(implementation.source_code.clone())
+    }
+
+    fn supports_implementation_language(&self, language: &Language) -> bool {
+        match language {
+            Language::Custom(name) => matches!(name.to_lowercase().as_str(), "lean" | "lean4"),
+            _ => false,
+        }
+    }
+
+    fn execute_verification(
+        &self,
+        converted_spec: &str,
+        converted_impl: &str,
+        options: &VerificationOptions,
+    ) -> AxiomResult<VerificationResult> {
+        let run_dir = create_run_dir(&self.work_root, "run")?;
+        debug!("Lean working directory: {}", run_dir.display());
+
+        // `lean` checks a single file, so both parts are elaborated together
+        let source = Self::combine_sources(converted_spec, converted_impl);
+        let spec_file = run_dir.join("AxiomSpec.lean");
+        write_run_file(&spec_file, &source)?;
+
+        let mut command = self.get_verification_command(&spec_file, &spec_file, options)?;
+        info!("Running lake env lean on {}", spec_file.display());
+        let output = run_with_timeout(&mut command, "Lean", options.timeout)?;
+
+        let log_path = run_dir.join("lean.log");
+        write_run_file(&log_path, &output.combined())?;
+
+        let mut result = if output.timed_out {
+            warn!("Lean timed out after {:?}", options.timeout);
+            VerificationResult {
+                status: VerificationStatus::Timeout,
+                proof_artifacts: vec![],
+                verification_time: output.elapsed,
+                resource_usage: ResourceUsage::default(),
+                goal_results: vec![],
+                counterexamples: vec![],
+            }
+        } else {
+            self.parse_verification_output(&output.combined(), output.exit_code.unwrap_or(-1))?
+        };
+
+        // Lean warns about `sorry`, but the source scan also names the declaration
+        let goals = proof_goals(&source);
+        if matches!(result.status, VerificationStatus::Verified)
+            && goals.iter().any(|g| g.outcome == GoalOutcome::Incomplete)
+        {
+            result.status = VerificationStatus::Unverified;
+        }
+        result.resource_usage.lemmas_proven = goals.iter().filter(|g| g.outcome.is_valid()).count();
+        result.goal_results = goals;
+
+        result.verification_time = output.elapsed;
+        result.resource_usage.cpu_seconds = output.elapsed.as_secs_f64();
+        result.proof_artifacts.push(ProofArtifact {
+            artifact_type: ArtifactType::Log,
+            path: log_path.display().to_string(),
+            description: "Lean output".to_string(),
+        });
+        result.proof_artifacts.extend(
+            self.extract_artifacts(&run_dir)?
+                .into_iter()
+                .filter(|a| !matches!(a.artifact_type, ArtifactType::Log)),
+        );
+
+        Ok(result)
+    }
+
+    fn extract_artifacts(&self, output_dir: &Path) -> AxiomResult<Vec<ProofArtifact>> {
+        let entries = std::fs::read_dir(output_dir).map_err(|e| {
+            AxiomError::SystemError(format!("Failed to read {}: {}", output_dir.display(), e))
+        })?;
+
+        let mut artifacts = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+            let (artifact_type, description) = match extension {
+                "olean" => (ArtifactType::Proof, "Compiled Lean module"),
+                "log" => (ArtifactType::Log, "Lean output"),
+                _ => continue,
+            };
+            artifacts.push(ProofArtifact {
+                artifact_type,
+                path: path.display().to_string(),
+                description: description.to_string(),
+            });
+        }
+
+        Ok(artifacts)
+    }
+
+    fn check_backend_availability(&self) -> AxiomResult<bool> {
+        Ok(probe_tool(&self.lake_path, &["--version"]))
+    }
+
+    fn get_backend_version(&self) -> AxiomResult<String> {
+        let mut command = Command::new(&self.lake_path);
+        command.args(["env", "lean", "--version"]);
+        if let Some(project) = &self.project_dir {
+            command.current_dir(project);
+        }
+        let output = run_with_timeout(&mut command, "Lean", self.timeout)?;
+
+        output
+            .stdout
+            .lines()
+            .map(|line| line.trim())
+            .find(|line| !line.is_empty())
+            .map(|line| line.to_string())
+            .ok_or_else(|| AxiomError::ExternalToolError {
+                tool: "Lean".to_string(),
+                message: "No version information reported".to_string(),
+            })
+    }
+
+    fn install_dependencies(&self) -> AxiomResult<()> {
+        if self.check_backend_availability()? {
+            return Ok(());
+        }
+
+        Err(AxiomError::MissingDependenciesError(format!(
+            "lake executable '{}' not found. Install Lean 4 with elan from \
+            https://github.com/leanprover/elan or set the 'lake' tool path in the configuration.",
+            self.lake_path
+        )))
+    }
+
+    fn get_verification_command(
+        &self,
+        spec_file: &Path,
+        _impl_file: &Path,
+        options: &VerificationOptions,
+    ) -> AxiomResult<Command> {
+        let mut command = Command::new(&self.lake_path);
+
+        match (&self.project_dir, spec_file.parent()) {
+            (Some(project), _) => {
+                command.current_dir(project);
+            }
+            (None, Some(dir)) => {
+                command.current_dir(dir);
+            }
+            (None, None) => {}
+        }
+        command
+            .args(["env", "lean", "-D"])
+            .arg(format!("maxHeartbeats={}", Self::max_heartbeats(&options.proof_level)))
+            .arg(spec_file);
+
+        Ok(command)
+    }
+
+    fn parse_verification_output(
+        &self,
+        output: &str,
+        exit_code: i32,
+    ) -> AxiomResult<VerificationResult> {
+        let errors: Vec<String> = output
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| line.contains(": error:") || line.starts_with("error:"))
+            .map(|line| line.to_string())
+            .collect();
+        let uses_sorry = output.contains("declaration uses 'sorry'");
+
+        let status = if !errors.is_empty() {
+            VerificationStatus::Failed(errors)
+        } else if exit_code != 0 {
+            let message = output
+                .lines()
+                .map(|line| line.trim())
+                .find(|line| !line.is_empty())
+                .map(|line| line.to_string())
+                .unwrap_or_else(|| format!("Lean exited with code {}", exit_code));
+            VerificationStatus::Error(message)
+        } else if uses_sorry {
+            VerificationStatus::Unverified
+        } else {
+            VerificationStatus::Verified
+        };
+
+        Ok(VerificationResult {
+            status,
+            proof_artifacts: vec![],
+            verification_time: Duration::ZERO,
+            resource_usage: ResourceUsage::default(),
+            goal_results: vec![],
+            counterexamples: vec![],
+        })
+    }
+}
+
+/// One goal per named declaration, marked `Incomplete` if its body uses `sorry` or `admit`
+pub fn proof_goals(source: &str) -> Vec<GoalResult> {
+    let code = strip_comments(source);
+    let mut goals: Vec<GoalResult> = Vec::new();
+
+    for line in code.lines() {
+        let mut words = line.split_whitespace().peekable();
+        // Skip modifiers such as `private`, `protected` or `noncomputable`
+        while let Some(word) = words.peek() {
+            if DECLARATION_KEYWORDS.contains(word) {
+                break;
+            }
+            if !matches!(*word, "private" | "protected" | "noncomputable" | "partial" | "unsafe") {
+                break;
+            }
+            words.next();
+        }
+
+        match (words.next(), words.next()) {
+            (Some(keyword), Some(name)) if DECLARATION_KEYWORDS.contains(&keyword) => {
+                goals.push(GoalResult {
+                    name: name.trim_end_matches(':').to_string(),
+                    outcome: GoalOutcome::Valid,
+                    duration: Duration::ZERO,
+                    resource_count: None,
+                    prover: None,
+                });
+            }
+            _ => {}
+        }
+
+        let has_hole = line
+            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .any(|word| word == "sorry" || word == "admit");
+        if has_hole {
+            if let Some(goal) = goals.last_mut() {
+                goal.outcome = GoalOutcome::Incomplete;
+            }
+        }
+    }
+
+    goals
+}
+
+/// Remove `-- ...` line comments and `/- ... -/` block comments, which may nest
+fn strip_comments(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut depth = 0usize;
+    let mut chars = source.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('/', Some('-')) => {
+                chars.next();
+                depth += 1;
+            }
+            ('-', Some('/')) if depth > 0 => {
+                chars.next();
+                depth -= 1;
+            }
+            ('-', Some('-')) if depth == 0 => {
+                while chars.peek().is_some_and(|&c| c != '\n') {
+                    chars.next();
+                }
+            }
+            ('\n', _) => out.push('\n'),
+            _ if depth > 0 => {}
+            _ => out.push(c),
+        }
+    }
+
+    out
+}
@@ -0,0 +1,173 @@
+pub mod coq;
+pub mod dafny;
+pub mod engine;
+pub mod frama_c;
+pub mod fstar;
+pub mod fstar_diagnostics;
+pub mod lean;
+pub mod registry;
+pub mod smt_output;
+pub mod tlc;
+pub mod why3;
+pub mod z3;
+
+pub use coq::CoqBackend;
+pub use dafny::DafnyBackend;
+pub use engine::BackendEngine;
+pub use frama_c::FramaCBackend;
+pub use fstar::FStarBackend;
+pub use fstar_diagnostics::{parse_fstar_diagnostics, FStarErrorCategory};
+pub use lean::LeanBackend;
+pub use registry::{BackendRegistry, BoxedBackend};
+pub use tlc::TlcBackend;
+pub use why3::Why3Backend;
+pub use z3::Z3Backend;
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use crate::errors::{AxiomError, AxiomResult};
+use crate::models::common::{Language, VerificationLanguage};
+
+/// Captured output of an external verification tool run
+#[derive(Debug, Clone)]
+pub struct ToolOutput {
+    pub stdout: String,
+    pub stderr: String,
+    /// Exit code of the process, `None` if it was killed or terminated by a signal
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    pub elapsed: Duration,
+}
+
+impl ToolOutput {
+    /// Combined stdout and stderr, in that order
+    pub fn combined(&self) -> String {
+        if self.stderr.is_empty() {
+            self.stdout.clone()
+        } else if self.stdout.is_empty() {
+            self.stderr.clone()
+        } else {
+            format!("{}\n{}", self.stdout, self.stderr)
+        }
+    }
+}
+
+/// Run a command to completion, killing it if it exceeds the timeout
+pub fn run_with_timeout(
+    command: &mut Command,
+    tool: &str,
+    timeout: Duration,
+) -> AxiomResult<ToolOutput> {
+    let start = Instant::now();
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| AxiomError::ExternalToolError {
+            tool: tool.to_string(),
+            message: format!("Failed to start process: {}", e),
+        })?;
+
+    // Drain the pipes on separate threads so a chatty tool cannot block on a full pipe
+    let stdout_reader = spawn_pipe_reader(child.stdout.take());
+    let stderr_reader = spawn_pipe_reader(child.stderr.take());
+
+    let mut timed_out = false;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) if start.elapsed() >= timeout => {
+                timed_out = true;
+                let _ = child.kill();
+                break child.wait().ok();
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(20)),
+            Err(e) => {
+                return Err(AxiomError::ExternalToolError {
+                    tool: tool.to_string(),
+                    message: format!("Failed to wait for process: {}", e),
+                });
+            }
+        }
+    };
+
+    // Grandchildren of a killed tool may keep the pipes open, so only wait briefly for them
+    let grace = if timed_out { Duration::from_millis(200) } else { Duration::from_secs(5) };
+    let stdout = stdout_reader.recv_timeout(grace).unwrap_or_default();
+    let stderr = stderr_reader.recv_timeout(grace).unwrap_or_default();
+
+    Ok(ToolOutput {
+        stdout,
+        stderr,
+        exit_code: if timed_out { None } else { status.and_then(|s| s.code()) },
+        timed_out,
+        elapsed: start.elapsed(),
+    })
+}
+
+/// Read a child pipe to the end on a background thread
+fn spawn_pipe_reader<R: Read + Send + 'static>(pipe: Option<R>) -> mpsc::Receiver<String> {
+    let (sender, receiver) = mpsc::channel();
+    if let Some(mut pipe) = pipe {
+        std::thread::spawn(move || {
+            let mut buf = String::new();
+            let _ = pipe.read_to_string(&mut buf);
+            let _ = sender.send(buf);
+        });
+    }
+    receiver
+}
+
+/// Check whether a tool can be started by asking for its version
+pub fn probe_tool(path: &str, version_args: &[&str]) -> bool {
+    Command::new(path)
+        .args(version_args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+/// Create a fresh working directory for a single verification run
+pub fn create_run_dir(root: &Path, prefix: &str) -> AxiomResult<PathBuf> {
+    static RUN_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let dir = root.join(format!(
+        "{}_{}_{}_{}",
+        prefix,
+        std::process::id(),
+        chrono::Utc::now().timestamp_millis(),
+        RUN_COUNTER.fetch_add(1, Ordering::SeqCst)
+    ));
+    std::fs::create_dir_all(&dir).map_err(|e| {
+        AxiomError::SystemError(format!("Failed to create working directory: {}", e))
+    })?;
+    Ok(dir)
+}
+
+/// Write a file used as input or output of a verification run
+pub fn write_run_file(path: &Path, contents: &str) -> AxiomResult<()> {
+    std::fs::write(path, contents).map_err(|e| {
+        AxiomError::SystemError(format!("Failed to write {}: {}", path.display(), e))
+    })
+}
+
+/// Error for an implementation whose language a backend cannot check against a
+/// specification in `verification_language`
+pub fn incompatible_implementation(
+    language: &Language,
+    verification_language: VerificationLanguage,
+) -> AxiomError {
+    AxiomError::LanguageCompatibilityError {
+        source_lang: language.to_string(),
+        target_lang: verification_language.to_string(),
+    }
+}
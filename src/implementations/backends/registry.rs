@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+use log::debug;
+
+use crate::config::ExternalToolsConfig;
+use crate::errors::AxiomResult;
+use crate::implementations::backends::{
+    BackendEngine, CoqBackend, DafnyBackend, FStarBackend, FramaCBackend, LeanBackend, TlcBackend, Why3Backend,
+    Z3Backend,
+};
+use crate::models::common::VerificationSystem;
+use crate::traits::verification_engine::VerificationBackendAdapter;
+
+/// Verification backend that can be shared with the threads running generators
+pub type BoxedBackend = Box<dyn VerificationBackendAdapter + Send + Sync>;
+
+/// Maps each verification system to the backend adapter that runs it
+///
+/// `BackendRegistry::from_config` registers the built-in backends; library users
+/// can replace them or add adapters for `VerificationSystem::Custom` systems with
+/// `register`.
+#[derive(Default)]
+pub struct BackendRegistry {
+    backends: HashMap<VerificationSystem, BoxedBackend>,
+}
+
+impl BackendRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry with every built-in backend, configured from the external tools config
+    pub fn from_config(tools: &ExternalToolsConfig) -> Self {
+        Self::new()
+            .with_backend(VerificationSystem::FStar, FStarBackend::new(tools))
+            .with_backend(VerificationSystem::Dafny, DafnyBackend::new(tools))
+            .with_backend(VerificationSystem::Z3, Z3Backend::new(tools))
+            .with_backend(VerificationSystem::TLA, TlcBackend::new(tools))
+            .with_backend(VerificationSystem::Coq, CoqBackend::new(tools))
+            .with_backend(VerificationSystem::Lean, LeanBackend::new(tools))
+            .with_backend(VerificationSystem::Why3, Why3Backend::new(tools))
+            .with_backend(VerificationSystem::FramaC, FramaCBackend::new(tools))
+    }
+
+    /// Register a backend, returning the one it replaces
+    pub fn register<B>(&mut self, system: VerificationSystem, backend: B) -> Option<BoxedBackend>
+    where
+        B: VerificationBackendAdapter + Send + Sync + 'static,
+    {
+        debug!("Registering verification backend for {}", system);
+        self.backends.insert(system, Box::new(backend))
+    }
+
+    /// Register a backend, builder style
+    pub fn with_backend<B>(mut self, system: VerificationSystem, backend: B) -> Self
+    where
+        B: VerificationBackendAdapter + Send + Sync + 'static,
+    {
+        self.register(system, backend);
+        self
+    }
+
+    /// Remove the backend for a system
+    pub fn unregister(&mut self, system: &VerificationSystem) -> Option<BoxedBackend> {
+        self.backends.remove(system)
+    }
+
+    /// Backend registered for a system
+    pub fn get(&self, system: &VerificationSystem) -> Option<&(dyn VerificationBackendAdapter + Send + Sync)> {
+        self.backends.get(system).map(|backend| backend.as_ref())
+    }
+
+    /// Verification engine running the backend registered for a system
+    pub fn engine(&self, system: &VerificationSystem) -> Option<BackendEngine<'_>> {
+        self.get(system)
+            .map(|backend| BackendEngine::new(system.clone(), backend))
+    }
+
+    /// Whether a backend is registered for a system, regardless of whether its tool is installed
+    pub fn contains(&self, system: &VerificationSystem) -> bool {
+        self.backends.contains_key(system)
+    }
+
+    /// Systems with a registered backend
+    pub fn systems(&self) -> Vec<VerificationSystem> {
+        let mut systems: Vec<VerificationSystem> = self.backends.keys().cloned().collect();
+        systems.sort_by_key(|system| system.to_string());
+        systems
+    }
+
+    /// Whether a system has a registered backend whose tool is installed and runnable
+    pub fn is_available(&self, system: &VerificationSystem) -> AxiomResult<bool> {
+        match self.get(system) {
+            Some(backend) => backend.check_backend_availability(),
+            None => Ok(false),
+        }
+    }
+}
+
+impl std::fmt::Debug for BackendRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BackendRegistry").field("systems", &self.systems()).finish()
+    }
+}
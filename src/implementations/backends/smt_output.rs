@@ -0,0 +1,287 @@
+//! Parser for the responses an SMT-LIB solver prints in `-smt2` mode
+//!
+//! Z3 answers each `(check-sat)` with a bare `sat`, `unsat`, `unknown` or `timeout`
+//! line, and each `(get-model)` with an s-expression of `define-fun`s:
+//!
+//! ```text
+//! sat
+//! (
+//!   (define-fun x () Int
+//!     (- 3))
+//!   (define-fun ok () Bool
+//!     false)
+//! )
+//! ```
+//!
+//! Text printed by `(echo "...")` is used to label the next check.
+
+use std::collections::BTreeMap;
+
+/// Answer to a single `(check-sat)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SatResult {
+    Sat,
+    Unsat,
+    Unknown,
+    Timeout,
+}
+
+/// Answer to a `(check-sat)` together with the model printed for it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckOutcome {
+    /// Text of the `(echo ...)` printed just before the check, if any
+    pub label: Option<String>,
+    pub result: SatResult,
+    /// Variable and function interpretations from `(get-model)`
+    pub model: Option<BTreeMap<String, String>>,
+}
+
+/// Everything a solver printed for one query file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SmtOutput {
+    pub checks: Vec<CheckOutcome>,
+    /// Messages of `(error "...")` responses
+    pub errors: Vec<String>,
+}
+
+/// A parsed s-expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SExpr {
+    Atom(String),
+    List(Vec<SExpr>),
+}
+
+impl SExpr {
+    fn as_atom(&self) -> Option<&str> {
+        match self {
+            SExpr::Atom(atom) => Some(atom),
+            SExpr::List(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for SExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SExpr::Atom(atom) => write!(f, "{}", atom),
+            SExpr::List(items) => {
+                let items: Vec<String> = items.iter().map(|i| i.to_string()).collect();
+                write!(f, "({})", items.join(" "))
+            }
+        }
+    }
+}
+
+/// Parse solver output into per-check results and errors
+pub fn parse_smt_output(output: &str) -> SmtOutput {
+    let mut parsed = SmtOutput::default();
+    let mut label: Option<String> = None;
+    let mut pending = String::new();
+
+    for line in output.lines() {
+        // Responses that are s-expressions may span several lines
+        if !pending.is_empty() || line.trim_start().starts_with('(') {
+            pending.push_str(line);
+            pending.push('\n');
+            if !is_balanced(&pending) {
+                continue;
+            }
+            for expr in parse_sexprs(&pending) {
+                handle_response(&expr, &mut parsed);
+            }
+            pending.clear();
+            continue;
+        }
+
+        let result = match line.trim() {
+            "" => continue,
+            "sat" => SatResult::Sat,
+            "unsat" => SatResult::Unsat,
+            "unknown" => SatResult::Unknown,
+            "timeout" => SatResult::Timeout,
+            other => {
+                label = Some(other.to_string());
+                continue;
+            }
+        };
+        parsed.checks.push(CheckOutcome {
+            label: label.take(),
+            result,
+            model: None,
+        });
+    }
+
+    parsed
+}
+
+/// Parse a `(get-model)` response into a name to value map
+///
+/// Constants map to their value, functions to a `lambda` over their parameters.
+pub fn parse_model(model: &SExpr) -> BTreeMap<String, String> {
+    let mut values = BTreeMap::new();
+    let definitions = match model {
+        SExpr::List(items) => items,
+        SExpr::Atom(_) => return values,
+    };
+
+    for definition in definitions {
+        let parts = match definition {
+            SExpr::List(parts) if parts.len() == 5 => parts,
+            _ => continue,
+        };
+        if parts[0].as_atom() != Some("define-fun") {
+            continue;
+        }
+        let name = match parts[1].as_atom() {
+            Some(name) => name.trim_matches('|').to_string(),
+            None => continue,
+        };
+        let value = match &parts[2] {
+            SExpr::List(params) if !params.is_empty() => {
+                format!("(lambda {} {})", parts[2], parts[4])
+            }
+            _ => render_value(&parts[4]),
+        };
+        values.insert(name, value);
+    }
+
+    values
+}
+
+/// Parse every complete s-expression in the text
+pub fn parse_sexprs(text: &str) -> Vec<SExpr> {
+    let tokens = tokenize(text);
+    let mut exprs = Vec::new();
+    let mut stack: Vec<Vec<SExpr>> = Vec::new();
+
+    for token in tokens {
+        match token.as_str() {
+            "(" => stack.push(Vec::new()),
+            ")" => {
+                let list = match stack.pop() {
+                    Some(list) => SExpr::List(list),
+                    None => continue,
+                };
+                match stack.last_mut() {
+                    Some(parent) => parent.push(list),
+                    None => exprs.push(list),
+                }
+            }
+            _ => match stack.last_mut() {
+                Some(parent) => parent.push(SExpr::Atom(token)),
+                None => exprs.push(SExpr::Atom(token)),
+            },
+        }
+    }
+
+    exprs
+}
+
+fn handle_response(expr: &SExpr, parsed: &mut SmtOutput) {
+    let items = match expr {
+        SExpr::List(items) => items,
+        SExpr::Atom(_) => return,
+    };
+
+    match items.first().and_then(|i| i.as_atom()) {
+        Some("error") => {
+            let message = items
+                .get(1)
+                .and_then(|m| m.as_atom())
+                .unwrap_or("")
+                .trim_matches('"')
+                .to_string();
+            parsed.errors.push(message);
+        }
+        // Older solvers wrap the definitions in `(model ...)`
+        Some("model") => attach_model(parsed, parse_model(&SExpr::List(items[1..].to_vec()))),
+        _ => attach_model(parsed, parse_model(expr)),
+    }
+}
+
+fn attach_model(parsed: &mut SmtOutput, model: BTreeMap<String, String>) {
+    if let Some(check) = parsed.checks.last_mut() {
+        if check.result == SatResult::Sat && check.model.is_none() {
+            check.model = Some(model);
+        }
+    }
+}
+
+/// Render a model value, folding negative literals such as `(- 3)` into `-3`
+fn render_value(value: &SExpr) -> String {
+    if let SExpr::List(items) = value {
+        if let [SExpr::Atom(minus), SExpr::Atom(number)] = items.as_slice() {
+            if minus == "-" && number.parse::<f64>().is_ok() {
+                return format!("-{}", number);
+            }
+        }
+    }
+    value.to_string()
+}
+
+/// Split text into parentheses, string literals, quoted symbols and plain atoms
+fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            '(' | ')' => {
+                tokens.push(c.to_string());
+                chars.next();
+            }
+            ';' => {
+                while chars.peek().is_some_and(|&c| c != '\n') {
+                    chars.next();
+                }
+            }
+            '"' | '|' => {
+                let mut token = String::new();
+                token.push(c);
+                chars.next();
+                while let Some(next) = chars.next() {
+                    token.push(next);
+                    if next == c {
+                        // SMT-LIB escapes a quote inside a string by doubling it
+                        if c == '"' && chars.peek() == Some(&'"') {
+                            token.push('"');
+                            chars.next();
+                            continue;
+                        }
+                        break;
+                    }
+                }
+                tokens.push(token);
+            }
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            _ => {
+                let mut token = String::new();
+                while let Some(&next) = chars.peek() {
+                    if next.is_whitespace() || next == '(' || next == ')' {
+                        break;
+                    }
+                    token.push(next);
+                    chars.next();
+                }
+                tokens.push(token);
+            }
+        }
+    }
+
+    tokens
+}
+
+/// Whether every opened parenthesis outside string literals has been closed
+fn is_balanced(text: &str) -> bool {
+    let mut depth = 0i32;
+    for token in tokenize(text) {
+        match token.as_str() {
+            "(" => depth += 1,
+            ")" => depth -= 1,
+            _ => {}
+        }
+    }
+    depth <= 0
+}
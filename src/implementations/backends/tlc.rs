@@ -0,0 +1,636 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use log::{debug, info, warn};
+
+use crate::config::ExternalToolsConfig;
+use crate::errors::{AxiomError, AxiomResult};
+use crate::implementations::backends::{
+    create_run_dir, incompatible_implementation, probe_tool, run_with_timeout, write_run_file,
+};
+use crate::models::common::{Language, ResourceUsage, VerificationLanguage};
+use crate::models::implementation::Implementation;
+use crate::models::specification::Specification;
+use crate::models::verification::{
+    ArtifactType, Counterexample, ProofArtifact, TraceStep, VerificationOptions,
+    VerificationResult, VerificationStatus,
+};
+use crate::traits::verification_engine::VerificationBackendAdapter;
+
+/// Module name used when a specification does not declare one
+const DEFAULT_MODULE_NAME: &str = "AxiomSpec";
+
+/// Prefix of the definitions generated for invariants (checked with `INVARIANT`)
+const INVARIANT_PREFIX: &str = "AxiomInv_";
+
+/// Prefix of the definitions generated for temporal properties (checked with `PROPERTY`)
+const PROPERTY_PREFIX: &str = "AxiomProp_";
+
+/// Comment introducing the generated property definitions at the end of a module
+const PROPERTIES_MARKER: &str = "\\* Properties checked by Axiom";
+
+/// Model-checking backend that runs TLC from `tla2tools.jar`
+///
+/// Java and the jar are looked up under the `java` and `tla2tools` keys of
+/// `ExternalToolsConfig::tool_paths`, defaulting to `java` on the `PATH` and
+/// `tla2tools.jar` in the working directory. The `.cfg` model is generated from
+/// the module's `Spec` (or `Init`/`Next`), its declared constants and the
+/// specification's properties. Constants take their values from
+/// `ExternalToolsConfig::model_constants`.
+///
+/// With an implementation, TLC checks a module that `EXTENDS` the implementation module
+/// and defines the specification's properties, so the behavior explored is the
+/// implementation's.
+#[derive(Debug, Clone)]
+pub struct TlcBackend {
+    java_path: String,
+    tla2tools_path: String,
+    timeout: Duration,
+    work_root: PathBuf,
+    /// Values of declared constants; the others become model values
+    constants: BTreeMap<String, String>,
+}
+
+/// What TLC reported for one model-checking run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TlcOutput {
+    /// `Model checking completed. No error has been found.` was printed
+    pub completed: bool,
+    pub states_generated: Option<u64>,
+    pub distinct_states: Option<u64>,
+    /// Violated invariants, properties and deadlocks, as TLC describes them
+    pub violations: Vec<String>,
+    /// Error traces, each paired with the violation it belongs to
+    pub traces: Vec<(String, Vec<TraceStep>)>,
+    /// Parse, semantic and configuration errors
+    pub errors: Vec<String>,
+}
+
+impl TlcBackend {
+    /// Create a backend from the external tools configuration
+    pub fn new(tools: &ExternalToolsConfig) -> Self {
+        Self {
+            java_path: tools.tool_path("java", "java"),
+            tla2tools_path: tools.tool_path("tla2tools", "tla2tools.jar"),
+            timeout: tools.timeout,
+            work_root: std::env::temp_dir().join("axiom_tlc"),
+            constants: tools.model_constants.clone(),
+        }
+    }
+
+    /// Set the directory under which per-run working directories are created
+    pub fn with_work_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.work_root = dir.into();
+        self
+    }
+
+    /// Bind a declared constant to a TLA+ value, such as `3` or `{"a", "b"}`, in the model
+    pub fn with_constant(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.constants.insert(name.into(), value.into());
+        self
+    }
+
+    /// Path of the `tla2tools.jar` this backend runs
+    pub fn tla2tools_path(&self) -> &str {
+        &self.tla2tools_path
+    }
+
+    /// Build the TLC model configuration for a module
+    ///
+    /// The behavior is `Spec` if the module defines it, `Init`/`Next` otherwise.
+    /// Declared constants take their value from `constants`, or become model values of
+    /// the same name. `TypeOK` and the generated `AxiomInv_*` definitions become
+    /// invariants and the generated `AxiomProp_*` definitions become temporal properties.
+    pub fn generate_config(module: &str, constants: &BTreeMap<String, String>) -> String {
+        let definitions = top_level_definitions(module);
+        let defines = |name: &str| definitions.iter().any(|d| d == name);
+
+        let mut config = String::new();
+        let declared = declared_constants(module);
+        if !declared.is_empty() {
+            config.push_str("CONSTANTS\n");
+            for name in &declared {
+                let value = constants.get(name).unwrap_or(name);
+                config.push_str(&format!("    {} = {}\n", name, value));
+            }
+        }
+        if defines("Spec") {
+            config.push_str("SPECIFICATION Spec\n");
+        } else {
+            config.push_str("INIT Init\nNEXT Next\n");
+        }
+        for name in &definitions {
+            if name == "TypeOK" || name.starts_with(INVARIANT_PREFIX) {
+                config.push_str(&format!("INVARIANT {}\n", name));
+            }
+        }
+        for name in &definitions {
+            if name.starts_with(PROPERTY_PREFIX) {
+                config.push_str(&format!("PROPERTY {}\n", name));
+            }
+        }
+
+        config
+    }
+
+    /// Extract the module name from a `---- MODULE Name ----` header
+    fn module_name(code: &str) -> Option<String> {
+        code.lines()
+            .map(|line| line.trim_start_matches('-').trim())
+            .find_map(|line| line.strip_prefix("MODULE "))
+            .and_then(|rest| rest.split_whitespace().next())
+            .map(|name| name.to_string())
+    }
+
+    /// Make sure the code is a complete module, returning the module name and source to write
+    fn with_module_frame(code: &str, default_name: &str) -> (String, String) {
+        match Self::module_name(code) {
+            Some(name) => (name, code.to_string()),
+            None => (
+                default_name.to_string(),
+                format!("---- MODULE {} ----\n{}\n====\n", default_name, code.trim_end()),
+            ),
+        }
+    }
+
+    /// Insert definitions just before the module's closing `====` line, after
+    /// `PROPERTIES_MARKER`
+    fn insert_definitions(module: &str, definitions: &[String]) -> String {
+        if definitions.is_empty() {
+            return module.to_string();
+        }
+
+        let lines: Vec<&str> = module.lines().collect();
+        let end = lines
+            .iter()
+            .rposition(|line| line.trim_start().starts_with("===="))
+            .unwrap_or(lines.len());
+
+        let mut out: Vec<String> = lines[..end].iter().map(|l| l.to_string()).collect();
+        out.push(String::new());
+        out.push(PROPERTIES_MARKER.to_string());
+        out.extend(definitions.iter().cloned());
+        out.push(String::new());
+        out.extend(lines[end..].iter().map(|l| l.to_string()));
+        out.join("\n") + "\n"
+    }
+
+    /// Definitions that `insert_definitions` added to a module
+    fn inserted_definitions(module: &str) -> String {
+        let Some(start) = module.find(PROPERTIES_MARKER) else {
+            return String::new();
+        };
+        let definitions = &module[start + PROPERTIES_MARKER.len()..];
+        let end = definitions
+            .lines()
+            .position(|line| line.trim_start().starts_with("===="))
+            .unwrap_or(usize::MAX);
+        definitions
+            .lines()
+            .take(end)
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string()
+    }
+
+    /// Module that extends the implementation module with the specification's properties,
+    /// with its name
+    fn check_module(impl_module: &str, spec_code: &str) -> (String, String) {
+        let name = format!("{}Check", impl_module);
+        let code = format!(
+            "---- MODULE {} ----\nEXTENDS {}\n\n{}\n{}\n====\n",
+            name,
+            impl_module,
+            PROPERTIES_MARKER,
+            Self::inserted_definitions(spec_code)
+        );
+        (name, code)
+    }
+}
+
+impl VerificationBackendAdapter for TlcBackend {
+    fn convert_specification(&self, spec: &Specification) -> AxiomResult<String> {
+        if spec.formal_spec.verification_language != VerificationLanguage::TLAPlus {
+            return Err(AxiomError::LanguageCompatibilityError {
+                source_lang: spec.formal_spec.verification_language.to_string(),
+                target_lang: VerificationLanguage::TLAPlus.to_string(),
+            });
+        }
+
+        let (_, module) = Self::with_module_frame(&spec.formal_spec.spec_code, DEFAULT_MODULE_NAME);
+        let definitions: Vec<String> = spec
+            .formal_properties
+            .iter()
+            .filter(|p| !p.formal_definition.trim().is_empty())
+            .map(|p| {
+                let prefix = if is_temporal(&p.formal_definition) {
+                    PROPERTY_PREFIX
+                } else {
+                    INVARIANT_PREFIX
+                };
+                format!("{}{} == {}", prefix, identifier(&p.id), p.formal_definition.trim())
+            })
+            .collect();
+
+        Ok(Self::insert_definitions(&module, &definitions))
+    }
+
+    fn convert_implementation(&self, implementation: &Implementation) -> AxiomResult<String> {
+        if !self.supports_implementation_language(&implementation.language) {
+            return Err(incompatible_implementation(
+                &implementation.language,
+                VerificationLanguage::TLAPlus,
+            ));
+        }
+        Ok(implementation.source_code.clone())
+    }
+
+    fn supports_implementation_language(&self, language: &Language) -> bool {
+        match language {
+            Language::Custom(name) => {
+                matches!(name.to_lowercase().as_str(), "tla" | "tla+" | "pluscal")
+            },
+            _ => false,
+        }
+    }
+
+    fn execute_verification(
+        &self,
+        converted_spec: &str,
+        converted_impl: &str,
+        options: &VerificationOptions,
+    ) -> AxiomResult<VerificationResult> {
+        let run_dir = create_run_dir(&self.work_root, "run")?;
+        debug!("TLC working directory: {}", run_dir.display());
+
+        let (module, spec_code) = Self::with_module_frame(converted_spec, DEFAULT_MODULE_NAME);
+        let spec_file = run_dir.join(format!("{}.tla", module));
+        write_run_file(&spec_file, &spec_code)?;
+        write_run_file(&run_dir.join(format!("{}.cfg", module)), &Self::generate_config(&spec_code, &self.constants))?;
+
+        // The implementation's behavior is checked against the specification's properties
+        // in a module extending it
+        let (checked, checked_file) = if converted_impl.trim().is_empty() {
+            (module.clone(), spec_file.clone())
+        } else {
+            let (impl_module, impl_code) =
+                Self::with_module_frame(converted_impl, &format!("{}Impl", module));
+            write_run_file(&run_dir.join(format!("{}.tla", impl_module)), &impl_code)?;
+            let (check_module, check_code) = Self::check_module(&impl_module, &spec_code);
+            let path = run_dir.join(format!("{}.tla", check_module));
+            write_run_file(&path, &check_code)?;
+            let config = Self::generate_config(&format!("{}\n{}", impl_code, check_code), &self.constants);
+            write_run_file(&run_dir.join(format!("{}.cfg", check_module)), &config)?;
+            (check_module, path)
+        };
+
+        let mut command = self.get_verification_command(&spec_file, &checked_file, options)?;
+        info!("Running TLC on module {}", checked);
+        let output = run_with_timeout(&mut command, "TLC", options.timeout)?;
+
+        let log_path = run_dir.join("tlc.log");
+        write_run_file(&log_path, &output.combined())?;
+
+        let mut result = if output.timed_out {
+            warn!("TLC timed out after {:?}", options.timeout);
+            VerificationResult {
+                status: VerificationStatus::Timeout,
+                proof_artifacts: vec![],
+                verification_time: output.elapsed,
+                resource_usage: ResourceUsage::default(),
+                goal_results: vec![],
+                counterexamples: vec![],
+            }
+        } else {
+            self.parse_verification_output(&output.combined(), output.exit_code.unwrap_or(-1))?
+        };
+
+        for (index, counterexample) in result.counterexamples.iter().enumerate() {
+            let steps: Vec<serde_json::Value> = counterexample
+                .trace
+                .iter()
+                .enumerate()
+                .map(|(step, s)| {
+                    serde_json::json!({ "step": step + 1, "action": s.action, "state": s.state })
+                })
+                .collect();
+            let trace = serde_json::json!({
+                "violation": counterexample.property,
+                "states": steps,
+            });
+            let path = run_dir.join(format!("trace_{}.json", index + 1));
+            write_run_file(&path, &serde_json::to_string_pretty(&trace).unwrap_or_default())?;
+        }
+
+        result.verification_time = output.elapsed;
+        result.resource_usage.cpu_seconds = output.elapsed.as_secs_f64();
+        result.proof_artifacts.push(ProofArtifact {
+            artifact_type: ArtifactType::Log,
+            path: log_path.display().to_string(),
+            description: format!("TLC output for module {}", checked),
+        });
+        let mut artifacts: Vec<ProofArtifact> = self
+            .extract_artifacts(&run_dir)?
+            .into_iter()
+            .filter(|a| !matches!(a.artifact_type, ArtifactType::Log))
+            .collect();
+        artifacts.sort_by(|a, b| a.path.cmp(&b.path));
+        result.proof_artifacts.extend(artifacts);
+
+        Ok(result)
+    }
+
+    fn extract_artifacts(&self, output_dir: &Path) -> AxiomResult<Vec<ProofArtifact>> {
+        let entries = std::fs::read_dir(output_dir).map_err(|e| {
+            AxiomError::SystemError(format!("Failed to read {}: {}", output_dir.display(), e))
+        })?;
+
+        let mut artifacts = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            let (artifact_type, description) = match path.extension().and_then(|e| e.to_str()) {
+                Some("json") if file_name.starts_with("trace") => {
+                    (ArtifactType::Counterexample, "TLC error trace")
+                }
+                Some("cfg") => (ArtifactType::Model, "TLC model configuration"),
+                Some("log") => (ArtifactType::Log, "TLC output"),
+                _ => continue,
+            };
+            artifacts.push(ProofArtifact {
+                artifact_type,
+                path: path.display().to_string(),
+                description: description.to_string(),
+            });
+        }
+
+        Ok(artifacts)
+    }
+
+    fn check_backend_availability(&self) -> AxiomResult<bool> {
+        Ok(Path::new(&self.tla2tools_path).is_file() && probe_tool(&self.java_path, &["-version"]))
+    }
+
+    fn get_backend_version(&self) -> AxiomResult<String> {
+        // TLC prints its version banner before complaining about the missing spec
+        let output = run_with_timeout(
+            Command::new(&self.java_path)
+                .arg("-cp")
+                .arg(&self.tla2tools_path)
+                .arg("tlc2.TLC"),
+            "TLC",
+            self.timeout,
+        )?;
+
+        output
+            .combined()
+            .lines()
+            .map(|line| line.trim())
+            .find(|line| line.starts_with("TLC2 Version"))
+            .map(|line| line.to_string())
+            .ok_or_else(|| AxiomError::ExternalToolError {
+                tool: "TLC".to_string(),
+                message: "No version information reported".to_string(),
+            })
+    }
+
+    fn install_dependencies(&self) -> AxiomResult<()> {
+        if self.check_backend_availability()? {
+            return Ok(());
+        }
+
+        Err(AxiomError::MissingDependenciesError(format!(
+            "TLC needs Java ('{}') and tla2tools.jar ('{}'). Download tla2tools.jar from \
+            https://github.com/tlaplus/tlaplus/releases and set the 'java' and 'tla2tools' \
+            tool paths in the configuration.",
+            self.java_path, self.tla2tools_path
+        )))
+    }
+
+    /// TLC command checking `impl_file`, the module extending the implementation, or the
+    /// specification itself when there is no implementation
+    fn get_verification_command(
+        &self,
+        _spec_file: &Path,
+        impl_file: &Path,
+        options: &VerificationOptions,
+    ) -> AxiomResult<Command> {
+        let mut command = Command::new(&self.java_path);
+
+        command
+            .arg(format!("-Xmx{}m", (options.resource_limits.max_memory_kb / 1024).max(64)))
+            .arg("-XX:+UseParallelGC")
+            .arg("-cp")
+            .arg(&self.tla2tools_path)
+            .arg("tlc2.TLC");
+
+        if let Some(dir) = impl_file.parent() {
+            command.current_dir(dir).arg("-metadir").arg(dir.join("states"));
+        }
+        if let Some(stem) = impl_file.file_stem() {
+            command.arg("-config").arg(Path::new(stem).with_extension("cfg"));
+        }
+        if let Some(jobs) = options.resource_limits.parallel_jobs {
+            command.arg("-workers").arg(jobs.to_string());
+        }
+
+        command.arg("-cleanup").arg(impl_file);
+
+        Ok(command)
+    }
+
+    fn parse_verification_output(
+        &self,
+        output: &str,
+        exit_code: i32,
+    ) -> AxiomResult<VerificationResult> {
+        let parsed = parse_tlc_output(output);
+
+        let counterexamples: Vec<Counterexample> = parsed
+            .traces
+            .iter()
+            .map(|(violation, steps)| Counterexample {
+                property: Some(violation.clone()),
+                assignments: steps.last().map(|s| s.state.clone()).unwrap_or_default(),
+                trace: steps.clone(),
+            })
+            .collect();
+
+        let status = if !parsed.violations.is_empty() {
+            VerificationStatus::Failed(parsed.violations.clone())
+        } else if !parsed.errors.is_empty() {
+            VerificationStatus::Error(parsed.errors.join("; "))
+        } else if parsed.completed {
+            VerificationStatus::Verified
+        } else {
+            VerificationStatus::Error(format!(
+                "TLC exited with code {} without completing model checking",
+                exit_code
+            ))
+        };
+
+        Ok(VerificationResult {
+            status,
+            proof_artifacts: vec![],
+            verification_time: Duration::ZERO,
+            resource_usage: ResourceUsage {
+                states_explored: parsed.distinct_states,
+                ..ResourceUsage::default()
+            },
+            goal_results: vec![],
+            counterexamples,
+        })
+    }
+}
+
+/// Parse TLC's console output into statistics, violations and error traces
+pub fn parse_tlc_output(output: &str) -> TlcOutput {
+    let mut parsed = TlcOutput::default();
+    let mut trace: Option<Vec<TraceStep>> = None;
+    let mut last_var: Option<String> = None;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+
+        if let Some(steps) = trace.as_mut() {
+            if let Some(action) = trace_state_header(trimmed) {
+                steps.push(TraceStep { action, state: BTreeMap::new() });
+                last_var = None;
+                continue;
+            }
+            if let Some(step) = steps.last_mut() {
+                let assignment = trimmed.strip_prefix("/\\ ").unwrap_or(trimmed);
+                if let Some((name, value)) = assignment.split_once(" = ") {
+                    if !name.contains(' ') && !name.is_empty() {
+                        step.state.insert(name.to_string(), value.trim().to_string());
+                        last_var = Some(name.to_string());
+                        continue;
+                    }
+                }
+                // Long values wrap onto indented continuation lines
+                if !trimmed.is_empty() && line.starts_with(' ') {
+                    if let Some(value) = last_var.as_ref().and_then(|v| step.state.get_mut(v)) {
+                        value.push(' ');
+                        value.push_str(trimmed);
+                        continue;
+                    }
+                }
+            }
+            if trimmed.is_empty() {
+                continue;
+            }
+            let violation = parsed.violations.last().cloned().unwrap_or_default();
+            parsed.traces.push((violation, trace.take().unwrap_or_default()));
+        }
+
+        if let Some(message) = trimmed.strip_prefix("Error: ") {
+            if message.starts_with("The behavior up to this point is")
+                || message.starts_with("The following behavior constitutes a counter-example")
+            {
+                trace = Some(Vec::new());
+            } else if is_violation(message) {
+                parsed.violations.push(message.trim_end_matches(':').to_string());
+            } else {
+                parsed.errors.push(message.to_string());
+            }
+        } else if trimmed.contains("***Parse Error***") || trimmed.starts_with("Semantic error") {
+            parsed.errors.push(trimmed.to_string());
+        } else if trimmed.starts_with("Model checking completed. No error has been found") {
+            parsed.completed = true;
+        } else if trimmed.contains("distinct states found") {
+            // Progress lines carry the same counts, so the last one wins
+            parsed.states_generated = number_before(trimmed, "states generated");
+            parsed.distinct_states = number_before(trimmed, "distinct states found");
+        }
+    }
+    if let Some(steps) = trace {
+        let violation = parsed.violations.last().cloned().unwrap_or_default();
+        parsed.traces.push((violation, steps));
+    }
+
+    parsed
+}
+
+/// Parse the count printed just before `marker`, e.g. `1,024` in `1,024 states generated`
+fn number_before(line: &str, marker: &str) -> Option<u64> {
+    let (before, _) = line.split_once(marker)?;
+    before.split_whitespace().last()?.replace(',', "").parse().ok()
+}
+
+/// Whether an `Error:` line reports a property violation rather than a tool error
+fn is_violation(message: &str) -> bool {
+    message.starts_with("Invariant")
+        || message.starts_with("Temporal properties were violated")
+        || message.starts_with("Deadlock reached")
+        || message.starts_with("Action property")
+        || message.contains("is violated")
+}
+
+/// Parse `State 3: <Next line 10 ...>`, `3: Stuttering` or `4: Back to state 2: <...>`
+fn trace_state_header(line: &str) -> Option<String> {
+    let rest = line.strip_prefix("State ").unwrap_or(line);
+    let (number, action) = rest.split_once(':')?;
+    number.trim().parse::<usize>().ok()?;
+    Some(action.trim().trim_start_matches('<').trim_end_matches('>').to_string())
+}
+
+/// Names defined at the top level of a module, without parameters
+fn top_level_definitions(module: &str) -> Vec<String> {
+    module
+        .lines()
+        .filter(|line| !line.starts_with(char::is_whitespace))
+        .filter_map(|line| line.split_once("=="))
+        .map(|(name, _)| name.trim())
+        .filter(|name| !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_'))
+        .map(|name| name.to_string())
+        .collect()
+}
+
+/// Names declared with `CONSTANT` or `CONSTANTS`, which may continue over several lines
+fn declared_constants(module: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut continues = false;
+    for line in module.lines() {
+        let line = line.split("\\*").next().unwrap_or("").trim();
+        let declaration = if continues {
+            Some(line)
+        } else {
+            line.strip_prefix("CONSTANTS ")
+                .or_else(|| line.strip_prefix("CONSTANT "))
+        };
+        let Some(declaration) = declaration else {
+            continue;
+        };
+        names.extend(
+            declaration
+                .split(',')
+                .map(|name| name.trim())
+                .filter(|name| {
+                    !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+                })
+                .map(|name| name.to_string()),
+        );
+        continues = declaration.ends_with(',');
+    }
+    names
+}
+
+/// Whether a formula uses a temporal operator (`[]`, `<>`, `~>` or fairness), so TLC has
+/// to check it as a `PROPERTY` over behaviors rather than as an `INVARIANT` of each state
+fn is_temporal(formula: &str) -> bool {
+    // `<<` and `>>` delimit tuples, as in `<<>>`, and are not the eventually operator
+    let formula = formula.replace("<<", " ").replace(">>", " ");
+    ["[]", "<>", "~>", "WF_", "SF_"]
+        .iter()
+        .any(|operator| formula.contains(operator))
+}
+
+/// Turn a property id into a TLA+ identifier
+fn identifier(id: &str) -> String {
+    id.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
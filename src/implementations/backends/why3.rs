@@ -0,0 +1,567 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use log::{debug, info, warn};
+
+use crate::config::ExternalToolsConfig;
+use crate::errors::{AxiomError, AxiomResult};
+use crate::implementations::backends::{
+    create_run_dir, incompatible_implementation, probe_tool, run_with_timeout, write_run_file,
+};
+use crate::models::common::{Language, ProofLevel, ResourceUsage, VerificationLanguage};
+use crate::models::implementation::Implementation;
+use crate::models::specification::Specification;
+use crate::models::verification::{
+    ArtifactType, GoalOutcome, GoalResult, ProofArtifact, VerificationOptions, VerificationResult,
+    VerificationStatus,
+};
+use crate::traits::verification_engine::VerificationBackendAdapter;
+
+/// Prover used when neither the options nor the configuration name one
+const DEFAULT_PROVER: &str = "alt-ergo";
+
+/// Verification backend that discharges WhyML goals with `why3 prove`
+///
+/// Provers are taken from `ResourceLimits::provers`, then from the comma-separated
+/// `why3_provers` entry of `ExternalToolsConfig::tool_paths`, then default to
+/// Alt-Ergo. When several are given, each goal left unproven by one prover is
+/// retried with the next, which is given only those goals; a prover that cannot be
+/// run is skipped. The provers share the verification timeout. The executable is looked up under the `why3` key and defaults to
+/// `why3` on the `PATH`.
+#[derive(Debug, Clone)]
+pub struct Why3Backend {
+    why3_path: String,
+    provers: Vec<String>,
+    timeout: Duration,
+    work_root: PathBuf,
+}
+
+impl Why3Backend {
+    /// Create a backend from the external tools configuration
+    pub fn new(tools: &ExternalToolsConfig) -> Self {
+        let provers = tools
+            .tool_paths
+            .get("why3_provers")
+            .map(|list| {
+                list.split(',')
+                    .map(|p| p.trim().to_string())
+                    .filter(|p| !p.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            why3_path: tools.tool_path("why3", "why3"),
+            provers,
+            timeout: tools.timeout,
+            work_root: std::env::temp_dir().join("axiom_why3"),
+        }
+    }
+
+    /// Set the directory under which per-run working directories are created
+    pub fn with_work_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.work_root = dir.into();
+        self
+    }
+
+    /// Set the provers tried when the verification options do not name any
+    pub fn with_provers(mut self, provers: Vec<String>) -> Self {
+        self.provers = provers;
+        self
+    }
+
+    /// Path of the Why3 executable this backend runs
+    pub fn why3_path(&self) -> &str {
+        &self.why3_path
+    }
+
+    /// Provers to try, in order, for a verification run
+    fn provers_for(&self, options: &VerificationOptions) -> Vec<String> {
+        if !options.resource_limits.provers.is_empty() {
+            options.resource_limits.provers.clone()
+        } else if !self.provers.is_empty() {
+            self.provers.clone()
+        } else {
+            vec![DEFAULT_PROVER.to_string()]
+        }
+    }
+
+    /// Per-goal time limit in seconds for each proof level
+    fn goal_time_limit(proof_level: &ProofLevel) -> u64 {
+        match proof_level {
+            ProofLevel::Quick => 5,
+            ProofLevel::Standard => 10,
+            ProofLevel::Thorough => 30,
+            ProofLevel::Exhaustive => 120,
+            ProofLevel::Custom(value) => value.trim().parse().unwrap_or(10),
+        }
+    }
+
+    /// Build the `why3 prove` command for one prover, restricted to `goals` unless empty
+    fn prove_command(
+        &self,
+        file: &Path,
+        prover: &str,
+        options: &VerificationOptions,
+        goals: &[String],
+    ) -> Command {
+        let mut command = Command::new(&self.why3_path);
+
+        if let Some(dir) = file.parent() {
+            command.current_dir(dir);
+        }
+        command
+            .arg("prove")
+            .arg("-P")
+            .arg(prover)
+            .arg("-t")
+            .arg(Self::goal_time_limit(&options.proof_level).to_string())
+            .arg("-m")
+            .arg((options.resource_limits.max_memory_kb / 1024).max(1).to_string())
+            .arg(file)
+            .args(goal_selection(goals));
+
+        command
+    }
+
+    /// Fold the results of a later prover into the goals so far, keeping the first proof found
+    fn merge_goals(goals: &mut Vec<GoalResult>, attempt: Vec<GoalResult>) {
+        for result in attempt {
+            match goals.iter_mut().find(|g| g.name == result.name) {
+                Some(goal) if !goal.outcome.is_valid() => {
+                    goal.duration += result.duration;
+                    goal.outcome = result.outcome;
+                    goal.resource_count = result.resource_count;
+                    goal.prover = result.prover;
+                }
+                Some(_) => {}
+                None => goals.push(result),
+            }
+        }
+    }
+
+    /// Overall status of a run from its per-goal outcomes
+    fn status_of(goals: &[GoalResult]) -> VerificationStatus {
+        let unproven: Vec<&GoalResult> = goals.iter().filter(|g| !g.outcome.is_valid()).collect();
+        let describe = |g: &&GoalResult| {
+            format!("{}: {:?} ({})", g.name, g.outcome, g.prover.as_deref().unwrap_or("?"))
+        };
+
+        if unproven.is_empty() {
+            VerificationStatus::Verified
+        } else if unproven.iter().any(|g| g.outcome == GoalOutcome::Invalid) {
+            VerificationStatus::Failed(unproven.iter().map(describe).collect())
+        } else if let Some(failure) = unproven.iter().find(|g| matches!(g.outcome, GoalOutcome::Error(_))) {
+            VerificationStatus::Error(describe(failure))
+        } else if unproven
+            .iter()
+            .any(|g| matches!(g.outcome, GoalOutcome::Timeout | GoalOutcome::OutOfResource))
+        {
+            VerificationStatus::Timeout
+        } else {
+            VerificationStatus::Unverified
+        }
+    }
+}
+
+impl VerificationBackendAdapter for Why3Backend {
+    fn convert_specification(&self, spec: &Specification) -> AxiomResult<String> {
+        if spec.formal_spec.verification_language != VerificationLanguage::Why3Lang {
+            return Err(AxiomError::LanguageCompatibilityError {
+                source_lang: spec.formal_spec.verification_language.to_string(),
+                target_lang: VerificationLanguage::Why3Lang.to_string(),
+            });
+        }
+
+        Ok(spec.formal_spec.spec_code.clone())
+    }
+
+    fn convert_implementation(&self, implementation: &Implementation) -> AxiomResult<String> {
+        if !self.supports_implementation_language(&implementation.language) {
+            return Err(incompatible_implementation(
+                &implementation.language,
+                VerificationLanguage::Why3Lang,
+            ));
+        }
+        Ok(implementation.source_code.clone())
+    }
+
+    fn supports_implementation_language(&self, language: &Language) -> bool {
+        match language {
+            Language::Custom(name) => matches!(name.to_lowercase().as_str(), "why3" | "whyml"),
+            _ => false,
+        }
+    }
+
+    fn execute_verification(
+        &self,
+        converted_spec: &str,
+        converted_impl: &str,
+        options: &VerificationOptions,
+    ) -> AxiomResult<VerificationResult> {
+        let run_dir = create_run_dir(&self.work_root, "run")?;
+        debug!("Why3 working directory: {}", run_dir.display());
+
+        // Modules of both parts live in one file so they can refer to each other
+        let mut source = converted_spec.trim_end().to_string();
+        if !converted_impl.trim().is_empty() {
+            source.push_str("\n\n");
+            source.push_str(converted_impl.trim_end());
+        }
+        source.push('\n');
+        let file = run_dir.join("spec.mlw");
+        write_run_file(&file, &source)?;
+
+        let mut goals: Vec<GoalResult> = Vec::new();
+        let mut log = String::new();
+        let mut elapsed = Duration::ZERO;
+        let mut errors: Vec<String> = Vec::new();
+        let mut prover_errors: Vec<String> = Vec::new();
+        let mut timed_out = false;
+
+        // The timeout bounds the whole fallback chain, not each prover
+        let deadline = Instant::now() + options.timeout;
+        for prover in self.provers_for(options) {
+            if !goals.is_empty() && goals.iter().all(|g| g.outcome.is_valid()) {
+                break;
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                warn!("No time left to run why3 prove with {}", prover);
+                timed_out = true;
+                break;
+            }
+
+            // Later provers only get the goals the earlier ones left unproven
+            let unproven: Vec<String> = goals
+                .iter()
+                .filter(|g| !g.outcome.is_valid())
+                .map(|g| g.name.clone())
+                .collect();
+            if unproven.is_empty() {
+                info!("Running why3 prove with {}", prover);
+            } else {
+                info!("Running why3 prove with {} on {} unproven goals", prover, unproven.len());
+            }
+            let output = run_with_timeout(
+                &mut self.prove_command(&file, &prover, options, &unproven),
+                "Why3",
+                remaining,
+            )?;
+            elapsed += output.elapsed;
+            log.push_str(&format!("== {} ==\n{}\n", prover, output.combined()));
+
+            if output.timed_out {
+                warn!("why3 prove with {} timed out after {:?}", prover, remaining);
+                timed_out = true;
+                continue;
+            }
+
+            let attempt = parse_why3_output(&output.combined(), &prover);
+            if attempt.is_empty() && output.exit_code != Some(0) {
+                // No goal was attempted: the file does not parse or type, or the prover is unknown
+                let mut reported = why3_errors(&output.combined());
+                if reported.iter().any(|e| e.starts_with("File \"")) {
+                    // Every prover would stop at the same place in the file
+                    errors = reported;
+                    break;
+                }
+                if reported.is_empty() {
+                    reported.push(format!(
+                        "why3 prove -P {} exited with code {:?}",
+                        prover, output.exit_code
+                    ));
+                }
+                warn!("why3 could not run {}: {}", prover, reported.join("; "));
+                prover_errors.extend(reported);
+                continue;
+            }
+            Self::merge_goals(&mut goals, attempt);
+        }
+
+        let log_path = run_dir.join("why3.log");
+        write_run_file(&log_path, &log)?;
+
+        let status = if !errors.is_empty() {
+            VerificationStatus::Failed(errors)
+        } else if goals.is_empty() {
+            if timed_out {
+                VerificationStatus::Timeout
+            } else if !prover_errors.is_empty() {
+                // No prover could run, which says nothing about the goals
+                VerificationStatus::Error(prover_errors.join("; "))
+            } else {
+                VerificationStatus::Error("Why3 reported no goals".to_string())
+            }
+        } else {
+            Self::status_of(&goals)
+        };
+
+        let mut proof_artifacts = vec![ProofArtifact {
+            artifact_type: ArtifactType::Log,
+            path: log_path.display().to_string(),
+            description: "why3 prove output for every prover tried".to_string(),
+        }];
+        proof_artifacts.extend(
+            self.extract_artifacts(&run_dir)?
+                .into_iter()
+                .filter(|a| !matches!(a.artifact_type, ArtifactType::Log)),
+        );
+
+        Ok(VerificationResult {
+            status,
+            proof_artifacts,
+            verification_time: elapsed,
+            resource_usage: ResourceUsage {
+                cpu_seconds: elapsed.as_secs_f64(),
+                lemmas_proven: goals.iter().filter(|g| g.outcome.is_valid()).count(),
+                ..ResourceUsage::default()
+            },
+            goal_results: goals,
+            counterexamples: vec![],
+        })
+    }
+
+    fn extract_artifacts(&self, output_dir: &Path) -> AxiomResult<Vec<ProofArtifact>> {
+        let entries = std::fs::read_dir(output_dir).map_err(|e| {
+            AxiomError::SystemError(format!("Failed to read {}: {}", output_dir.display(), e))
+        })?;
+
+        let mut artifacts = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let (artifact_type, description) = if path.is_dir() {
+                // `why3 ide`/`replay` keep proof sessions in a directory named after the file
+                (ArtifactType::Proof, "Why3 proof session")
+            } else {
+                match path.extension().and_then(|e| e.to_str()) {
+                    Some("log") => (ArtifactType::Log, "why3 prove output"),
+                    _ => continue,
+                }
+            };
+            artifacts.push(ProofArtifact {
+                artifact_type,
+                path: path.display().to_string(),
+                description: description.to_string(),
+            });
+        }
+
+        Ok(artifacts)
+    }
+
+    fn check_backend_availability(&self) -> AxiomResult<bool> {
+        Ok(probe_tool(&self.why3_path, &["--version"]))
+    }
+
+    fn get_backend_version(&self) -> AxiomResult<String> {
+        let output = run_with_timeout(
+            Command::new(&self.why3_path).arg("--version"),
+            "Why3",
+            self.timeout,
+        )?;
+
+        output
+            .stdout
+            .lines()
+            .map(|line| line.trim())
+            .find(|line| !line.is_empty())
+            .map(|line| line.to_string())
+            .ok_or_else(|| AxiomError::ExternalToolError {
+                tool: "Why3".to_string(),
+                message: "No version information reported".to_string(),
+            })
+    }
+
+    fn install_dependencies(&self) -> AxiomResult<()> {
+        if self.check_backend_availability()? {
+            return Ok(());
+        }
+
+        Err(AxiomError::MissingDependenciesError(format!(
+            "Why3 executable '{}' not found. Install Why3 and a prover with \
+            `opam install why3 alt-ergo`, run `why3 config detect`, or set the 'why3' tool path \
+            in the configuration.",
+            self.why3_path
+        )))
+    }
+
+    fn get_verification_command(
+        &self,
+        spec_file: &Path,
+        _impl_file: &Path,
+        options: &VerificationOptions,
+    ) -> AxiomResult<Command> {
+        let prover = self.provers_for(options).remove(0);
+        Ok(self.prove_command(spec_file, &prover, options, &[]))
+    }
+
+    fn parse_verification_output(
+        &self,
+        output: &str,
+        exit_code: i32,
+    ) -> AxiomResult<VerificationResult> {
+        let goals = parse_why3_output(output, "");
+        let status = if goals.is_empty() {
+            let errors = why3_errors(output);
+            if errors.is_empty() {
+                VerificationStatus::Error(format!("why3 exited with code {}", exit_code))
+            } else {
+                VerificationStatus::Failed(errors)
+            }
+        } else {
+            Self::status_of(&goals)
+        };
+
+        Ok(VerificationResult {
+            status,
+            proof_artifacts: vec![],
+            verification_time: Duration::ZERO,
+            resource_usage: ResourceUsage {
+                lemmas_proven: goals.iter().filter(|g| g.outcome.is_valid()).count(),
+                ..ResourceUsage::default()
+            },
+            goal_results: goals,
+            counterexamples: vec![],
+        })
+    }
+}
+
+/// Parse `why3 prove` output into one result per goal
+///
+/// Both layouts are understood:
+///
+/// ```text
+/// spec.mlw M incr'vc: Valid (0.02s, 15 steps).
+///
+/// File "spec.mlw", line 5, characters 6-10:
+/// Verification condition incr'vc.
+/// Prover result is: Timeout (10.00s).
+/// ```
+pub fn parse_why3_output(output: &str, prover: &str) -> Vec<GoalResult> {
+    let mut goals = Vec::new();
+    let mut pending_goal: Option<String> = None;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+
+        let goal_name = trimmed
+            .strip_prefix("Verification condition ")
+            .or_else(|| trimmed.strip_prefix("Goal "));
+        if let Some(name) = goal_name {
+            pending_goal = Some(name.trim_end_matches('.').to_string());
+            continue;
+        }
+
+        let (name, result) = if let Some(result) = trimmed.strip_prefix("Prover result is:") {
+            match pending_goal.take() {
+                Some(name) => (name, result.trim()),
+                None => continue,
+            }
+        } else {
+            match trimmed.rsplit_once(": ") {
+                Some((left, result)) if parse_outcome(result).is_some() => {
+                    let parts: Vec<&str> = left.split_whitespace().collect();
+                    let name = if parts.len() >= 3 { parts[1..].join(".") } else { left.to_string() };
+                    (name, result)
+                }
+                _ => continue,
+            }
+        };
+
+        if let Some(outcome) = parse_outcome(result) {
+            let (duration, steps) = parse_measurements(result);
+            goals.push(GoalResult {
+                name,
+                outcome,
+                duration,
+                resource_count: steps,
+                prover: if prover.is_empty() { None } else { Some(prover.to_string()) },
+            });
+        }
+    }
+
+    goals
+}
+
+/// Map a Why3 prover answer onto a goal outcome
+fn parse_outcome(result: &str) -> Option<GoalOutcome> {
+    let word = result
+        .split([' ', '(', '.'])
+        .next()
+        .unwrap_or("");
+    let outcome = match word {
+        "Valid" => GoalOutcome::Valid,
+        "Invalid" => GoalOutcome::Invalid,
+        "Timeout" => GoalOutcome::Timeout,
+        "OutOfMemory" | "StepLimitExceeded" => GoalOutcome::OutOfResource,
+        "Step" if result.starts_with("Step limit exceeded") => GoalOutcome::OutOfResource,
+        "Unknown" => GoalOutcome::Unknown,
+        "Failure" | "HighFailure" => GoalOutcome::Error(result.trim_end_matches('.').to_string()),
+        _ => return None,
+    };
+    Some(outcome)
+}
+
+/// Extract `(0.02s, 15 steps)` from a prover answer
+fn parse_measurements(result: &str) -> (Duration, Option<u64>) {
+    let inside = match result.split_once('(').and_then(|(_, rest)| rest.split_once(')')) {
+        Some((inside, _)) => inside,
+        None => return (Duration::ZERO, None),
+    };
+
+    let mut duration = Duration::ZERO;
+    let mut steps = None;
+    for part in inside.split(',').map(|p| p.trim()) {
+        if let Some(seconds) = part.strip_suffix('s').and_then(|s| s.parse::<f64>().ok()) {
+            duration = Duration::from_secs_f64(seconds.max(0.0));
+        } else if let Some(count) = part.strip_suffix(" steps").and_then(|s| s.trim().parse().ok()) {
+            steps = Some(count);
+        }
+    }
+    (duration, steps)
+}
+
+/// `-T <theory> -G <goal>` arguments selecting `Theory.goal` names, or none when a
+/// goal's theory is not known and the whole file has to be proved again
+fn goal_selection(goals: &[String]) -> Vec<String> {
+    let mut theories: Vec<(&str, Vec<&str>)> = Vec::new();
+    for name in goals {
+        let Some((theory, goal)) = name.split_once('.') else {
+            return Vec::new();
+        };
+        match theories.iter_mut().find(|(t, _)| *t == theory) {
+            Some((_, selected)) => selected.push(goal),
+            None => theories.push((theory, vec![goal])),
+        }
+    }
+
+    let mut args = Vec::new();
+    for (theory, selected) in theories {
+        args.push("-T".to_string());
+        args.push(theory.to_string());
+        for goal in selected {
+            args.push("-G".to_string());
+            args.push(goal.to_string());
+        }
+    }
+    args
+}
+
+/// Collect `File "...", line N, characters a-b:` errors reported before any goal was attempted
+fn why3_errors(output: &str) -> Vec<String> {
+    let mut errors = Vec::new();
+    let mut lines = output.lines().map(|l| l.trim()).peekable();
+
+    while let Some(line) = lines.next() {
+        if line.starts_with("File \"") {
+            let location = line.trim_end_matches(':');
+            let message = lines.next().unwrap_or("").to_string();
+            errors.push(format!("{}: {}", location, message));
+        } else if line.starts_with("Error") || line.contains("unknown prover") {
+            errors.push(line.to_string());
+        }
+    }
+
+    errors
+}
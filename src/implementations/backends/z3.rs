@@ -0,0 +1,467 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use log::{debug, info, warn};
+
+use crate::config::ExternalToolsConfig;
+use crate::errors::{AxiomError, AxiomResult};
+use crate::implementations::backends::smt_output::{parse_sexprs, parse_smt_output, SExpr, SatResult};
+use crate::implementations::backends::{
+    create_run_dir, incompatible_implementation, probe_tool, run_with_timeout, write_run_file,
+};
+use crate::models::common::{Language, ResourceUsage, VerificationLanguage, VerificationSystem};
+use crate::models::implementation::Implementation;
+use crate::models::property::Property;
+use crate::models::specification::Specification;
+use crate::models::verification::{
+    ArtifactType, Counterexample, ProofArtifact, VerificationOptions, VerificationResult,
+    VerificationStatus,
+};
+use crate::traits::verification_engine::{
+    AutomationLevel, ProofDifficulty, VerificationBackendAdapter, VerificationComplexity,
+    VerificationEngine,
+};
+
+/// Extra time given to Z3 to report `timeout` itself before the process is killed
+const KILL_GRACE: Duration = Duration::from_secs(5);
+
+/// Commands that make Z3 print a response, dropped from the context of a property check
+const QUERY_COMMANDS: &[&str] = &[
+    "check-sat",
+    "check-sat-assuming",
+    "get-model",
+    "get-value",
+    "get-assignment",
+    "get-unsat-core",
+    "get-proof",
+    "echo",
+    "exit",
+];
+
+/// Verification backend that runs SMT-LIB queries through `z3 -smt2`
+///
+/// A query holds when every `(check-sat)` answers `unsat`; a `sat` answer
+/// fails the query and its `(get-model)` output becomes a `Counterexample`.
+/// The executable is looked up under the `z3` key of
+/// `ExternalToolsConfig::tool_paths` and defaults to `z3` on the `PATH`.
+#[derive(Debug, Clone)]
+pub struct Z3Backend {
+    z3_path: String,
+    timeout: Duration,
+    work_root: PathBuf,
+}
+
+impl Z3Backend {
+    /// Create a backend from the external tools configuration
+    pub fn new(tools: &ExternalToolsConfig) -> Self {
+        Self {
+            z3_path: tools.tool_path("z3", "z3"),
+            timeout: tools.timeout,
+            work_root: std::env::temp_dir().join("axiom_z3"),
+        }
+    }
+
+    /// Set the directory under which per-run working directories are created
+    pub fn with_work_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.work_root = dir.into();
+        self
+    }
+
+    /// Path of the Z3 executable this backend runs
+    pub fn z3_path(&self) -> &str {
+        &self.z3_path
+    }
+
+    /// Combine specification and implementation into one query that checks and prints a model
+    fn build_query(spec: &str, implementation: &str) -> String {
+        let mut query = spec.trim_end().to_string();
+        if !implementation.trim().is_empty() {
+            query.push_str("\n\n");
+            query.push_str(implementation.trim_end());
+        }
+        if !query.contains("(check-sat") {
+            query.push_str("\n(check-sat)");
+        }
+        if !query.contains("(get-model") {
+            query.push_str("\n(get-model)");
+        }
+        query.push('\n');
+        query
+    }
+
+    /// Declarations and assertions of a script without its own checks and output commands
+    fn without_queries(code: &str) -> String {
+        parse_sexprs(code)
+            .into_iter()
+            .filter(|expr| match expr {
+                SExpr::List(items) => !matches!(
+                    items.first(),
+                    Some(SExpr::Atom(h)) if QUERY_COMMANDS.contains(&h.as_str())
+                ),
+                SExpr::Atom(_) => true,
+            })
+            .map(|expr| expr.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Top-level commands of an SMT-LIB script with the given head symbol
+    fn commands<'a>(code: &'a str, head: &'a str) -> impl Iterator<Item = SExpr> + 'a {
+        parse_sexprs(code).into_iter().filter(move |expr| match expr {
+            SExpr::List(items) => matches!(items.first(), Some(SExpr::Atom(h)) if h == head),
+            SExpr::Atom(_) => false,
+        })
+    }
+}
+
+impl VerificationBackendAdapter for Z3Backend {
+    fn convert_specification(&self, spec: &Specification) -> AxiomResult<String> {
+        if spec.formal_spec.verification_language != VerificationLanguage::Z3SMT {
+            return Err(AxiomError::LanguageCompatibilityError {
+                source_lang: spec.formal_spec.verification_language.to_string(),
+                target_lang: VerificationLanguage::Z3SMT.to_string(),
+            });
+        }
+
+        Ok(spec.formal_spec.spec_code.clone())
+    }
+
+    fn convert_implementation(&self, implementation: &Implementation) -> AxiomResult<String> {
+        if !self.supports_implementation_language(&implementation.language) {
+            return Err(incompatible_implementation(
+                &implementation.language,
+                VerificationLanguage::Z3SMT,
+            ));
+        }
+        Ok(implementation.source_code.clone())
+    }
+
+    fn supports_implementation_language(&self, language: &Language) -> bool {
+        match language {
+            Language::Custom(name) => {
+                matches!(name.to_lowercase().as_str(), "smt" | "smt2" | "smt-lib" | "z3")
+            },
+            _ => false,
+        }
+    }
+
+    fn execute_verification(
+        &self,
+        converted_spec: &str,
+        converted_impl: &str,
+        options: &VerificationOptions,
+    ) -> AxiomResult<VerificationResult> {
+        let run_dir = create_run_dir(&self.work_root, "run")?;
+        debug!("Z3 working directory: {}", run_dir.display());
+
+        // Z3 reads a single script, so both parts go into one query file
+        let query_file = run_dir.join("query.smt2");
+        write_run_file(&query_file, &Self::build_query(converted_spec, converted_impl))?;
+
+        let mut command = self.get_verification_command(&query_file, &query_file, options)?;
+        info!("Running Z3 on {}", query_file.display());
+        let output = run_with_timeout(&mut command, "Z3", options.timeout + KILL_GRACE)?;
+
+        let log_path = run_dir.join("z3.log");
+        write_run_file(&log_path, &output.combined())?;
+
+        let mut result = if output.timed_out {
+            warn!("Z3 timed out after {:?}", options.timeout);
+            VerificationResult {
+                status: VerificationStatus::Timeout,
+                proof_artifacts: vec![],
+                verification_time: output.elapsed,
+                resource_usage: ResourceUsage::default(),
+                goal_results: vec![],
+                counterexamples: vec![],
+            }
+        } else {
+            self.parse_verification_output(&output.stdout, output.exit_code.unwrap_or(-1))?
+        };
+
+        for (index, counterexample) in result.counterexamples.iter().enumerate() {
+            let path = run_dir.join(format!("counterexample_{}.json", index + 1));
+            let json = serde_json::to_string_pretty(&counterexample.assignments).map_err(|e| {
+                AxiomError::SystemError(format!("Failed to serialize counterexample: {}", e))
+            })?;
+            write_run_file(&path, &json)?;
+        }
+
+        result.verification_time = output.elapsed;
+        result.resource_usage.cpu_seconds = output.elapsed.as_secs_f64();
+        result.proof_artifacts.push(ProofArtifact {
+            artifact_type: ArtifactType::Log,
+            path: log_path.display().to_string(),
+            description: "Z3 output".to_string(),
+        });
+        let mut artifacts: Vec<ProofArtifact> = self
+            .extract_artifacts(&run_dir)?
+            .into_iter()
+            .filter(|a| !matches!(a.artifact_type, ArtifactType::Log))
+            .collect();
+        artifacts.sort_by(|a, b| a.path.cmp(&b.path));
+        result.proof_artifacts.extend(artifacts);
+
+        Ok(result)
+    }
+
+    fn extract_artifacts(&self, output_dir: &Path) -> AxiomResult<Vec<ProofArtifact>> {
+        let entries = std::fs::read_dir(output_dir).map_err(|e| {
+            AxiomError::SystemError(format!("Failed to read {}: {}", output_dir.display(), e))
+        })?;
+
+        let mut artifacts = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            let (artifact_type, description) = match path.extension().and_then(|e| e.to_str()) {
+                Some("json") if file_name.starts_with("counterexample") => {
+                    (ArtifactType::Counterexample, "Model refuting the query")
+                }
+                Some("smt2") => (ArtifactType::Model, "SMT-LIB query sent to Z3"),
+                Some("log") => (ArtifactType::Log, "Z3 output"),
+                _ => continue,
+            };
+            artifacts.push(ProofArtifact {
+                artifact_type,
+                path: path.display().to_string(),
+                description: description.to_string(),
+            });
+        }
+
+        Ok(artifacts)
+    }
+
+    fn check_backend_availability(&self) -> AxiomResult<bool> {
+        Ok(probe_tool(&self.z3_path, &["--version"]))
+    }
+
+    fn get_backend_version(&self) -> AxiomResult<String> {
+        let output = run_with_timeout(
+            Command::new(&self.z3_path).arg("--version"),
+            "Z3",
+            self.timeout,
+        )?;
+
+        output
+            .stdout
+            .lines()
+            .map(|line| line.trim())
+            .find(|line| !line.is_empty())
+            .map(|line| line.to_string())
+            .ok_or_else(|| AxiomError::ExternalToolError {
+                tool: "Z3".to_string(),
+                message: "No version information reported".to_string(),
+            })
+    }
+
+    fn install_dependencies(&self) -> AxiomResult<()> {
+        if self.check_backend_availability()? {
+            return Ok(());
+        }
+
+        Err(AxiomError::MissingDependenciesError(format!(
+            "Z3 executable '{}' not found. Install Z3 from https://github.com/Z3Prover/z3 \
+            or set the 'z3' tool path in the configuration.",
+            self.z3_path
+        )))
+    }
+
+    fn get_verification_command(
+        &self,
+        spec_file: &Path,
+        _impl_file: &Path,
+        options: &VerificationOptions,
+    ) -> AxiomResult<Command> {
+        let mut command = Command::new(&self.z3_path);
+
+        if let Some(dir) = spec_file.parent() {
+            command.current_dir(dir);
+        }
+        command
+            .arg("-smt2")
+            .arg(format!("-T:{}", options.timeout.as_secs().max(1)))
+            .arg(format!("-memory:{}", (options.resource_limits.max_memory_kb / 1024).max(1)))
+            .arg(spec_file);
+
+        Ok(command)
+    }
+
+    fn parse_verification_output(
+        &self,
+        output: &str,
+        exit_code: i32,
+    ) -> AxiomResult<VerificationResult> {
+        let parsed = parse_smt_output(output);
+
+        let mut counterexamples = Vec::new();
+        for (index, check) in parsed.checks.iter().enumerate() {
+            if check.result == SatResult::Sat {
+                counterexamples.push(Counterexample {
+                    property: Some(
+                        check.label.clone().unwrap_or_else(|| format!("check-sat #{}", index + 1)),
+                    ),
+                    assignments: check.model.clone().unwrap_or_default(),
+                    trace: vec![],
+                });
+            }
+        }
+        // `(get-model)` after an `unsat` answer is an expected error, not a failure
+        let errors: Vec<String> = parsed
+            .errors
+            .iter()
+            .filter(|e| !e.contains("model is not available"))
+            .cloned()
+            .collect();
+        let results: Vec<SatResult> = parsed.checks.iter().map(|c| c.result).collect();
+
+        let status = if !counterexamples.is_empty() {
+            VerificationStatus::Failed(
+                counterexamples
+                    .iter()
+                    .map(|c| format!("Counterexample for {}", c))
+                    .collect(),
+            )
+        } else if results.contains(&SatResult::Timeout) {
+            VerificationStatus::Timeout
+        } else if !errors.is_empty() {
+            VerificationStatus::Error(errors.join("; "))
+        } else if results.is_empty() {
+            VerificationStatus::Error(format!("Z3 exited with code {} without answering", exit_code))
+        } else if results.contains(&SatResult::Unknown) {
+            VerificationStatus::Unverified
+        } else {
+            VerificationStatus::Verified
+        };
+
+        Ok(VerificationResult {
+            status,
+            proof_artifacts: vec![],
+            verification_time: Duration::ZERO,
+            resource_usage: ResourceUsage {
+                lemmas_proven: results.iter().filter(|r| **r == SatResult::Unsat).count(),
+                ..ResourceUsage::default()
+            },
+            goal_results: vec![],
+            counterexamples,
+        })
+    }
+}
+
+impl VerificationEngine for Z3Backend {
+    fn verify(
+        &self,
+        implementation: &Implementation,
+        spec: &Specification,
+        options: &VerificationOptions,
+    ) -> AxiomResult<VerificationResult> {
+        let converted_spec = self.convert_specification(spec)?;
+        let converted_impl = self.convert_implementation(implementation)?;
+        self.execute_verification(&converted_spec, &converted_impl, options)
+    }
+
+    fn can_verify(&self, spec: &Specification, language: &Language) -> bool {
+        spec.formal_spec.verification_language == VerificationLanguage::Z3SMT
+            && self.supports_implementation_language(language)
+    }
+
+    fn verification_system(&self) -> VerificationSystem {
+        VerificationSystem::Z3
+    }
+
+    fn supports_formal_proofs(&self, language: VerificationLanguage) -> bool {
+        language == VerificationLanguage::Z3SMT
+    }
+
+    fn generate_verification_conditions(
+        &self,
+        spec: &Specification,
+        implementation: &Implementation,
+    ) -> AxiomResult<Vec<String>> {
+        let spec_code = self.convert_specification(spec)?;
+        let impl_code = self.convert_implementation(implementation)?;
+
+        Ok(Self::commands(&spec_code, "assert")
+            .chain(Self::commands(&impl_code, "assert"))
+            .map(|assertion| assertion.to_string())
+            .collect())
+    }
+
+    fn extract_counterexamples(
+        &self,
+        verification_result: &VerificationResult,
+    ) -> AxiomResult<Vec<String>> {
+        Ok(verification_result
+            .counterexamples
+            .iter()
+            .map(|c| c.to_string())
+            .collect())
+    }
+
+    fn verify_property(
+        &self,
+        implementation: &Implementation,
+        property: &Property,
+        options: &VerificationOptions,
+    ) -> AxiomResult<bool> {
+        // The property holds if its negation is unsatisfiable in the implementation's context
+        let query = format!(
+            "(push)\n(assert (not {}))\n(echo \"{}\")\n(check-sat)\n(get-model)\n(pop)",
+            property.formal_definition.trim(),
+            property.id.replace('"', "\"\"")
+        );
+        // Only the property's own check may answer, so the implementation's queries are dropped
+        let context = Self::without_queries(&self.convert_implementation(implementation)?);
+        let result = self.execute_verification(&context, &query, options)?;
+
+        match result.status {
+            VerificationStatus::Verified => Ok(true),
+            VerificationStatus::Failed(_) => Ok(false),
+            VerificationStatus::Unverified | VerificationStatus::Timeout => {
+                Err(AxiomError::VerificationError(format!(
+                    "Z3 could not decide property '{}'",
+                    property.id
+                )))
+            }
+            VerificationStatus::Error(message) => Err(AxiomError::ExternalToolError {
+                tool: "Z3".to_string(),
+                message,
+            }),
+        }
+    }
+
+    fn estimate_verification_complexity(
+        &self,
+        spec: &Specification,
+        implementation: &Implementation,
+    ) -> AxiomResult<VerificationComplexity> {
+        let code = format!(
+            "{}\n{}",
+            self.convert_specification(spec)?,
+            self.convert_implementation(implementation)?
+        );
+        let assertions = Self::commands(&code, "assert").count();
+        let quantifiers = code.matches("(forall").count() + code.matches("(exists").count();
+
+        let (proof_difficulty, automation_level) = match quantifiers {
+            0 if assertions <= 10 => (ProofDifficulty::Trivial, AutomationLevel::FullyAutomated),
+            0 => (ProofDifficulty::Easy, AutomationLevel::FullyAutomated),
+            1..=5 => (ProofDifficulty::Moderate, AutomationLevel::MostlyAutomated),
+            _ => (ProofDifficulty::Hard, AutomationLevel::SemiAutomated),
+        };
+
+        Ok(VerificationComplexity {
+            estimated_time: Duration::from_secs(1 + (assertions + 10 * quantifiers) as u64),
+            memory_required: 64 * 1024 * (1 + quantifiers as u64),
+            proof_difficulty,
+            automation_level,
+        })
+    }
+
+    fn cancel_verification(&self) -> AxiomResult<()> {
+        Err(AxiomError::VerificationError(
+            "Z3 runs are bounded by their timeout and cannot be cancelled".to_string(),
+        ))
+    }
+}
@@ -4,6 +4,12 @@ use std::fs;
 use std::path::Path;
 use thiserror::Error;
 
+use crate::implementations::llm::cache::ResponseCacheConfig;
+use crate::implementations::llm::fixtures::LlmFixtureConfig;
+use crate::implementations::llm::retry::RetryPolicy;
+use crate::implementations::llm::usage::{ModelPrice, UsageBudget};
+use crate::implementations::prompts::PromptTemplates;
+
 #[derive(Debug, Error)]
 pub enum ConfigError {
     #[error("Failed to read config file: {0}")]
@@ -17,10 +23,20 @@ pub enum ConfigError {
     
     #[error("Environment variable not found: {0}")]
     EnvVarNotFound(String),
+
+    #[error("Missing required setting: {0}")]
+    MissingSetting(String),
+
+    #[error("Invalid setting: {0}")]
+    InvalidSetting(String),
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ApiConfig {
+    /// LLM provider to use ("anthropic", "openai", "local", ...); picked from the available API keys when unset
+    #[serde(default)]
+    pub provider: Option<String>,
+
     /// API key for LLM service
     pub api_key: Option<String>,
     
@@ -45,10 +61,12 @@ pub struct GeneratorConfig {
     /// Configuration for verification tooling APIs (if any)
     pub verification_apis: HashMap<String, ApiConfig>,
     
-    /// Default templates directory
+    /// Directory of `<name>.txt` files overriding the built-in prompt templates
+    #[serde(default)]
     pub templates_dir: Option<String>,
     
-    /// Default prompt templates
+    /// Prompt templates keyed by name, overriding both the built-in ones and `templates_dir`
+    #[serde(default)]
     pub prompt_templates: HashMap<String, String>,
     
     /// Use chain-of-thought reasoning for improved accuracy
@@ -62,6 +80,35 @@ pub struct GeneratorConfig {
     
     /// Custom domains and their configurations
     pub domain_configs: Option<HashMap<String, DomainConfig>>,
+
+    /// Record LLM responses to, or replay them from, a fixture directory
+    #[serde(default)]
+    pub llm_fixtures: Option<LlmFixtureConfig>,
+
+    /// Reuse successful LLM responses from an on-disk cache; disabled when unset
+    #[serde(default)]
+    pub response_cache: Option<ResponseCacheConfig>,
+
+    /// Retries for rate-limited, failed or timed-out LLM calls
+    #[serde(default)]
+    pub retry: RetryPolicy,
+
+    /// Stream LLM responses where the provider supports it, so long generations are not
+    /// cut off by the request timeout and can report progress
+    #[serde(default = "default_stream")]
+    pub stream: bool,
+
+    /// Spending limits for one run; a run stops with an error once it goes over
+    #[serde(default)]
+    pub budget: UsageBudget,
+
+    /// Prices in USD per million tokens, overriding the built-in table, keyed by model name
+    #[serde(default)]
+    pub model_prices: HashMap<String, ModelPrice>,
+
+    /// Models that multi-candidate generation draws from in turn; only `llm_api` when empty
+    #[serde(default)]
+    pub candidate_models: Vec<ApiConfig>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -79,13 +126,46 @@ pub struct DomainConfig {
     pub recommended_verification_languages: Option<Vec<String>>,
 }
 
+fn default_stream() -> bool {
+    true
+}
+
 impl GeneratorConfig {
     /// Load configuration from a YAML file
     pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        Self::load_file(path, None)
+    }
+
+    /// Load configuration from a YAML file, resolving a relative `templates_dir` and
+    /// response cache directory from `base` instead of the current directory
+    pub fn from_file_in(path: &Path, base: &Path) -> Result<Self, ConfigError> {
+        Self::load_file(path, Some(base))
+    }
+
+    fn load_file(path: &Path, base: Option<&Path>) -> Result<Self, ConfigError> {
         let contents = fs::read_to_string(path)?;
-        let config: GeneratorConfig = serde_yaml::from_str(&contents)?;
+        let mut config: GeneratorConfig = serde_yaml::from_str(&contents)?;
+        if let Some(base) = base {
+            config.resolve_paths(base);
+        }
+        // Reject unknown prompt templates and undefined placeholders before anything runs
+        PromptTemplates::load(&config)?;
         Ok(config)
     }
+
+    /// Resolve a relative `templates_dir` and response cache directory from `base`
+    pub fn resolve_paths(&mut self, base: &Path) {
+        if let Some(dir) = &self.templates_dir {
+            if Path::new(dir).is_relative() {
+                self.templates_dir = Some(base.join(dir).display().to_string());
+            }
+        }
+        if let Some(cache) = &mut self.response_cache {
+            if cache.dir.is_relative() {
+                cache.dir = base.join(&cache.dir);
+            }
+        }
+    }
     
     /// Get the API key, checking environment variables if not in config
     /// If the specified provider key is not found, it will try other providers
@@ -168,7 +248,7 @@ impl GeneratorConfig {
         }
     }
     
-    /// Get the template for a specific task
+    /// Prompt template configured inline for a task; see `PromptTemplates` for the one in effect
     pub fn get_template(&self, template_name: &str) -> Option<String> {
         self.prompt_templates.get(template_name).cloned()
     }
@@ -177,35 +257,9 @@ impl GeneratorConfig {
 /// Default configuration
 impl Default for GeneratorConfig {
     fn default() -> Self {
-        let mut prompt_templates = HashMap::new();
-        prompt_templates.insert(
-            "specification".to_string(),
-            r#"
-You are a formal verification expert. Your task is to translate natural language requirements into
-formal specifications in the {{verification_language}} verification language.
-
-Given the following requirements for a {{domain}} system:
-
-{{requirements}}
-
-Generate a complete, formal specification in {{verification_language}} that captures all the 
-requirements and ensures correctness, safety, and security properties. Be thorough and precise.
-
-The specification should include:
-1. All necessary types and functions
-2. Formal properties that must be satisfied
-3. Preconditions and postconditions
-4. Invariants that must be maintained
-5. Security properties (if applicable)
-6. Resource usage constraints (if applicable)
-
-Additional context for this domain:
-{{domain_context}}
-"#.to_string(),
-        );
-        
         GeneratorConfig {
             llm_api: ApiConfig {
+                provider: None,
                 api_key: None,
                 api_endpoint: Some("https://api.openai.com/v1/chat/completions".to_string()),
                 model: Some("gpt-4o".to_string()),
@@ -214,11 +268,18 @@ Additional context for this domain:
             },
             verification_apis: HashMap::new(),
             templates_dir: None,
-            prompt_templates,
+            prompt_templates: HashMap::new(),
             use_chain_of_thought: Some(true),
             max_tokens: Some(4096),
             temperature: Some(0.2),
             domain_configs: None,
+            llm_fixtures: None,
+            response_cache: None,
+            retry: RetryPolicy::default(),
+            stream: default_stream(),
+            budget: UsageBudget::default(),
+            model_prices: HashMap::new(),
+            candidate_models: Vec::new(),
         }
     }
 }
\ No newline at end of file
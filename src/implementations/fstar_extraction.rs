@@ -0,0 +1,362 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use log::{debug, info, warn};
+
+use crate::config::ExternalToolsConfig;
+use crate::errors::{AxiomError, AxiomResult};
+use crate::implementations::backends::{
+    create_run_dir, run_with_timeout, write_run_file, FStarBackend,
+};
+use crate::models::common::{Language, ProofLevel, VerificationLanguage};
+use crate::models::implementation::{Implementation, ImplementationOptions};
+use crate::models::specification::Specification;
+use crate::models::verification::{
+    ArtifactType, ProofArtifact, VerificationOptions, VerificationResult, VerificationStatus,
+};
+use crate::traits::implementation_generator::ImplementationGenerator;
+use crate::traits::verification_engine::VerificationBackendAdapter;
+
+/// Module name used when a specification does not declare one
+const DEFAULT_MODULE_NAME: &str = "AxiomSpec";
+
+/// Artifact type of the source files produced by extraction
+pub const EXTRACTED_CODE_ARTIFACT: &str = "extracted_code";
+
+/// Qualifiers that may precede `let` in a top-level F* definition
+const DEFINITION_QUALIFIERS: &[&str] = &[
+    "inline_for_extraction",
+    "noextract",
+    "private",
+    "unfold",
+    "irreducible",
+];
+
+/// Implementation generator that extracts code from the definitions in an F* specification
+///
+/// OCaml is extracted with `fstar.exe --codegen OCaml`. C is extracted with
+/// `--codegen krml` and compiled to C by KaRaMeL, looked up under the `krml` key of
+/// `ExternalToolsConfig::tool_paths`. F* checks the module while extracting it, so the
+/// implementation carries the result of that check. Specifications F* rejects are an
+/// error, since nothing is extracted from them.
+#[derive(Debug, Clone)]
+pub struct FStarExtractor {
+    backend: FStarBackend,
+    krml_path: String,
+    timeout: Duration,
+    work_root: PathBuf,
+    proof_level: ProofLevel,
+}
+
+impl FStarExtractor {
+    /// Create an extractor from the external tools configuration
+    pub fn new(tools: &ExternalToolsConfig) -> Self {
+        Self {
+            backend: FStarBackend::new(tools),
+            krml_path: tools.tool_path("krml", "krml"),
+            timeout: tools.timeout,
+            work_root: std::env::temp_dir().join("axiom_fstar_extraction"),
+            proof_level: ProofLevel::Standard,
+        }
+    }
+
+    /// Set the directory under which per-run working directories are created
+    pub fn with_work_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.work_root = dir.into();
+        self
+    }
+
+    /// Proof level of the F* check that runs during extraction
+    pub fn with_proof_level(mut self, proof_level: ProofLevel) -> Self {
+        self.proof_level = proof_level;
+        self
+    }
+
+    /// Path of the KaRaMeL executable used for C extraction
+    pub fn krml_path(&self) -> &str {
+        &self.krml_path
+    }
+
+    /// Whether `spec` is F* with definitions that can be extracted to `language`
+    pub fn can_extract(spec: &Specification, language: &Language) -> bool {
+        spec.formal_spec.verification_language == VerificationLanguage::FStarLang
+            && matches!(language, Language::OCaml | Language::C)
+            && has_definitions(&spec.formal_spec.spec_code)
+    }
+
+    /// Whether an implementation was extracted from an F* module, so that it carries the
+    /// result of checking that module
+    pub fn is_extracted(implementation: &Implementation) -> bool {
+        implementation
+            .verification_result
+            .proof_artifacts
+            .iter()
+            .any(|artifact| {
+                artifact.artifact_type == ArtifactType::Custom(EXTRACTED_CODE_ARTIFACT.to_string())
+            })
+    }
+
+    /// Check and extract the module, returning the extracted files and the F* result
+    fn extract(
+        &self,
+        spec: &Specification,
+        language: &Language,
+    ) -> AxiomResult<(Vec<PathBuf>, VerificationResult)> {
+        let run_dir = create_run_dir(&self.work_root, "extract")?;
+        let out_dir = run_dir.join("out");
+        std::fs::create_dir_all(&out_dir).map_err(|e| {
+            AxiomError::SystemError(format!("Failed to create {}: {}", out_dir.display(), e))
+        })?;
+        debug!("F* extraction directory: {}", run_dir.display());
+
+        let (module, code) =
+            FStarBackend::with_module_header(&spec.formal_spec.spec_code, DEFAULT_MODULE_NAME);
+        let spec_file = run_dir.join(format!("{}.fst", module));
+        write_run_file(&spec_file, &code)?;
+
+        let codegen = if *language == Language::C {
+            "krml"
+        } else {
+            "OCaml"
+        };
+        let options = VerificationOptions {
+            timeout: self.timeout,
+            proof_level: self.proof_level.clone(),
+            ..VerificationOptions::default()
+        };
+        let mut command = self
+            .backend
+            .get_verification_command(&spec_file, &spec_file, &options)?;
+        command
+            .arg("--odir")
+            .arg(&out_dir)
+            .arg("--codegen")
+            .arg(codegen)
+            .arg("--extract")
+            .arg(&module);
+
+        info!("Extracting F* module {} with --codegen {}", module, codegen);
+        let output = run_with_timeout(&mut command, "F*", self.timeout)?;
+        let log_path = run_dir.join("fstar.log");
+        write_run_file(&log_path, &output.combined())?;
+        if output.timed_out {
+            return Err(AxiomError::ExternalToolError {
+                tool: "F*".to_string(),
+                message: format!("Extraction timed out after {:?}", self.timeout),
+            });
+        }
+
+        let mut result = self
+            .backend
+            .parse_verification_output(&output.combined(), output.exit_code.unwrap_or(-1))?;
+        match &result.status {
+            VerificationStatus::Verified => {}
+            VerificationStatus::Failed(reasons) => {
+                return Err(AxiomError::ProofError(format!(
+                    "F* rejected module {}, nothing was extracted: {}",
+                    module,
+                    reasons.join("; ")
+                )));
+            }
+            other => {
+                return Err(AxiomError::ExternalToolError {
+                    tool: "F*".to_string(),
+                    message: format!("Extraction of module {} failed: {}", module, other),
+                });
+            }
+        }
+        result.verification_time = output.elapsed;
+        result.resource_usage.cpu_seconds = output.elapsed.as_secs_f64();
+        result.proof_artifacts.push(ProofArtifact {
+            artifact_type: ArtifactType::Log,
+            path: log_path.display().to_string(),
+            description: format!("F* output for module {}", module),
+        });
+
+        let files = if *language == Language::C {
+            let krml_files = files_with_extensions(&out_dir, &["krml"])?;
+            if krml_files.is_empty() {
+                return Err(nothing_extracted(&module, "F*"));
+            }
+            let c_dir = run_dir.join("c");
+            self.run_krml(&krml_files, &c_dir, &run_dir)?;
+            files_with_extensions(&c_dir, &["h", "c"])?
+        } else {
+            files_with_extensions(&out_dir, &["ml"])?
+        };
+        if files.is_empty() {
+            let tool = if *language == Language::C {
+                "KaRaMeL"
+            } else {
+                "F*"
+            };
+            return Err(nothing_extracted(&module, tool));
+        }
+
+        Ok((files, result))
+    }
+
+    /// Compile `.krml` files to C sources in `c_dir`
+    fn run_krml(&self, krml_files: &[PathBuf], c_dir: &Path, run_dir: &Path) -> AxiomResult<()> {
+        std::fs::create_dir_all(c_dir).map_err(|e| {
+            AxiomError::SystemError(format!("Failed to create {}: {}", c_dir.display(), e))
+        })?;
+        let mut command = Command::new(&self.krml_path);
+        command
+            .current_dir(run_dir)
+            .arg("-skip-compilation")
+            .arg("-tmpdir")
+            .arg(c_dir)
+            .args(krml_files);
+
+        info!("Running KaRaMeL on {} file(s)", krml_files.len());
+        let output = run_with_timeout(&mut command, "KaRaMeL", self.timeout)?;
+        write_run_file(&run_dir.join("krml.log"), &output.combined())?;
+
+        if output.timed_out {
+            return Err(AxiomError::ExternalToolError {
+                tool: "KaRaMeL".to_string(),
+                message: format!("Timed out after {:?}", self.timeout),
+            });
+        }
+        if output.exit_code != Some(0) {
+            let message = output
+                .combined()
+                .lines()
+                .map(|line| line.trim())
+                .find(|line| !line.is_empty())
+                .map(|line| line.to_string())
+                .unwrap_or_else(|| format!("krml exited with code {:?}", output.exit_code));
+            return Err(AxiomError::ExternalToolError {
+                tool: "KaRaMeL".to_string(),
+                message,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ImplementationGenerator for FStarExtractor {
+    async fn generate_implementation(
+        &self,
+        spec: &Specification,
+        language: Language,
+        options: &ImplementationOptions,
+    ) -> AxiomResult<Implementation> {
+        if !Self::can_extract(spec, &language) {
+            return Err(AxiomError::ImplementationError(format!(
+                "Cannot extract {} code from specification {}: extraction needs F* \
+                definitions and targets OCaml or C",
+                language, spec.id
+            )));
+        }
+        if options.style_guide.is_some() {
+            warn!("Extracted code does not follow a style guide; ignoring it");
+        }
+
+        let (files, mut verification_result) = self.extract(spec, &language)?;
+        let mut source_code = String::new();
+        for file in &files {
+            let contents = std::fs::read_to_string(file).map_err(|e| {
+                AxiomError::SystemError(format!("Failed to read {}: {}", file.display(), e))
+            })?;
+            let name = file
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if files.len() > 1 {
+                source_code.push_str(&file_banner(&language, &name));
+            }
+            source_code.push_str(&contents);
+            if !source_code.ends_with('\n') {
+                source_code.push('\n');
+            }
+            verification_result.proof_artifacts.push(ProofArtifact {
+                artifact_type: ArtifactType::Custom(EXTRACTED_CODE_ARTIFACT.to_string()),
+                path: file.display().to_string(),
+                description: format!("Extracted {} module {}", language, name),
+            });
+        }
+
+        Ok(Implementation {
+            id: format!("{}_impl", spec.id),
+            specification_id: spec.id.clone(),
+            language,
+            source_code,
+            verification_result,
+        })
+    }
+
+    async fn refine_implementation(
+        &self,
+        _implementation: &Implementation,
+        _spec: &Specification,
+        _verification_result: &VerificationResult,
+    ) -> AxiomResult<Implementation> {
+        Err(AxiomError::ImplementationError(
+            "Extracted code cannot be refined; change the F* definitions and extract again"
+                .to_string(),
+        ))
+    }
+
+    async fn validate_implementation(
+        &self,
+        implementation: &Implementation,
+        spec: &Specification,
+    ) -> AxiomResult<bool> {
+        Ok(implementation.specification_id == spec.id
+            && implementation.verification_result.status == VerificationStatus::Verified
+            && !implementation.source_code.trim().is_empty())
+    }
+}
+
+/// Whether F* code has a top-level `let` definition, as opposed to only `val` declarations
+pub fn has_definitions(code: &str) -> bool {
+    code.lines()
+        .filter(|line| !line.starts_with(char::is_whitespace))
+        .any(|line| {
+            line.split_whitespace()
+                .find(|word| !DEFINITION_QUALIFIERS.contains(word))
+                == Some("let")
+        })
+}
+
+/// Files directly in `dir` with one of the extensions, in extension order, then by name
+fn files_with_extensions(dir: &Path, extensions: &[&str]) -> AxiomResult<Vec<PathBuf>> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| AxiomError::SystemError(format!("Failed to read {}: {}", dir.display(), e)))?;
+
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| extensions.contains(&e))
+        })
+        .collect();
+    files.sort_by_key(|path| {
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let rank = extensions.iter().position(|e| *e == extension);
+        (rank, path.file_name().map(|name| name.to_os_string()))
+    });
+    Ok(files)
+}
+
+/// Comment separating the extracted files in the combined source code
+fn file_banner(language: &Language, name: &str) -> String {
+    match language {
+        Language::OCaml => format!("(* {} *)\n", name),
+        _ => format!("/* {} */\n", name),
+    }
+}
+
+fn nothing_extracted(module: &str, tool: &str) -> AxiomError {
+    AxiomError::ExternalToolError {
+        tool: tool.to_string(),
+        message: format!("No code was extracted from module {}", module),
+    }
+}
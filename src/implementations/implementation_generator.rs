@@ -0,0 +1,369 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use log::{info, warn};
+
+use crate::errors::AxiomResult;
+use crate::implementations::config::GeneratorConfig;
+use crate::implementations::llm::{
+    client_from_config, default_http_client, send_recorded, CancelFlag, LlmClient, LlmRequest,
+    StreamContext, StreamObserver, UsageSummary, UsageTracker,
+};
+use crate::implementations::prompts::PromptTemplates;
+use crate::implementations::specification_generator::SpecGenError;
+use crate::models::common::{Language, OptimizationLevel};
+use crate::models::implementation::{Implementation, ImplementationOptions};
+use crate::models::specification::Specification;
+use crate::models::verification::{VerificationOptions, VerificationResult, VerificationStatus};
+use crate::traits::implementation_generator::ImplementationGenerator;
+use crate::traits::verification_engine::VerificationEngine;
+
+/// System prompt sent with implementation requests
+pub const IMPLEMENTATION_SYSTEM_PROMPT: &str =
+    "You are an expert programmer who writes correct code that satisfies formal specifications.";
+
+/// Refinement rounds `refine_until_verified` runs unless configured otherwise
+pub const DEFAULT_REFINEMENT_ROUNDS: usize = 3;
+
+/// Implementation generator that asks an LLM for code satisfying a specification
+///
+/// Uses the same configuration, prompt templates and provider selection as
+/// `LLMSpecificationGenerator`. `refine_until_verified` runs a verification engine on
+/// the code and feeds its failures and counterexamples back to the LLM until the code
+/// verifies or the refinement rounds run out.
+#[derive(Clone)]
+pub struct LLMImplementationGenerator {
+    config: GeneratorConfig,
+    http_client: reqwest::Client,
+    /// Client used for all LLM calls; resolved from the config on each call when unset
+    llm_client: Option<Arc<dyn LlmClient>>,
+    /// Token usage of every LLM call, shared between clones
+    usage: UsageTracker,
+    /// Receives streamed text and carries the cancellation flag, shared between clones
+    stream: StreamContext,
+    prompts: PromptTemplates,
+    max_refinement_rounds: usize,
+}
+
+impl LLMImplementationGenerator {
+    pub fn new(config: GeneratorConfig) -> Self {
+        // `GeneratorConfig::from_file` already rejected broken overrides
+        let prompts = PromptTemplates::load(&config).unwrap_or_else(|e| {
+            warn!("Ignoring prompt template overrides: {}", e);
+            PromptTemplates::builtin()
+        });
+
+        Self {
+            config,
+            http_client: default_http_client(),
+            llm_client: None,
+            usage: UsageTracker::new(),
+            stream: StreamContext::new(),
+            prompts,
+            max_refinement_rounds: DEFAULT_REFINEMENT_ROUNDS,
+        }
+    }
+
+    /// Send all LLM calls through the given client instead of picking a provider
+    /// from the configured API keys.
+    pub fn with_llm_client(mut self, client: Arc<dyn LlmClient>) -> Self {
+        self.llm_client = Some(client);
+        self
+    }
+
+    /// Record LLM calls in `usage`, e.g. the tracker of a specification generator, so
+    /// that one summary and one budget cover both
+    pub fn with_usage_tracker(mut self, usage: UsageTracker) -> Self {
+        self.usage = usage;
+        self
+    }
+
+    /// Report LLM output to `observer` as it streams in
+    pub fn with_stream_observer(mut self, observer: Arc<dyn StreamObserver>) -> Self {
+        self.stream = self.stream.with_observer(observer);
+        self
+    }
+
+    /// Abort LLM calls, including the one in flight, once `cancel` is set
+    pub fn with_cancel_flag(mut self, cancel: CancelFlag) -> Self {
+        self.stream = self.stream.with_cancel_flag(cancel);
+        self
+    }
+
+    /// Maximum number of times `refine_until_verified` asks the LLM for a fix
+    pub fn with_max_refinement_rounds(mut self, rounds: usize) -> Self {
+        self.max_refinement_rounds = rounds;
+        self
+    }
+
+    /// Token usage and cost of all LLM calls recorded in this generator's tracker
+    pub fn usage_summary(&self) -> UsageSummary {
+        self.usage.summary()
+    }
+
+    /// Verify the implementation and, while it does not verify, refine it with the
+    /// verifier's feedback, for at most `max_refinement_rounds` rounds
+    ///
+    /// An implementation that already carries a verification result other than
+    /// `Unverified` is not verified again before the first refinement. Returns the last
+    /// implementation with the result of its verification. The engine runs on the
+    /// calling thread. An implementation the engine cannot check is returned unchanged,
+    /// since there is no feedback to refine it with.
+    pub async fn refine_until_verified(
+        &self,
+        implementation: &Implementation,
+        spec: &Specification,
+        engine: &(dyn VerificationEngine + Sync),
+        options: &VerificationOptions,
+    ) -> AxiomResult<Implementation> {
+        let mut current = implementation.clone();
+        if !engine.can_verify(spec, &current.language) {
+            warn!(
+                "{} cannot check {} code against {}; leaving it unrefined",
+                engine.verification_system(),
+                current.language,
+                spec.id
+            );
+            return Ok(current);
+        }
+        if current.verification_result.status == VerificationStatus::Unverified {
+            current.verification_result = engine.verify(&current, spec, options)?;
+        }
+
+        for round in 1..=self.max_refinement_rounds {
+            if current.verification_result.status == VerificationStatus::Verified {
+                break;
+            }
+            let result = current.verification_result.clone();
+            current = self.refine_implementation(&current, spec, &result).await?;
+            current.verification_result = engine.verify(&current, spec, options)?;
+            info!(
+                "Refinement round {}: {}",
+                round, current.verification_result.status
+            );
+        }
+        Ok(current)
+    }
+
+    fn client(&self) -> Result<Arc<dyn LlmClient>, SpecGenError> {
+        match &self.llm_client {
+            Some(client) => Ok(client.clone()),
+            None => client_from_config(&self.config, self.http_client.clone()).map_err(|e| {
+                warn!("API key error: {}", e);
+                SpecGenError::ApiError(format!("API key error: {}", e))
+            }),
+        }
+    }
+
+    /// Render a prompt template, send it and return the code in the answer
+    async fn request_code(
+        &self,
+        template_name: &str,
+        params: &HashMap<String, String>,
+    ) -> Result<String, SpecGenError> {
+        let prompt = self.prompts.render(template_name, params)?;
+        let mut request = LlmRequest::new(
+            prompt,
+            self.config.temperature.unwrap_or(0.2),
+            self.config.max_tokens.unwrap_or(4096),
+        );
+        request.system = IMPLEMENTATION_SYSTEM_PROMPT.to_string();
+
+        let response = send_recorded(
+            self.client()?.as_ref(),
+            &request,
+            &self.config,
+            &self.usage,
+            &self.stream,
+        )
+        .await?;
+        let code = extract_code(&response);
+        if code.trim().is_empty() {
+            return Err(SpecGenError::ParseError(
+                "The LLM answered without any code".to_string(),
+            ));
+        }
+        Ok(code)
+    }
+}
+
+#[async_trait]
+impl ImplementationGenerator for LLMImplementationGenerator {
+    async fn generate_implementation(
+        &self,
+        spec: &Specification,
+        language: Language,
+        options: &ImplementationOptions,
+    ) -> AxiomResult<Implementation> {
+        info!("Generating {} implementation of {}", language, spec.id);
+
+        let mut params = spec_params(spec, &language);
+        params.insert(
+            "optimization".to_string(),
+            optimization_goal(&options.optimization_level),
+        );
+        params.insert(
+            "comments".to_string(),
+            if options.include_comments {
+                "Document each function with a comment naming the part of the specification it implements.".to_string()
+            } else {
+                "Do not include comments.".to_string()
+            },
+        );
+        params.insert(
+            "style_guide".to_string(),
+            options
+                .style_guide
+                .as_ref()
+                .map(|guide| format!("Follow this style guide:\n{}\n", guide))
+                .unwrap_or_default(),
+        );
+
+        let source_code = self.request_code("implementation", &params).await?;
+        Ok(Implementation {
+            id: format!("{}_impl", spec.id),
+            specification_id: spec.id.clone(),
+            language,
+            source_code,
+            verification_result: unverified(),
+        })
+    }
+
+    async fn refine_implementation(
+        &self,
+        implementation: &Implementation,
+        spec: &Specification,
+        verification_result: &VerificationResult,
+    ) -> AxiomResult<Implementation> {
+        if verification_result.status == VerificationStatus::Verified {
+            return Ok(implementation.clone());
+        }
+        info!(
+            "Refining implementation {} after verification result: {}",
+            implementation.id, verification_result.status
+        );
+
+        let mut params = spec_params(spec, &implementation.language);
+        params.insert(
+            "source_code".to_string(),
+            implementation.source_code.clone(),
+        );
+        params.insert(
+            "verification_status".to_string(),
+            verification_result.status.to_string(),
+        );
+        params.insert(
+            "failures".to_string(),
+            bullet_list(failures(verification_result)),
+        );
+        params.insert(
+            "counterexamples".to_string(),
+            bullet_list(
+                verification_result
+                    .counterexamples
+                    .iter()
+                    .map(|c| c.to_string())
+                    .collect(),
+            ),
+        );
+
+        let source_code = self.request_code("refine_implementation", &params).await?;
+        Ok(Implementation {
+            source_code,
+            verification_result: unverified(),
+            ..implementation.clone()
+        })
+    }
+
+    async fn validate_implementation(
+        &self,
+        implementation: &Implementation,
+        spec: &Specification,
+    ) -> AxiomResult<bool> {
+        Ok(implementation.specification_id == spec.id
+            && !implementation.source_code.trim().is_empty())
+    }
+}
+
+fn spec_params(spec: &Specification, language: &Language) -> HashMap<String, String> {
+    let mut params = HashMap::new();
+    params.insert("target_language".to_string(), language.to_string());
+    params.insert(
+        "verification_language".to_string(),
+        spec.formal_spec.verification_language.to_string(),
+    );
+    params.insert("spec_code".to_string(), spec.formal_spec.spec_code.clone());
+    params
+}
+
+fn optimization_goal(level: &OptimizationLevel) -> String {
+    match level {
+        OptimizationLevel::None => "none; prefer the most direct code".to_string(),
+        OptimizationLevel::Speed => "speed".to_string(),
+        OptimizationLevel::Size => "small code size".to_string(),
+        OptimizationLevel::Security => {
+            "security; validate all inputs and avoid undefined behavior".to_string()
+        }
+        OptimizationLevel::Readability => "readability".to_string(),
+        OptimizationLevel::Custom(goal) => goal.clone(),
+    }
+}
+
+/// What the verifier reported as wrong: failure reasons, errors and unproven goals
+fn failures(result: &VerificationResult) -> Vec<String> {
+    let mut failures = match &result.status {
+        VerificationStatus::Failed(reasons) => reasons.clone(),
+        VerificationStatus::Error(message) => vec![message.clone()],
+        VerificationStatus::Timeout => vec!["Verification timed out".to_string()],
+        VerificationStatus::Verified | VerificationStatus::Unverified => vec![],
+    };
+    failures.extend(
+        result
+            .goal_results
+            .iter()
+            .filter(|goal| !goal.outcome.is_valid())
+            .map(|goal| format!("{}: {:?}", goal.name, goal.outcome)),
+    );
+    failures
+}
+
+fn bullet_list(items: Vec<String>) -> String {
+    if items.is_empty() {
+        return "None reported".to_string();
+    }
+    items
+        .iter()
+        .map(|item| format!("- {}", item))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Contents of the first fenced code block, or the whole answer when it has none
+fn extract_code(response: &str) -> String {
+    let mut lines = response
+        .lines()
+        .skip_while(|line| !line.trim_start().starts_with("```"));
+    if lines.next().is_none() {
+        return response.trim().to_string();
+    }
+
+    let code: Vec<&str> = lines
+        .take_while(|line| !line.trim_start().starts_with("```"))
+        .collect();
+    let mut code = code.join("\n");
+    code.push('\n');
+    code
+}
+
+fn unverified() -> VerificationResult {
+    VerificationResult {
+        status: VerificationStatus::Unverified,
+        proof_artifacts: vec![],
+        verification_time: Duration::ZERO,
+        resource_usage: Default::default(),
+        goal_results: vec![],
+        counterexamples: vec![],
+    }
+}
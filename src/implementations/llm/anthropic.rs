@@ -0,0 +1,202 @@
+use async_trait::async_trait;
+use log::{debug, warn};
+
+use crate::implementations::llm::stream::{SseEvent, StreamDelta};
+use crate::implementations::llm::{
+    post_json, post_streaming, LlmClient, LlmRequest, LlmResponse, StreamContext, TokenUsage,
+};
+use crate::implementations::specification_generator::SpecGenError;
+
+const DEFAULT_ENDPOINT: &str = "https://api.anthropic.com/v1/messages";
+const DEFAULT_MODEL: &str = "claude-3-sonnet-20240229";
+const API_VERSION: &str = "2023-06-01";
+
+/// Client for the Anthropic Messages API
+#[derive(Clone)]
+pub struct AnthropicClient {
+    http_client: reqwest::Client,
+    api_key: String,
+    endpoint: String,
+    model: String,
+}
+
+impl AnthropicClient {
+    pub fn new(http_client: reqwest::Client, api_key: impl Into<String>) -> Self {
+        Self {
+            http_client,
+            api_key: api_key.into(),
+            endpoint: DEFAULT_ENDPOINT.to_string(),
+            model: DEFAULT_MODEL.to_string(),
+        }
+    }
+
+    /// Send requests to a different model
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// Send requests to a different endpoint, e.g. a proxy
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into();
+        self
+    }
+
+    fn http_request(&self) -> reqwest::RequestBuilder {
+        self.http_client
+            .post(&self.endpoint)
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", API_VERSION)
+    }
+
+    /// JSON body of a Messages API request
+    ///
+    /// A request with a response schema forces a call to a tool taking that schema as input.
+    pub fn request_body(&self, request: &LlmRequest) -> serde_json::Value {
+        let mut body = serde_json::json!({
+            "model": self.model,
+            "max_tokens": request.max_tokens,
+            "temperature": request.temperature,
+            "system": request.system,
+            "messages": [
+                {
+                    "role": "user",
+                    "content": request.prompt
+                }
+            ]
+        });
+        if let Some(schema) = &request.response_schema {
+            body["tools"] = serde_json::json!([
+                {
+                    "name": schema.name,
+                    "description": schema.description,
+                    "input_schema": schema.schema
+                }
+            ]);
+            body["tool_choice"] = serde_json::json!({ "type": "tool", "name": schema.name });
+        }
+        body
+    }
+}
+
+#[async_trait]
+impl LlmClient for AnthropicClient {
+    fn provider(&self) -> &str {
+        "anthropic"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn complete(&self, request: &LlmRequest) -> Result<LlmResponse, SpecGenError> {
+        let text = post_json(self.http_request(), &self.request_body(request), self.provider()).await?;
+        messages_response(&text)
+    }
+
+    async fn complete_streaming(
+        &self,
+        request: &LlmRequest,
+        stream: &StreamContext,
+    ) -> Result<LlmResponse, SpecGenError> {
+        let mut body = self.request_body(request);
+        body["stream"] = serde_json::Value::Bool(true);
+
+        post_streaming(
+            self.http_request(),
+            &body,
+            self.provider(),
+            stream,
+            parse_stream_event,
+            messages_response,
+        )
+        .await
+    }
+}
+
+fn messages_response(text: &str) -> Result<LlmResponse, SpecGenError> {
+    Ok(LlmResponse::text(parse_messages_response(text)?).with_usage(parse_messages_usage(text)))
+}
+
+/// Extract the completion text from a Messages API response body
+///
+/// For a tool call, the text is the tool input as JSON.
+pub fn parse_messages_response(text: &str) -> Result<String, SpecGenError> {
+    let response: serde_json::Value = serde_json::from_str(text).map_err(|e| {
+        warn!("JSON parsing error: {}", e);
+        SpecGenError::ParseError(e.to_string())
+    })?;
+
+    let content = match response["content"].as_array() {
+        Some(blocks) => {
+            if let Some(tool_use) = blocks.iter().find(|block| block["type"] == "tool_use") {
+                let input = tool_use["input"].to_string();
+                debug!("Tool input length: {} characters", input.len());
+                return Ok(input);
+            }
+            let first = blocks.first().ok_or_else(|| {
+                SpecGenError::ParseError("Empty content array in Anthropic response".to_string())
+            })?;
+            first["text"].as_str().ok_or_else(|| {
+                SpecGenError::ParseError("Missing text in Anthropic response content".to_string())
+            })?
+        }
+        // Older response layouts carried the text directly
+        None => response["content"]
+            .as_str()
+            .or_else(|| response["completion"].as_str())
+            .ok_or_else(|| {
+                SpecGenError::ParseError("Unable to find content in Anthropic response".to_string())
+            })?,
+    };
+
+    debug!("Content length: {} characters", content.len());
+    Ok(content.to_string())
+}
+
+/// Token counts from the `usage` object of a Messages API response body
+pub fn parse_messages_usage(text: &str) -> Option<TokenUsage> {
+    let response: serde_json::Value = serde_json::from_str(text).ok()?;
+    let usage = &response["usage"];
+    Some(TokenUsage::new(
+        usage["input_tokens"].as_u64()?,
+        usage["output_tokens"].as_u64()?,
+    ))
+}
+
+/// Interpret one event of a streaming Messages API response
+pub fn parse_stream_event(event: &SseEvent) -> Result<StreamDelta, SpecGenError> {
+    if event.data.is_empty() {
+        return Ok(StreamDelta::default());
+    }
+    let data: serde_json::Value = serde_json::from_str(&event.data)?;
+
+    let mut delta = StreamDelta::default();
+    match data["type"].as_str().or(event.event.as_deref()) {
+        Some("message_start") => {
+            delta.prompt_tokens = data["message"]["usage"]["input_tokens"].as_u64();
+        }
+        Some("content_block_delta") => {
+            let text = match data["delta"]["type"].as_str() {
+                Some("input_json_delta") => data["delta"]["partial_json"].as_str(),
+                _ => data["delta"]["text"].as_str(),
+            };
+            delta.text = text.map(|text| text.to_string());
+        }
+        Some("message_delta") => {
+            delta.completion_tokens = data["usage"]["output_tokens"].as_u64();
+        }
+        Some("message_stop") => {
+            delta.done = true;
+        }
+        Some("error") => {
+            let message = data["error"]["message"].as_str().unwrap_or(&event.data).to_string();
+            return Err(match data["error"]["type"].as_str() {
+                Some("overloaded_error") => SpecGenError::from_status(529, None, message),
+                _ => SpecGenError::ApiError(message),
+            });
+        }
+        _ => {}
+    }
+    Ok(delta)
+}
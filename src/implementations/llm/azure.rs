@@ -0,0 +1,59 @@
+use async_trait::async_trait;
+
+use crate::implementations::llm::openai::{parse_chat_response, parse_chat_usage, ChatRequest};
+use crate::implementations::llm::{post_json, LlmClient, LlmRequest, LlmResponse};
+use crate::implementations::specification_generator::SpecGenError;
+
+const DEFAULT_MODEL: &str = "gpt-4";
+
+/// Client for an Azure OpenAI deployment
+///
+/// Azure has no public default endpoint, so the deployment's chat completions URL
+/// (including its `api-version` query) must be given.
+#[derive(Clone)]
+pub struct AzureOpenAiClient {
+    http_client: reqwest::Client,
+    api_key: String,
+    endpoint: String,
+    model: String,
+}
+
+impl AzureOpenAiClient {
+    pub fn new(
+        http_client: reqwest::Client,
+        api_key: impl Into<String>,
+        endpoint: impl Into<String>,
+    ) -> Self {
+        Self {
+            http_client,
+            api_key: api_key.into(),
+            endpoint: endpoint.into(),
+            model: DEFAULT_MODEL.to_string(),
+        }
+    }
+
+    /// Send requests to a different model
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+}
+
+#[async_trait]
+impl LlmClient for AzureOpenAiClient {
+    fn provider(&self) -> &str {
+        "azure"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn complete(&self, request: &LlmRequest) -> Result<LlmResponse, SpecGenError> {
+        let body = ChatRequest::new(&self.model, request).function_call().to_json();
+        let http_request = self.http_client.post(&self.endpoint).header("api-key", &self.api_key);
+
+        let text = post_json(http_request, &body, self.provider()).await?;
+        Ok(LlmResponse::text(parse_chat_response(&text)?).with_usage(parse_chat_usage(&text)))
+    }
+}
@@ -0,0 +1,270 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::implementations::llm::{fnv1a_hex, LlmClient, LlmRequest, LlmResponse, StreamContext};
+use crate::implementations::specification_generator::SpecGenError;
+
+/// Cache location relative to the project directory
+pub const DEFAULT_CACHE_DIR: &str = ".axiom/cache/llm";
+/// How long responses are reused unless configured otherwise
+pub const DEFAULT_CACHE_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Settings for the on-disk LLM response cache
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResponseCacheConfig {
+    /// Directory holding cached responses
+    #[serde(default = "default_cache_dir")]
+    pub dir: PathBuf,
+
+    /// Seconds before a cached response is ignored; `None` keeps responses forever
+    #[serde(default = "default_cache_ttl")]
+    pub ttl_secs: Option<u64>,
+}
+
+fn default_cache_dir() -> PathBuf {
+    PathBuf::from(DEFAULT_CACHE_DIR)
+}
+
+fn default_cache_ttl() -> Option<u64> {
+    Some(DEFAULT_CACHE_TTL_SECS)
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            dir: default_cache_dir(),
+            ttl_secs: default_cache_ttl(),
+        }
+    }
+}
+
+/// A cached response, stored as `<key>.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub key: String,
+    pub provider: String,
+    pub model: String,
+    pub temperature: f32,
+    pub created_at: DateTime<Utc>,
+    pub response: String,
+}
+
+/// Summary of the cache contents
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub entries: usize,
+    pub expired: usize,
+    pub total_bytes: u64,
+    pub oldest: Option<DateTime<Utc>>,
+    pub newest: Option<DateTime<Utc>>,
+}
+
+/// Content-addressed store of LLM responses
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    dir: PathBuf,
+    ttl: Option<Duration>,
+}
+
+impl ResponseCache {
+    pub fn new(dir: impl Into<PathBuf>, ttl: Option<Duration>) -> Self {
+        Self {
+            dir: dir.into(),
+            ttl,
+        }
+    }
+
+    pub fn from_config(config: &ResponseCacheConfig) -> Self {
+        Self::new(&config.dir, config.ttl_secs.map(Duration::from_secs))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Key for a request to the given provider and model
+    ///
+    /// The sample index only counts from the second sample on, so single requests keep
+    /// their keys.
+    pub fn key(provider: &str, model: &str, request: &LlmRequest) -> String {
+        let temperature = request.temperature.to_bits().to_le_bytes();
+        let sample = request.sample.to_le_bytes();
+        let mut parts: Vec<&[u8]> = vec![
+            provider.as_bytes(),
+            model.as_bytes(),
+            &temperature,
+            request.system.as_bytes(),
+            request.prompt.as_bytes(),
+        ];
+        if request.sample > 0 {
+            parts.push(&sample);
+        }
+        fnv1a_hex(&parts)
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+
+    fn is_expired(&self, entry: &CacheEntry) -> bool {
+        match self.ttl {
+            Some(ttl) => {
+                let age = Utc::now().signed_duration_since(entry.created_at);
+                age.to_std().map(|age| age > ttl).unwrap_or(false)
+            }
+            None => false,
+        }
+    }
+
+    /// Cached response for a key, if present and not expired
+    pub fn get(&self, key: &str) -> Option<String> {
+        let contents = std::fs::read_to_string(self.entry_path(key)).ok()?;
+        let entry: CacheEntry = serde_json::from_str(&contents).ok()?;
+        if self.is_expired(&entry) {
+            debug!("LLM cache entry {} has expired", key);
+            return None;
+        }
+        Some(entry.response)
+    }
+
+    /// Store a response
+    pub fn put(&self, entry: &CacheEntry) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(self.entry_path(&entry.key), serde_json::to_string_pretty(entry)?)
+    }
+
+    fn entries(&self) -> Vec<(PathBuf, u64, Option<CacheEntry>)> {
+        let Ok(read_dir) = std::fs::read_dir(&self.dir) else {
+            return vec![];
+        };
+
+        read_dir
+            .flatten()
+            .map(|dir_entry| dir_entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .map(|path| {
+                let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+                let entry = std::fs::read_to_string(&path)
+                    .ok()
+                    .and_then(|contents| serde_json::from_str(&contents).ok());
+                (path, size, entry)
+            })
+            .collect()
+    }
+
+    /// Count entries, expired entries and their size on disk
+    pub fn stats(&self) -> CacheStats {
+        let mut stats = CacheStats::default();
+        for (_, size, entry) in self.entries() {
+            stats.entries += 1;
+            stats.total_bytes += size;
+            match entry {
+                Some(entry) => {
+                    if self.is_expired(&entry) {
+                        stats.expired += 1;
+                    }
+                    stats.oldest = Some(stats.oldest.map_or(entry.created_at, |t| t.min(entry.created_at)));
+                    stats.newest = Some(stats.newest.map_or(entry.created_at, |t| t.max(entry.created_at)));
+                }
+                // Unreadable entries are never served, so count them as expired
+                None => stats.expired += 1,
+            }
+        }
+        stats
+    }
+
+    /// Remove cached responses, only expired or unreadable ones if `expired_only`; returns the number removed
+    pub fn clear(&self, expired_only: bool) -> std::io::Result<usize> {
+        let mut removed = 0;
+        for (path, _, entry) in self.entries() {
+            let expired = entry.as_ref().is_none_or(|entry| self.is_expired(entry));
+            if !expired_only || expired {
+                std::fs::remove_file(&path)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+/// Client that serves repeated requests from a `ResponseCache`
+///
+/// Only successful responses are stored, so failures are retried on the next call.
+pub struct CachingClient {
+    inner: Arc<dyn LlmClient>,
+    cache: ResponseCache,
+}
+
+impl CachingClient {
+    pub fn new(inner: Arc<dyn LlmClient>, cache: ResponseCache) -> Self {
+        Self { inner, cache }
+    }
+
+    /// Serve from the cache, or call the inner client, streaming if `stream` is given
+    async fn cached_or_complete(
+        &self,
+        request: &LlmRequest,
+        stream: Option<&StreamContext>,
+    ) -> Result<LlmResponse, SpecGenError> {
+        let key = ResponseCache::key(self.inner.provider(), self.inner.model(), request);
+        if let Some(response) = self.cache.get(&key) {
+            debug!("LLM cache hit for {} ({})", key, self.cache.dir().display());
+            if let Some(stream) = stream {
+                stream.emit(&response);
+            }
+            return Ok(LlmResponse {
+                cached: true,
+                ..LlmResponse::text(response)
+            });
+        }
+        debug!("LLM cache miss for {}", key);
+
+        let response = match stream {
+            Some(stream) => self.inner.complete_streaming(request, stream).await?,
+            None => self.inner.complete(request).await?,
+        };
+
+        let entry = CacheEntry {
+            key,
+            provider: self.inner.provider().to_string(),
+            model: self.inner.model().to_string(),
+            temperature: request.temperature,
+            created_at: Utc::now(),
+            response: response.content.clone(),
+        };
+        if let Err(e) = self.cache.put(&entry) {
+            warn!("Could not write LLM cache entry {}: {}", entry.key, e);
+        }
+
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl LlmClient for CachingClient {
+    fn provider(&self) -> &str {
+        self.inner.provider()
+    }
+
+    fn model(&self) -> &str {
+        self.inner.model()
+    }
+
+    async fn complete(&self, request: &LlmRequest) -> Result<LlmResponse, SpecGenError> {
+        self.cached_or_complete(request, None).await
+    }
+
+    async fn complete_streaming(
+        &self,
+        request: &LlmRequest,
+        stream: &StreamContext,
+    ) -> Result<LlmResponse, SpecGenError> {
+        self.cached_or_complete(request, Some(stream)).await
+    }
+}
@@ -0,0 +1,183 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use log::{debug, info};
+use serde::{Deserialize, Serialize};
+
+use crate::implementations::llm::{fnv1a_hex, LlmClient, LlmRequest, LlmResponse, StreamContext};
+use crate::implementations::specification_generator::SpecGenError;
+
+/// Environment variable selecting `record` or `replay` mode
+pub const FIXTURE_MODE_ENV: &str = "AXIOM_LLM_FIXTURES";
+/// Environment variable holding the fixture directory
+pub const FIXTURE_DIR_ENV: &str = "AXIOM_LLM_FIXTURE_DIR";
+
+/// Whether LLM traffic is written to or served from fixtures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LlmFixtureMode {
+    /// Call the real provider and save every prompt/response pair
+    Record,
+    /// Answer from saved pairs only, without network access
+    Replay,
+}
+
+/// Where LLM fixtures live and how they are used
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmFixtureConfig {
+    pub mode: LlmFixtureMode,
+    pub dir: PathBuf,
+}
+
+impl LlmFixtureConfig {
+    /// Read the fixture mode from `AXIOM_LLM_FIXTURES` and `AXIOM_LLM_FIXTURE_DIR`
+    pub fn from_env() -> Option<Self> {
+        let mode = match std::env::var(FIXTURE_MODE_ENV).ok()?.to_lowercase().as_str() {
+            "record" => LlmFixtureMode::Record,
+            "replay" => LlmFixtureMode::Replay,
+            _ => {
+                return None;
+            }
+        };
+        let dir = std::env::var(FIXTURE_DIR_ENV).ok()?;
+        Some(Self {
+            mode,
+            dir: PathBuf::from(dir),
+        })
+    }
+}
+
+/// A recorded prompt/response pair, stored as `<prompt hash>.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmFixture {
+    pub key: String,
+    pub provider: String,
+    pub model: String,
+    pub system: String,
+    pub prompt: String,
+    pub response: String,
+}
+
+/// Stable key for a request: 64-bit FNV-1a over the system prompt, prompt and, from the
+/// second sample on, the sample index
+///
+/// Sampling parameters are deliberately left out so fixtures survive temperature
+/// and token limit tweaks, but each candidate drawn for the same prompt is its own
+/// fixture, as in the response cache.
+pub fn prompt_hash(request: &LlmRequest) -> String {
+    let sample = request.sample.to_le_bytes();
+    let mut parts: Vec<&[u8]> = vec![request.system.as_bytes(), request.prompt.as_bytes()];
+    if request.sample > 0 {
+        parts.push(&sample);
+    }
+    fnv1a_hex(&parts)
+}
+
+/// Path of the fixture for a request
+pub fn fixture_path(dir: &Path, request: &LlmRequest) -> PathBuf {
+    dir.join(format!("{}.json", prompt_hash(request)))
+}
+
+/// Client that forwards to a real provider and records each exchange
+pub struct RecordingClient {
+    inner: Arc<dyn LlmClient>,
+    dir: PathBuf,
+}
+
+impl RecordingClient {
+    pub fn new(inner: Arc<dyn LlmClient>, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            inner,
+            dir: dir.into(),
+        }
+    }
+
+    /// Call the inner client, streaming if `stream` is given, and save the exchange
+    async fn complete_and_record(
+        &self,
+        request: &LlmRequest,
+        stream: Option<&StreamContext>,
+    ) -> Result<LlmResponse, SpecGenError> {
+        let response = match stream {
+            Some(stream) => self.inner.complete_streaming(request, stream).await?,
+            None => self.inner.complete(request).await?,
+        };
+
+        let fixture = LlmFixture {
+            key: prompt_hash(request),
+            provider: self.inner.provider().to_string(),
+            model: self.inner.model().to_string(),
+            system: request.system.clone(),
+            prompt: request.prompt.clone(),
+            response: response.content.clone(),
+        };
+        std::fs::create_dir_all(&self.dir)?;
+        let path = fixture_path(&self.dir, request);
+        std::fs::write(&path, serde_json::to_string_pretty(&fixture)?)?;
+        info!("Recorded LLM response to {}", path.display());
+
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl LlmClient for RecordingClient {
+    fn provider(&self) -> &str {
+        self.inner.provider()
+    }
+
+    fn model(&self) -> &str {
+        self.inner.model()
+    }
+
+    async fn complete(&self, request: &LlmRequest) -> Result<LlmResponse, SpecGenError> {
+        self.complete_and_record(request, None).await
+    }
+
+    async fn complete_streaming(
+        &self,
+        request: &LlmRequest,
+        stream: &StreamContext,
+    ) -> Result<LlmResponse, SpecGenError> {
+        self.complete_and_record(request, Some(stream)).await
+    }
+}
+
+/// Client that answers from recorded fixtures and never touches the network
+pub struct ReplayClient {
+    dir: PathBuf,
+}
+
+impl ReplayClient {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+#[async_trait]
+impl LlmClient for ReplayClient {
+    fn provider(&self) -> &str {
+        "replay"
+    }
+
+    fn model(&self) -> &str {
+        "fixtures"
+    }
+
+    async fn complete(&self, request: &LlmRequest) -> Result<LlmResponse, SpecGenError> {
+        let path = fixture_path(&self.dir, request);
+        debug!("Replaying LLM response from {}", path.display());
+
+        let contents = std::fs::read_to_string(&path).map_err(|_| {
+            SpecGenError::ApiError(format!(
+                "No recorded response for prompt {} in {}; re-record with {}=record",
+                prompt_hash(request),
+                self.dir.display(),
+                FIXTURE_MODE_ENV
+            ))
+        })?;
+        let fixture: LlmFixture = serde_json::from_str(&contents)?;
+        Ok(LlmResponse::text(fixture.response))
+    }
+}
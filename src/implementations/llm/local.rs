@@ -0,0 +1,261 @@
+use std::sync::OnceLock;
+
+use async_trait::async_trait;
+use log::{debug, info, warn};
+use reqwest::Url;
+
+use crate::implementations::config::ConfigError;
+use crate::implementations::llm::openai::{parse_chat_response, parse_chat_usage, ChatRequest};
+use crate::implementations::llm::{
+    post_json, LlmClient, LlmRequest, LlmResponse, TokenUsage, REQUEST_TIMEOUT,
+};
+use crate::implementations::specification_generator::SpecGenError;
+
+/// Endpoint used when a local provider is selected without one
+pub const DEFAULT_LOCAL_ENDPOINT: &str = "http://localhost:11434/v1/chat/completions";
+
+/// Wire format spoken by a local model server
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalApi {
+    /// OpenAI-compatible `/v1/chat/completions`, served by llama.cpp, vLLM, LM Studio and Ollama
+    OpenAiCompatible,
+    /// Ollama's native `/api/chat`
+    Ollama,
+}
+
+impl LocalApi {
+    /// Guess the wire format from the endpoint path
+    pub fn from_endpoint(endpoint: &Url) -> Self {
+        if endpoint.path().trim_end_matches('/').ends_with("/api/chat") {
+            LocalApi::Ollama
+        } else {
+            LocalApi::OpenAiCompatible
+        }
+    }
+}
+
+/// Whether an endpoint points at this machine
+pub fn is_local_endpoint(endpoint: &str) -> bool {
+    Url::parse(endpoint)
+        .ok()
+        .and_then(|url| url.host_str().map(|host| host.to_string()))
+        .map(|host| matches!(host.as_str(), "localhost" | "127.0.0.1" | "[::1]"))
+        .unwrap_or(false)
+}
+
+/// Client for a self-hosted model server that needs no API key
+///
+/// The model is resolved on first use: the configured model if the server lists it,
+/// otherwise the first model the server reports.
+pub struct LocalClient {
+    http_client: reqwest::Client,
+    endpoint: Url,
+    api: LocalApi,
+    api_key: Option<String>,
+    configured_model: Option<String>,
+    resolved_model: OnceLock<String>,
+}
+
+impl LocalClient {
+    /// Create a client for a chat endpoint, or for a bare server URL such as `http://localhost:8080`
+    pub fn new(http_client: reqwest::Client, endpoint: &str) -> Result<Self, ConfigError> {
+        let mut url = Url::parse(endpoint).map_err(|e| {
+            ConfigError::InvalidSetting(format!("llm_api.api_endpoint {}: {}", endpoint, e))
+        })?;
+        if url.path().trim_end_matches('/').is_empty() {
+            url.set_path("/v1/chat/completions");
+        }
+
+        Ok(Self {
+            http_client,
+            api: LocalApi::from_endpoint(&url),
+            endpoint: url,
+            api_key: None,
+            configured_model: None,
+            resolved_model: OnceLock::new(),
+        })
+    }
+
+    /// Prefer this model if the server provides it
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.configured_model = Some(model.into());
+        self
+    }
+
+    /// Send a bearer token, for servers started with an API key
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Wire format detected from the endpoint
+    pub fn api(&self) -> LocalApi {
+        self.api
+    }
+
+    /// URL listing the server's models
+    pub fn models_url(&self) -> Url {
+        let mut url = self.endpoint.clone();
+        let path = self.endpoint.path().trim_end_matches('/');
+        let models_path = match self.api {
+            LocalApi::Ollama => format!("{}/tags", path.trim_end_matches("/chat")),
+            LocalApi::OpenAiCompatible => {
+                format!("{}/models", path.trim_end_matches("/chat/completions"))
+            }
+        };
+        url.set_path(&models_path);
+        url
+    }
+
+    /// Ask the server which models it serves
+    pub async fn discover_models(&self) -> Result<Vec<String>, SpecGenError> {
+        let url = self.models_url();
+        debug!("Listing local models from {}", url);
+
+        let mut request = self.http_client.get(url.clone()).timeout(REQUEST_TIMEOUT);
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+        let response = request.send().await.map_err(|e| {
+            SpecGenError::NetworkError(format!("Could not reach local model server at {}: {}", url, e))
+        })?;
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = response.text().await.unwrap_or_default();
+            return Err(SpecGenError::from_status(status, None, message));
+        }
+
+        let text = response.text().await.map_err(|e| SpecGenError::ParseError(e.to_string()))?;
+        parse_model_list(&text)
+    }
+
+    /// Model to send requests to, discovering it from the server on first use
+    async fn resolve_model(&self) -> Result<String, SpecGenError> {
+        if let Some(model) = self.resolved_model.get() {
+            return Ok(model.clone());
+        }
+
+        let model = match (self.discover_models().await, &self.configured_model) {
+            (Ok(models), Some(configured)) if models.contains(configured) => configured.clone(),
+            (Ok(models), configured) => {
+                let first = models.first().cloned().ok_or_else(|| {
+                    SpecGenError::ApiError("Local model server reports no models".to_string())
+                })?;
+                if let Some(configured) = configured {
+                    warn!(
+                        "Model {} is not served locally, using {} instead",
+                        configured, first
+                    );
+                }
+                first
+            }
+            (Err(e), Some(configured)) => {
+                warn!("Model discovery failed ({}), using configured model {}", e, configured);
+                configured.clone()
+            }
+            (Err(e), None) => {
+                return Err(e);
+            }
+        };
+
+        info!("Using local model {}", model);
+        Ok(self.resolved_model.get_or_init(|| model).clone())
+    }
+
+    /// JSON body for the detected wire format
+    pub fn request_body(&self, model: &str, request: &LlmRequest) -> serde_json::Value {
+        match self.api {
+            LocalApi::OpenAiCompatible => ChatRequest::new(model, request).json_mode().to_json(),
+            LocalApi::Ollama => {
+                let mut body = serde_json::json!({
+                    "model": model,
+                    "stream": false,
+                    "messages": [
+                        { "role": "system", "content": request.system },
+                        { "role": "user", "content": request.prompt }
+                    ],
+                    "options": {
+                        "temperature": request.temperature,
+                        "num_predict": request.max_tokens
+                    }
+                });
+                // Ollama constrains the output to a JSON schema given as the format
+                if let Some(schema) = &request.response_schema {
+                    body["format"] = schema.schema.clone();
+                }
+                body
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl LlmClient for LocalClient {
+    fn provider(&self) -> &str {
+        "local"
+    }
+
+    fn model(&self) -> &str {
+        self.resolved_model
+            .get()
+            .or(self.configured_model.as_ref())
+            .map(|model| model.as_str())
+            .unwrap_or("auto")
+    }
+
+    async fn complete(&self, request: &LlmRequest) -> Result<LlmResponse, SpecGenError> {
+        let model = self.resolve_model().await?;
+        let body = self.request_body(&model, request);
+
+        let mut http_request = self.http_client.post(self.endpoint.clone());
+        if let Some(api_key) = &self.api_key {
+            http_request = http_request.bearer_auth(api_key);
+        }
+
+        let text = post_json(http_request, &body, self.provider()).await?;
+        match self.api {
+            LocalApi::OpenAiCompatible => {
+                Ok(LlmResponse::text(parse_chat_response(&text)?).with_usage(parse_chat_usage(&text)))
+            }
+            LocalApi::Ollama => {
+                Ok(LlmResponse::text(parse_ollama_response(&text)?).with_usage(parse_ollama_usage(&text)))
+            }
+        }
+    }
+}
+
+/// Model names from an OpenAI `/v1/models` or Ollama `/api/tags` response
+pub fn parse_model_list(text: &str) -> Result<Vec<String>, SpecGenError> {
+    let response: serde_json::Value = serde_json::from_str(text)?;
+
+    let (entries, key) = if let Some(data) = response["data"].as_array() {
+        (data, "id")
+    } else if let Some(models) = response["models"].as_array() {
+        (models, "name")
+    } else {
+        return Err(SpecGenError::ParseError("Unrecognised model list response".to_string()));
+    };
+
+    Ok(entries
+        .iter()
+        .filter_map(|entry| entry[key].as_str().map(|name| name.to_string()))
+        .collect())
+}
+
+/// Extract the message from an Ollama `/api/chat` response body
+pub fn parse_ollama_response(text: &str) -> Result<String, SpecGenError> {
+    let response: serde_json::Value = serde_json::from_str(text)?;
+    response["message"]["content"]
+        .as_str()
+        .map(|content| content.to_string())
+        .ok_or_else(|| SpecGenError::ParseError("Missing message content in Ollama response".to_string()))
+}
+
+/// Token counts from an Ollama `/api/chat` response body
+pub fn parse_ollama_usage(text: &str) -> Option<TokenUsage> {
+    let response: serde_json::Value = serde_json::from_str(text).ok()?;
+    Some(TokenUsage::new(
+        response["prompt_eval_count"].as_u64()?,
+        response["eval_count"].as_u64()?,
+    ))
+}
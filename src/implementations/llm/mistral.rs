@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+
+use crate::implementations::llm::openai::{parse_chat_response, parse_chat_usage, ChatRequest};
+use crate::implementations::llm::{post_json, LlmClient, LlmRequest, LlmResponse};
+use crate::implementations::specification_generator::SpecGenError;
+
+const DEFAULT_ENDPOINT: &str = "https://api.mistral.ai/v1/chat/completions";
+const DEFAULT_MODEL: &str = "mistral-large-latest";
+
+/// Client for the Mistral chat completions API
+#[derive(Clone)]
+pub struct MistralClient {
+    http_client: reqwest::Client,
+    api_key: String,
+    endpoint: String,
+    model: String,
+}
+
+impl MistralClient {
+    pub fn new(http_client: reqwest::Client, api_key: impl Into<String>) -> Self {
+        Self {
+            http_client,
+            api_key: api_key.into(),
+            endpoint: DEFAULT_ENDPOINT.to_string(),
+            model: DEFAULT_MODEL.to_string(),
+        }
+    }
+
+    /// Send requests to a different model
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+}
+
+#[async_trait]
+impl LlmClient for MistralClient {
+    fn provider(&self) -> &str {
+        "mistral"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn complete(&self, request: &LlmRequest) -> Result<LlmResponse, SpecGenError> {
+        let body = ChatRequest::new(&self.model, request).json_mode().to_json();
+        let http_request = self.http_client.post(&self.endpoint).bearer_auth(&self.api_key);
+
+        let text = post_json(http_request, &body, self.provider()).await?;
+        Ok(LlmResponse::text(parse_chat_response(&text)?).with_usage(parse_chat_usage(&text)))
+    }
+}
@@ -0,0 +1,491 @@
+pub mod anthropic;
+pub mod azure;
+pub mod cache;
+pub mod fixtures;
+pub mod local;
+pub mod mistral;
+pub mod openai;
+pub mod retry;
+pub mod stream;
+pub mod structured;
+pub mod together;
+pub mod usage;
+
+pub use anthropic::AnthropicClient;
+pub use azure::AzureOpenAiClient;
+pub use cache::{CachingClient, ResponseCache, ResponseCacheConfig};
+pub use fixtures::{LlmFixtureConfig, LlmFixtureMode, RecordingClient, ReplayClient};
+pub use local::LocalClient;
+pub use mistral::MistralClient;
+pub use openai::OpenAiClient;
+pub use retry::{RetryPolicy, RetryingClient};
+pub use stream::{CancelFlag, StreamContext, StreamObserver};
+pub use structured::StructuredResponse;
+pub use together::TogetherClient;
+pub use usage::{LlmCallRecord, ModelPrice, TokenUsage, UsageBudget, UsageSummary, UsageTracker};
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use log::{debug, info, warn};
+
+use crate::implementations::config::{ConfigError, GeneratorConfig};
+use crate::implementations::specification_generator::SpecGenError;
+
+/// System prompt sent with every completion request
+pub const DEFAULT_SYSTEM_PROMPT: &str =
+    "You are a formal verification expert who creates precise, detailed formal specifications.";
+
+/// Provider tried first when the config does not pin one
+const PREFERRED_PROVIDER: &str = "anthropic";
+
+/// Upper bound for a whole non-streaming request
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+/// Upper bound for the silence between two chunks of a streaming response
+pub const STREAM_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A single prompt/response exchange with an LLM
+#[derive(Debug, Clone)]
+pub struct LlmRequest {
+    pub system: String,
+    pub prompt: String,
+    pub temperature: f32,
+    pub max_tokens: usize,
+    /// Ask for a JSON answer matching this schema instead of free text
+    pub response_schema: Option<ResponseSchema>,
+    /// Which of several completions drawn for the same prompt this is, so that they are
+    /// cached separately
+    pub sample: usize,
+}
+
+impl LlmRequest {
+    /// Create a request using the default system prompt
+    pub fn new(prompt: impl Into<String>, temperature: f32, max_tokens: usize) -> Self {
+        Self {
+            system: DEFAULT_SYSTEM_PROMPT.to_string(),
+            prompt: prompt.into(),
+            temperature,
+            max_tokens,
+            response_schema: None,
+            sample: 0,
+        }
+    }
+
+    pub fn with_response_schema(mut self, schema: ResponseSchema) -> Self {
+        self.response_schema = Some(schema);
+        self
+    }
+
+    pub fn with_sample(mut self, sample: usize) -> Self {
+        self.sample = sample;
+        self
+    }
+}
+
+/// JSON schema of a structured answer, with the tool name providers with tool use are made to call
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseSchema {
+    pub name: String,
+    pub description: String,
+    pub schema: serde_json::Value,
+}
+
+/// Text of a completion together with what the provider reported about it
+#[derive(Debug, Clone, PartialEq)]
+pub struct LlmResponse {
+    pub content: String,
+    /// Token counts, when the provider reports them
+    pub usage: Option<TokenUsage>,
+    /// Served from the response cache without calling the provider
+    pub cached: bool,
+}
+
+impl LlmResponse {
+    /// Response with no usage information
+    pub fn text(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+            usage: None,
+            cached: false,
+        }
+    }
+
+    pub fn with_usage(mut self, usage: Option<TokenUsage>) -> Self {
+        self.usage = usage;
+        self
+    }
+}
+
+impl From<String> for LlmResponse {
+    fn from(content: String) -> Self {
+        Self::text(content)
+    }
+}
+
+/// Client for one LLM provider's completion API
+///
+/// Each provider is its own type; `client_from_config` picks one based on the
+/// configured or available API keys. Tests and library users can hand any
+/// implementation to `LLMSpecificationGenerator::with_llm_client`.
+#[async_trait]
+pub trait LlmClient: Send + Sync {
+    /// Short provider name used in logs, e.g. "anthropic"
+    fn provider(&self) -> &str;
+
+    /// Model the client sends requests to
+    fn model(&self) -> &str;
+
+    /// Send the request and return the first completion
+    async fn complete(&self, request: &LlmRequest) -> Result<LlmResponse, SpecGenError>;
+
+    /// Like `complete`, but hand text to the stream's observer as it arrives and stop
+    /// when the stream is cancelled
+    ///
+    /// Clients without streaming support report the whole completion at once.
+    async fn complete_streaming(
+        &self,
+        request: &LlmRequest,
+        stream: &StreamContext,
+    ) -> Result<LlmResponse, SpecGenError> {
+        let response = stream.cancel_flag().run(self.complete(request)).await?;
+        stream.emit(&response.content);
+        Ok(response)
+    }
+}
+
+/// Shared HTTP client for all LLM requests
+///
+/// Only connecting is bounded here; `post_json` limits whole requests to `REQUEST_TIMEOUT`,
+/// while streaming requests may run longer as long as data keeps arriving.
+pub fn default_http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(30))
+        .build()
+        .expect("Failed to create HTTP client")
+}
+
+/// Send a request, streaming it if configured, and record its usage against the budget
+///
+/// Shared by the generators so that all LLM calls of a run count towards one budget.
+pub async fn send_recorded(
+    client: &dyn LlmClient,
+    request: &LlmRequest,
+    config: &GeneratorConfig,
+    usage: &UsageTracker,
+    stream: &StreamContext,
+) -> Result<String, SpecGenError> {
+    info!("Making LLM API request to {}", client.provider());
+    debug!("Model: {}", client.model());
+    debug!("Temperature: {}", request.temperature);
+    debug!("Max tokens: {}", request.max_tokens);
+    debug!("Prompt length: {} characters", request.prompt.len());
+
+    // Refuse to start another call once a previous one went over the budget
+    usage.summary().check_budget(&config.budget)?;
+
+    let started = std::time::Instant::now();
+    let observer = stream.observer();
+    if let Some(observer) = observer {
+        observer.on_start(client.provider(), client.model());
+    }
+    let response = if config.stream {
+        client.complete_streaming(request, stream).await
+    } else {
+        stream.cancel_flag().run(client.complete(request)).await
+    };
+    if let Some(observer) = observer {
+        observer.on_finish();
+    }
+    let response = response?;
+    let latency_ms = started.elapsed().as_millis() as u64;
+    info!("API call completed successfully");
+
+    let (token_usage, estimated) = match (response.cached, response.usage) {
+        (true, _) => (TokenUsage::default(), false),
+        (false, Some(token_usage)) => (token_usage, false),
+        (false, None) => (TokenUsage::estimate(&request.prompt, &response.content), true),
+    };
+    let cost_usd = if response.cached || client.provider() == "local" {
+        Some(0.0)
+    } else {
+        usage::price_for(client.model(), &config.model_prices).map(|price| price.cost(&token_usage))
+    };
+    debug!(
+        "LLM usage: {} prompt + {} completion tokens{}, {} ms",
+        token_usage.prompt_tokens,
+        token_usage.completion_tokens,
+        if estimated { " (estimated)" } else { "" },
+        latency_ms
+    );
+    usage.record(LlmCallRecord {
+        provider: client.provider().to_string(),
+        model: client.model().to_string(),
+        usage: token_usage,
+        estimated,
+        cached: response.cached,
+        latency_ms,
+        cost_usd,
+    });
+    usage.summary().check_budget(&config.budget)?;
+
+    Ok(response.content)
+}
+
+/// Build the client for whichever provider has an API key, preferring Anthropic
+///
+/// With `llm_fixtures` set, replay mode needs no API key and record mode wraps
+/// the provider client in a `RecordingClient`. Otherwise `response_cache` wraps it
+/// in a `CachingClient`. Provider calls are retried according to `retry`.
+pub fn client_from_config(
+    config: &GeneratorConfig,
+    http_client: reqwest::Client,
+) -> Result<Arc<dyn LlmClient>, ConfigError> {
+    match &config.llm_fixtures {
+        Some(fixtures) if fixtures.mode == LlmFixtureMode::Replay => {
+            info!("Replaying LLM responses from {}", fixtures.dir.display());
+            Ok(Arc::new(ReplayClient::new(&fixtures.dir)))
+        }
+        Some(fixtures) => {
+            let inner = retrying_provider_client(config, http_client)?;
+            info!("Recording LLM responses to {}", fixtures.dir.display());
+            Ok(Arc::new(RecordingClient::new(inner, &fixtures.dir)))
+        }
+        None => {
+            let client = retrying_provider_client(config, http_client)?;
+            Ok(match &config.response_cache {
+                Some(cache) => Arc::new(CachingClient::new(client, ResponseCache::from_config(cache))),
+                None => client,
+            })
+        }
+    }
+}
+
+fn retrying_provider_client(
+    config: &GeneratorConfig,
+    http_client: reqwest::Client,
+) -> Result<Arc<dyn LlmClient>, ConfigError> {
+    let client = provider_client(config, http_client)?;
+    Ok(Arc::new(RetryingClient::new(client, config.retry.clone())))
+}
+
+fn provider_client(
+    config: &GeneratorConfig,
+    http_client: reqwest::Client,
+) -> Result<Arc<dyn LlmClient>, ConfigError> {
+    let api = &config.llm_api;
+    if uses_local_provider(config) {
+        let endpoint = api.api_endpoint.as_deref().unwrap_or(local::DEFAULT_LOCAL_ENDPOINT);
+        let mut client = LocalClient::new(http_client, endpoint)?;
+        if let Some(model) = &api.model {
+            client = client.with_model(model);
+        }
+        if let Some(api_key) = &api.api_key {
+            client = client.with_api_key(api_key);
+        }
+        info!("Using local provider at {}", endpoint);
+        return Ok(Arc::new(client));
+    }
+
+    let preferred = api.provider.as_deref().unwrap_or(PREFERRED_PROVIDER);
+    let (provider, api_key) = config.get_api_key(preferred)?;
+
+    let client: Arc<dyn LlmClient> = match provider.as_str() {
+        "anthropic" => Arc::new(AnthropicClient::new(http_client, api_key)),
+        "azure" => {
+            let endpoint = api.api_endpoint.clone().ok_or_else(|| {
+                ConfigError::MissingSetting("llm_api.api_endpoint for Azure OpenAI".to_string())
+            })?;
+            let mut client = AzureOpenAiClient::new(http_client, api_key, endpoint);
+            if let Some(model) = &api.model {
+                client = client.with_model(model);
+            }
+            Arc::new(client)
+        }
+        "mistral" => Arc::new(MistralClient::new(http_client, api_key)),
+        "together" => Arc::new(TogetherClient::new(http_client, api_key)),
+        other => {
+            if other != "openai" {
+                warn!("Unknown provider: {}, falling back to OpenAI", other);
+            }
+            let mut client = OpenAiClient::new(http_client, api_key);
+            if let Some(endpoint) = &api.api_endpoint {
+                client = client.with_endpoint(endpoint);
+            }
+            if let Some(model) = &api.model {
+                client = client.with_model(model);
+            }
+            Arc::new(client)
+        }
+    };
+
+    info!("Using {} provider with model {}", client.provider(), client.model());
+    Ok(client)
+}
+
+/// Whether to talk to a self-hosted server: requested explicitly, or implied by a localhost endpoint
+pub fn uses_local_provider(config: &GeneratorConfig) -> bool {
+    match config.llm_api.provider.as_deref() {
+        Some(provider) => provider.eq_ignore_ascii_case("local"),
+        None => config.llm_api.api_endpoint.as_deref().is_some_and(local::is_local_endpoint),
+    }
+}
+
+/// 64-bit FNV-1a hash of NUL-separated parts as 16 hex digits, stable across Rust versions
+pub(crate) fn fnv1a_hex(parts: &[&[u8]]) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    let mut hash = OFFSET_BASIS;
+    for (index, part) in parts.iter().enumerate() {
+        let separator: &[u8] = if index == 0 { &[] } else { &[0] };
+        for byte in separator.iter().chain(part.iter()) {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(PRIME);
+        }
+    }
+    format!("{:016x}", hash)
+}
+
+/// POST a JSON body and return the response text, mapping transport and HTTP failures
+pub(crate) async fn post_json(
+    request: reqwest::RequestBuilder,
+    body: &serde_json::Value,
+    provider: &str,
+) -> Result<String, SpecGenError> {
+    let response = send_json(request.timeout(REQUEST_TIMEOUT), body, provider).await?;
+
+    let text = response.text().await.map_err(|e| {
+        warn!("Failed to get response text: {}", e);
+        if e.is_timeout() {
+            return SpecGenError::Timeout(e.to_string());
+        }
+        SpecGenError::ParseError(e.to_string())
+    })?;
+
+    info!("Successfully received response from {} API", provider);
+    debug!("Response length: {} characters", text.len());
+    Ok(text)
+}
+
+/// POST a JSON body asking for a server-sent event stream and assemble the completion
+///
+/// Each event goes through `parse_event`. Servers that answer with a plain JSON body
+/// instead, as some proxies do, are handled by `parse_body`.
+pub(crate) async fn post_streaming(
+    request: reqwest::RequestBuilder,
+    body: &serde_json::Value,
+    provider: &str,
+    stream: &StreamContext,
+    parse_event: fn(&stream::SseEvent) -> Result<stream::StreamDelta, SpecGenError>,
+    parse_body: fn(&str) -> Result<LlmResponse, SpecGenError>,
+) -> Result<LlmResponse, SpecGenError> {
+    let cancel = stream.cancel_flag();
+    let request = request.header(reqwest::header::ACCEPT, "text/event-stream");
+    let mut response = cancel.run(send_json(request, body, provider)).await?;
+
+    let is_event_stream = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/event-stream"));
+    if !is_event_stream {
+        debug!("{} answered a streaming request without an event stream", provider);
+        let text = cancel
+            .run(async {
+                tokio::time::timeout(REQUEST_TIMEOUT, response.text())
+                    .await
+                    .map_err(|_| SpecGenError::Timeout(format!("{} response body", provider)))?
+                    .map_err(|e| SpecGenError::ParseError(e.to_string()))
+            })
+            .await?;
+        let parsed = parse_body(&text)?;
+        stream.emit(&parsed.content);
+        return Ok(parsed);
+    }
+
+    let mut parser = stream::SseParser::new();
+    let mut collector = stream::StreamCollector::new();
+    loop {
+        let chunk = cancel
+            .run(async {
+                tokio::time::timeout(STREAM_IDLE_TIMEOUT, response.chunk())
+                    .await
+                    .map_err(|_| {
+                        SpecGenError::Timeout(format!(
+                            "No data from {} API for {:?}",
+                            provider, STREAM_IDLE_TIMEOUT
+                        ))
+                    })?
+                    .map_err(|e| {
+                        SpecGenError::NetworkError(format!(
+                            "{} stream interrupted: {}",
+                            provider, e
+                        ))
+                    })
+            })
+            .await?;
+        let Some(chunk) = chunk else {
+            break;
+        };
+        for event in parser.feed(&chunk) {
+            collector.apply(parse_event(&event)?, stream);
+        }
+    }
+    if let Some(event) = parser.finish() {
+        collector.apply(parse_event(&event)?, stream);
+    }
+
+    if !collector.is_done() {
+        return Err(SpecGenError::NetworkError(format!(
+            "{} stream ended before the completion finished",
+            provider
+        )));
+    }
+    info!("Successfully streamed response from {} API", provider);
+    Ok(collector.into_response())
+}
+
+/// Send a JSON POST and turn transport failures and non-2xx statuses into errors
+async fn send_json(
+    request: reqwest::RequestBuilder,
+    body: &serde_json::Value,
+    provider: &str,
+) -> Result<reqwest::Response, SpecGenError> {
+    debug!("Sending request to {} API", provider);
+
+    let response = request
+        .header("Content-Type", "application/json")
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| {
+            let error_msg = format!("Network error when calling {} API: {}", provider, e);
+            warn!("{}", error_msg);
+            if e.is_timeout() {
+                return SpecGenError::Timeout(error_msg);
+            }
+            if e.is_connect() {
+                warn!("Connection error - check network connectivity");
+            }
+            SpecGenError::NetworkError(error_msg)
+        })?;
+
+    if !response.status().is_success() {
+        let status = response.status().as_u16();
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(retry::parse_retry_after);
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Failed to get error message".to_string());
+
+        warn!("API error: HTTP {} - {}", status, error_text);
+        return Err(SpecGenError::from_status(status, retry_after, error_text));
+    }
+
+    Ok(response)
+}
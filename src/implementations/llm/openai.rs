@@ -0,0 +1,248 @@
+use async_trait::async_trait;
+use log::{debug, warn};
+use serde::Serialize;
+
+use crate::implementations::llm::stream::{SseEvent, StreamDelta};
+use crate::implementations::llm::{
+    post_json, post_streaming, LlmClient, LlmRequest, LlmResponse, ResponseSchema, StreamContext,
+    TokenUsage,
+};
+use crate::implementations::specification_generator::SpecGenError;
+
+const DEFAULT_ENDPOINT: &str = "https://api.openai.com/v1/chat/completions";
+const DEFAULT_MODEL: &str = "gpt-4o";
+
+/// Chat completions request shared by OpenAI and the providers that copy its API
+#[derive(Debug, Serialize)]
+pub(crate) struct ChatRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage<'a>>,
+    temperature: f32,
+    max_tokens: usize,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<StreamOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
+    #[serde(skip)]
+    response_schema: Option<&'a ResponseSchema>,
+}
+
+#[derive(Debug, Serialize)]
+struct StreamOptions {
+    include_usage: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+impl<'a> ChatRequest<'a> {
+    pub(crate) fn new(model: &'a str, request: &'a LlmRequest) -> Self {
+        Self {
+            model,
+            messages: vec![
+                ChatMessage {
+                    role: "system",
+                    content: &request.system,
+                },
+                ChatMessage {
+                    role: "user",
+                    content: &request.prompt,
+                }
+            ],
+            temperature: request.temperature,
+            max_tokens: request.max_tokens,
+            stream: false,
+            stream_options: None,
+            tools: None,
+            tool_choice: None,
+            response_format: None,
+            response_schema: request.response_schema.as_ref(),
+        }
+    }
+
+    /// Force a call to a function whose parameters follow the request's response schema
+    pub(crate) fn function_call(mut self) -> Self {
+        if let Some(schema) = self.response_schema {
+            self.tools = Some(serde_json::json!([
+                {
+                    "type": "function",
+                    "function": {
+                        "name": schema.name,
+                        "description": schema.description,
+                        "parameters": schema.schema
+                    }
+                }
+            ]));
+            self.tool_choice = Some(serde_json::json!({
+                "type": "function",
+                "function": { "name": schema.name }
+            }));
+        }
+        self
+    }
+
+    /// Ask for a JSON object when the request has a response schema, for servers without
+    /// function calling; the schema itself only appears in the prompt
+    pub(crate) fn json_mode(mut self) -> Self {
+        if self.response_schema.is_some() {
+            self.response_format = Some(serde_json::json!({ "type": "json_object" }));
+        }
+        self
+    }
+
+    /// Ask for a server-sent event stream that ends with a usage report
+    pub(crate) fn streaming(mut self) -> Self {
+        self.stream = true;
+        self.stream_options = Some(StreamOptions { include_usage: true });
+        self
+    }
+
+    pub(crate) fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+}
+
+/// Client for the OpenAI chat completions API
+#[derive(Clone)]
+pub struct OpenAiClient {
+    http_client: reqwest::Client,
+    api_key: String,
+    endpoint: String,
+    model: String,
+}
+
+impl OpenAiClient {
+    pub fn new(http_client: reqwest::Client, api_key: impl Into<String>) -> Self {
+        Self {
+            http_client,
+            api_key: api_key.into(),
+            endpoint: DEFAULT_ENDPOINT.to_string(),
+            model: DEFAULT_MODEL.to_string(),
+        }
+    }
+
+    /// Send requests to a different model
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// Send requests to a different endpoint, e.g. a proxy
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into();
+        self
+    }
+}
+
+#[async_trait]
+impl LlmClient for OpenAiClient {
+    fn provider(&self) -> &str {
+        "openai"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn complete(&self, request: &LlmRequest) -> Result<LlmResponse, SpecGenError> {
+        let body = ChatRequest::new(&self.model, request).function_call().to_json();
+        let http_request = self.http_client.post(&self.endpoint).bearer_auth(&self.api_key);
+
+        let text = post_json(http_request, &body, self.provider()).await?;
+        chat_response(&text)
+    }
+
+    async fn complete_streaming(
+        &self,
+        request: &LlmRequest,
+        stream: &StreamContext,
+    ) -> Result<LlmResponse, SpecGenError> {
+        let body = ChatRequest::new(&self.model, request).function_call().streaming().to_json();
+        let http_request = self.http_client.post(&self.endpoint).bearer_auth(&self.api_key);
+
+        post_streaming(
+            http_request,
+            &body,
+            self.provider(),
+            stream,
+            parse_chat_stream_event,
+            chat_response,
+        )
+        .await
+    }
+}
+
+fn chat_response(text: &str) -> Result<LlmResponse, SpecGenError> {
+    Ok(LlmResponse::text(parse_chat_response(text)?).with_usage(parse_chat_usage(text)))
+}
+
+/// Extract the first choice's message from a chat completions response body
+///
+/// For a function call, the message is the call's JSON arguments.
+pub fn parse_chat_response(text: &str) -> Result<String, SpecGenError> {
+    let response: serde_json::Value = serde_json::from_str(text).map_err(|e| {
+        warn!("Failed to parse response as JSON: {}", e);
+        SpecGenError::ParseError(format!("Invalid JSON response: {}", e))
+    })?;
+
+    let choices = response["choices"]
+        .as_array()
+        .ok_or_else(|| SpecGenError::ParseError("Missing choices in API response".to_string()))?;
+    let first = choices
+        .first()
+        .ok_or_else(|| SpecGenError::ApiError("No response from API".to_string()))?;
+    let function_arguments = first["message"]["tool_calls"][0]["function"]["arguments"].as_str();
+    let content = function_arguments.or(first["message"]["content"].as_str()).ok_or_else(|| {
+        SpecGenError::ParseError("Missing message content in API response".to_string())
+    })?;
+
+    debug!("Response content length: {} characters", content.len());
+    Ok(content.to_string())
+}
+
+/// Token counts from the `usage` object of a chat completions response body
+pub fn parse_chat_usage(text: &str) -> Option<TokenUsage> {
+    let response: serde_json::Value = serde_json::from_str(text).ok()?;
+    let usage = &response["usage"];
+    Some(TokenUsage::new(
+        usage["prompt_tokens"].as_u64()?,
+        usage["completion_tokens"].as_u64()?,
+    ))
+}
+
+/// Interpret one event of a streaming chat completions response
+pub fn parse_chat_stream_event(event: &SseEvent) -> Result<StreamDelta, SpecGenError> {
+    if event.data.trim() == "[DONE]" {
+        return Ok(StreamDelta {
+            done: true,
+            ..StreamDelta::default()
+        });
+    }
+    if event.data.is_empty() {
+        return Ok(StreamDelta::default());
+    }
+    let data: serde_json::Value = serde_json::from_str(&event.data)?;
+    if let Some(message) = data["error"]["message"].as_str() {
+        return Err(SpecGenError::ApiError(message.to_string()));
+    }
+
+    let choice = &data["choices"][0];
+    let function_arguments = choice["delta"]["tool_calls"][0]["function"]["arguments"].as_str();
+    Ok(StreamDelta {
+        text: function_arguments
+            .or(choice["delta"]["content"].as_str())
+            .map(|text| text.to_string()),
+        prompt_tokens: data["usage"]["prompt_tokens"].as_u64(),
+        completion_tokens: data["usage"]["completion_tokens"].as_u64(),
+        done: choice["finish_reason"].is_string(),
+    })
+}
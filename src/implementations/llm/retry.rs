@@ -0,0 +1,175 @@
+use std::collections::hash_map::RandomState;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::RecoverableError;
+use crate::implementations::llm::{LlmClient, LlmRequest, LlmResponse, StreamContext};
+use crate::implementations::specification_generator::SpecGenError;
+
+/// How failed LLM calls are retried
+///
+/// Only errors that `RecoverableError::is_recoverable` accepts (rate limits, 5xx,
+/// timeouts and connection failures) are retried. The delay doubles after each
+/// attempt with jitter, unless the provider sent a `retry-after` header, which is
+/// honoured as long as it fits in the remaining `max_total_wait_ms` budget.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Total attempts including the first; 1 disables retries
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub initial_backoff_ms: u64,
+    /// Upper bound for a single backoff delay
+    pub max_backoff_ms: u64,
+    /// Upper bound for the time spent waiting across all retries of one call
+    pub max_total_wait_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            initial_backoff_ms: 1_000,
+            max_backoff_ms: 30_000,
+            max_total_wait_ms: 120_000,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy that never retries
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Exponential backoff before retry number `retry` (starting at 1), with equal jitter
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exponential = self
+            .initial_backoff_ms
+            .saturating_mul(1u64 << retry.saturating_sub(1).min(32))
+            .min(self.max_backoff_ms);
+        let half = exponential / 2;
+        Duration::from_millis(half + random_below(exponential - half + 1))
+    }
+
+    /// Delay before retrying after `error`, preferring the server's `retry-after`
+    pub fn delay(&self, retry: u32, error: &SpecGenError) -> Duration {
+        error.retry_after().unwrap_or_else(|| self.backoff(retry))
+    }
+}
+
+/// Random number in `0..bound` without pulling in an RNG crate
+fn random_below(bound: u64) -> u64 {
+    if bound == 0 {
+        return 0;
+    }
+    RandomState::new().build_hasher().finish() % bound
+}
+
+/// Parse a `retry-after` header given as delay seconds or an HTTP date
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<f64>() {
+        return (seconds >= 0.0).then(|| Duration::from_secs_f64(seconds));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let wait = date.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    Some(wait.to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Client that retries recoverable failures of another client according to a `RetryPolicy`
+pub struct RetryingClient {
+    inner: Arc<dyn LlmClient>,
+    policy: RetryPolicy,
+}
+
+impl RetryingClient {
+    pub fn new(inner: Arc<dyn LlmClient>, policy: RetryPolicy) -> Self {
+        Self { inner, policy }
+    }
+
+    /// Run `call` until it succeeds, fails for good, or the policy gives up
+    async fn with_retries<F, Fut>(&self, call: F) -> Result<LlmResponse, SpecGenError>
+    where
+        F: Fn() -> Fut + Send + Sync,
+        Fut: Future<Output = Result<LlmResponse, SpecGenError>> + Send,
+    {
+        let budget = Duration::from_millis(self.policy.max_total_wait_ms);
+        let mut waited = Duration::ZERO;
+        let mut attempt = 1;
+
+        loop {
+            let error = match call().await {
+                Ok(response) => {
+                    return Ok(response);
+                }
+                Err(e) => e,
+            };
+
+            if !error.is_recoverable() {
+                return Err(error);
+            }
+            if attempt >= self.policy.max_attempts {
+                warn!("Giving up on {} after {} attempts: {}", self.provider(), attempt, error);
+                return Err(error);
+            }
+
+            let delay = self.policy.delay(attempt, &error);
+            if waited + delay > budget {
+                warn!(
+                    "Giving up on {}: waiting {:?} more would exceed the {:?} retry budget ({})",
+                    self.provider(),
+                    delay,
+                    budget,
+                    error
+                );
+                return Err(error);
+            }
+
+            warn!(
+                "{} request failed ({}), retrying in {:?} (attempt {} of {})",
+                self.provider(),
+                error,
+                delay,
+                attempt + 1,
+                self.policy.max_attempts
+            );
+            tokio::time::sleep(delay).await;
+            waited += delay;
+            attempt += 1;
+        }
+    }
+}
+
+#[async_trait]
+impl LlmClient for RetryingClient {
+    fn provider(&self) -> &str {
+        self.inner.provider()
+    }
+
+    fn model(&self) -> &str {
+        self.inner.model()
+    }
+
+    async fn complete(&self, request: &LlmRequest) -> Result<LlmResponse, SpecGenError> {
+        self.with_retries(|| self.inner.complete(request)).await
+    }
+
+    async fn complete_streaming(
+        &self,
+        request: &LlmRequest,
+        stream: &StreamContext,
+    ) -> Result<LlmResponse, SpecGenError> {
+        self.with_retries(|| self.inner.complete_streaming(request, stream)).await
+    }
+}
@@ -0,0 +1,237 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::implementations::llm::{LlmResponse, TokenUsage};
+use crate::implementations::specification_generator::SpecGenError;
+
+/// How often a pending call checks whether it was cancelled
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Shared flag that stops in-flight and future LLM calls, e.g. on Ctrl-C
+#[derive(Debug, Clone, Default)]
+pub struct CancelFlag(Arc<AtomicBool>);
+
+impl CancelFlag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Resolves once the flag is set
+    pub async fn cancelled(&self) {
+        while !self.is_cancelled() {
+            tokio::time::sleep(CANCEL_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Run `future` unless the flag is set first, in which case it is dropped
+    pub async fn run<T>(
+        &self,
+        future: impl Future<Output = Result<T, SpecGenError>>,
+    ) -> Result<T, SpecGenError> {
+        if self.is_cancelled() {
+            return Err(SpecGenError::Cancelled);
+        }
+        tokio::select! {
+            result = future => result,
+            _ = self.cancelled() => Err(SpecGenError::Cancelled),
+        }
+    }
+}
+
+/// Receives the text of streaming completions as it arrives
+pub trait StreamObserver: Send + Sync {
+    /// A completion request is about to be sent
+    fn on_start(&self, _provider: &str, _model: &str) {}
+
+    /// More text arrived
+    fn on_text(&self, text: &str);
+
+    /// The completion finished, successfully or not
+    fn on_finish(&self) {}
+}
+
+/// Where streamed text goes and how streaming calls are cancelled; clones share both
+#[derive(Clone, Default)]
+pub struct StreamContext {
+    observer: Option<Arc<dyn StreamObserver>>,
+    cancel: CancelFlag,
+}
+
+impl StreamContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_observer(mut self, observer: Arc<dyn StreamObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    pub fn with_cancel_flag(mut self, cancel: CancelFlag) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    pub fn cancel_flag(&self) -> &CancelFlag {
+        &self.cancel
+    }
+
+    pub fn observer(&self) -> Option<&Arc<dyn StreamObserver>> {
+        self.observer.as_ref()
+    }
+
+    /// Pass text on to the observer, if any
+    pub fn emit(&self, text: &str) {
+        if let Some(observer) = &self.observer {
+            if !text.is_empty() {
+                observer.on_text(text);
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for StreamContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamContext")
+            .field("observer", &self.observer.is_some())
+            .field("cancel", &self.cancel)
+            .finish()
+    }
+}
+
+/// One server-sent event
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SseEvent {
+    /// Value of the `event:` field, if sent
+    pub event: Option<String>,
+    /// `data:` lines joined with newlines
+    pub data: String,
+}
+
+/// Incremental `text/event-stream` parser that copes with events split across chunks
+#[derive(Debug, Default)]
+pub struct SseParser {
+    pending: Vec<u8>,
+    event: Option<String>,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next chunk of the body and return the events it completes
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.pending.extend_from_slice(chunk);
+
+        let mut events = Vec::new();
+        while let Some(newline) = self.pending.iter().position(|&byte| byte == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=newline).collect();
+            let line = String::from_utf8_lossy(&line);
+            if let Some(event) = self.process_line(line.trim_end_matches(['\n', '\r'])) {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    /// Event left over when the body ends without a trailing blank line
+    pub fn finish(&mut self) -> Option<SseEvent> {
+        if !self.pending.is_empty() {
+            let line = String::from_utf8_lossy(&std::mem::take(&mut self.pending)).to_string();
+            if let Some(event) = self.process_line(line.trim_end_matches('\r')) {
+                return Some(event);
+            }
+        }
+        self.dispatch()
+    }
+
+    fn process_line(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "event" => {
+                self.event = Some(value.to_string());
+            }
+            "data" => self.data.push(value.to_string()),
+            _ => {}
+        }
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        if self.data.is_empty() && self.event.is_none() {
+            return None;
+        }
+        Some(SseEvent {
+            event: self.event.take(),
+            data: std::mem::take(&mut self.data).join("\n"),
+        })
+    }
+}
+
+/// What one provider event contributes to a streaming completion
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StreamDelta {
+    pub text: Option<String>,
+    pub prompt_tokens: Option<u64>,
+    pub completion_tokens: Option<u64>,
+    /// The provider signalled the end of the completion
+    pub done: bool,
+}
+
+/// Assembles a response from stream deltas
+#[derive(Debug, Default)]
+pub struct StreamCollector {
+    content: String,
+    prompt_tokens: Option<u64>,
+    completion_tokens: Option<u64>,
+    done: bool,
+}
+
+impl StreamCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a delta, forwarding its text to the stream's observer
+    pub fn apply(&mut self, delta: StreamDelta, stream: &StreamContext) {
+        if let Some(text) = delta.text {
+            stream.emit(&text);
+            self.content.push_str(&text);
+        }
+        self.prompt_tokens = delta.prompt_tokens.or(self.prompt_tokens);
+        self.completion_tokens = delta.completion_tokens.or(self.completion_tokens);
+        self.done |= delta.done;
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    pub fn into_response(self) -> LlmResponse {
+        let usage = match (self.prompt_tokens, self.completion_tokens) {
+            (Some(prompt), Some(completion)) => Some(TokenUsage::new(prompt, completion)),
+            _ => None,
+        };
+        LlmResponse::text(self.content).with_usage(usage)
+    }
+}
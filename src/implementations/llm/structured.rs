@@ -0,0 +1,306 @@
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::implementations::llm::ResponseSchema;
+use crate::models::common::VerificationLanguage;
+use crate::models::specification::{
+    IssueSeverity, SpecificationTranslation, ValidationIssue, ValidationReport, VerificationTemplate,
+};
+
+/// Translations below this confidence are flagged for human review
+const REVIEW_CONFIDENCE: f32 = 0.8;
+
+/// JSON answer the LLM is asked for instead of free text
+///
+/// Providers with tool use or function calling are forced to call a tool named
+/// `NAME` whose parameters follow `json_schema()`; the others get JSON mode. Either
+/// way the schema is also spelled out in the prompt.
+pub trait StructuredResponse: DeserializeOwned {
+    /// Tool or function name, in snake case
+    const NAME: &'static str;
+    /// What the tool is for, shown to the model
+    const DESCRIPTION: &'static str;
+
+    fn json_schema() -> serde_json::Value;
+
+    /// Checks serde cannot express, such as value ranges
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn response_schema() -> ResponseSchema {
+        ResponseSchema {
+            name: Self::NAME.to_string(),
+            description: Self::DESCRIPTION.to_string(),
+            schema: Self::json_schema(),
+        }
+    }
+}
+
+/// Append the schema to a prompt so that providers without tool use know the expected shape
+pub fn structured_prompt(prompt: &str, schema: &ResponseSchema) -> String {
+    format!(
+        "{}\n\nRespond with a single JSON object, and nothing else, that matches this JSON schema:\n{}",
+        prompt,
+        serde_json::to_string_pretty(&schema.schema).unwrap_or_default()
+    )
+}
+
+/// Prompt for the one retry after a response did not match the schema
+pub fn reask_prompt(prompt: &str, response: &str, error: &str) -> String {
+    format!(
+        "{}\n\nYour previous answer was:\n{}\n\nIt does not match the schema: {}\n\
+        Answer again with only a JSON object that matches the schema.",
+        prompt, response, error
+    )
+}
+
+/// Deserialize and validate a structured response, describing what is wrong if it does not fit
+pub fn parse_structured<T: StructuredResponse>(text: &str) -> Result<T, String> {
+    let json = extract_json(text).ok_or_else(|| "no JSON object found".to_string())?;
+    let value: T = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    value.validate()?;
+    Ok(value)
+}
+
+/// The outermost JSON object in a response, ignoring code fences and surrounding prose
+fn extract_json(text: &str) -> Option<&str> {
+    let start = text.find('{')?;
+    let end = text.rfind('}')?;
+    (start < end).then(|| &text[start..=end])
+}
+
+/// Formal properties extracted from natural language requirements
+#[derive(Debug, Clone, Deserialize)]
+pub struct TranslationList {
+    pub translations: Vec<TranslatedRequirement>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TranslatedRequirement {
+    pub requirement: String,
+    pub interpreted_properties: Vec<String>,
+    pub formal_representation: String,
+    pub translation_confidence: f32,
+}
+
+impl TranslatedRequirement {
+    pub fn into_translation(self, language: VerificationLanguage) -> SpecificationTranslation {
+        SpecificationTranslation {
+            requires_human_review: self.translation_confidence < REVIEW_CONFIDENCE,
+            requirement: self.requirement,
+            interpreted_properties: self.interpreted_properties,
+            formal_representation: self.formal_representation,
+            translation_confidence: self.translation_confidence,
+            verification_language: language,
+        }
+    }
+}
+
+impl StructuredResponse for TranslationList {
+    const NAME: &'static str = "record_translations";
+    const DESCRIPTION: &'static str = "Record the formal properties extracted from each requirement";
+
+    fn json_schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "translations": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "requirement": { "type": "string", "description": "Original requirement text" },
+                            "interpreted_properties": {
+                                "type": "array",
+                                "items": { "type": "string" },
+                                "description": "The requirement interpreted as formal properties"
+                            },
+                            "formal_representation": { "type": "string", "description": "The properties in mathematical notation" },
+                            "translation_confidence": { "type": "number", "minimum": 0, "maximum": 1 }
+                        },
+                        "required": ["requirement", "interpreted_properties", "formal_representation", "translation_confidence"]
+                    }
+                }
+            },
+            "required": ["translations"]
+        })
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        for translation in &self.translations {
+            if !(0.0..=1.0).contains(&translation.translation_confidence) {
+                return Err(format!(
+                    "translation_confidence {} of \"{}\" is not between 0 and 1",
+                    translation.translation_confidence, translation.requirement
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parameterized specification templates
+#[derive(Debug, Clone, Deserialize)]
+pub struct TemplateList {
+    pub templates: Vec<TemplateEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TemplateEntry {
+    pub template_name: String,
+    pub template_code: String,
+    pub placeholders: Vec<String>,
+    pub documentation: String,
+}
+
+impl TemplateEntry {
+    pub fn into_template(self, language: VerificationLanguage) -> VerificationTemplate {
+        VerificationTemplate {
+            language,
+            template_name: self.template_name,
+            template_code: self.template_code,
+            placeholders: self.placeholders,
+            documentation: self.documentation,
+        }
+    }
+}
+
+impl StructuredResponse for TemplateList {
+    const NAME: &'static str = "record_templates";
+    const DESCRIPTION: &'static str = "Record parameterized specification templates";
+
+    fn json_schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "templates": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "template_name": { "type": "string", "description": "Name describing the template's purpose" },
+                            "template_code": { "type": "string", "description": "Complete template code, without code fences" },
+                            "placeholders": {
+                                "type": "array",
+                                "items": { "type": "string" },
+                                "description": "Placeholders that need to be filled in"
+                            },
+                            "documentation": { "type": "string", "description": "How to use the template" }
+                        },
+                        "required": ["template_name", "template_code", "placeholders", "documentation"]
+                    }
+                }
+            },
+            "required": ["templates"]
+        })
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        match self.templates.iter().find(|template| template.template_code.trim().is_empty()) {
+            Some(template) => Err(format!("template \"{}\" has no template_code", template.template_name)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Severity as the model reports it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportedSeverity {
+    #[serde(alias = "Error")]
+    Error,
+    #[serde(alias = "Warning")]
+    Warning,
+    #[serde(alias = "Info")]
+    Info,
+}
+
+impl From<ReportedSeverity> for IssueSeverity {
+    fn from(severity: ReportedSeverity) -> Self {
+        match severity {
+            ReportedSeverity::Error => IssueSeverity::Error,
+            ReportedSeverity::Warning => IssueSeverity::Warning,
+            ReportedSeverity::Info => IssueSeverity::Info,
+        }
+    }
+}
+
+/// LLM judgment of a specification, for validation without a verification tool
+#[derive(Debug, Clone, Deserialize)]
+pub struct ValidationVerdict {
+    pub is_valid: bool,
+    #[serde(default)]
+    pub issues: Vec<ReportedIssue>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReportedIssue {
+    #[serde(default)]
+    pub line_number: Option<usize>,
+    pub message: String,
+    pub severity: ReportedSeverity,
+    #[serde(default)]
+    pub suggested_fix: Option<String>,
+}
+
+impl ReportedIssue {
+    pub fn into_issue(self) -> ValidationIssue {
+        ValidationIssue {
+            severity: self.severity.into(),
+            message: self.message,
+            related_property: None,
+            line_number: self.line_number,
+            span: None,
+            error_code: None,
+            suggested_fix: self.suggested_fix,
+        }
+    }
+}
+
+impl ValidationVerdict {
+    pub fn into_report(self) -> ValidationReport {
+        ValidationReport {
+            is_valid: self.is_valid,
+            issues: self.issues.into_iter().map(ReportedIssue::into_issue).collect(),
+            tool_validated: false,
+            tool_output: None,
+        }
+    }
+}
+
+impl StructuredResponse for ValidationVerdict {
+    const NAME: &'static str = "report_validation";
+    const DESCRIPTION: &'static str = "Report the issues found in a specification and whether it is valid";
+
+    fn json_schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "is_valid": { "type": "boolean", "description": "Final judgment on the specification" },
+                "issues": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "line_number": { "type": ["integer", "null"], "minimum": 1 },
+                            "message": { "type": "string", "description": "Description of the issue" },
+                            "severity": { "type": "string", "enum": ["error", "warning", "info"] },
+                            "suggested_fix": { "type": ["string", "null"] }
+                        },
+                        "required": ["message", "severity"]
+                    }
+                }
+            },
+            "required": ["is_valid", "issues"]
+        })
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.is_valid && self.issues.iter().any(|issue| issue.severity == ReportedSeverity::Error) {
+            return Err("is_valid is true although an issue has severity \"error\"".to_string());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::implementations::specification_generator::SpecGenError;
+
+/// Tokens consumed by one LLM call
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+}
+
+impl TokenUsage {
+    pub fn new(prompt_tokens: u64, completion_tokens: u64) -> Self {
+        Self {
+            prompt_tokens,
+            completion_tokens,
+        }
+    }
+
+    /// Rough count for providers that do not report usage: about four characters per token
+    pub fn estimate(prompt: &str, completion: &str) -> Self {
+        let tokens = |text: &str| text.chars().count().div_ceil(4) as u64;
+        Self::new(tokens(prompt), tokens(completion))
+    }
+
+    pub fn total(&self) -> u64 {
+        self.prompt_tokens + self.completion_tokens
+    }
+}
+
+/// Price of a model in USD per million tokens
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub input_per_mtok: f64,
+    pub output_per_mtok: f64,
+}
+
+impl ModelPrice {
+    pub const fn new(input_per_mtok: f64, output_per_mtok: f64) -> Self {
+        Self {
+            input_per_mtok,
+            output_per_mtok,
+        }
+    }
+
+    /// Cost of the given usage in USD
+    pub fn cost(&self, usage: &TokenUsage) -> f64 {
+        (usage.prompt_tokens as f64 * self.input_per_mtok
+            + usage.completion_tokens as f64 * self.output_per_mtok)
+            / 1_000_000.0
+    }
+}
+
+/// List prices of the models the built-in providers default to, matched by prefix
+const KNOWN_PRICES: &[(&str, ModelPrice)] = &[
+    ("claude-3-opus", ModelPrice::new(15.0, 75.0)),
+    ("claude-3-sonnet", ModelPrice::new(3.0, 15.0)),
+    ("claude-3-5-sonnet", ModelPrice::new(3.0, 15.0)),
+    ("claude-3-haiku", ModelPrice::new(0.25, 1.25)),
+    ("gpt-4o-mini", ModelPrice::new(0.15, 0.6)),
+    ("gpt-4o", ModelPrice::new(2.5, 10.0)),
+    ("gpt-4-turbo", ModelPrice::new(10.0, 30.0)),
+    ("gpt-4", ModelPrice::new(30.0, 60.0)),
+    ("gpt-3.5-turbo", ModelPrice::new(0.5, 1.5)),
+    ("mistral-large", ModelPrice::new(2.0, 6.0)),
+    ("mistral-small", ModelPrice::new(0.2, 0.6)),
+    ("meta-llama/Llama-3-70b", ModelPrice::new(0.9, 0.9)),
+];
+
+/// Price for a model: a configured override by exact name, else the longest matching built-in prefix
+pub fn price_for(model: &str, overrides: &HashMap<String, ModelPrice>) -> Option<ModelPrice> {
+    if let Some(price) = overrides.get(model) {
+        return Some(*price);
+    }
+    KNOWN_PRICES.iter()
+        .filter(|(prefix, _)| model.starts_with(prefix))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(_, price)| *price)
+}
+
+/// Limits on what a single run may spend on LLM calls
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UsageBudget {
+    /// Abort once the estimated cost exceeds this many USD; calls to unpriced models count as free
+    pub max_cost_usd: Option<f64>,
+    /// Abort once prompt and completion tokens together exceed this
+    pub max_tokens_total: Option<u64>,
+}
+
+/// One recorded LLM call
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LlmCallRecord {
+    pub provider: String,
+    pub model: String,
+    pub usage: TokenUsage,
+    /// Token counts were estimated because the provider did not report them
+    pub estimated: bool,
+    /// Served from the response cache, so nothing was spent
+    pub cached: bool,
+    pub latency_ms: u64,
+    /// `None` when the model has no known price
+    pub cost_usd: Option<f64>,
+}
+
+/// Totals over all LLM calls of a run
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageSummary {
+    pub calls: usize,
+    pub cached_calls: usize,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
+    pub cost_usd: f64,
+    /// Calls whose cost is missing from `cost_usd` because their model has no known price
+    pub unpriced_calls: usize,
+    pub latency_ms: u64,
+    pub records: Vec<LlmCallRecord>,
+}
+
+impl UsageSummary {
+    pub fn from_records(records: Vec<LlmCallRecord>) -> Self {
+        let mut summary = Self::default();
+        for record in &records {
+            summary.calls += 1;
+            summary.cached_calls += usize::from(record.cached);
+            summary.prompt_tokens += record.usage.prompt_tokens;
+            summary.completion_tokens += record.usage.completion_tokens;
+            summary.latency_ms += record.latency_ms;
+            match record.cost_usd {
+                Some(cost) => summary.cost_usd += cost,
+                None => summary.unpriced_calls += 1,
+            }
+        }
+        summary.total_tokens = summary.prompt_tokens + summary.completion_tokens;
+        summary.records = records;
+        summary
+    }
+
+    /// Error if the totals are over the budget
+    pub fn check_budget(&self, budget: &UsageBudget) -> Result<(), SpecGenError> {
+        if let Some(max_tokens) = budget.max_tokens_total {
+            if self.total_tokens > max_tokens {
+                return Err(SpecGenError::BudgetExceeded(format!(
+                    "{} tokens used across {} LLM calls, limit is max_tokens_total = {}",
+                    self.total_tokens, self.calls, max_tokens
+                )));
+            }
+        }
+        if let Some(max_cost) = budget.max_cost_usd {
+            if self.cost_usd > max_cost {
+                return Err(SpecGenError::BudgetExceeded(format!(
+                    "${:.4} spent across {} LLM calls, limit is max_cost_usd = {}",
+                    self.cost_usd, self.calls, max_cost
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for UsageSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} LLM call{}", self.calls, if self.calls == 1 { "" } else { "s" })?;
+        if self.cached_calls > 0 {
+            write!(f, " ({} cached)", self.cached_calls)?;
+        }
+        write!(
+            f,
+            ", {} tokens ({} prompt + {} completion), ${:.4}",
+            self.total_tokens, self.prompt_tokens, self.completion_tokens, self.cost_usd
+        )?;
+        if self.unpriced_calls > 0 {
+            write!(f, " excluding {} unpriced", self.unpriced_calls)?;
+        }
+        write!(f, ", {:.1}s", self.latency_ms as f64 / 1000.0)
+    }
+}
+
+/// Collects the LLM calls of a run; clones share the same records
+#[derive(Debug, Clone, Default)]
+pub struct UsageTracker {
+    records: Arc<Mutex<Vec<LlmCallRecord>>>,
+}
+
+impl UsageTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, record: LlmCallRecord) {
+        self.records.lock().unwrap().push(record);
+    }
+
+    pub fn summary(&self) -> UsageSummary {
+        UsageSummary::from_records(self.records.lock().unwrap().clone())
+    }
+}
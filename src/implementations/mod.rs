@@ -1,2 +1,8 @@
 pub mod specification_generator;
-pub mod config;
\ No newline at end of file
+pub mod implementation_generator;
+pub mod fstar_extraction;
+pub mod adapters;
+pub mod config;
+pub mod backends;
+pub mod llm;
+pub mod prompts;
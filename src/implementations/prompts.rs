@@ -0,0 +1,351 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use log::{debug, info};
+
+use crate::implementations::config::{ConfigError, GeneratorConfig};
+use crate::implementations::specification_generator::SpecGenError;
+
+/// Extension of prompt template files in `templates_dir`
+pub const TEMPLATE_EXTENSION: &str = "txt";
+
+/// A shipped prompt and the placeholders the generator fills in for it
+struct BuiltinPrompt {
+    name: &'static str,
+    placeholders: &'static [&'static str],
+    text: &'static str,
+}
+
+const BUILTIN_PROMPTS: &[BuiltinPrompt] = &[
+    BuiltinPrompt {
+        name: "specification",
+        placeholders: &[
+            "domain",
+            "verification_language",
+            "requirements",
+            "domain_context",
+            "language_guidelines",
+        ],
+        text: include_str!("prompts/specification.txt"),
+    },
+    BuiltinPrompt {
+        name: "refine",
+        placeholders: &["verification_language", "original_spec", "feedback"],
+        text: include_str!("prompts/refine.txt"),
+    },
+    BuiltinPrompt {
+        name: "translate_to_properties",
+        placeholders: &["domain", "requirements"],
+        text: include_str!("prompts/translate_to_properties.txt"),
+    },
+    BuiltinPrompt {
+        name: "convert_to_formal",
+        placeholders: &["verification_language", "paradigm", "properties"],
+        text: include_str!("prompts/convert_to_formal.txt"),
+    },
+    BuiltinPrompt {
+        name: "translate",
+        placeholders: &["source_language", "target_language", "spec_code"],
+        text: include_str!("prompts/translate.txt"),
+    },
+    BuiltinPrompt {
+        name: "completeness",
+        placeholders: &["verification_language", "spec_code", "requirements"],
+        text: include_str!("prompts/completeness.txt"),
+    },
+    BuiltinPrompt {
+        name: "verification_code",
+        placeholders: &["verification_language", "verification_system", "spec_code"],
+        text: include_str!("prompts/verification_code.txt"),
+    },
+    BuiltinPrompt {
+        name: "templates",
+        placeholders: &["domain", "verification_language"],
+        text: include_str!("prompts/templates.txt"),
+    },
+    BuiltinPrompt {
+        name: "apply_template",
+        placeholders: &[
+            "template_name",
+            "template_code",
+            "properties",
+            "verification_language",
+        ],
+        text: include_str!("prompts/apply_template.txt"),
+    },
+    BuiltinPrompt {
+        name: "import",
+        placeholders: &["verification_language", "spec_code"],
+        text: include_str!("prompts/import.txt"),
+    },
+    BuiltinPrompt {
+        name: "fix",
+        placeholders: &[
+            "spec_code",
+            "issues",
+            "specific_fixes",
+            "language_guidelines",
+            "verification_language",
+        ],
+        text: include_str!("prompts/fix.txt"),
+    },
+    BuiltinPrompt {
+        name: "implementation",
+        placeholders: &[
+            "target_language",
+            "verification_language",
+            "spec_code",
+            "optimization",
+            "comments",
+            "style_guide",
+        ],
+        text: include_str!("prompts/implementation.txt"),
+    },
+    BuiltinPrompt {
+        name: "refine_implementation",
+        placeholders: &[
+            "target_language",
+            "verification_language",
+            "spec_code",
+            "source_code",
+            "verification_status",
+            "failures",
+            "counterexamples",
+        ],
+        text: include_str!("prompts/refine_implementation.txt"),
+    },
+    BuiltinPrompt {
+        name: "validate_syntax",
+        placeholders: &["verification_language", "spec_code"],
+        text: include_str!("prompts/validate_syntax.txt"),
+    },
+    BuiltinPrompt {
+        name: "validate_type_checking",
+        placeholders: &["verification_language", "spec_code"],
+        text: include_str!("prompts/validate_type_checking.txt"),
+    },
+    BuiltinPrompt {
+        name: "validate_formal_verification",
+        placeholders: &["verification_language", "spec_code"],
+        text: include_str!("prompts/validate_formal_verification.txt"),
+    },
+];
+
+/// Where the active version of a prompt template came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateSource {
+    Builtin,
+    File(PathBuf),
+    /// `prompt_templates` in the configuration file
+    Config,
+}
+
+impl fmt::Display for TemplateSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateSource::Builtin => write!(f, "built-in"),
+            TemplateSource::File(path) => write!(f, "{}", path.display()),
+            TemplateSource::Config => write!(f, "prompt_templates"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PromptTemplate {
+    pub text: String,
+    pub source: TemplateSource,
+}
+
+/// The prompts sent to the LLM, each a template with `{{placeholders}}`
+///
+/// Starts from the built-in prompts. A `<name>.txt` file in `templates_dir` replaces the
+/// prompt of that name, and an entry in `prompt_templates` replaces both. Overrides may
+/// only use the placeholders the built-in prompt gets, so typos fail at load time rather
+/// than reaching the LLM.
+#[derive(Debug, Clone)]
+pub struct PromptTemplates {
+    templates: HashMap<String, PromptTemplate>,
+}
+
+impl PromptTemplates {
+    /// The shipped prompts without any overrides
+    pub fn builtin() -> Self {
+        let templates = BUILTIN_PROMPTS
+            .iter()
+            .map(|prompt| {
+                (
+                    prompt.name.to_string(),
+                    PromptTemplate {
+                        text: strip_final_newline(prompt.text).to_string(),
+                        source: TemplateSource::Builtin,
+                    },
+                )
+            })
+            .collect();
+        Self { templates }
+    }
+
+    /// Built-in prompts with the overrides from `templates_dir` and `prompt_templates` applied
+    pub fn load(config: &GeneratorConfig) -> Result<Self, ConfigError> {
+        let mut templates = Self::builtin();
+
+        if let Some(dir) = &config.templates_dir {
+            for (name, path) in template_files(Path::new(dir))? {
+                let text = std::fs::read_to_string(&path)?;
+                templates.set(&name, &text, TemplateSource::File(path))?;
+            }
+        }
+        for (name, text) in &config.prompt_templates {
+            templates.set(name, text, TemplateSource::Config)?;
+        }
+
+        Ok(templates)
+    }
+
+    /// Replace a prompt, checking that it exists and only uses its own placeholders
+    pub fn set(
+        &mut self,
+        name: &str,
+        text: &str,
+        source: TemplateSource,
+    ) -> Result<(), ConfigError> {
+        let builtin = BUILTIN_PROMPTS
+            .iter()
+            .find(|prompt| prompt.name == name)
+            .ok_or_else(|| {
+                ConfigError::InvalidSetting(format!(
+                    "unknown prompt template '{}' in {}; expected one of: {}",
+                    name,
+                    source,
+                    Self::names().collect::<Vec<_>>().join(", ")
+                ))
+            })?;
+
+        if let Some(undefined) = placeholders(text).find(|p| !builtin.placeholders.contains(p)) {
+            return Err(ConfigError::InvalidSetting(format!(
+                "prompt template '{}' in {} uses undefined placeholder {{{{{}}}}}; available: {}",
+                name,
+                source,
+                undefined,
+                builtin.placeholders.join(", ")
+            )));
+        }
+
+        if source != TemplateSource::Builtin {
+            info!("Using prompt template '{}' from {}", name, source);
+        }
+        self.templates.insert(
+            name.to_string(),
+            PromptTemplate {
+                text: strip_final_newline(text).to_string(),
+                source,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&PromptTemplate> {
+        self.templates.get(name)
+    }
+
+    /// Names of all prompts that can be overridden
+    pub fn names() -> impl Iterator<Item = &'static str> {
+        BUILTIN_PROMPTS.iter().map(|prompt| prompt.name)
+    }
+
+    /// Text of a shipped prompt, e.g. to write out as a starting point for overrides
+    pub fn builtin_text(name: &str) -> Option<&'static str> {
+        BUILTIN_PROMPTS
+            .iter()
+            .find(|prompt| prompt.name == name)
+            .map(|prompt| prompt.text)
+    }
+
+    /// Fill in a prompt's placeholders; values are inserted verbatim, never re-expanded
+    pub fn render(
+        &self,
+        name: &str,
+        params: &HashMap<String, String>,
+    ) -> Result<String, SpecGenError> {
+        let template = self
+            .get(name)
+            .ok_or_else(|| SpecGenError::TemplateError(format!("Template not found: {}", name)))?;
+
+        let mut rendered = String::with_capacity(template.text.len());
+        let mut rest = template.text.as_str();
+        while let Some((before, placeholder, after)) = next_placeholder(rest) {
+            let value = params.get(placeholder).ok_or_else(|| {
+                SpecGenError::TemplateError(format!(
+                    "No value for {{{{{}}}}} in template {}",
+                    placeholder, name
+                ))
+            })?;
+            rendered.push_str(before);
+            rendered.push_str(value);
+            rest = after;
+        }
+        rendered.push_str(rest);
+
+        debug!("Rendered prompt template '{}' ({})", name, template.source);
+        Ok(rendered)
+    }
+}
+
+/// `<name>.txt` files in a templates directory, sorted by name
+fn template_files(dir: &Path) -> Result<Vec<(String, PathBuf)>, ConfigError> {
+    if !dir.is_dir() {
+        return Err(ConfigError::InvalidSetting(format!(
+            "templates_dir {} is not a directory",
+            dir.display()
+        )));
+    }
+
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(TEMPLATE_EXTENSION) {
+            continue;
+        }
+        if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+            files.push((name.to_string(), path.clone()));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Names of the `{{placeholders}}` in a template, in order of appearance
+pub fn placeholders(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        let (_, placeholder, after) = next_placeholder(rest)?;
+        rest = after;
+        Some(placeholder)
+    })
+}
+
+/// Split off the text before the next placeholder, its name and the text after it
+///
+/// Braces that do not enclose a plain identifier, as in code samples, are left alone.
+fn next_placeholder(text: &str) -> Option<(&str, &str, &str)> {
+    let mut offset = 0;
+    while let Some(start) = text[offset..].find("{{").map(|i| offset + i) {
+        let inner_start = start + 2;
+        if let Some(len) = text[inner_start..].find("}}") {
+            let name = text[inner_start..inner_start + len].trim();
+            if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return Some((&text[..start], name, &text[inner_start + len + 2..]));
+            }
+        }
+        offset = inner_start;
+    }
+    None
+}
+
+/// Drop the newline most editors put at the end of a file
+fn strip_final_newline(text: &str) -> &str {
+    text.strip_suffix("\r\n")
+        .or_else(|| text.strip_suffix('\n'))
+        .unwrap_or(text)
+}
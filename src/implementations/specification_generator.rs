@@ -1,17 +1,49 @@
 use std::collections::HashMap;
 use std::path::{ Path, PathBuf };
+use std::sync::Arc;
 use async_trait::async_trait;
-use log::{ debug, error, info, warn };
-use serde::{ Deserialize, Serialize };
+use log::{ info, warn };
 use thiserror::Error;
 
-use crate::errors::{ AxiomError, AxiomResult, ErrorContext, ErrorSeverity };
+use crate::errors::{ AxiomError, AxiomResult, ErrorContext, ErrorSeverity, RecoverableError };
+use crate::implementations::backends::{
+    parse_fstar_diagnostics,
+    BackendRegistry,
+    FStarBackend,
+    FStarErrorCategory,
+};
+use crate::implementations::backends::fstar_diagnostics::missing_identifier;
 use crate::implementations::config::{ ConfigError, GeneratorConfig };
-use crate::models::common::{ Domain, SpecificationParadigm, VerificationLanguage };
+use crate::implementations::prompts::PromptTemplates;
+use crate::implementations::llm::{
+    client_from_config,
+    default_http_client,
+    send_recorded,
+    structured::{
+        parse_structured,
+        reask_prompt,
+        structured_prompt,
+        TemplateList,
+        TranslationList,
+        ValidationVerdict,
+    },
+    LlmClient,
+    CancelFlag,
+    LlmRequest,
+    StreamContext,
+    StreamObserver,
+    StructuredResponse,
+    UsageSummary,
+    UsageTracker,
+};
+use crate::models::common::{ Domain, ProofLevel, SpecificationParadigm, VerificationLanguage };
+use crate::models::verification::{ VerificationOptions, VerificationResult, VerificationStatus };
 use crate::models::property::Property;
+use crate::models::schema::to_document_json;
 use crate::models::specification::{
     FormalSpecification,
     Specification,
+    SpecificationCandidate,
     SpecificationOptions,
     SpecificationTranslation,
     ValidationReport,
@@ -19,7 +51,12 @@ use crate::models::specification::{
     VerificationTemplate,
     IssueSeverity,
 };
+use crate::project::Project;
 use crate::traits::specification_generator::{ SpecificationGenerator, ValidationDepth };
+use crate::traits::verification_engine::VerificationBackendAdapter;
+
+/// Confidence in a specification that no tool or LLM has checked
+const UNCHECKED_CONFIDENCE: f32 = 0.9;
 
 #[derive(Debug, Error)]
 pub enum SpecGenError {
@@ -43,6 +80,81 @@ pub enum SpecGenError {
         status: u16,
         message: String,
     },
+
+    #[error("Rate limited: {status} - {message}")] RateLimited {
+        status: u16,
+        retry_after: Option<std::time::Duration>,
+        message: String,
+    },
+
+    #[error("Server error: {status} - {message}")] ServerError {
+        status: u16,
+        retry_after: Option<std::time::Duration>,
+        message: String,
+    },
+
+    #[error("Request timed out: {0}")] Timeout(String),
+
+    #[error("LLM budget exceeded: {0}")] BudgetExceeded(String),
+
+    #[error("LLM request cancelled")] Cancelled,
+}
+
+impl SpecGenError {
+    /// Classify a non-2xx response: 429 and Anthropic's 529 are rate limits, other 5xx are server errors
+    pub fn from_status(
+        status: u16,
+        retry_after: Option<std::time::Duration>,
+        message: String
+    ) -> Self {
+        match status {
+            429 | 529 => SpecGenError::RateLimited { status, retry_after, message },
+            500..=599 => SpecGenError::ServerError { status, retry_after, message },
+            _ => SpecGenError::HttpError { status, message },
+        }
+    }
+
+    /// HTTP status of the failed response, if any
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            SpecGenError::HttpError { status, .. } |
+            SpecGenError::RateLimited { status, .. } |
+            SpecGenError::ServerError { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// How long the server asked us to wait before retrying
+    pub fn retry_after(&self) -> Option<std::time::Duration> {
+        match self {
+            SpecGenError::RateLimited { retry_after, .. } |
+            SpecGenError::ServerError { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+impl RecoverableError for SpecGenError {
+    fn is_recoverable(&self) -> bool {
+        matches!(
+            self,
+            SpecGenError::RateLimited { .. } |
+                SpecGenError::ServerError { .. } |
+                SpecGenError::Timeout(_) |
+                SpecGenError::NetworkError(_)
+        )
+    }
+
+    fn recovery_strategy(&self) -> Option<String> {
+        match self {
+            SpecGenError::RateLimited { retry_after: Some(delay), .. } =>
+                Some(format!("Retry after {:?} as requested by the provider", delay)),
+            SpecGenError::RateLimited { .. } => Some("Retry with exponential backoff".to_string()),
+            SpecGenError::ServerError { .. } | SpecGenError::Timeout(_) | SpecGenError::NetworkError(_) =>
+                Some("Retry with exponential backoff".to_string()),
+            _ => None,
+        }
+    }
 }
 
 impl From<SpecGenError> for AxiomError {
@@ -69,6 +181,19 @@ impl From<SpecGenError> for AxiomError {
                     tool: "HTTP".to_string(),
                     message: format!("Status {}: {}", status, message),
                 },
+            SpecGenError::RateLimited { status, message, .. } |
+            SpecGenError::ServerError { status, message, .. } =>
+                AxiomError::ExternalToolError {
+                    tool: "LLM API".to_string(),
+                    message: format!("Status {}: {}", status, message),
+                },
+            SpecGenError::Timeout(msg) =>
+                AxiomError::ExternalToolError {
+                    tool: "LLM API".to_string(),
+                    message: format!("Request timed out: {}", msg),
+                },
+            SpecGenError::BudgetExceeded(msg) => AxiomError::BudgetExceeded(msg),
+            SpecGenError::Cancelled => AxiomError::Cancelled,
         }
     }
 }
@@ -78,51 +203,9 @@ impl From<SpecGenError> for AxiomError {
 struct DomainContext {
     description: String,
     common_properties: Vec<String>,
-    example_snippets: Vec<String>,
     verification_advice: String,
 }
 
-/// OpenAI API request and response types
-#[derive(Debug, Serialize, Deserialize, Clone)]
-struct ChatMessage {
-    role: String,
-    content: String,
-}
-
-#[derive(Debug, Serialize)]
-struct ChatRequest {
-    model: String,
-    messages: Vec<ChatMessage>,
-    temperature: f32,
-    max_tokens: usize,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    stream: Option<bool>,
-}
-
-#[derive(Debug, Deserialize)]
-struct ChatResponseChoice {
-    message: ChatMessage,
-    finish_reason: String,
-    index: usize,
-}
-
-#[derive(Debug, Deserialize)]
-struct Usage {
-    prompt_tokens: usize,
-    completion_tokens: usize,
-    total_tokens: usize,
-}
-
-#[derive(Debug, Deserialize)]
-struct ChatResponse {
-    id: String,
-    object: String,
-    created: u64,
-    model: String,
-    choices: Vec<ChatResponseChoice>,
-    usage: Usage,
-}
-
 /// Implementation of the SpecificationGenerator trait
 /// LLMSpecificationGenerator uses LLMs to generate and translate formal specifications
 ///
@@ -138,6 +221,11 @@ struct ChatResponse {
 ///    - Mistral: MISTRAL_API_KEY
 ///    - Together: TOGETHER_API_KEY
 ///
+/// No key is needed for a self-hosted OpenAI-compatible or Ollama server: set
+/// `llm_api.provider` to "local" or point `llm_api.api_endpoint` at localhost.
+///
+/// Alternatively, pass any `LlmClient` to `with_llm_client` to bypass provider selection.
+///
 /// # Usage Example
 /// ```rust,no_run
 /// use axiom::{
@@ -177,37 +265,63 @@ struct ChatResponse {
 pub struct LLMSpecificationGenerator {
     config: GeneratorConfig,
     http_client: reqwest::Client,
+    /// Client used for all LLM calls; resolved from the config on each call when unset
+    llm_client: Option<Arc<dyn LlmClient>>,
+    /// Clients that candidates are drawn from in turn, instead of `candidate_models`
+    candidate_clients: Vec<Arc<dyn LlmClient>>,
+    /// Token usage of every LLM call, shared between clones
+    usage: UsageTracker,
+    /// Receives streamed text and carries the cancellation flag, shared between clones
+    stream: StreamContext,
     domain_contexts: HashMap<String, DomainContext>,
+    /// Prompts with the overrides from the config applied
+    prompts: PromptTemplates,
+    /// F* backend used for type checking and formal verification when available
+    fstar_backend: Option<FStarBackend>,
+    /// Backends checking specifications in the other verification languages
+    backends: Option<Arc<BackendRegistry>>,
+    /// Time each backend check may take
+    tool_timeout: std::time::Duration,
 }
 
 impl LLMSpecificationGenerator {
     /// Create a new LLMSpecificationGenerator with the given configuration
     pub fn new(config: GeneratorConfig) -> Self {
-        let http_client = reqwest::Client
-            ::builder()
-            .timeout(std::time::Duration::from_secs(120))
-            .build()
-            .expect("Failed to create HTTP client");
+        // `GeneratorConfig::from_file` already rejected broken overrides
+        let prompts = PromptTemplates::load(&config).unwrap_or_else(|e| {
+            warn!("Ignoring prompt template overrides: {}", e);
+            PromptTemplates::builtin()
+        });
 
         let mut generator = Self {
             config,
-            http_client,
+            http_client: default_http_client(),
+            llm_client: None,
+            candidate_clients: Vec::new(),
+            usage: UsageTracker::new(),
+            stream: StreamContext::new(),
             domain_contexts: HashMap::new(),
+            prompts,
+            fstar_backend: None,
+            backends: None,
+            tool_timeout: VerificationOptions::default().timeout,
         };
 
         // Initialize domain contexts
         generator.init_domain_contexts();
 
-        // Ensure projects directory exists
-        if let Err(e) = std::fs::create_dir_all("projects") {
-            warn!("Could not create projects directory: {}", e);
-        } else {
-            info!("Projects directory created or already exists");
-        }
-
         generator
     }
 
+    /// Guidelines for writing the given verification language, added to generation and fix
+    /// prompts
+    fn language_guidelines(&self, language: &VerificationLanguage) -> String {
+        match language {
+            VerificationLanguage::FStarLang => self.get_fstar_guidelines(),
+            _ => String::new(),
+        }
+    }
+
     /// Get F* specific guidelines to improve code generation
     fn get_fstar_guidelines(&self) -> String {
         r#"
@@ -265,114 +379,114 @@ impl LLMSpecificationGenerator {
         Self::new(GeneratorConfig::default())
     }
 
+    /// Use the given F* backend for `TypeCheck` and `FormalVerification` validation.
+    /// The LLM is only asked to judge the specification when F* is not available.
+    pub fn with_fstar_backend(mut self, backend: FStarBackend) -> Self {
+        self.fstar_backend = Some(backend);
+        self
+    }
+
+    /// Check specifications with the backend registered for their verification language,
+    /// letting each check run for `timeout`. F* specifications still go to the backend
+    /// from `with_fstar_backend` when one is set, since it can type check without proving.
+    pub fn with_backend_registry(mut self, backends: Arc<BackendRegistry>, timeout: std::time::Duration) -> Self {
+        self.backends = Some(backends);
+        self.tool_timeout = timeout;
+        self
+    }
+
+    /// Send all LLM calls through the given client instead of picking a provider
+    /// from the configured API keys.
+    pub fn with_llm_client(mut self, client: Arc<dyn LlmClient>) -> Self {
+        self.llm_client = Some(client);
+        self
+    }
+
+    /// Draw candidate specifications from these clients in turn instead of from the
+    /// configured `candidate_models`
+    pub fn with_candidate_clients(mut self, clients: Vec<Arc<dyn LlmClient>>) -> Self {
+        self.candidate_clients = clients;
+        self
+    }
+
+    /// Report LLM output to `observer` as it streams in
+    pub fn with_stream_observer(mut self, observer: Arc<dyn StreamObserver>) -> Self {
+        self.stream = self.stream.with_observer(observer);
+        self
+    }
+
+    /// Abort LLM calls, including the one in flight, once `cancel` is set
+    pub fn with_cancel_flag(mut self, cancel: CancelFlag) -> Self {
+        self.stream = self.stream.with_cancel_flag(cancel);
+        self
+    }
+
     /// Simplified API for generating a specification and saving it to a project
     /// This is the main method you should use for generating specifications
     pub async fn generate_and_save(
         &self,
-        project_name: &str,
+        project: &Project,
         requirements: &[String],
         domain: Domain,
         language: VerificationLanguage
     ) -> AxiomResult<(Specification, PathBuf)> {
+        let project_name = &project.manifest.project.name;
         info!("Generating specification for project: {}", project_name);
 
         // Set up options with the specified verification language
-        let mut options = SpecificationOptions::default();
-        options.verification_language = language;
+        let options = SpecificationOptions {
+            verification_language: language,
+            ..SpecificationOptions::default()
+        };
 
         // Generate the specification
         let spec = self.generate_specification(requirements, domain, &options).await?;
 
         // Save it to the project
-        let project_dir = self.save_to_project(project_name, &spec)?;
+        let spec_path = self.save_to_project(project, &spec)?;
 
         info!("Specification generated and saved to project: {}", project_name);
-        Ok((spec, project_dir))
+        Ok((spec, spec_path))
     }
 
-    /// Save specification to a project folder
-    pub fn save_to_project(
-        &self,
-        project_name: &str,
-        spec: &Specification
-    ) -> AxiomResult<PathBuf> {
-        use std::fs::{ self, File };
-        use std::io::Write;
+    /// Save a specification in the project's `specs/` directory and return its path
+    ///
+    /// The whole specification, requirements and metadata included, is saved next to the
+    /// code as a versioned JSON document.
+    pub fn save_to_project(&self, project: &Project, spec: &Specification) -> AxiomResult<PathBuf> {
+        use std::fs;
 
-        // Create project directory
-        let project_dir = PathBuf::from("projects").join(project_name);
+        let specs_dir = project.specs_dir();
         fs
-            ::create_dir_all(&project_dir)
+            ::create_dir_all(&specs_dir)
             .map_err(|e|
-                AxiomError::SystemError(format!("Failed to create project directory: {}", e))
+                AxiomError::SystemError(format!("Failed to create specs directory: {}", e))
             )?;
 
-        info!("Saving specification to project: {}", project_name);
+        info!("Saving specification to project: {}", project.manifest.project.name);
 
         // Save specification code
-        let spec_file_ext = match spec.formal_spec.verification_language {
-            VerificationLanguage::FStarLang => "fst",
-            VerificationLanguage::DafnyLang => "dfy",
-            VerificationLanguage::CoqLang => "v",
-            VerificationLanguage::IsabelleLang => "thy",
-            VerificationLanguage::LeanLang => "lean",
-            VerificationLanguage::TLAPlus => "tla",
-            VerificationLanguage::Why3Lang => "why",
-            VerificationLanguage::Z3SMT => "smt2",
-            _ => "txt",
-        };
-
-        let spec_file_path = project_dir.join(format!("specification.{}", spec_file_ext));
-        let mut spec_file = File::create(&spec_file_path).map_err(|e|
-            AxiomError::SystemError(format!("Failed to create specification file: {}", e))
-        )?;
-
-        spec_file
-            .write_all(spec.formal_spec.spec_code.as_bytes())
+        let spec_file_path = project.spec_path(&spec.formal_spec.verification_language);
+        fs
+            ::write(&spec_file_path, &spec.formal_spec.spec_code)
             .map_err(|e|
                 AxiomError::SystemError(format!("Failed to write specification file: {}", e))
             )?;
 
-        // Save metadata
-        let metadata_file_path = project_dir.join("metadata.json");
-        let metadata =
-            serde_json::json!({
-            "id": spec.id,
-            "domain": format!("{:?}", spec.metadata.domain),
-            "verification_language": format!("{:?}", spec.formal_spec.verification_language),
-            "verification_system": format!("{:?}", spec.metadata.verification_system),
-            "created_at": spec.metadata.created_at.to_rfc3339(),
-            "confidence_score": spec.metadata.confidence_score,
-            "is_formally_validated": spec.metadata.is_formally_validated,
-        });
-
-        let metadata_str = serde_json
-            ::to_string_pretty(&metadata)
-            .map_err(|e| AxiomError::SystemError(format!("Failed to serialize metadata: {}", e)))?;
-
-        let mut metadata_file = File::create(&metadata_file_path).map_err(|e|
-            AxiomError::SystemError(format!("Failed to create metadata file: {}", e))
-        )?;
-
-        metadata_file
-            .write_all(metadata_str.as_bytes())
-            .map_err(|e| AxiomError::SystemError(format!("Failed to write metadata file: {}", e)))?;
-
-        // Save requirements
-        let requirements_file_path = project_dir.join("requirements.txt");
-        let requirements_str = spec.source_requirements.join("\n");
-        let mut requirements_file = File::create(&requirements_file_path).map_err(|e|
-            AxiomError::SystemError(format!("Failed to create requirements file: {}", e))
+        // Save the whole specification, metadata included, as a versioned JSON document
+        let document_file_path = spec_file_path.with_extension("json");
+        let document = to_document_json(spec).map_err(|e|
+            AxiomError::SystemError(format!("Failed to serialize specification: {}", e))
         )?;
 
-        requirements_file
-            .write_all(requirements_str.as_bytes())
+        fs
+            ::write(&document_file_path, document)
             .map_err(|e|
-                AxiomError::SystemError(format!("Failed to write requirements file: {}", e))
+                AxiomError::SystemError(format!("Failed to write specification document: {}", e))
             )?;
 
         info!("Specification saved to {}", spec_file_path.display());
-        Ok(project_dir)
+        Ok(spec_file_path)
     }
 
     /// Initialize domain-specific context information
@@ -387,9 +501,6 @@ impl LLMSpecificationGenerator {
                 "Non-repudiation: Actions cannot be denied by the party that performed them".to_string(),
                 "Forward secrecy: Compromise of long-term keys does not compromise past session keys".to_string()
             ],
-            example_snippets: vec![
-                "lemma confidentiality (m:message, k:key, c:ciphertext):\n  requires enc(m, k) = c\n  ensures forall k'. k' != k ==> dec(c, k') != m".to_string()
-            ],
             verification_advice: "Focus on proving security properties against active adversaries with defined capabilities. Consider side-channel attacks and timing vulnerabilities.".to_string(),
         });
 
@@ -403,9 +514,6 @@ impl LLMSpecificationGenerator {
                 "Consistency: All nodes eventually agree on the state".to_string(),
                 "Deadlock freedom: The system never reaches a state where progress is impossible".to_string()
             ],
-            example_snippets: vec![
-                "theorem consensus_safety:\n  forall n1, n2: Node, v1, v2: Value.\n  decided(n1, v1) && decided(n2, v2) => v1 = v2".to_string()
-            ],
             verification_advice: "Use temporal logic to reason about system behavior over time. Consider all possible interleavings of events across nodes.".to_string(),
         });
 
@@ -446,363 +554,104 @@ impl LLMSpecificationGenerator {
         }
     }
 
-    /// Render a template with the given parameters
+    /// Render a prompt template with the given parameters
     fn render_template(
         &self,
         template_name: &str,
         params: &HashMap<String, String>
     ) -> Result<String, SpecGenError> {
-        let template = self.config
-            .get_template(template_name)
-            .ok_or_else(||
-                SpecGenError::TemplateError(format!("Template not found: {}", template_name))
-            )?;
-
-        let mut result = template.clone();
-        for (key, value) in params {
-            let placeholder = format!("{{{{{}}}}}", key);
-            result = result.replace(&placeholder, value);
-        }
-
-        Ok(result)
+        self.prompts.render(template_name, params)
     }
 
     /// Call the LLM API with the given prompt
     async fn call_llm_api(&self, prompt: &str) -> Result<String, SpecGenError> {
-        use log::{ debug, info, warn };
-
-        let preferred_provider = "anthropic"; // Try Anthropic first, then fall back to other providers
-        let (provider, api_key) = match self.config.get_api_key(preferred_provider) {
-            Ok(result) => result,
-            Err(e) => {
-                warn!("API key error: {}", e);
-                return Err(SpecGenError::ApiError(format!("API key error: {}", e)));
-            }
-        };
+        self.send_llm_request(self.llm_request(prompt)).await
+    }
 
-        // Adjust endpoint and model based on the provider
-        let (api_endpoint, model) = match provider.as_str() {
-            "openai" => {
-                info!("Using OpenAI provider");
-                (
-                    self.config.llm_api.api_endpoint
-                        .clone()
-                        .unwrap_or_else(||
-                            "https://api.openai.com/v1/chat/completions".to_string()
-                        ),
-                    self.config.llm_api.model.clone().unwrap_or_else(|| "gpt-4o".to_string()),
-                )
-            }
-            "anthropic" => {
-                info!("Using Anthropic provider");
-                (
-                    "https://api.anthropic.com/v1/messages".to_string(),
-                    "claude-3-sonnet-20240229".to_string(), // Using Claude 3.7 Sonnet
-                )
-            }
-            "azure" => {
-                info!("Using Azure OpenAI provider");
-                (
-                    self.config.llm_api.api_endpoint
-                        .clone()
-                        .unwrap_or_else(|| panic!("Azure OpenAI endpoint must be configured")),
-                    self.config.llm_api.model.clone().unwrap_or_else(|| "gpt-4".to_string()),
-                )
-            }
-            "mistral" => {
-                info!("Using Mistral provider");
-                (
-                    "https://api.mistral.ai/v1/chat/completions".to_string(),
-                    "mistral-large-latest".to_string(),
-                )
-            }
-            "together" => {
-                info!("Using Together provider");
-                (
-                    "https://api.together.xyz/v1/completions".to_string(),
-                    "llama-3-70b-instruct".to_string(),
-                )
-            }
-            _ => {
-                warn!("Unknown provider: {}, falling back to OpenAI", provider);
-                ("https://api.openai.com/v1/chat/completions".to_string(), "gpt-4o".to_string())
+    /// Ask the LLM for a JSON answer and deserialize it, re-asking once if it does not match
+    /// the schema
+    async fn call_llm_structured<T: StructuredResponse>(&self, prompt: &str) -> Result<T, SpecGenError> {
+        let schema = T::response_schema();
+        let prompt = structured_prompt(prompt, &schema);
+        let response = self.send_llm_request(
+            self.llm_request(&prompt).with_response_schema(schema.clone())
+        ).await?;
+
+        let error = match parse_structured::<T>(&response) {
+            Ok(value) => {
+                return Ok(value);
             }
+            Err(error) => error,
         };
+        warn!("{} response does not match its schema ({}), asking again", T::NAME, error);
+
+        let retry_prompt = reask_prompt(&prompt, &response, &error);
+        let response = self.send_llm_request(
+            self.llm_request(&retry_prompt).with_response_schema(schema)
+        ).await?;
+        parse_structured::<T>(&response).map_err(|error| {
+            SpecGenError::ParseError(
+                format!("{} response does not match its schema after a retry: {}", T::NAME, error)
+            )
+        })
+    }
 
-        let temperature = self.config.temperature.unwrap_or(0.2);
-        let max_tokens = self.config.max_tokens.unwrap_or(4096);
-
-        // General log info about the request
-        info!("Making LLM API request to {}", provider);
-        debug!("API endpoint: {}", api_endpoint);
-        debug!("Model: {}", model);
-        debug!("Temperature: {}", temperature);
-        debug!("Max tokens: {}", max_tokens);
-        debug!("Prompt length: {} characters", prompt.len());
-
-        // Simplified version - a more complete implementation would handle different API formats
-        if provider == "anthropic" {
-            // For Anthropic (Claude API format)
-            info!("Using Anthropic (Claude) API format");
-
-            // Anthropic-specific request format
-            let claude_request =
-                serde_json::json!({
-                "model": model,
-                "max_tokens": max_tokens,
-                "temperature": temperature,
-                "system": "You are a formal verification expert who creates precise, detailed formal specifications.",
-                "messages": [
-                    {
-                        "role": "user",
-                        "content": prompt
-                    }
-                ]
-            });
-
-            debug!(
-                "Claude request: {}",
-                serde_json::to_string(&claude_request).unwrap_or_default()
-            );
-
-            debug!("Sending request to Anthropic API");
-            debug!("Anthropic API endpoint: {}", api_endpoint);
-
-            let request_builder = self.http_client
-                .post(&api_endpoint)
-                .header("Content-Type", "application/json")
-                .header("x-api-key", &api_key)
-                .header("anthropic-version", "2023-06-01") // Use the current API version for Claude
-                .json(&claude_request);
-
-            let response = request_builder.send().await.map_err(|e| {
-                let error_msg = format!("Network error when calling Anthropic API: {}", e);
-                warn!("{}", error_msg);
-                if e.is_timeout() {
-                    warn!("Request timed out");
-                }
-                if e.is_connect() {
-                    warn!("Connection error - check network connectivity");
-                }
-                if e.is_request() {
-                    warn!("Request construction error");
-                }
-                SpecGenError::NetworkError(error_msg)
-            })?;
-
-            if !response.status().is_success() {
-                let status = response.status().as_u16();
-                let error_text = response
-                    .text().await
-                    .unwrap_or_else(|_| "Failed to get error message".to_string());
-
-                warn!("API error: HTTP {} - {}", status, error_text);
-                return Err(SpecGenError::HttpError {
-                    status,
-                    message: error_text,
-                });
-            }
-
-            // Parse Anthropic response format
-            let response_text = response.text().await.map_err(|e| {
-                warn!("Failed to get response text: {}", e);
-                SpecGenError::ParseError(e.to_string())
-            })?;
-
-            info!("Successfully received response from Anthropic API");
-            debug!("Response length: {} characters", response_text.len());
-
-            // Parse the response to extract content
-            let response_json: serde_json::Value = serde_json
-                ::from_str(&response_text)
-                .map_err(|e| {
-                    warn!("JSON parsing error: {}", e);
-                    SpecGenError::ParseError(e.to_string())
-                })?;
-
-            debug!(
-                "Anthropic response structure: {}",
-                serde_json
-                    ::to_string_pretty(&response_json)
-                    .unwrap_or_else(|_| "unable to format".to_string())
-            );
-
-            // Extract content based on the Anthropic Claude API response structure
-            let content = if let Some(content_array) = response_json["content"].as_array() {
-                if let Some(first_content) = content_array.get(0) {
-                    if let Some(text) = first_content["text"].as_str() {
-                        text.to_string()
-                    } else {
-                        warn!("Failed to extract text from Anthropic response content");
-                        return Err(
-                            SpecGenError::ParseError(
-                                "Missing text in Anthropic response content".to_string()
-                            )
-                        );
-                    }
-                } else {
-                    warn!("No content items in Anthropic response");
-                    return Err(
-                        SpecGenError::ParseError(
-                            "Empty content array in Anthropic response".to_string()
-                        )
-                    );
-                }
-            } else {
-                // Try alternative response structure (in case API changed)
-                response_json["content"]
-                    .as_str()
-                    .or_else(|| response_json["completion"].as_str())
-                    .ok_or_else(|| {
-                        warn!("Failed to extract content from Anthropic response");
-                        SpecGenError::ParseError(
-                            "Unable to find content in Anthropic response".to_string()
-                        )
-                    })?
-                    .to_string()
-            };
-
-            info!("Successfully extracted content from Anthropic response");
-            debug!("Content length: {} characters", content.len());
-
-            Ok(content)
-        } else {
-            // For OpenAI and similar APIs
-            info!("Using OpenAI-compatible API format");
-
-            let request = ChatRequest {
-                model: model.clone(),
-                messages: vec![
-                    ChatMessage {
-                        role: "system".to_string(),
-                        content: "You are a formal verification expert who creates precise, detailed formal specifications.".to_string(),
-                    },
-                    ChatMessage {
-                        role: "user".to_string(),
-                        content: prompt.to_string(),
-                    }
-                ],
-                temperature,
-                max_tokens,
-                stream: None,
-            };
-
-            debug!("Calling LLM API with prompt: {} (truncated)", if prompt.len() > 100 {
-                &prompt[0..100]
-            } else {
-                prompt
-            });
-
-            // Different auth header for different providers
-            let auth_header = match provider.as_str() {
-                "azure" => format!("Bearer {}", api_key),
-                "mistral" => format!("Bearer {}", api_key),
-                "together" => format!("Bearer {}", api_key),
-                _ => format!("Bearer {}", api_key), // Default for OpenAI
-            };
-
-            debug!("Sending request to API endpoint");
-            debug!("OpenAI API endpoint: {}", api_endpoint);
-
-            let request_builder = self.http_client
-                .post(&api_endpoint)
-                .header("Content-Type", "application/json")
-                .header("Authorization", auth_header)
-                .json(&request);
-
-            let response = request_builder.send().await.map_err(|e| {
-                let error_msg = format!("Network error when calling OpenAI API: {}", e);
-                warn!("{}", error_msg);
-                if e.is_timeout() {
-                    warn!("Request timed out");
-                }
-                if e.is_connect() {
-                    warn!("Connection error - check network connectivity");
-                }
-                if e.is_request() {
-                    warn!("Request construction error");
-                }
-                SpecGenError::NetworkError(error_msg)
-            })?;
-
-            if !response.status().is_success() {
-                let status = response.status().as_u16();
-                let error_text = response
-                    .text().await
-                    .unwrap_or_else(|_| "Failed to get error message".to_string());
-
-                warn!("API error: HTTP {} - {}", status, error_text);
-                return Err(SpecGenError::HttpError {
-                    status,
-                    message: error_text,
-                });
-            }
-
-            info!("Successfully received response from API");
-
-            // First try to parse as a raw JSON value to inspect and debug
-            let response_text = response.text().await.map_err(|e| {
-                warn!("Failed to get text from response: {}", e);
-                SpecGenError::ParseError(e.to_string())
-            })?;
-
-            debug!("OpenAI response length: {} characters", response_text.len());
-
-            // Parse as JSON to inspect the structure
-            let response_json_value: serde_json::Value = match serde_json::from_str(&response_text) {
-                Ok(v) => v,
-                Err(e) => {
-                    warn!("Failed to parse response as JSON: {}", e);
-                    return Err(SpecGenError::ParseError(format!("Invalid JSON response: {}", e)));
-                }
-            };
+    fn llm_request(&self, prompt: &str) -> LlmRequest {
+        LlmRequest::new(
+            prompt,
+            self.config.temperature.unwrap_or(0.2),
+            self.config.max_tokens.unwrap_or(4096)
+        )
+    }
 
-            debug!(
-                "OpenAI response structure: {}",
-                serde_json
-                    ::to_string_pretty(&response_json_value)
-                    .unwrap_or_else(|_| "unable to format".to_string())
-            );
+    /// Send a request to the configured client
+    async fn send_llm_request(&self, request: LlmRequest) -> Result<String, SpecGenError> {
+        self.send_llm_request_to(self.client_for(&self.config)?, request).await
+    }
 
-            // Try to extract content directly from JSON structure (more robust approach)
-            if let Some(choices) = response_json_value["choices"].as_array() {
-                if !choices.is_empty() {
-                    if let Some(message) = choices[0]["message"].as_object() {
-                        if let Some(content) = message.get("content") {
-                            if let Some(text) = content.as_str() {
-                                debug!("Successfully extracted content from JSON structure");
-                                debug!("Response content length: {} characters", text.len());
-                                info!("API call completed successfully");
-                                return Ok(text.to_string());
-                            }
-                        }
-                    }
-                }
-            }
+    /// The client set with `with_llm_client`, or one built from `config`
+    fn client_for(&self, config: &GeneratorConfig) -> Result<Arc<dyn LlmClient>, SpecGenError> {
+        match &self.llm_client {
+            Some(client) => Ok(client.clone()),
+            None =>
+                client_from_config(config, self.http_client.clone()).map_err(|e| {
+                    warn!("API key error: {}", e);
+                    SpecGenError::ApiError(format!("API key error: {}", e))
+                }),
+        }
+    }
 
-            // Fallback: Try to parse using the struct
-            warn!("Direct extraction failed, trying to parse using the struct");
-            let response_json: ChatResponse = match serde_json::from_str(&response_text) {
-                Ok(r) => r,
-                Err(e) => {
-                    warn!("Failed to parse response using structured approach: {}", e);
-                    return Err(
-                        SpecGenError::ParseError(format!("Failed to parse API response: {}", e))
-                    );
-                }
-            };
+    /// Client for the candidate at `index`, cycling through the candidate clients or models
+    fn candidate_client(&self, index: usize) -> Result<Arc<dyn LlmClient>, SpecGenError> {
+        if !self.candidate_clients.is_empty() {
+            return Ok(self.candidate_clients[index % self.candidate_clients.len()].clone());
+        }
+        let models = &self.config.candidate_models;
+        if models.is_empty() {
+            return self.client_for(&self.config);
+        }
+        let mut config = self.config.clone();
+        config.llm_api = models[index % models.len()].clone();
+        self.client_for(&config)
+    }
 
-            if response_json.choices.is_empty() {
-                warn!("API returned empty choices array");
-                return Err(SpecGenError::ApiError("No response from API".to_string()));
-            }
+    /// Send a request, streaming it if configured, and record its usage against the budget
+    async fn send_llm_request_to(
+        &self,
+        client: Arc<dyn LlmClient>,
+        request: LlmRequest
+    ) -> Result<String, SpecGenError> {
+        send_recorded(client.as_ref(), &request, &self.config, &self.usage, &self.stream).await
+    }
 
-            let content = response_json.choices[0].message.content.clone();
-            debug!("Response content length: {} characters", content.len());
-            info!("API call completed successfully");
+    /// Token usage and cost of all LLM calls made so far by this generator and its clones
+    pub fn usage_summary(&self) -> UsageSummary {
+        self.usage.summary()
+    }
 
-            Ok(content)
-        }
+    /// Tracker the LLM calls of this generator are recorded in, to share with other generators
+    pub fn usage_tracker(&self) -> UsageTracker {
+        self.usage.clone()
     }
 
     /// Create a FormalSpecification from the LLM response
@@ -879,11 +728,194 @@ impl LLMSpecificationGenerator {
             spec_code: extracted_code,
             components,
             dependencies,
+            candidates: vec![],
         };
 
         info!("Extracted specification code of {} characters", spec.spec_code.len());
         Ok(spec)
     }
+
+    /// Turn a generation response into a specification of the requirements
+    fn build_specification(
+        &self,
+        response: &str,
+        requirements: &[String],
+        domain: Domain,
+        options: &SpecificationOptions
+    ) -> AxiomResult<Specification> {
+        // Parse the response into a formal specification
+        let formal_spec = self
+            .parse_formal_specification(response, options.verification_language.clone())
+            .map_err(AxiomError::from)?;
+
+        // Create the specification object
+        let spec = Specification {
+            id: format!("spec_{}", chrono::Utc::now().timestamp()),
+            source_requirements: requirements.to_vec(),
+            formal_properties: vec![], // In a real implementation, we would extract these from the response
+            formal_spec,
+            metadata: crate::models::specification::SpecificationMetadata {
+                created_at: chrono::Utc::now(),
+                verification_system: options.verification_language.verification_system(),
+                domain,
+                confidence_score: UNCHECKED_CONFIDENCE,
+                is_formally_validated: false,
+            },
+        };
+
+        Ok(spec)
+    }
+
+    /// Draw `options.candidates` specifications in parallel, check each with the backend for
+    /// its verification language (or the LLM when none is installed), and keep the one with
+    /// the fewest errors. Every candidate and its score is recorded in the result's
+    /// `candidates`.
+    async fn generate_best_candidate(
+        &self,
+        prompt: &str,
+        requirements: &[String],
+        domain: Domain,
+        options: &SpecificationOptions
+    ) -> AxiomResult<Specification> {
+        info!("Drawing {} candidate specifications", options.candidates);
+
+        let mut tasks = tokio::task::JoinSet::new();
+        for index in 0..options.candidates {
+            let client = self.candidate_client(index).map_err(AxiomError::from)?;
+            let request = self.llm_request(prompt).with_sample(index);
+            let generator = self.clone();
+            let requirements = requirements.to_vec();
+            let domain = domain.clone();
+            let options = options.clone();
+            tasks.spawn(async move {
+                let candidate = SpecificationCandidate {
+                    index,
+                    provider: client.provider().to_string(),
+                    model: client.model().to_string(),
+                    spec_code: String::new(),
+                    errors: 0,
+                    warnings: 0,
+                    tool_validated: false,
+                    failure: None,
+                    selected: false,
+                };
+                let outcome = generator.check_candidate(
+                    client,
+                    request,
+                    &requirements,
+                    domain,
+                    &options
+                ).await;
+                (candidate, outcome)
+            });
+        }
+
+        let mut candidates = Vec::new();
+        let mut specs = HashMap::new();
+        while let Some(joined) = tasks.join_next().await {
+            let (mut candidate, outcome) = joined.map_err(|e|
+                AxiomError::SystemError(format!("Candidate generation task failed: {}", e))
+            )?;
+            match outcome {
+                Ok((spec, report)) => {
+                    candidate.spec_code = spec.formal_spec.spec_code.clone();
+                    candidate.errors = count_issues(&report, IssueSeverity::Error);
+                    candidate.warnings = count_issues(&report, IssueSeverity::Warning);
+                    candidate.tool_validated = report.tool_validated;
+                    info!(
+                        "Candidate {} from {}: {} errors, {} warnings",
+                        candidate.index + 1,
+                        candidate.model,
+                        candidate.errors,
+                        candidate.warnings
+                    );
+                    specs.insert(candidate.index, spec);
+                }
+                // Stop every candidate once the run is cancelled or over budget
+                Err(e @ (AxiomError::Cancelled | AxiomError::BudgetExceeded(_))) => {
+                    return Err(e);
+                }
+                Err(e) => {
+                    warn!("Candidate {} from {} failed: {}", candidate.index + 1, candidate.model, e);
+                    candidate.failure = Some(e.to_string());
+                }
+            }
+            candidates.push(candidate);
+        }
+        candidates.sort_by_key(|candidate| candidate.index);
+
+        let best = candidates
+            .iter()
+            .filter(|candidate| candidate.failure.is_none())
+            .min_by_key(|candidate| (candidate.errors, candidate.warnings, candidate.index))
+            .map(|candidate| candidate.index);
+        let best = match best {
+            Some(index) => index,
+            None => {
+                let reasons = candidates
+                    .iter()
+                    .filter_map(|candidate| candidate.failure.as_deref())
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                return Err(
+                    AxiomError::SpecTranslationError(format!("Every candidate specification failed: {}", reasons))
+                );
+            }
+        };
+        candidates[best].selected = true;
+        info!(
+            "Selected candidate {} of {} with {} errors",
+            best + 1,
+            candidates.len(),
+            candidates[best].errors
+        );
+
+        let mut spec = specs.remove(&best).expect("selected candidate has a specification");
+        spec.metadata.confidence_score = candidate_confidence(&candidates[best]);
+        spec.formal_spec.candidates = candidates;
+        Ok(spec)
+    }
+
+    /// Generate one candidate with the given client and type check it
+    async fn check_candidate(
+        &self,
+        client: Arc<dyn LlmClient>,
+        request: LlmRequest,
+        requirements: &[String],
+        domain: Domain,
+        options: &SpecificationOptions
+    ) -> AxiomResult<(Specification, ValidationReport)> {
+        let response = self.send_llm_request_to(client, request).await.map_err(AxiomError::from)?;
+        let spec = self.build_specification(&response, requirements, domain, options)?;
+        let report = self.validate_type_checking(&spec).await?;
+        Ok((spec, report))
+    }
+}
+
+/// Number of issues of one severity in a report
+fn count_issues(report: &ValidationReport, severity: IssueSeverity) -> usize {
+    report.issues
+        .iter()
+        .filter(|issue| issue.severity == severity)
+        .count()
+}
+
+/// Confidence in a checked candidate: halved by every error, and never above
+/// `UNCHECKED_CONFIDENCE` when only the LLM judged it
+fn candidate_confidence(candidate: &SpecificationCandidate) -> f32 {
+    let ceiling = if candidate.tool_validated { 1.0 } else { UNCHECKED_CONFIDENCE };
+    ceiling / (2.0f32).powi(candidate.errors.min(30) as i32)
+}
+
+/// Template parameters describing a specification: its language and code
+fn spec_params(spec: &Specification) -> HashMap<String, String> {
+    let mut params = HashMap::new();
+    params.insert(
+        "verification_language".to_string(),
+        spec.formal_spec.verification_language.to_string()
+    );
+    params.insert("spec_code".to_string(), spec.formal_spec.spec_code.clone());
+    params
 }
 
 /// Helper function to extract dependencies from code
@@ -910,13 +942,12 @@ fn extract_dependencies(content: &str, language: &VerificationLanguage) -> Vec<S
     for line in content.lines() {
         let trimmed = line.trim();
         for pattern in &patterns {
-            if trimmed.starts_with(pattern) {
-                let dep = trimmed[pattern.len()..]
-                    .trim()
+            if let Some(rest) = trimmed.strip_prefix(pattern) {
+                let dep = rest
                     .split_whitespace()
                     .next()
                     .unwrap_or("")
-                    .trim_end_matches(|c| (c == ',' || c == ';'));
+                    .trim_end_matches([',', ';']);
 
                 if !dep.is_empty() {
                     dependencies.push(dep.to_string());
@@ -943,12 +974,7 @@ impl SpecificationGenerator for LLMSpecificationGenerator {
         let template_name = "specification";
 
         // Get language-specific guidelines for improved code generation
-        let language_guidelines = match options.verification_language {
-            VerificationLanguage::FStarLang => self.get_fstar_guidelines(),
-            VerificationLanguage::DafnyLang => self.get_dafny_guidelines(),
-            VerificationLanguage::CoqLang => self.get_coq_guidelines(),
-            _ => String::new(),
-        };
+        let language_guidelines = self.language_guidelines(&options.verification_language);
 
         // Prepare template parameters
         let mut params = HashMap::new();
@@ -964,48 +990,14 @@ impl SpecificationGenerator for LLMSpecificationGenerator {
         // Render the template
         let prompt = self.render_template(template_name, &params).map_err(AxiomError::from)?;
 
+        if options.candidates > 1 {
+            return self.generate_best_candidate(&prompt, requirements, domain, options).await;
+        }
+
         // Call the LLM API
         let response = self.call_llm_api(&prompt).await.map_err(AxiomError::from)?;
 
-        // Parse the response into a formal specification
-        let formal_spec = self
-            .parse_formal_specification(&response, options.verification_language.clone())
-            .map_err(AxiomError::from)?;
-
-        // Create the specification object
-        let spec = Specification {
-            id: format!("spec_{}", chrono::Utc::now().timestamp()),
-            source_requirements: requirements.to_vec(),
-            formal_properties: vec![], // In a real implementation, we would extract these from the response
-            formal_spec,
-            metadata: crate::models::specification::SpecificationMetadata {
-                created_at: chrono::Utc::now(),
-                verification_system: match options.verification_language {
-                    VerificationLanguage::FStarLang =>
-                        crate::models::common::VerificationSystem::FStar,
-                    VerificationLanguage::DafnyLang =>
-                        crate::models::common::VerificationSystem::Dafny,
-                    VerificationLanguage::CoqLang => crate::models::common::VerificationSystem::Coq,
-                    VerificationLanguage::IsabelleLang =>
-                        crate::models::common::VerificationSystem::Isabelle,
-                    VerificationLanguage::LeanLang =>
-                        crate::models::common::VerificationSystem::Lean,
-                    VerificationLanguage::TLAPlus => crate::models::common::VerificationSystem::TLA,
-                    VerificationLanguage::Why3Lang =>
-                        crate::models::common::VerificationSystem::Why3,
-                    VerificationLanguage::Z3SMT => crate::models::common::VerificationSystem::Z3,
-                    _ =>
-                        crate::models::common::VerificationSystem::Custom(
-                            options.verification_language.to_string()
-                        ),
-                },
-                domain: domain.clone(),
-                confidence_score: 0.9, // In a real implementation, this would be calculated
-                is_formally_validated: false,
-            },
-        };
-
-        Ok(spec)
+        self.build_specification(&response, requirements, domain, options)
     }
 
     async fn refine_specification(
@@ -1025,17 +1017,7 @@ impl SpecificationGenerator for LLMSpecificationGenerator {
         params.insert("original_spec".to_string(), spec.formal_spec.spec_code.clone());
         params.insert("feedback".to_string(), feedback.to_string());
 
-        let prompt = format!(
-            "You are a formal verification expert. You need to refine a formal specification based on feedback.\n\n\
-            Original specification in {}:\n\
-            ```\n{}\n```\n\n\
-            Feedback to address:\n{}\n\n\
-            Please provide a revised specification that addresses the feedback while maintaining \
-            all the original requirements. Include all necessary types, functions, and properties.",
-            spec.formal_spec.verification_language.to_string(),
-            spec.formal_spec.spec_code,
-            feedback
-        );
+        let prompt = self.render_template("refine", &params).map_err(AxiomError::from)?;
 
         // Call the LLM API
         let response = self.call_llm_api(&prompt).await.map_err(AxiomError::from)?;
@@ -1100,6 +1082,11 @@ impl SpecificationGenerator for LLMSpecificationGenerator {
                     });
                     return Ok(fixed_report);
                 }
+                // Running out of budget or a cancellation ends the run instead of hiding
+                // behind the original report
+                Err(e @ (AxiomError::BudgetExceeded(_) | AxiomError::Cancelled)) => {
+                    return Err(e);
+                }
                 Err(e) => {
                     warn!("Failed to automatically fix specification: {}", e);
                     // Return the original validation report if fixing failed
@@ -1119,69 +1106,25 @@ impl SpecificationGenerator for LLMSpecificationGenerator {
         info!("Translating requirements to properties for domain: {}", domain);
 
         // Prepare the prompt for property extraction
-        let prompt = format!(
-            "You are a formal verification expert. Extract formal properties from these requirements for a {} system:\n\n{}\n\n\
-            For each requirement, provide:\n\
-            1. The formal interpretation as a property\n\
-            2. The property expressed in a mathematical notation\n\
-            3. A confidence score (0-1) for your translation\n\
-            Format each property as: \"Requirement: [original text]\\nFormal property: [interpretation]\\nMathematical form: [formal notation]\\nConfidence: [score]\"",
-            domain.to_string(),
-            requirements.join("\n")
-        );
-
-        // Call the LLM API
-        let response = self.call_llm_api(&prompt).await.map_err(AxiomError::from)?;
-
-        // Parse the response into property translations
-        let mut translations = Vec::new();
-
-        // Simple parser for the response format
-        let sections = response.split("\n\n").collect::<Vec<_>>();
-
-        for section in sections {
-            if section.is_empty() {
-                continue;
-            }
-
-            let lines: Vec<&str> = section.lines().collect();
-            if lines.len() < 4 {
-                continue;
-            }
-
-            let requirement = lines[0]
-                .strip_prefix("Requirement: ")
-                .unwrap_or(lines[0])
-                .to_string();
-
-            let interpreted_property = lines[1]
-                .strip_prefix("Formal property: ")
-                .unwrap_or(lines[1])
-                .to_string();
-
-            let formal_representation = lines[2]
-                .strip_prefix("Mathematical form: ")
-                .unwrap_or(lines[2])
-                .to_string();
-
-            let confidence_str = lines[3].strip_prefix("Confidence: ").unwrap_or("0.7").trim();
-
-            let translation_confidence = confidence_str.parse::<f32>().unwrap_or(0.7);
-
-            // Determine if human review is needed based on confidence
-            let requires_human_review = translation_confidence < 0.8;
+        let mut params = HashMap::new();
+        params.insert("domain".to_string(), domain.to_string());
+        params.insert("requirements".to_string(), requirements.join("\n"));
+        let prompt = self
+            .render_template("translate_to_properties", &params)
+            .map_err(AxiomError::from)?;
 
-            translations.push(SpecificationTranslation {
-                requirement,
-                interpreted_properties: vec![interpreted_property],
-                formal_representation,
-                translation_confidence,
-                verification_language: VerificationLanguage::FStarLang, // Default - would be adjustable
-                requires_human_review,
-            });
-        }
+        // Call the LLM API for a JSON list of translations
+        let response = self
+            .call_llm_structured::<TranslationList>(&prompt).await
+            .map_err(AxiomError::from)?;
 
-        Ok(translations)
+        // Translations below 0.8 confidence are flagged for human review
+        Ok(
+            response.translations
+                .into_iter()
+                .map(|translation| translation.into_translation(VerificationLanguage::FStarLang)) // Default - would be adjustable
+                .collect()
+        )
     }
 
     async fn convert_to_formal_specification(
@@ -1217,17 +1160,13 @@ impl SpecificationGenerator for LLMSpecificationGenerator {
             SpecificationParadigm::Custom(ref s) => s,
         };
 
-        let prompt = format!(
-            "You are a formal verification expert. Convert these formal properties into a complete {} specification using {}:\n\n\
-            {}\n\n\
-            Generate a complete, well-structured formal specification that captures all these properties. \
-            Include all necessary type definitions, functions, and verification statements. \
-            Format your response as a valid {} specification that could be directly input to the verification tool.",
-            target_language.to_string(),
-            paradigm_str,
-            properties_text,
-            target_language.to_string()
-        );
+        let mut params = HashMap::new();
+        params.insert("verification_language".to_string(), target_language.to_string());
+        params.insert("paradigm".to_string(), paradigm_str.to_string());
+        params.insert("properties".to_string(), properties_text);
+        let prompt = self
+            .render_template("convert_to_formal", &params)
+            .map_err(AxiomError::from)?;
 
         // Call the LLM API
         let response = self.call_llm_api(&prompt).await.map_err(AxiomError::from)?;
@@ -1256,16 +1195,14 @@ impl SpecificationGenerator for LLMSpecificationGenerator {
         }
 
         // Prepare the translation prompt
-        let prompt = format!(
-            "You are a formal verification expert. Translate this {} specification to {}:\n\n\
-            ```\n{}\n```\n\n\
-            Ensure that all properties and semantics are preserved in the translation. \
-            Format your response as a valid {} specification.",
-            spec.formal_spec.verification_language.to_string(),
-            target_language.to_string(),
-            spec.formal_spec.spec_code,
-            target_language.to_string()
+        let mut params = HashMap::new();
+        params.insert(
+            "source_language".to_string(),
+            spec.formal_spec.verification_language.to_string()
         );
+        params.insert("target_language".to_string(), target_language.to_string());
+        params.insert("spec_code".to_string(), spec.formal_spec.spec_code.clone());
+        let prompt = self.render_template("translate", &params).map_err(AxiomError::from)?;
 
         // Call the LLM API
         let response = self.call_llm_api(&prompt).await.map_err(AxiomError::from)?;
@@ -1297,6 +1234,7 @@ impl SpecificationGenerator for LLMSpecificationGenerator {
                     VerificationLanguage::Why3Lang =>
                         crate::models::common::VerificationSystem::Why3,
                     VerificationLanguage::Z3SMT => crate::models::common::VerificationSystem::Z3,
+                    VerificationLanguage::ACSL => crate::models::common::VerificationSystem::FramaC,
                     _ =>
                         crate::models::common::VerificationSystem::Custom(
                             target_language.to_string()
@@ -1319,21 +1257,21 @@ impl SpecificationGenerator for LLMSpecificationGenerator {
         info!("Verifying specification completeness against requirements");
 
         // Prepare the prompt
-        let prompt = format!(
-            "You are a formal verification expert. Check if this {} specification completely covers all requirements:\n\n\
-            Specification:\n```\n{}\n```\n\n\
-            Requirements:\n{}\n\n\
-            For each requirement, indicate whether it is fully covered, partially covered, or not covered by the specification. \
-            List any requirements that are not fully covered, explaining what aspects are missing. \
-            Finally, provide a boolean judgment: Is the specification complete (true/false)?",
-            spec.formal_spec.verification_language.to_string(),
-            spec.formal_spec.spec_code,
+        let mut params = HashMap::new();
+        params.insert(
+            "verification_language".to_string(),
+            spec.formal_spec.verification_language.to_string()
+        );
+        params.insert("spec_code".to_string(), spec.formal_spec.spec_code.clone());
+        params.insert(
+            "requirements".to_string(),
             requirements
                 .iter()
                 .map(|r| format!("- {}", r))
                 .collect::<Vec<_>>()
                 .join("\n")
         );
+        let prompt = self.render_template("completeness", &params).map_err(AxiomError::from)?;
 
         // Call the LLM API
         let response = self.call_llm_api(&prompt).await.map_err(AxiomError::from)?;
@@ -1376,6 +1314,7 @@ impl SpecificationGenerator for LLMSpecificationGenerator {
             crate::models::common::VerificationSystem::TLA => VerificationLanguage::TLAPlus,
             crate::models::common::VerificationSystem::Why3 => VerificationLanguage::Why3Lang,
             crate::models::common::VerificationSystem::Z3 => VerificationLanguage::Z3SMT,
+            crate::models::common::VerificationSystem::FramaC => VerificationLanguage::ACSL,
             _ => spec.formal_spec.verification_language.clone(),
         };
 
@@ -1391,16 +1330,13 @@ impl SpecificationGenerator for LLMSpecificationGenerator {
 
         // For some verification systems, we might need to add additional verification code
         // Here we'll simulate that with an LLM prompt
-        let prompt = format!(
-            "You are a formal verification expert. Generate executable verification code for this {} specification \
-            that can be used with {}:\n\n\
-            ```\n{}\n```\n\n\
-            Add any necessary verification directives, proof scripts, or commands needed to verify this specification. \
-            The result should be a complete file that can be directly verified using the appropriate tool.",
-            target_language.to_string(),
-            target_system.to_string(),
-            specification_code
-        );
+        let mut params = HashMap::new();
+        params.insert("verification_language".to_string(), target_language.to_string());
+        params.insert("verification_system".to_string(), target_system.to_string());
+        params.insert("spec_code".to_string(), specification_code);
+        let prompt = self
+            .render_template("verification_code", &params)
+            .map_err(AxiomError::from)?;
 
         // Call the LLM API
         let response = self.call_llm_api(&prompt).await.map_err(AxiomError::from)?;
@@ -1439,103 +1375,22 @@ impl SpecificationGenerator for LLMSpecificationGenerator {
         // In a real implementation, this would load templates from a repository or database
         // Here we're generating them on-the-fly with an LLM
 
-        let prompt = format!(
-            "You are a formal verification expert. Generate 3 template examples for {} specifications in {} for the {} domain. \
-            Each template should be a complete code example that can be parameterized. \
-            For each template, provide:\n\
-            1. A name describing its purpose\n\
-            2. The template code\n\
-            3. A list of placeholders that need to be filled in\n\
-            4. A brief documentation explaining how to use the template\n\
-            Format each template as: \"Name: [name]\\nTemplate:\\n```\\n[code]\\n```\\nPlaceholders: [list]\\nDocumentation: [explanation]\"",
-            language.to_string(),
-            language.to_string(),
-            domain.to_string()
-        );
-
-        // Call the LLM API
-        let response = self.call_llm_api(&prompt).await.map_err(AxiomError::from)?;
-
-        // Parse the response into templates
-        let mut templates = Vec::new();
-
-        // Split the response into template sections
-        let sections = response.split("\n\nName:").collect::<Vec<_>>();
-
-        for (i, section) in sections.iter().enumerate() {
-            if i == 0 && !section.starts_with("Name:") {
-                continue; // Skip introduction text
-            }
-
-            let content = if i == 0 { *section } else { &format!("Name:{}", *section) };
-
-            // Parse template components
-            let name_pattern = if i == 0 { "Name:" } else { "" };
-
-            let name = match content.find(name_pattern) {
-                Some(idx) => {
-                    let start = idx + name_pattern.len();
-                    let end = content[start..]
-                        .find('\n')
-                        .map(|e| start + e)
-                        .unwrap_or(content.len());
-                    content[start..end].trim().to_string()
-                }
-                None => format!("Template {}", i + 1),
-            };
-
-            let template_code = match content.find("```") {
-                Some(start_idx) => {
-                    let code_start = start_idx + 3;
-                    let code_end = content[code_start..]
-                        .find("```")
-                        .map(|e| code_start + e)
-                        .unwrap_or(content.len());
-                    content[code_start..code_end].trim().to_string()
-                }
-                None => "// Template code not found".to_string(),
-            };
-
-            let placeholders_start = content.find("Placeholders:");
-            let doc_start = content.find("Documentation:");
-
-            let placeholders = match (placeholders_start, doc_start) {
-                (Some(start), Some(end)) => {
-                    let ph_text = &content[start + "Placeholders:".len()..end];
-                    ph_text
-                        .lines()
-                        .map(|l| l.trim())
-                        .filter(|l| !l.is_empty())
-                        .map(|l| l.to_string())
-                        .collect::<Vec<_>>()
-                }
-                (Some(start), None) => {
-                    let ph_text = &content[start + "Placeholders:".len()..];
-                    ph_text
-                        .lines()
-                        .map(|l| l.trim())
-                        .filter(|l| !l.is_empty())
-                        .map(|l| l.to_string())
-                        .collect::<Vec<_>>()
-                }
-                _ => vec![],
-            };
-
-            let documentation = match doc_start {
-                Some(start) => content[start + "Documentation:".len()..].trim().to_string(),
-                None => "No documentation provided".to_string(),
-            };
+        let mut params = HashMap::new();
+        params.insert("domain".to_string(), domain.to_string());
+        params.insert("verification_language".to_string(), language.to_string());
+        let prompt = self.render_template("templates", &params).map_err(AxiomError::from)?;
 
-            templates.push(VerificationTemplate {
-                language: language.clone(),
-                template_name: name,
-                template_code,
-                placeholders,
-                documentation,
-            });
-        }
+        // Call the LLM API for a JSON list of templates
+        let response = self
+            .call_llm_structured::<TemplateList>(&prompt).await
+            .map_err(AxiomError::from)?;
 
-        Ok(templates)
+        Ok(
+            response.templates
+                .into_iter()
+                .map(|template| template.into_template(language.clone()))
+                .collect()
+        )
     }
 
     async fn apply_template(
@@ -1552,18 +1407,12 @@ impl SpecificationGenerator for LLMSpecificationGenerator {
             .collect::<Vec<_>>()
             .join("\n");
 
-        let prompt = format!(
-            "You are a formal verification expert. Apply this template to generate a formal specification for these properties:\n\n\
-            Template: {}\n\n\
-            ```\n{}\n```\n\n\
-            Properties:\n{}\n\n\
-            Fill in the template placeholders using these properties. The result should be a complete {} specification. \
-            Return only the filled template, no explanations.",
-            template.template_name,
-            template.template_code,
-            properties_text,
-            template.language.to_string()
-        );
+        let mut params = HashMap::new();
+        params.insert("template_name".to_string(), template.template_name.clone());
+        params.insert("template_code".to_string(), template.template_code.clone());
+        params.insert("properties".to_string(), properties_text);
+        params.insert("verification_language".to_string(), template.language.to_string());
+        let prompt = self.render_template("apply_template", &params).map_err(AxiomError::from)?;
 
         // Call the LLM API
         let response = self.call_llm_api(&prompt).await.map_err(AxiomError::from)?;
@@ -1613,14 +1462,10 @@ impl SpecificationGenerator for LLMSpecificationGenerator {
             .map_err(|e| AxiomError::SystemError(format!("Failed to read specification: {}", e)))?;
 
         // Ask the LLM to analyze the specification and extract requirements
-        let prompt = format!(
-            "You are a formal verification expert. Analyze this {} specification and extract the requirements it fulfills:\n\n\
-            ```\n{}\n```\n\n\
-            List each requirement that this specification addresses, in natural language form. \
-            Also extract any formal properties defined in the specification.",
-            language.to_string(),
-            spec_code
-        );
+        let mut params = HashMap::new();
+        params.insert("verification_language".to_string(), language.to_string());
+        params.insert("spec_code".to_string(), spec_code.clone());
+        let prompt = self.render_template("import", &params).map_err(AxiomError::from)?;
 
         // Call the LLM API
         let response = self.call_llm_api(&prompt).await.map_err(AxiomError::from)?;
@@ -1675,6 +1520,7 @@ impl SpecificationGenerator for LLMSpecificationGenerator {
                     VerificationLanguage::Why3Lang =>
                         crate::models::common::VerificationSystem::Why3,
                     VerificationLanguage::Z3SMT => crate::models::common::VerificationSystem::Z3,
+                    VerificationLanguage::ACSL => crate::models::common::VerificationSystem::FramaC,
                     _ => crate::models::common::VerificationSystem::Custom(language.to_string()),
                 },
                 domain: Domain::Custom("imported".to_string()),
@@ -1723,13 +1569,25 @@ impl LLMSpecificationGenerator {
 
             // Validate the fixed specification
             let validation_depth = if
+                current_report.tool_validated &&
+                current_report.issues
+                    .iter()
+                    .any(|i| FStarErrorCategory::of(i) == FStarErrorCategory::Proof)
+            {
+                ValidationDepth::FormalVerification // Proof failures need the full F* check again
+            } else if
+                current_report.tool_validated ||
                 current_report.issues
                     .iter()
                     .any(
-                        |i| matches!(i.severity, IssueSeverity::Error) && i.message.contains("type")
+                        |i|
+                            matches!(i.severity, IssueSeverity::Error) &&
+                            FStarErrorCategory::of(i) == FStarErrorCategory::Type
                     )
             {
-                ValidationDepth::TypeCheck // Upgrade to type checking if there are type errors
+                // Keep re-checking with the tool that found the issues, and upgrade to
+                // type checking if there are type errors
+                ValidationDepth::TypeCheck
             } else {
                 ValidationDepth::Basic // Start with basic syntax validation
             };
@@ -1753,6 +1611,8 @@ impl LLMSpecificationGenerator {
                     message: format!("Specification was automatically fixed after {} attempts", attempt),
                     related_property: None,
                     line_number: None,
+                    span: None,
+                    error_code: None,
                     suggested_fix: Some(fixed_spec.formal_spec.spec_code.clone()),
                 });
                 return Ok(success_report);
@@ -1771,6 +1631,8 @@ impl LLMSpecificationGenerator {
             message: format!("Automatic fixing was attempted {} times but issues remain", MAX_RETRIES),
             related_property: None,
             line_number: None,
+            span: None,
+            error_code: None,
             suggested_fix: Some(current_spec.formal_spec.spec_code.clone()),
         });
 
@@ -1793,16 +1655,20 @@ impl LLMSpecificationGenerator {
         let mut type_errors = Vec::new();
 
         for issue in &report.issues {
-            let msg = issue.message.to_lowercase();
-            if msg.contains("undefined") && (msg.contains("function") || msg.contains("predicate")) {
-                // Extract the function name
-                if let Some(name) = Self::extract_name_from_error(&issue.message) {
-                    missing_functions.push(name);
+            match FStarErrorCategory::of(issue) {
+                FStarErrorCategory::MissingDefinition => {
+                    // Extract the function name
+                    if
+                        let Some(name) = missing_identifier(issue).or_else(||
+                            Self::extract_name_from_error(&issue.message)
+                        )
+                    {
+                        missing_functions.push(name);
+                    }
                 }
-            } else if msg.contains("syntax") || msg.contains("expected") || msg.contains("missing") {
-                syntax_issues.push(issue);
-            } else if msg.contains("type") {
-                type_errors.push(issue);
+                FStarErrorCategory::Syntax => syntax_issues.push(issue),
+                FStarErrorCategory::Type => type_errors.push(issue),
+                FStarErrorCategory::Proof | FStarErrorCategory::Other => {}
             }
         }
 
@@ -1816,10 +1682,22 @@ impl LLMSpecificationGenerator {
                     IssueSeverity::Info => "Info",
                 };
 
-                let line_info = if let Some(line) = issue.line_number {
-                    format!("Line {}", line)
-                } else {
-                    "Unknown location".to_string()
+                let line_info = match (&issue.span, issue.line_number) {
+                    (Some(span), _) =>
+                        format!(
+                            "Line {}, columns {}-{} (to line {})",
+                            span.start_line,
+                            span.start_column,
+                            span.end_column,
+                            span.end_line
+                        ),
+                    (None, Some(line)) => format!("Line {}", line),
+                    (None, None) => "Unknown location".to_string(),
+                };
+
+                let severity = match issue.error_code {
+                    Some(code) => format!("{} {}", severity, code),
+                    None => severity.to_string(),
                 };
 
                 let fix_info = if let Some(fix) = &issue.suggested_fix {
@@ -1833,8 +1711,8 @@ impl LLMSpecificationGenerator {
             .collect::<Vec<_>>()
             .join("\n\n");
 
-        // Always use F* guidelines since we only support F*
-        let language_guidelines = self.get_fstar_guidelines();
+        let language = &spec.formal_spec.verification_language;
+        let language_guidelines = self.language_guidelines(language);
 
         // Add specific fixing requirements based on issue analysis
         let mut specific_fixes = String::new();
@@ -1853,8 +1731,13 @@ impl LLMSpecificationGenerator {
         }
 
         if !syntax_issues.is_empty() {
+            let syntax = match language {
+                VerificationLanguage::FStarLang =>
+                    "proper keywords (let/val), closing braces, and proper F* syntax".to_string(),
+                other => format!("balanced delimiters and proper {} syntax", other),
+            };
             specific_fixes.push_str(
-                "IMPORTANT: Fix all syntax errors, ensuring proper keywords (let/val), closing braces, and proper F* syntax.\n\n"
+                &format!("IMPORTANT: Fix all syntax errors, ensuring {}.\n\n", syntax)
             );
         }
 
@@ -1864,25 +1747,13 @@ impl LLMSpecificationGenerator {
             );
         }
 
-        let prompt = format!(
-            "You are a formal verification expert. Fix the following issues in this F* specification:\n\n\
-            Original specification:\n\
-            ```\n{}\n```\n\n\
-            Issues to fix:\n\
-            {}\n\n\
-            {}\n\
-            {}\n\
-            Requirements:\n\
-            1. Implement ALL missing functions/predicates with simple but valid implementations\n\
-            2. Ensure proper syntax for F* (including all keywords and braces)\n\
-            3. Fix ALL identified issues\n\
-            4. Return a COMPLETE specification that preserves the original functionality\n\n\
-            Return ONLY the corrected specification code without any explanations.",
-            spec.formal_spec.spec_code,
-            issue_list,
-            specific_fixes,
-            language_guidelines
-        );
+        let mut params = HashMap::new();
+        params.insert("spec_code".to_string(), spec.formal_spec.spec_code.clone());
+        params.insert("issues".to_string(), issue_list);
+        params.insert("specific_fixes".to_string(), specific_fixes);
+        params.insert("language_guidelines".to_string(), language_guidelines);
+        params.insert("verification_language".to_string(), language.to_string());
+        let prompt = self.render_template("fix", &params).map_err(AxiomError::from)?;
 
         // Call the LLM API to get a fixed specification
         let response = self.call_llm_api(&prompt).await.map_err(AxiomError::from)?;
@@ -1938,7 +1809,7 @@ impl LLMSpecificationGenerator {
                 let start = start_idx + pattern.len();
                 // Find the end of the identifier (usually followed by space, comma, or period)
                 let end_idx = error_message[start..]
-                    .find(|c| (c == ' ' || c == ',' || c == '.' || c == ':'))
+                    .find([' ', ',', '.', ':'])
                     .unwrap_or(error_message[start..].len());
 
                 return Some(error_message[start..start + end_idx].trim().to_string());
@@ -1949,304 +1820,179 @@ impl LLMSpecificationGenerator {
     }
     async fn validate_syntax(&self, spec: &Specification) -> AxiomResult<ValidationReport> {
         // Prepare the prompt for syntax validation
-        let prompt = format!(
-            "You are a formal verification expert. Validate the syntax of this {} specification:\n\n\
-            ```\n{}\n```\n\n\
-            Check for syntax errors, undefined references, and basic consistency issues. \
-            For each issue found, provide:\n\
-            1. The line number or location\n\
-            2. A description of the issue\n\
-            3. The severity (Error, Warning, or Info)\n\
-            4. A suggested fix\n\
-            Format each issue as: \"Line [number]: [description] - [severity]\\nSuggestion: [fix]\"\n\n\
-            After listing all issues, provide a final judgment: Is the specification syntax valid (true/false)?",
-            spec.formal_spec.verification_language.to_string(),
-            spec.formal_spec.spec_code
-        );
-
-        // Call the LLM API
-        let response = self.call_llm_api(&prompt).await.map_err(AxiomError::from)?;
+        let prompt = self
+            .render_template("validate_syntax", &spec_params(spec))
+            .map_err(AxiomError::from)?;
 
-        // Parse the response to determine validity and extract issues
-        let is_valid =
-            response.to_lowercase().contains("the specification syntax is valid: true") ||
-            response.to_lowercase().contains("is the specification syntax valid? true");
+        // Call the LLM API for a JSON verdict
+        let verdict = self
+            .call_llm_structured::<ValidationVerdict>(&prompt).await
+            .map_err(AxiomError::from)?;
 
-        // Extract issues
-        let mut issues = Vec::new();
-        let mut current_issue = String::new();
-        let mut current_line = None;
-        let mut current_severity = IssueSeverity::Info;
-        let mut current_suggestion = None;
+        Ok(verdict.into_report())
+    }
 
-        for line in response.lines() {
-            if line.starts_with("Line ") || line.starts_with("Location ") {
-                // Save the previous issue if it exists
-                if !current_issue.is_empty() {
-                    issues.push(ValidationIssue {
-                        severity: current_severity.clone(),
-                        message: current_issue.clone(),
-                        related_property: None,
-                        line_number: current_line,
-                        suggested_fix: current_suggestion.clone(),
-                    });
+    /// Run the F* backend on the specification alone
+    async fn run_fstar(
+        backend: FStarBackend,
+        spec: &Specification,
+        lax: bool
+    ) -> AxiomResult<Option<VerificationResult>> {
+        let converted_spec = backend.convert_specification(spec)?;
+        tokio::task
+            ::spawn_blocking(move || {
+                if !backend.check_backend_availability()? {
+                    warn!("F* not found at '{}', falling back to LLM validation", backend.fstar_path());
+                    return Ok(None);
                 }
 
-                // Parse the new issue
-                current_issue = line.to_string();
-
-                // Extract line number
-                if let Some(start) = line.find("Line ") {
-                    if let Some(end) = line[start + 5..].find(':') {
-                        if
-                            let Ok(line_num) = line[start + 5..start + 5 + end]
-                                .trim()
-                                .parse::<usize>()
-                        {
-                            current_line = Some(line_num);
-                        }
-                    }
-                }
+                info!("Checking specification with F*{}", if lax { " (lax)" } else { "" });
+                let options = VerificationOptions {
+                    timeout: backend.timeout(),
+                    ..VerificationOptions::default()
+                };
+                backend.execute_verification(&converted_spec, "", &options).map(Some)
+            }).await
+            .map_err(|e| AxiomError::SystemError(format!("F* check was interrupted: {}", e)))?
+    }
 
-                // Extract severity
-                if line.contains("Error") {
-                    current_severity = IssueSeverity::Error;
-                } else if line.contains("Warning") {
-                    current_severity = IssueSeverity::Warning;
-                } else {
-                    current_severity = IssueSeverity::Info;
+    /// Run the registered backend for the specification's verification system on the
+    /// specification alone
+    ///
+    /// Backends have no type-check-only mode, so a lax check runs at the quick proof level.
+    async fn run_registered_backend(
+        &self,
+        spec: &Specification,
+        lax: bool
+    ) -> AxiomResult<Option<VerificationResult>> {
+        let Some(backends) = self.backends.clone() else {
+            return Ok(None);
+        };
+        let system = spec.formal_spec.verification_language.verification_system();
+        let Some(backend) = backends.get(&system) else {
+            return Ok(None);
+        };
+        let converted_spec = backend.convert_specification(spec)?;
+        let options = VerificationOptions {
+            timeout: self.tool_timeout,
+            proof_level: if lax { ProofLevel::Quick } else { ProofLevel::Standard },
+            ..VerificationOptions::default()
+        };
+        let name = system.to_string();
+        tokio::task
+            ::spawn_blocking(move || {
+                let backend = backends.get(&system).expect("backend is registered");
+                if !backend.check_backend_availability()? {
+                    warn!("{} is not installed, falling back to LLM validation", system);
+                    return Ok(None);
                 }
 
-                current_suggestion = None;
-            } else if line.starts_with("Suggestion:") {
-                current_suggestion = Some(
-                    line.trim_start_matches("Suggestion:").trim().to_string()
-                );
-            }
-        }
-
-        // Add the last issue if it exists
-        if !current_issue.is_empty() {
-            issues.push(ValidationIssue {
-                severity: current_severity,
-                message: current_issue,
-                related_property: None,
-                line_number: current_line,
-                suggested_fix: current_suggestion,
-            });
-        }
-
-        Ok(ValidationReport {
-            is_valid,
-            issues,
-            tool_validated: false,
-            tool_output: None,
-        })
+                info!("Checking specification with {}{}", system, if lax { " (quick)" } else { "" });
+                backend.execute_verification(&converted_spec, "", &options).map(Some)
+            }).await
+            .map_err(|e| AxiomError::SystemError(format!("{} check was interrupted: {}", name, e)))?
     }
 
-    async fn validate_type_checking(&self, spec: &Specification) -> AxiomResult<ValidationReport> {
-        // Prepare the prompt for type checking validation
-        let prompt = format!(
-            "You are a formal verification expert with deep knowledge of {} type systems. \
-            Perform type checking on this specification:\n\n\
-            ```\n{}\n```\n\n\
-            Check for type errors, type inconsistencies, and type-related issues. \
-            For each issue found, provide:\n\
-            1. The line number or location\n\
-            2. A description of the type error\n\
-            3. The severity (Error, Warning, or Info)\n\
-            4. A suggested fix\n\
-            Format each issue as: \"Line [number]: [description] - [severity]\\nSuggestion: [fix]\"\n\n\
-            After listing all issues, provide a final judgment: Does the specification pass type checking (true/false)?",
-            spec.formal_spec.verification_language.to_string(),
-            spec.formal_spec.spec_code
-        );
-
-        // Call the LLM API
-        let response = self.call_llm_api(&prompt).await.map_err(AxiomError::from)?;
+    /// Check the specification with the backend for its verification language if one is
+    /// configured and installed. Returns `None` when validation has to fall back to the LLM.
+    ///
+    /// The tool runs on the blocking thread pool, within the configured tool timeout.
+    async fn validate_with_backend(
+        &self,
+        spec: &Specification,
+        lax: bool
+    ) -> AxiomResult<Option<ValidationReport>> {
+        let language = spec.formal_spec.verification_language.clone();
+        let result = match &self.fstar_backend {
+            Some(backend) if language == VerificationLanguage::FStarLang =>
+                Self::run_fstar(backend.clone().with_lax(lax), spec, lax).await?,
+            _ => self.run_registered_backend(spec, lax).await?,
+        };
+        let Some(result) = result else {
+            return Ok(None);
+        };
+        let system = language.verification_system();
 
-        // Parse the response to determine validity and extract issues
-        let is_valid =
-            response.to_lowercase().contains("the specification passes type checking: true") ||
-            response.to_lowercase().contains("does the specification pass type checking? true");
+        let tool_output = result.proof_artifacts
+            .iter()
+            .find(|a| matches!(a.artifact_type, crate::models::verification::ArtifactType::Log))
+            .and_then(|a| std::fs::read_to_string(&a.path).ok());
 
-        // Extract issues (similar to validate_syntax)
-        let mut issues = Vec::new();
-        let mut current_issue = String::new();
-        let mut current_line = None;
-        let mut current_severity = IssueSeverity::Info;
-        let mut current_suggestion = None;
+        let issue = |message: String| ValidationIssue {
+            severity: IssueSeverity::Error,
+            message,
+            related_property: None,
+            line_number: None,
+            span: None,
+            error_code: None,
+            suggested_fix: None,
+        };
 
-        for line in response.lines() {
-            if line.starts_with("Line ") || line.starts_with("Location ") {
-                // Save the previous issue if it exists
-                if !current_issue.is_empty() {
-                    issues.push(ValidationIssue {
-                        severity: current_severity.clone(),
-                        message: current_issue.clone(),
-                        related_property: None,
-                        line_number: current_line,
-                        suggested_fix: current_suggestion.clone(),
-                    });
-                }
+        // Keep warnings even when the specification checks, so the report shows them
+        let diagnostics = match language {
+            VerificationLanguage::FStarLang =>
+                tool_output.as_deref().map(parse_fstar_diagnostics).unwrap_or_default(),
+            _ => Vec::new(),
+        };
 
-                // Parse the new issue
-                current_issue = line.to_string();
-
-                // Extract line number
-                if let Some(start) = line.find("Line ") {
-                    if let Some(end) = line[start + 5..].find(':') {
-                        if
-                            let Ok(line_num) = line[start + 5..start + 5 + end]
-                                .trim()
-                                .parse::<usize>()
-                        {
-                            current_line = Some(line_num);
-                        }
-                    }
-                }
+        let (is_valid, issues) = match result.status {
+            VerificationStatus::Verified => (true, diagnostics),
+            VerificationStatus::Failed(_) if !diagnostics.is_empty() => (false, diagnostics),
+            VerificationStatus::Failed(reasons) => (false, reasons.into_iter().map(issue).collect()),
+            VerificationStatus::Timeout =>
+                (false, vec![issue(format!("{} timed out while checking the specification", system))]),
+            VerificationStatus::Unverified =>
+                (false, vec![issue(format!("{} could not check the specification", system))]),
+            VerificationStatus::Error(msg) => (false, vec![issue(msg)]),
+        };
 
-                // Extract severity
-                if line.contains("Error") {
-                    current_severity = IssueSeverity::Error;
-                } else if line.contains("Warning") {
-                    current_severity = IssueSeverity::Warning;
-                } else {
-                    current_severity = IssueSeverity::Info;
-                }
+        Ok(
+            Some(ValidationReport {
+                is_valid,
+                issues,
+                tool_validated: true,
+                tool_output,
+            })
+        )
+    }
 
-                current_suggestion = None;
-            } else if line.starts_with("Suggestion:") {
-                current_suggestion = Some(
-                    line.trim_start_matches("Suggestion:").trim().to_string()
-                );
-            }
+    async fn validate_type_checking(&self, spec: &Specification) -> AxiomResult<ValidationReport> {
+        if let Some(report) = self.validate_with_backend(spec, true).await? {
+            return Ok(report);
         }
 
-        // Add the last issue if it exists
-        if !current_issue.is_empty() {
-            issues.push(ValidationIssue {
-                severity: current_severity,
-                message: current_issue,
-                related_property: None,
-                line_number: current_line,
-                suggested_fix: current_suggestion,
-            });
-        }
+        // Prepare the prompt for type checking validation
+        let prompt = self
+            .render_template("validate_type_checking", &spec_params(spec))
+            .map_err(AxiomError::from)?;
 
-        Ok(ValidationReport {
-            is_valid,
-            issues,
-            tool_validated: false,
-            tool_output: None,
-        })
+        // Call the LLM API for a JSON verdict
+        let verdict = self
+            .call_llm_structured::<ValidationVerdict>(&prompt).await
+            .map_err(AxiomError::from)?;
+
+        Ok(verdict.into_report())
     }
 
     async fn validate_formal_verification(
         &self,
         spec: &Specification
     ) -> AxiomResult<ValidationReport> {
-        // Prepare the prompt for formal verification validation
-        let prompt = format!(
-            "You are a formal verification expert with deep knowledge of {}. \
-            Validate whether this specification can be formally verified:\n\n\
-            ```\n{}\n```\n\n\
-            Check for issues that would prevent successful verification, such as:\n\
-            1. Incompleteness in definitions\n\
-            2. Unprovable assertions or theorems\n\
-            3. Missing lemmas or auxiliary functions\n\
-            4. Inconsistent axioms\n\
-            For each issue found, provide:\n\
-            1. The line number or location\n\
-            2. A description of the verification issue\n\
-            3. The severity (Error, Warning, or Info)\n\
-            4. A suggested fix\n\
-            Format each issue as: \"Line [number]: [description] - [severity]\\nSuggestion: [fix]\"\n\n\
-            After listing all issues, provide a final judgment: Can the specification be formally verified as written (true/false)?",
-            spec.formal_spec.verification_language.to_string(),
-            spec.formal_spec.spec_code
-        );
-
-        // Call the LLM API
-        let response = self.call_llm_api(&prompt).await.map_err(AxiomError::from)?;
-
-        // Parse the response to determine validity and extract issues
-        let is_valid =
-            response.to_lowercase().contains("the specification can be formally verified: true") ||
-            response
-                .to_lowercase()
-                .contains("can the specification be formally verified as written? true");
-
-        // Extract issues (similar to validate_syntax)
-        let mut issues = Vec::new();
-        let mut current_issue = String::new();
-        let mut current_line = None;
-        let mut current_severity = IssueSeverity::Info;
-        let mut current_suggestion = None;
-
-        for line in response.lines() {
-            if line.starts_with("Line ") || line.starts_with("Location ") {
-                // Save the previous issue if it exists
-                if !current_issue.is_empty() {
-                    issues.push(ValidationIssue {
-                        severity: current_severity.clone(),
-                        message: current_issue.clone(),
-                        related_property: None,
-                        line_number: current_line,
-                        suggested_fix: current_suggestion.clone(),
-                    });
-                }
-
-                // Parse the new issue
-                current_issue = line.to_string();
-
-                // Extract line number
-                if let Some(start) = line.find("Line ") {
-                    if let Some(end) = line[start + 5..].find(':') {
-                        if
-                            let Ok(line_num) = line[start + 5..start + 5 + end]
-                                .trim()
-                                .parse::<usize>()
-                        {
-                            current_line = Some(line_num);
-                        }
-                    }
-                }
-
-                // Extract severity
-                if line.contains("Error") {
-                    current_severity = IssueSeverity::Error;
-                } else if line.contains("Warning") {
-                    current_severity = IssueSeverity::Warning;
-                } else {
-                    current_severity = IssueSeverity::Info;
-                }
-
-                current_suggestion = None;
-            } else if line.starts_with("Suggestion:") {
-                current_suggestion = Some(
-                    line.trim_start_matches("Suggestion:").trim().to_string()
-                );
-            }
+        if let Some(report) = self.validate_with_backend(spec, false).await? {
+            return Ok(report);
         }
 
-        // Add the last issue if it exists
-        if !current_issue.is_empty() {
-            issues.push(ValidationIssue {
-                severity: current_severity,
-                message: current_issue,
-                related_property: None,
-                line_number: current_line,
-                suggested_fix: current_suggestion,
-            });
-        }
+        // Prepare the prompt for formal verification validation
+        let prompt = self
+            .render_template("validate_formal_verification", &spec_params(spec))
+            .map_err(AxiomError::from)?;
 
-        Ok(ValidationReport {
-            is_valid,
-            issues,
-            tool_validated: false,
-            tool_output: Some(response),
-        })
+        // Call the LLM API for a JSON verdict
+        let verdict = self
+            .call_llm_structured::<ValidationVerdict>(&prompt).await
+            .map_err(AxiomError::from)?;
+
+        Ok(verdict.into_report())
     }
 }
 // Implement to_string for Domain, VerificationLanguage, etc.
@@ -2297,7 +2043,28 @@ impl std::fmt::Display for crate::models::common::VerificationSystem {
             crate::models::common::VerificationSystem::TLA => write!(f, "TLA+"),
             crate::models::common::VerificationSystem::Why3 => write!(f, "Why3"),
             crate::models::common::VerificationSystem::Z3 => write!(f, "Z3"),
+            crate::models::common::VerificationSystem::FramaC => write!(f, "Frama-C"),
             crate::models::common::VerificationSystem::Custom(name) => write!(f, "{}", name),
         }
     }
 }
+
+impl std::fmt::Display for crate::models::common::Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            crate::models::common::Language::Rust => write!(f, "Rust"),
+            crate::models::common::Language::C => write!(f, "C"),
+            crate::models::common::Language::CPlusPlus => write!(f, "C++"),
+            crate::models::common::Language::Python => write!(f, "Python"),
+            crate::models::common::Language::JavaScript => write!(f, "JavaScript"),
+            crate::models::common::Language::Go => write!(f, "Go"),
+            crate::models::common::Language::Haskell => write!(f, "Haskell"),
+            crate::models::common::Language::OCaml => write!(f, "OCaml"),
+            crate::models::common::Language::Java => write!(f, "Java"),
+            crate::models::common::Language::CSharp => write!(f, "C#"),
+            crate::models::common::Language::Scala => write!(f, "Scala"),
+            crate::models::common::Language::Swift => write!(f, "Swift"),
+            crate::models::common::Language::Custom(name) => write!(f, "{}", name),
+        }
+    }
+}
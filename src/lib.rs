@@ -2,14 +2,24 @@ pub mod models;
 pub mod traits;
 pub mod errors;
 pub mod config;
+pub mod project;
 pub mod implementations;
 #[cfg(test)]
 pub mod tests;
 
 // Re-export core components
 pub use config::{AxiomConfig, AxiomOptions};
+pub use project::{Project, ProjectManifest};
 pub use errors::{AxiomError, AxiomResult, ErrorContext, ErrorSeverity, RecoverableError};
 pub use implementations::specification_generator::LLMSpecificationGenerator;
+pub use implementations::implementation_generator::LLMImplementationGenerator;
+pub use implementations::fstar_extraction::FStarExtractor;
+pub use implementations::adapters::{CAdapter, RustAdapter};
+pub use implementations::llm::{LlmClient, LlmRequest};
+pub use implementations::backends::{
+    BackendRegistry, CoqBackend, DafnyBackend, FStarBackend, FramaCBackend, LeanBackend, TlcBackend, Why3Backend,
+    Z3Backend,
+};
 pub use models::{
     common::{
         Domain, 
@@ -29,6 +39,7 @@ pub use models::{
     },
     implementation::Implementation, 
     verification::{
+        Counterexample,
         VerificationResult, 
         VerificationStatus,
     },
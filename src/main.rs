@@ -1,34 +1,83 @@
-use std::path::PathBuf;
-use axiom::SpecificationGenerator;
+use std::sync::Arc;
+use axiom::{ ImplementationGenerator, SpecificationGenerator, VerificationEngine };
 use clap::Parser;
-use log::{ error, info };
+use log::{ error, info, warn };
 use anyhow::Result;
-use axiom;
-use crate::axiom::models;
-use crate::axiom::config;
-use crate::axiom::errors;
-use crate::axiom::traits;
-use crate::axiom::implementations::specification_generator::LLMSpecificationGenerator;
-use crate::axiom::implementations::config::GeneratorConfig;
+use axiom::models;
+use axiom::config;
+use axiom::errors;
+use axiom::traits;
+use axiom::project;
+use axiom::implementations::specification_generator::LLMSpecificationGenerator;
+use axiom::implementations::implementation_generator::LLMImplementationGenerator;
+use axiom::implementations::fstar_extraction::FStarExtractor;
+use axiom::implementations::config::GeneratorConfig;
+use axiom::implementations::llm::{ CancelFlag, ResponseCacheConfig, StreamObserver };
+use axiom::implementations::backends::{ BackendRegistry, FStarBackend };
 mod cli;
 use cli::{ AxiomCli, Commands };
 
-// Implementation of AxiomSystem that uses the LLMSpecificationGenerator for specification functionality
+// Implementation of AxiomSystem that uses the LLM generators for specifications and implementations,
+// and extracts OCaml and C code from F* specifications with definitions
 struct AxiomSystemImpl {
     spec_generator: LLMSpecificationGenerator,
+    impl_generator: LLMImplementationGenerator,
+    extractor: FStarExtractor,
+    /// Specification and source code of each implementation extracted in this run
+    extracted: std::sync::Mutex<std::collections::HashSet<(String, String)>>,
+    backends: Arc<BackendRegistry>,
 }
 
 impl AxiomSystemImpl {
-    fn new() -> Self {
-        // Create a default config for the specification generator
-        let config = GeneratorConfig::default();
-        let spec_generator = LLMSpecificationGenerator::new(config);
+    fn new(
+        config: GeneratorConfig,
+        tools_config: axiom::config::ExternalToolsConfig,
+        progress: Arc<dyn StreamObserver>,
+        cancel: CancelFlag
+    ) -> Self {
+        let backends = Arc::new(BackendRegistry::from_config(&tools_config));
+        let spec_generator = LLMSpecificationGenerator::new(config.clone())
+            .with_fstar_backend(FStarBackend::new(&tools_config))
+            .with_backend_registry(backends.clone(), tools_config.timeout)
+            .with_stream_observer(progress.clone())
+            .with_cancel_flag(cancel.clone());
+        let impl_generator = LLMImplementationGenerator::new(config)
+            .with_usage_tracker(spec_generator.usage_tracker())
+            .with_stream_observer(progress)
+            .with_cancel_flag(cancel);
+        let extractor = FStarExtractor::new(&tools_config);
+
+        Self {
+            spec_generator,
+            impl_generator,
+            extractor,
+            extracted: Default::default(),
+            backends,
+        }
+    }
+
+    /// Engine running the backend for the language of a specification
+    fn engine_for(
+        &self,
+        formal_spec: &crate::models::specification::FormalSpecification
+    ) -> crate::errors::AxiomResult<axiom::implementations::backends::BackendEngine<'_>> {
+        let system = formal_spec.verification_language.verification_system();
+        self.backends
+            .engine(&system)
+            .ok_or_else(||
+                crate::errors::AxiomError::VerificationError(
+                    format!("No verification backend is registered for {}", system)
+                )
+            )
+    }
 
-        Self { spec_generator }
+    /// Token usage and cost of the LLM calls made so far
+    fn llm_usage(&self) -> axiom::implementations::llm::UsageSummary {
+        self.spec_generator.usage_summary()
     }
 }
 
-impl crate::axiom::traits::axiom_system::AxiomSystem for AxiomSystemImpl {
+impl axiom::traits::axiom_system::AxiomSystem for AxiomSystemImpl {
     fn process_requirements(
         &self,
         _requirements: &[String],
@@ -51,10 +100,14 @@ impl crate::axiom::traits::axiom_system::AxiomSystem for AxiomSystemImpl {
 
     fn refine_to_satisfy(
         &self,
-        _implementation: &crate::models::implementation::Implementation,
-        _spec: &crate::models::specification::Specification
+        implementation: &crate::models::implementation::Implementation,
+        spec: &crate::models::specification::Specification
     ) -> crate::errors::AxiomResult<crate::models::implementation::Implementation> {
-        Err(crate::errors::AxiomError::SystemError("Not implemented".to_string()))
+        let engine = self.engine_for(&spec.formal_spec)?;
+        let options = crate::models::verification::VerificationOptions::default();
+        run_on_runtime_thread(
+            self.impl_generator.refine_until_verified(implementation, spec, &engine, &options)
+        )
     }
 
     // Generate a formal specification from requirements
@@ -141,7 +194,7 @@ impl crate::axiom::traits::axiom_system::AxiomSystem for AxiomSystemImpl {
     fn validate_specification(
         &self,
         spec: &crate::models::specification::Specification,
-        requirements: &[String],
+        _requirements: &[String],
         validation_depth: crate::traits::specification_generator::ValidationDepth
     ) -> crate::errors::AxiomResult<crate::models::specification::ValidationReport> {
         info!("Validating specification with depth: {:?}", validation_depth);
@@ -177,7 +230,7 @@ impl crate::axiom::traits::axiom_system::AxiomSystem for AxiomSystemImpl {
                         if let Some(line) = issue.line_number {
                             info!("  At line: {}", line);
                         }
-                        if let Some(fix) = &issue.suggested_fix {
+                        if issue.suggested_fix.is_some() {
                             info!("  Suggested fix available");
                         }
                     }
@@ -222,28 +275,69 @@ impl crate::axiom::traits::axiom_system::AxiomSystem for AxiomSystemImpl {
 
     fn generate_implementation_from_formal_spec(
         &self,
-        _formal_spec: &crate::models::specification::FormalSpecification,
-        _target_language: crate::models::common::Language,
-        _options: &crate::models::implementation::ImplementationOptions
+        formal_spec: &crate::models::specification::FormalSpecification,
+        target_language: crate::models::common::Language,
+        options: &crate::models::implementation::ImplementationOptions
     ) -> crate::errors::AxiomResult<crate::models::implementation::Implementation> {
-        Err(crate::errors::AxiomError::SystemError("Not implemented".to_string()))
+        info!("Generating implementation in {}", target_language);
+        let spec = specification_of(formal_spec);
+        if FStarExtractor::can_extract(&spec, &target_language) {
+            info!("Extracting the implementation from the F* definitions");
+            let implementation = run_on_runtime_thread(
+                self.extractor.generate_implementation(&spec, target_language, options)
+            )?;
+            self.extracted
+                .lock()
+                .unwrap()
+                .insert((formal_spec.spec_code.clone(), implementation.source_code.clone()));
+            return Ok(implementation);
+        }
+        run_on_runtime_thread(
+            self.impl_generator.generate_implementation(&spec, target_language, options)
+        )
     }
 
     fn verify_against_formal_spec(
         &self,
-        _implementation: &crate::models::implementation::Implementation,
-        _formal_spec: &crate::models::specification::FormalSpecification,
-        _options: &crate::models::verification::VerificationOptions
+        implementation: &crate::models::implementation::Implementation,
+        formal_spec: &crate::models::specification::FormalSpecification,
+        options: &crate::models::verification::VerificationOptions
     ) -> crate::errors::AxiomResult<crate::models::verification::VerificationResult> {
-        Err(crate::errors::AxiomError::SystemError("Not implemented".to_string()))
+        let engine = self.engine_for(formal_spec)?;
+        let spec = specification_of(formal_spec);
+        if engine.can_verify(&spec, &implementation.language) {
+            return engine.verify(implementation, &spec, options);
+        }
+
+        // Code extracted from F* in this run carries the result of checking the module it
+        // came from; a stored result, e.g. one loaded from JSON, is not trusted
+        let extracted_here = self.extracted
+            .lock()
+            .unwrap()
+            .contains(&(formal_spec.spec_code.clone(), implementation.source_code.clone()));
+        if extracted_here && FStarExtractor::is_extracted(implementation) {
+            return Ok(implementation.verification_result.clone());
+        }
+        warn!(
+            "{} cannot check {} code; the implementation is unverified",
+            engine.verification_system(),
+            implementation.language
+        );
+        Ok(crate::models::verification::VerificationResult {
+            status: crate::models::verification::VerificationStatus::Unverified,
+            proof_artifacts: vec![],
+            verification_time: std::time::Duration::ZERO,
+            resource_usage: Default::default(),
+            goal_results: vec![],
+            counterexamples: vec![],
+        })
     }
 
     fn is_verification_system_available(
         &self,
-        _system: crate::models::common::VerificationSystem
+        system: crate::models::common::VerificationSystem
     ) -> crate::errors::AxiomResult<bool> {
-        // Assume all verification systems are available for this prototype
-        Ok(true)
+        self.backends.is_available(&system)
     }
 
     fn get_recommended_verification_system(
@@ -252,15 +346,7 @@ impl crate::axiom::traits::axiom_system::AxiomSystem for AxiomSystemImpl {
         _implementation_language: crate::models::common::Language
     ) -> crate::errors::AxiomResult<crate::models::common::VerificationSystem> {
         // Return a recommended system based on the domain
-        match domain {
-            crate::models::common::Domain::Cryptography =>
-                Ok(crate::models::common::VerificationSystem::FStar),
-            crate::models::common::Domain::DistributedSystems =>
-                Ok(crate::models::common::VerificationSystem::TLA),
-            crate::models::common::Domain::WebSecurity =>
-                Ok(crate::models::common::VerificationSystem::Dafny),
-            _ => Ok(crate::models::common::VerificationSystem::FStar),
-        }
+        Ok(project::recommended_verification_system(&domain))
     }
 
     fn export_verification_project(
@@ -332,6 +418,48 @@ impl crate::axiom::traits::axiom_system::AxiomSystem for AxiomSystemImpl {
     }
 }
 
+/// Specification wrapping a formal specification that is used on its own
+fn specification_of(
+    formal_spec: &crate::models::specification::FormalSpecification
+) -> crate::models::specification::Specification {
+    crate::models::specification::Specification {
+        id: "spec".to_string(),
+        source_requirements: vec![],
+        formal_properties: vec![],
+        formal_spec: formal_spec.clone(),
+        metadata: crate::models::specification::SpecificationMetadata {
+            created_at: chrono::Utc::now(),
+            verification_system: formal_spec.verification_language.verification_system(),
+            domain: crate::models::common::Domain::Custom("implementation".to_string()),
+            confidence_score: 0.9,
+            is_formally_validated: false,
+        },
+    }
+}
+
+/// Run an async generator call from a synchronous `AxiomSystem` method
+///
+/// The CLI is already inside a Tokio runtime, so the call gets its own runtime on a
+/// scoped thread, which may borrow from the caller.
+fn run_on_runtime_thread<T: Send>(
+    future: impl std::future::Future<Output = crate::errors::AxiomResult<T>> + Send
+) -> crate::errors::AxiomResult<T> {
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                let rt = tokio::runtime::Runtime
+                    ::new()
+                    .map_err(|e| crate::errors::AxiomError::SystemError(e.to_string()))?;
+                rt.block_on(future)
+            })
+            .join()
+            .unwrap_or_else(|_| {
+                error!("Thread panicked while running a generator");
+                Err(crate::errors::AxiomError::SystemError("Generator thread panicked".to_string()))
+            })
+    })
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Parse the command line arguments
@@ -340,11 +468,76 @@ async fn main() -> Result<()> {
     // Setup logging
     setup_logging(&cli.log_level);
 
+    // Keep stdout for the JSON document in JSON mode
+    cli::ui::set_json_output(cli.output_format.eq_ignore_ascii_case("json"));
+
+    // Scaffolding a project does not need the rest of the system
+    if let Commands::Init { directory, language, verification_system, domain } = &cli.command {
+        return cli::commands::init::execute(
+            directory,
+            language.as_deref(),
+            verification_system.as_deref(),
+            domain.as_deref()
+        );
+    }
+
+    // Find the project containing the current directory, if any
+    let project = project::Project::discover(&std::env::current_dir()?)?;
+    if let Some(project) = &project {
+        info!("Using project manifest {}", project.manifest_path().display());
+    }
+
+    // Load the generator configuration, caching LLM responses unless disabled;
+    // an explicit --config wins over the one named in the manifest
+    let config_path = cli.config
+        .clone()
+        .or_else(|| project.as_ref().and_then(|project| project.llm_config_path()));
+    // Relative directories in it belong to the project, wherever in it the command runs
+    let mut generator_config = match (&config_path, &project) {
+        (Some(path), Some(project)) => GeneratorConfig::from_file_in(path, project.root())?,
+        (Some(path), None) => GeneratorConfig::from_file(path)?,
+        (None, _) => GeneratorConfig::default(),
+    };
+    if let Some(project) = &project {
+        project.manifest.llm.apply(&mut generator_config);
+    }
+    if cli.no_cache {
+        generator_config.response_cache = None;
+    } else if generator_config.response_cache.is_none() {
+        generator_config.response_cache = Some(ResponseCacheConfig::default());
+    }
+    if let Some(project) = &project {
+        generator_config.resolve_paths(project.root());
+    }
+
+    // Cache maintenance does not need the rest of the system
+    if let Commands::Cache { action } = &cli.command {
+        return cli::commands::cache::execute(action, generator_config.response_cache.as_ref());
+    }
+
+    // Stop LLM calls on the first Ctrl-C
+    let cancel = CancelFlag::new();
+    handle_ctrl_c(cancel.clone());
+
     // Create an instance of the Axiom system using our implementation
-    let axiom_system = AxiomSystemImpl::new();
+    let progress = Arc::new(cli::ui::LlmProgress::new(cli.stream_output));
+    let mut tools_config = project
+        .as_ref()
+        .map(|project| project.tools_config())
+        .unwrap_or_default();
+    // Model constants given on the command line win over the manifest's
+    if let Commands::Verify { constants, .. } = &cli.command {
+        for constant in constants {
+            let (name, value) = constant
+                .split_once('=')
+                .ok_or_else(|| anyhow::anyhow!("Expected NAME=VALUE for --constant, got '{}'", constant))?;
+            tools_config.model_constants.insert(name.trim().to_string(), value.trim().to_string());
+        }
+    }
+    let axiom_system = AxiomSystemImpl::new(generator_config, tools_config, progress, cancel.clone());
 
     // Display a welcome message
-    println!("Axiom - AI-generated Code Verification System");
+    cli::ui::print_line("Axiom - AI-generated Code Verification System");
 
     // Handle commands
     match &cli.command {
@@ -358,24 +551,10 @@ async fn main() -> Result<()> {
             interactive,
         } => {
             // Parse implementation language
-            let lang = match language.to_lowercase().as_str() {
-                "rust" => crate::models::common::Language::Rust,
-                "c" => crate::models::common::Language::C,
-                "c++" | "cpp" => crate::models::common::Language::CPlusPlus,
-                "python" | "py" => crate::models::common::Language::Python,
-                "javascript" | "js" => crate::models::common::Language::JavaScript,
-                "go" => crate::models::common::Language::Go,
-                "haskell" | "hs" => crate::models::common::Language::Haskell,
-                "ocaml" | "ml" => crate::models::common::Language::OCaml,
-                "java" => crate::models::common::Language::Java,
-                "csharp" | "c#" => crate::models::common::Language::CSharp,
-                "scala" => crate::models::common::Language::Scala,
-                "swift" => crate::models::common::Language::Swift,
-                _ => crate::models::common::Language::Custom(language.clone()),
-            };
+            let lang = language.as_deref().map(cli::commands::init::parse_language);
 
             // Parse domain
-            let dom = match domain.to_lowercase().as_str() {
+            let dom = domain.as_deref().map(|domain| match domain.to_lowercase().as_str() {
                 "crypto" | "cryptography" => crate::models::common::Domain::Cryptography,
                 "distributed" | "distributedsystems" =>
                     crate::models::common::Domain::DistributedSystems,
@@ -385,77 +564,91 @@ async fn main() -> Result<()> {
                 "blockchain" => crate::models::common::Domain::Blockchain,
                 "safety" | "safetycontrol" => crate::models::common::Domain::SafetyControl,
                 "highassurance" => crate::models::common::Domain::HighAssuranceSoftware,
-                _ => crate::models::common::Domain::Custom(domain.clone()),
-            };
-
-            // Parse verification system if provided
-            let verification_sys = match system {
-                Some(sys) =>
-                    match sys.to_lowercase().as_str() {
-                        "fstar" | "f*" => Some(crate::models::common::VerificationSystem::FStar),
-                        "dafny" => Some(crate::models::common::VerificationSystem::Dafny),
-                        "coq" => Some(crate::models::common::VerificationSystem::Coq),
-                        "isabelle" => Some(crate::models::common::VerificationSystem::Isabelle),
-                        "lean" => Some(crate::models::common::VerificationSystem::Lean),
-                        "tla" | "tla+" => Some(crate::models::common::VerificationSystem::TLA),
-                        "why3" => Some(crate::models::common::VerificationSystem::Why3),
-                        "z3" => Some(crate::models::common::VerificationSystem::Z3),
-                        _ => Some(crate::models::common::VerificationSystem::Custom(sys.clone())),
-                    }
-                None => None,
-            };
-
-            // Always use F* as the verification language
-            let verification_lang = Some(crate::models::common::VerificationLanguage::FStarLang);
-            
-            // Log if a different language was requested but F* is being used instead
-            if let Some(lang) = verification_language {
-                if !lang.to_lowercase().contains("fstar") && !lang.to_lowercase().contains("f*") {
-                    info!("Requested verification language '{}' ignored - using F* only", lang);
-                }
-            }
+                _ => crate::models::common::Domain::Custom(domain.to_string()),
+            });
+
+            // The verification language follows from the system unless both are given
+            let verification_sys = system
+                .as_deref()
+                .map(cli::commands::init::parse_verification_system);
+            let verification_lang = verification_language
+                .as_deref()
+                .map(cli::commands::spec::parse_verification_language)
+                .transpose()?;
 
             // Execute the process command
-            cli::commands::process::execute(
+            let result = cli::commands::process::execute(
                 &axiom_system,
-                requirements,
+                project.as_ref(),
+                requirements.as_deref(),
                 lang,
                 dom,
-                output,
+                output.as_deref(),
                 verification_sys,
                 verification_lang,
                 *interactive
-            ).await?;
+            ).await.map(|()| None);
+            finish_llm_command(&axiom_system, &cli.output_format, &cancel, result)?;
         }
 
         // Spec command - generate a formal specification
-        Commands::Spec { requirements, verification_language, domain, output, detail_level } => {
-            cli::commands::spec::execute(
+        Commands::Spec {
+            requirements,
+            verification_language,
+            domain,
+            output,
+            detail_level,
+            candidates,
+        } => {
+            let result = cli::commands::spec::execute(
                 &axiom_system,
-                requirements,
-                verification_language,
-                domain,
+                project.as_ref(),
+                requirements.as_deref(),
+                verification_language.as_deref(),
+                domain.as_deref(),
                 output.as_deref(), // Convert Option<PathBuf> to Option<&Path>
-                detail_level
-            ).await?;
+                detail_level,
+                *candidates
+            ).await.map(Some);
+            finish_llm_command(&axiom_system, &cli.output_format, &cancel, result)?;
         }
 
         // Validate command - validate a formal specification
-        Commands::Validate { spec, depth, requirements, project } => {
-            cli::commands::validate::execute(
+        Commands::Validate { spec, depth, requirements, project: in_project } => {
+            let result = cli::commands::validate::execute(
                 &axiom_system,
-                spec,
+                project.as_ref(),
+                spec.as_deref(),
                 depth,
                 requirements.as_deref(),
-                *project
-            ).await?;
+                *in_project
+            ).await.map(Some);
+            finish_llm_command(&axiom_system, &cli.output_format, &cancel, result)?;
+        }
+
+        Commands::Verify { implementation, spec, system, output, proof_level, timeout, .. } => {
+            let status = cli::commands::verify::execute(
+                &axiom_system.backends,
+                project.as_ref(),
+                implementation,
+                spec.as_deref(),
+                system.as_deref(),
+                output.as_deref(),
+                proof_level,
+                *timeout,
+                &cli.output_format
+            )?;
+            let code = cli::commands::verify::exit_code(&status);
+            if code != 0 {
+                std::process::exit(code);
+            }
         }
 
         // Other commands are not yet implemented
         _ => {
             cli::ui::print_info("Command not yet implemented.");
             cli::ui::print_info(
-                "This is a prototype CLI interface. Only the 'spec', 'validate', and 'process' commands are implemented."
+                "This is a prototype CLI interface. Only the 'init', 'spec', 'validate', 'verify', 'process' and 'cache' commands are implemented."
             );
         }
     }
@@ -463,6 +656,45 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Report LLM usage after a command, and exit with the conventional status if it was cancelled
+///
+/// With `--output-format json` the usage goes into the JSON document the command returned,
+/// so that a single document is printed.
+fn finish_llm_command(
+    axiom_system: &AxiomSystemImpl,
+    output_format: &str,
+    cancel: &CancelFlag,
+    result: Result<Option<String>>
+) -> Result<()> {
+    let usage = axiom_system.llm_usage();
+    if output_format.eq_ignore_ascii_case("json") {
+        let document = result.as_ref().ok().and_then(|document| document.as_deref());
+        cli::ui::print_json_document(document, &usage);
+    } else {
+        cli::ui::print_usage_summary(&usage);
+    }
+    if cancel.is_cancelled() {
+        cli::ui::print_warning("Cancelled by user");
+        std::process::exit(130);
+    }
+    result.map(|_| ())
+}
+
+/// Cancel LLM calls on the first Ctrl-C and exit immediately on the second
+fn handle_ctrl_c(cancel: CancelFlag) {
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_err() {
+            return;
+        }
+        cli::ui::print_warning("Cancelling... press Ctrl-C again to exit immediately");
+        cancel.cancel();
+
+        if tokio::signal::ctrl_c().await.is_ok() {
+            std::process::exit(130);
+        }
+    });
+}
+
 fn setup_logging(log_level: &str) {
     // Set up the logger based on the log level
     let level = match log_level.to_lowercase().as_str() {
@@ -1,8 +1,11 @@
+use serde::{Deserialize, Serialize};
+
 use crate::models::implementation::Implementation;
 use crate::models::specification::Specification;
 use crate::models::verification::VerificationResult;
 
 /// Final output of the Axiom system
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct VerifiedArtifact {
     pub requirements: Vec<String>,
     pub specification: Specification,
@@ -12,6 +15,7 @@ pub struct VerifiedArtifact {
 }
 
 /// Documentation for verified artifacts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Documentation {
     pub spec_explanation: String,
     pub impl_explanation: String,
@@ -1,5 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 /// Supported formal verification systems
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum VerificationSystem {
     FStar,
     Dafny,
@@ -9,29 +12,112 @@ pub enum VerificationSystem {
     TLA,
     Why3,
     Z3,
+    #[serde(rename = "framac")]
+    FramaC,
     Custom(String),
 }
 
+impl VerificationSystem {
+    /// Specification language checked by this system
+    pub fn verification_language(&self) -> VerificationLanguage {
+        match self {
+            VerificationSystem::FStar => VerificationLanguage::FStarLang,
+            VerificationSystem::Dafny => VerificationLanguage::DafnyLang,
+            VerificationSystem::Coq => VerificationLanguage::CoqLang,
+            VerificationSystem::Isabelle => VerificationLanguage::IsabelleLang,
+            VerificationSystem::Lean => VerificationLanguage::LeanLang,
+            VerificationSystem::TLA => VerificationLanguage::TLAPlus,
+            VerificationSystem::Why3 => VerificationLanguage::Why3Lang,
+            VerificationSystem::Z3 => VerificationLanguage::Z3SMT,
+            VerificationSystem::FramaC => VerificationLanguage::ACSL,
+            VerificationSystem::Custom(name) => VerificationLanguage::Custom(name.clone()),
+        }
+    }
+}
+
 /// Formal verification specification languages
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Serialized under the names the CLI accepts, e.g. `"fstar"`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum VerificationLanguage {
+    #[serde(rename = "fstar")]
     FStarLang,
+    #[serde(rename = "dafny")]
     DafnyLang,
+    #[serde(rename = "coq")]
     CoqLang,
+    #[serde(rename = "isabelle")]
     IsabelleLang,
+    #[serde(rename = "lean")]
     LeanLang,
     TLAPlus,
+    #[serde(rename = "why3")]
     Why3Lang,
+    #[serde(rename = "smt")]
     Z3SMT,
     ACSL,         // For C verification
     JML,          // For Java verification
     Liquid,       // For Haskell verification
+    #[serde(rename = "mirai")]
     RustMIRAI,    // For Rust verification
     Custom(String),
 }
 
+impl VerificationLanguage {
+    /// Extension of specification files in this language, "txt" when it has none of its own
+    pub fn spec_file_extension(&self) -> &'static str {
+        match self {
+            VerificationLanguage::FStarLang => "fst",
+            VerificationLanguage::DafnyLang => "dfy",
+            VerificationLanguage::CoqLang => "v",
+            VerificationLanguage::IsabelleLang => "thy",
+            VerificationLanguage::LeanLang => "lean",
+            VerificationLanguage::TLAPlus => "tla",
+            VerificationLanguage::Why3Lang => "why",
+            VerificationLanguage::Z3SMT => "smt2",
+            VerificationLanguage::ACSL => "h",
+            _ => "txt",
+        }
+    }
+
+    /// Language of a specification file with the given extension, if it is one of the
+    /// extensions returned by `spec_file_extension`
+    pub fn from_spec_file_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "fst" | "fsti" => Some(VerificationLanguage::FStarLang),
+            "dfy" => Some(VerificationLanguage::DafnyLang),
+            "v" => Some(VerificationLanguage::CoqLang),
+            "thy" => Some(VerificationLanguage::IsabelleLang),
+            "lean" => Some(VerificationLanguage::LeanLang),
+            "tla" => Some(VerificationLanguage::TLAPlus),
+            "why" | "mlw" => Some(VerificationLanguage::Why3Lang),
+            "smt2" | "smt" => Some(VerificationLanguage::Z3SMT),
+            "h" => Some(VerificationLanguage::ACSL),
+            _ => None,
+        }
+    }
+
+    /// System that checks specifications in this language
+    pub fn verification_system(&self) -> VerificationSystem {
+        match self {
+            VerificationLanguage::FStarLang => VerificationSystem::FStar,
+            VerificationLanguage::DafnyLang => VerificationSystem::Dafny,
+            VerificationLanguage::CoqLang => VerificationSystem::Coq,
+            VerificationLanguage::IsabelleLang => VerificationSystem::Isabelle,
+            VerificationLanguage::LeanLang => VerificationSystem::Lean,
+            VerificationLanguage::TLAPlus => VerificationSystem::TLA,
+            VerificationLanguage::Why3Lang => VerificationSystem::Why3,
+            VerificationLanguage::Z3SMT => VerificationSystem::Z3,
+            VerificationLanguage::ACSL => VerificationSystem::FramaC,
+            other => VerificationSystem::Custom(other.to_string()),
+        }
+    }
+}
+
 /// Application domains for verification
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Domain {
     Cryptography,
     DistributedSystems,
@@ -45,7 +131,8 @@ pub enum Domain {
 }
 
 /// Target implementation language
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Language {
     Rust,
     C,
@@ -63,7 +150,8 @@ pub enum Language {
 }
 
 /// Levels of proof strength
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ProofLevel {
     Quick,         // Fast but less thorough
     Standard,      // Balance between thoroughness and speed
@@ -73,7 +161,8 @@ pub enum ProofLevel {
 }
 
 /// Optimization levels for implementation generation
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum OptimizationLevel {
     None,
     Speed,
@@ -84,26 +173,43 @@ pub enum OptimizationLevel {
 }
 
 /// Resource usage during verification
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct ResourceUsage {
     pub memory_kb: u64,
     pub cpu_seconds: f64,
     pub peak_memory_kb: u64,
     pub lemmas_proven: usize,
+    /// Distinct states visited, reported by model checkers
+    pub states_explored: Option<u64>,
 }
 
 /// Resource limits for verification
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ResourceLimits {
     pub max_memory_kb: u64,
     pub max_cpu_seconds: u64,
     pub max_verification_time: std::time::Duration,
     pub max_proof_depth: Option<usize>,
     pub parallel_jobs: Option<usize>,
+    /// Provers to try in order, for backends that drive several (empty uses the backend's default)
+    pub provers: Vec<String>,
+}
+
+impl Default for ResourceLimits {
+    fn default() -> Self {
+        Self {
+            max_memory_kb: 1024 * 1024, // 1GB
+            max_cpu_seconds: 600,
+            max_verification_time: std::time::Duration::from_secs(600),
+            max_proof_depth: None,
+            parallel_jobs: None,
+            provers: vec![],
+        }
+    }
 }
 
 /// Maps between verification languages and implementation languages
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LanguageMapping {
     pub verification_language: VerificationLanguage,
     pub implementation_language: Language,
@@ -112,7 +218,8 @@ pub struct LanguageMapping {
 }
 
 /// Formal specification paradigm
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SpecificationParadigm {
     PrePostConditions,
     TypeTheoretic,
@@ -125,7 +232,7 @@ pub enum SpecificationParadigm {
 }
 
 /// Features of a verification language
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct VerificationLanguageFeatures {
     pub language: VerificationLanguage,
     pub paradigm: SpecificationParadigm,
@@ -1,7 +1,10 @@
+use serde::{Deserialize, Serialize};
+
 use crate::models::common::Language;
 use crate::models::verification::VerificationResult;
 
 /// Represents a verified implementation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Implementation {
     pub id: String,
     pub specification_id: String,
@@ -11,6 +14,7 @@ pub struct Implementation {
 }
 
 /// Options for implementation generation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ImplementationOptions {
     pub optimization_level: crate::models::common::OptimizationLevel,
     pub include_comments: bool,
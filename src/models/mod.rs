@@ -4,6 +4,7 @@ pub mod specification;
 pub mod implementation;
 pub mod verification;
 pub mod artifact;
+pub mod schema;
 
 // Re-export common model types
 pub use common::{Domain, Language, VerificationSystem};
@@ -11,4 +12,5 @@ pub use property::{Property, PropertyKind};
 pub use specification::Specification;
 pub use implementation::Implementation;
 pub use verification::{VerificationResult, VerificationStatus};
-pub use artifact::{VerifiedArtifact, Documentation};
\ No newline at end of file
+pub use artifact::{VerifiedArtifact, Documentation};
+pub use schema::{from_document_json, to_document_json, Document, DocumentError, ModelDocument, SCHEMA_VERSION};
@@ -1,5 +1,7 @@
+use serde::{Deserialize, Serialize};
+
 /// A formal property that must be satisfied by an implementation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Property {
     pub id: String,
     pub description: String,
@@ -8,7 +10,8 @@ pub struct Property {
 }
 
 /// Types of formal properties that can be verified
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum PropertyKind {
     Functional,       // Correct behavior
     Safety,           // Nothing bad happens
@@ -0,0 +1,103 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::models::artifact::VerifiedArtifact;
+use crate::models::implementation::Implementation;
+use crate::models::specification::{FormalSpecification, Specification, ValidationReport};
+use crate::models::verification::VerificationResult;
+
+/// Version of the JSON representation of the model, raised whenever a change could break
+/// tools reading it
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// JSON Schema of version `SCHEMA_VERSION` documents, also found in `schemas/`
+pub const JSON_SCHEMA: &str = include_str!("../../schemas/axiom-model.v1.schema.json");
+
+/// Model type that can be written on its own as a versioned document
+pub trait ModelDocument: Serialize + DeserializeOwned {
+    /// Value of the document's `kind` field
+    const KIND: &'static str;
+}
+
+impl ModelDocument for Specification {
+    const KIND: &'static str = "specification";
+}
+
+impl ModelDocument for FormalSpecification {
+    const KIND: &'static str = "formal_specification";
+}
+
+impl ModelDocument for ValidationReport {
+    const KIND: &'static str = "validation_report";
+}
+
+impl ModelDocument for VerificationResult {
+    const KIND: &'static str = "verification_result";
+}
+
+impl ModelDocument for Implementation {
+    const KIND: &'static str = "implementation";
+}
+
+impl ModelDocument for VerifiedArtifact {
+    const KIND: &'static str = "verified_artifact";
+}
+
+/// A model value together with the schema version and its kind, as written to project
+/// files and printed with `--output-format json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Document<T> {
+    pub schema_version: u32,
+    pub kind: String,
+    pub data: T,
+}
+
+#[derive(Debug, Error)]
+pub enum DocumentError {
+    #[error("Invalid document: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Unsupported schema version {found}; this version of Axiom reads version {supported}")]
+    UnsupportedVersion { found: u32, supported: u32 },
+
+    #[error("Expected a {expected} document, found {found}")]
+    WrongKind { expected: &'static str, found: String },
+}
+
+/// The fields every document starts with, read before the data so that a document from a
+/// newer version is reported as such rather than as a missing field
+#[derive(Deserialize)]
+struct DocumentHeader {
+    schema_version: u32,
+    kind: String,
+}
+
+/// Write a model value as a pretty-printed versioned document
+pub fn to_document_json<T: ModelDocument>(value: &T) -> Result<String, DocumentError> {
+    let document = Document {
+        schema_version: SCHEMA_VERSION,
+        kind: T::KIND.to_string(),
+        data: value,
+    };
+    Ok(serde_json::to_string_pretty(&document)?)
+}
+
+/// Read a model value from a versioned document, checking its version and kind
+pub fn from_document_json<T: ModelDocument>(json: &str) -> Result<T, DocumentError> {
+    let header: DocumentHeader = serde_json::from_str(json)?;
+    if header.schema_version != SCHEMA_VERSION {
+        return Err(DocumentError::UnsupportedVersion {
+            found: header.schema_version,
+            supported: SCHEMA_VERSION,
+        });
+    }
+    if header.kind != T::KIND {
+        return Err(DocumentError::WrongKind {
+            expected: T::KIND,
+            found: header.kind,
+        });
+    }
+    let document: Document<T> = serde_json::from_str(json)?;
+    Ok(document.data)
+}
@@ -1,11 +1,12 @@
 use chrono;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::models::common::{Domain, VerificationSystem, VerificationLanguage};
 use crate::models::property::Property;
 
 /// Represents a formal specification derived from natural language requirements
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Specification {
     pub id: String,
     pub source_requirements: Vec<String>,
@@ -16,7 +17,7 @@ pub struct Specification {
 }
 
 /// The formal specification in a verification language
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FormalSpecification {
     /// The verification language used for this specification
     pub verification_language: VerificationLanguage,
@@ -26,10 +27,33 @@ pub struct FormalSpecification {
     pub components: HashMap<String, String>,
     /// Environment/imports needed for the specification
     pub dependencies: Vec<String>,
+    /// Candidates drawn for this specification, in the order they were requested; empty
+    /// unless it was generated with `SpecificationOptions::candidates` above one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub candidates: Vec<SpecificationCandidate>,
+}
+
+/// One of several specifications drawn for the same requirements, with the result of checking it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpecificationCandidate {
+    /// Position among the candidates, starting at 0
+    pub index: usize,
+    pub provider: String,
+    pub model: String,
+    pub spec_code: String,
+    /// Error-severity issues found when checking the candidate
+    pub errors: usize,
+    pub warnings: usize,
+    /// The check was done by the verification tool rather than judged by the LLM
+    pub tool_validated: bool,
+    /// Why the candidate could not be generated or checked
+    pub failure: Option<String>,
+    /// This candidate became the specification
+    pub selected: bool,
 }
 
 /// Metadata associated with a specification
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SpecificationMetadata {
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub verification_system: VerificationSystem,
@@ -40,7 +64,7 @@ pub struct SpecificationMetadata {
 }
 
 /// Validation report for specifications
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ValidationReport {
     pub is_valid: bool,
     pub issues: Vec<ValidationIssue>,
@@ -51,19 +75,34 @@ pub struct ValidationReport {
 }
 
 /// Issues found during specification validation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ValidationIssue {
     pub severity: IssueSeverity,
     pub message: String,
     pub related_property: Option<String>,
     /// Line number in formal specification if applicable
     pub line_number: Option<usize>,
+    /// Exact source range reported by a verification tool
+    pub span: Option<SourceSpan>,
+    /// Tool-specific error number (e.g. F* `Error 189`)
+    pub error_code: Option<u32>,
     /// Suggested fix if available
     pub suggested_fix: Option<String>,
 }
 
+/// A range in a specification file, with 1-based lines and 0-based columns as reported by F*
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceSpan {
+    pub file: Option<String>,
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
 /// Severity levels for validation issues
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum IssueSeverity {
     Error,
     Warning,
@@ -71,7 +110,7 @@ pub enum IssueSeverity {
 }
 
 /// Options for specification generation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SpecificationOptions {
     pub detail_level: DetailLevel,
     pub include_security_properties: bool,
@@ -82,6 +121,8 @@ pub struct SpecificationOptions {
     pub generation_targets: SpecGenerationTargets,
     /// Additional options specific to the verification language
     pub language_specific_options: HashMap<String, String>,
+    /// Number of candidates to draw and check; the one with the fewest errors is kept
+    pub candidates: usize,
 }
 
 impl Default for SpecificationOptions {
@@ -93,12 +134,13 @@ impl Default for SpecificationOptions {
             verification_language: VerificationLanguage::FStarLang,
             generation_targets: SpecGenerationTargets::default(),
             language_specific_options: HashMap::new(),
+            candidates: 1,
         }
     }
 }
 
 /// Controls which specification components to generate
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SpecGenerationTargets {
     pub generate_invariants: bool,
     pub generate_pre_post_conditions: bool,
@@ -120,7 +162,8 @@ impl Default for SpecGenerationTargets {
 }
 
 /// Level of detail in specifications
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum DetailLevel {
     Minimal,
     Standard,
@@ -129,7 +172,7 @@ pub enum DetailLevel {
 }
 
 /// Type for tracking the translation of natural language to formal specifications
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SpecificationTranslation {
     pub requirement: String,
     pub interpreted_properties: Vec<String>,
@@ -140,7 +183,7 @@ pub struct SpecificationTranslation {
 }
 
 /// Defines a template for verification code in a specific language
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct VerificationTemplate {
     pub language: VerificationLanguage,
     pub template_name: String,
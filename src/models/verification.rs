@@ -1,12 +1,18 @@
-use std::{ fmt, time::Duration };
+use std::{ collections::BTreeMap, fmt, time::Duration };
+use serde::{ Deserialize, Serialize };
 use crate::models::common::{ ProofLevel, ResourceLimits, ResourceUsage };
 
 /// Result of the verification process
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct VerificationResult {
     pub status: VerificationStatus,
     pub proof_artifacts: Vec<ProofArtifact>,
     pub verification_time: Duration,
     pub resource_usage: ResourceUsage,
+    /// Per-goal breakdown (methods, lemmas, proof obligations) when the backend reports one
+    pub goal_results: Vec<GoalResult>,
+    /// Concrete witnesses for the properties that failed, when the backend can produce them
+    pub counterexamples: Vec<Counterexample>,
 }
 
 impl fmt::Debug for VerificationStatus {
@@ -39,6 +45,8 @@ impl fmt::Display for VerificationStatus {
     }
 }
 /// Status of a verification attempt
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum VerificationStatus {
     Verified, // Successfully verified
     Unverified, // Verification incomplete
@@ -48,7 +56,7 @@ pub enum VerificationStatus {
 }
 
 /// Artifacts produced during the verification process
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ProofArtifact {
     pub artifact_type: ArtifactType,
     pub path: String,
@@ -56,7 +64,8 @@ pub struct ProofArtifact {
 }
 
 /// Types of proof artifacts
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ArtifactType {
     Proof,
     Model,
@@ -65,9 +74,87 @@ pub enum ArtifactType {
     Custom(String),
 }
 
+/// Concrete witness showing that a property does not hold
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Counterexample {
+    /// Property or query the witness refutes, when known
+    pub property: Option<String>,
+    /// Values of variables and functions, in the backend's own syntax
+    ///
+    /// For a trace this is the final, violating state.
+    pub assignments: BTreeMap<String, String>,
+    /// States leading to the violation, for model checkers; empty for single-state witnesses
+    pub trace: Vec<TraceStep>,
+}
+
+/// One state of a counterexample trace
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceStep {
+    /// Action or predicate that produced the state, as reported by the tool
+    pub action: String,
+    pub state: BTreeMap<String, String>,
+}
+
+impl fmt::Display for Counterexample {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(property) = &self.property {
+            write!(f, "{}: ", property)?;
+        }
+        let assignments: Vec<String> = self.assignments
+            .iter()
+            .map(|(name, value)| format!("{} = {}", name, value))
+            .collect();
+        write!(f, "{}", assignments.join(", "))
+    }
+}
+
+/// Outcome of an individual proof goal, such as a Dafny method or a Why3 goal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GoalResult {
+    pub name: String,
+    pub outcome: GoalOutcome,
+    pub duration: Duration,
+    /// Solver resource units spent on the goal, if reported
+    pub resource_count: Option<u64>,
+    /// Prover that produced the outcome, if the backend uses several
+    pub prover: Option<String>,
+}
+
+/// Outcome of a single proof goal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GoalOutcome {
+    Valid,
+    Invalid,
+    Timeout,
+    OutOfResource,
+    Unknown,
+    /// The proof compiles but was left unfinished (`Admitted`, `sorry`)
+    Incomplete,
+    Error(String),
+}
+
+impl GoalOutcome {
+    /// True if the goal was proven
+    pub fn is_valid(&self) -> bool {
+        matches!(self, GoalOutcome::Valid)
+    }
+}
+
 /// Options for the verification process
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct VerificationOptions {
     pub timeout: Duration,
     pub proof_level: ProofLevel,
     pub resource_limits: ResourceLimits,
 }
+
+impl Default for VerificationOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(300),
+            proof_level: ProofLevel::Standard,
+            resource_limits: ResourceLimits::default(),
+        }
+    }
+}
@@ -0,0 +1,327 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use thiserror::Error;
+
+use crate::config::ExternalToolsConfig;
+use crate::implementations::config::GeneratorConfig;
+use crate::models::common::{Domain, Language, VerificationLanguage, VerificationSystem};
+
+/// Name of the manifest file at the root of every project
+pub const MANIFEST_FILE: &str = "axiom.toml";
+
+/// Directory of requirement files, one requirement per line
+pub const REQUIREMENTS_DIR: &str = "requirements";
+/// Directory of generated specifications
+pub const SPECS_DIR: &str = "specs";
+/// Directory of implementations
+pub const IMPL_DIR: &str = "impl";
+/// Directory of proofs and verification results
+pub const PROOFS_DIR: &str = "proofs";
+
+const REQUIREMENTS_TEMPLATE: &str = "\
+# One requirement per line. Empty lines and lines starting with # are ignored.
+";
+
+/// Commented-out settings appended to new manifests
+const MANIFEST_EXAMPLES: &str = "
+# [llm]
+# config = \"axiom.yaml\"
+# provider = \"anthropic\"
+# model = \"claude-sonnet-4-20250514\"
+#
+# [tools]
+# timeout_secs = 300
+#
+# [tools.paths]
+# fstar = \"/opt/fstar/bin/fstar.exe\"
+#
+# [tools.constants]
+# N = \"3\"
+";
+
+#[derive(Debug, Error)]
+pub enum ProjectError {
+    #[error("Failed to access project files: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid manifest {path}: {message}")]
+    InvalidManifest { path: PathBuf, message: String },
+
+    #[error("{0} already contains an Axiom project")]
+    AlreadyInitialized(PathBuf),
+}
+
+/// Contents of `axiom.toml`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectManifest {
+    pub project: ProjectSettings,
+
+    /// LLM settings, applied over the configuration file
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub llm: LlmSettings,
+
+    /// Verification tool paths and limits
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub tools: ToolSettings,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectSettings {
+    pub name: String,
+
+    /// Target implementation language
+    pub language: Language,
+
+    pub verification_system: VerificationSystem,
+
+    pub domain: Domain,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LlmSettings {
+    /// Generator configuration file, relative to the project root; `--config` takes precedence
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<usize>,
+}
+
+impl LlmSettings {
+    /// Override the settings of a generator configuration with those given here
+    pub fn apply(&self, config: &mut GeneratorConfig) {
+        if let Some(provider) = &self.provider {
+            config.llm_api.provider = Some(provider.clone());
+        }
+        if let Some(model) = &self.model {
+            config.llm_api.model = Some(model.clone());
+        }
+        if let Some(temperature) = self.temperature {
+            config.temperature = Some(temperature);
+        }
+        if let Some(max_tokens) = self.max_tokens {
+            config.max_tokens = Some(max_tokens);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ToolSettings {
+    /// Timeout for one run of a verification tool
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+
+    /// Executables keyed by tool name ("fstar", "dafny", "z3", ...); relative paths are
+    /// resolved from the project root, bare names are looked up on the PATH
+    #[serde(default)]
+    pub paths: BTreeMap<String, String>,
+
+    /// Values of the constants TLA+ models declare, such as `N = "3"`
+    #[serde(default)]
+    pub constants: BTreeMap<String, String>,
+}
+
+impl ProjectManifest {
+    /// Manifest for a new project, using the recommended verification system for the domain
+    /// unless one is given
+    pub fn new(
+        name: &str,
+        language: Language,
+        domain: Domain,
+        verification_system: Option<VerificationSystem>,
+    ) -> Self {
+        let verification_system =
+            verification_system.unwrap_or_else(|| recommended_verification_system(&domain));
+        Self {
+            project: ProjectSettings {
+                name: name.to_string(),
+                language,
+                verification_system,
+                domain,
+            },
+            llm: LlmSettings::default(),
+            tools: ToolSettings::default(),
+        }
+    }
+
+    /// Specification language of the project's verification system
+    pub fn verification_language(&self) -> VerificationLanguage {
+        self.project.verification_system.verification_language()
+    }
+}
+
+fn is_default<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+/// Verification system best suited to a domain
+pub fn recommended_verification_system(domain: &Domain) -> VerificationSystem {
+    match domain {
+        Domain::DistributedSystems => VerificationSystem::TLA,
+        Domain::WebSecurity => VerificationSystem::Dafny,
+        _ => VerificationSystem::FStar,
+    }
+}
+
+/// A project directory with its manifest
+#[derive(Debug, Clone, PartialEq)]
+pub struct Project {
+    root: PathBuf,
+    pub manifest: ProjectManifest,
+}
+
+impl Project {
+    /// Create the manifest and directory layout of a new project in `root`
+    ///
+    /// Existing files are kept, but a directory that already has a manifest is refused.
+    pub fn init(root: &Path, manifest: ProjectManifest) -> Result<Self, ProjectError> {
+        let manifest_path = root.join(MANIFEST_FILE);
+        if manifest_path.exists() {
+            return Err(ProjectError::AlreadyInitialized(root.to_path_buf()));
+        }
+
+        for dir in [REQUIREMENTS_DIR, SPECS_DIR, IMPL_DIR, PROOFS_DIR] {
+            fs::create_dir_all(root.join(dir))?;
+        }
+        let requirements_path = root.join(REQUIREMENTS_DIR).join("requirements.txt");
+        if !requirements_path.exists() {
+            fs::write(&requirements_path, REQUIREMENTS_TEMPLATE)?;
+        }
+
+        let text = toml::to_string(&manifest).map_err(|e| ProjectError::InvalidManifest {
+            path: manifest_path.clone(),
+            message: e.to_string(),
+        })?;
+        fs::write(&manifest_path, text + MANIFEST_EXAMPLES)?;
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            manifest,
+        })
+    }
+
+    /// Read the project whose manifest is in `root`
+    pub fn load(root: &Path) -> Result<Self, ProjectError> {
+        let manifest_path = root.join(MANIFEST_FILE);
+        let text = fs::read_to_string(&manifest_path)?;
+        let manifest = toml::from_str(&text).map_err(|e| ProjectError::InvalidManifest {
+            path: manifest_path,
+            message: e.to_string(),
+        })?;
+        Ok(Self {
+            root: root.to_path_buf(),
+            manifest,
+        })
+    }
+
+    /// Find the project containing `start`, looking for a manifest in it and then in each
+    /// of its parents
+    pub fn discover(start: &Path) -> Result<Option<Self>, ProjectError> {
+        for dir in start.ancestors() {
+            if dir.join(MANIFEST_FILE).is_file() {
+                return Self::load(dir).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn manifest_path(&self) -> PathBuf {
+        self.root.join(MANIFEST_FILE)
+    }
+
+    pub fn requirements_dir(&self) -> PathBuf {
+        self.root.join(REQUIREMENTS_DIR)
+    }
+
+    pub fn specs_dir(&self) -> PathBuf {
+        self.root.join(SPECS_DIR)
+    }
+
+    pub fn impl_dir(&self) -> PathBuf {
+        self.root.join(IMPL_DIR)
+    }
+
+    pub fn proofs_dir(&self) -> PathBuf {
+        self.root.join(PROOFS_DIR)
+    }
+
+    /// Where the project's specification in `language` is kept
+    pub fn spec_path(&self, language: &VerificationLanguage) -> PathBuf {
+        self.specs_dir().join(format!(
+            "{}.{}",
+            self.manifest.project.name,
+            language.spec_file_extension()
+        ))
+    }
+
+    /// Requirements from the `.txt` and `.md` files in `requirements/`, in file name order
+    pub fn requirements(&self) -> Result<Vec<String>, ProjectError> {
+        let mut files = Vec::new();
+        for entry in fs::read_dir(self.requirements_dir())? {
+            let path = entry?.path();
+            if matches!(path.extension().and_then(|ext| ext.to_str()), Some("txt" | "md")) {
+                files.push(path);
+            }
+        }
+        files.sort();
+
+        let mut requirements = Vec::new();
+        for path in files {
+            let text = fs::read_to_string(&path)?;
+            requirements.extend(
+                text.lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .map(str::to_string),
+            );
+        }
+        Ok(requirements)
+    }
+
+    /// Generator configuration file named in the manifest, resolved from the project root
+    pub fn llm_config_path(&self) -> Option<PathBuf> {
+        self.manifest
+            .llm
+            .config
+            .as_ref()
+            .map(|path| self.root.join(path))
+    }
+
+    /// Tool configuration for the verification backends
+    pub fn tools_config(&self) -> ExternalToolsConfig {
+        let mut config = ExternalToolsConfig::default();
+        for (tool, path) in &self.manifest.tools.paths {
+            let resolved = if Path::new(path).is_relative() && Path::new(path).components().count() > 1 {
+                self.root.join(path).display().to_string()
+            } else {
+                path.clone()
+            };
+            config.tool_paths.insert(tool.clone(), resolved);
+        }
+        if let Some(secs) = self.manifest.tools.timeout_secs {
+            config.timeout = Duration::from_secs(secs);
+        }
+        config.model_constants = self.manifest.tools.constants.clone();
+        config
+    }
+}
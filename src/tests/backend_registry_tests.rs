@@ -0,0 +1,199 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::ExternalToolsConfig;
+    use crate::implementations::backends::{ BackendRegistry, DafnyBackend, Z3Backend };
+    use crate::models::common::{ VerificationLanguage, VerificationSystem };
+    use crate::traits::verification_engine::VerificationEngine;
+
+    fn missing_tools() -> ExternalToolsConfig {
+        let mut tools = ExternalToolsConfig::default();
+        for key in ["fstar", "dafny", "z3", "java", "coqc", "lake", "why3"] {
+            tools.tool_paths.insert(key.to_string(), "/nonexistent/axiom-tool".to_string());
+        }
+        tools
+    }
+
+    #[test]
+    fn test_from_config_registers_builtin_backends() {
+        let registry = BackendRegistry::from_config(&ExternalToolsConfig::default());
+
+        for system in [
+            VerificationSystem::FStar,
+            VerificationSystem::Dafny,
+            VerificationSystem::Z3,
+            VerificationSystem::TLA,
+            VerificationSystem::Coq,
+            VerificationSystem::Lean,
+            VerificationSystem::Why3,
+            VerificationSystem::FramaC,
+        ] {
+            assert!(registry.contains(&system), "missing backend for {}", system);
+        }
+        assert!(!registry.contains(&VerificationSystem::Isabelle));
+        assert_eq!(registry.systems().len(), 8);
+    }
+
+    #[test]
+    fn test_register_custom_system_at_runtime() {
+        let tools = ExternalToolsConfig::default();
+        let mut registry = BackendRegistry::new();
+        let custom = VerificationSystem::Custom("boogie".to_string());
+
+        assert!(registry.register(custom.clone(), Z3Backend::new(&tools)).is_none());
+        assert!(registry.contains(&custom));
+        assert!(!registry.contains(&VerificationSystem::Custom("viper".to_string())));
+
+        // Registering again replaces the existing backend
+        assert!(registry.register(custom.clone(), DafnyBackend::new(&tools)).is_some());
+        assert_eq!(registry.systems(), vec![custom.clone()]);
+
+        assert!(registry.unregister(&custom).is_some());
+        assert!(registry.get(&custom).is_none());
+    }
+
+    #[test]
+    fn test_unregistered_and_missing_tools_are_unavailable() {
+        let registry = BackendRegistry::from_config(&missing_tools());
+
+        assert!(!registry.is_available(&VerificationSystem::Isabelle).unwrap());
+        assert!(!registry.is_available(&VerificationSystem::Custom("viper".to_string())).unwrap());
+        assert!(!registry.is_available(&VerificationSystem::Dafny).unwrap());
+        assert!(!registry.is_available(&VerificationSystem::Z3).unwrap());
+    }
+
+    #[test]
+    fn test_engine_checks_the_language_of_its_system() {
+        let registry = BackendRegistry::from_config(&missing_tools());
+
+        assert!(registry.engine(&VerificationSystem::Isabelle).is_none());
+        let engine = registry.engine(&VerificationSystem::Dafny).unwrap();
+        assert_eq!(engine.verification_system(), VerificationSystem::Dafny);
+        assert!(engine.supports_formal_proofs(VerificationLanguage::DafnyLang));
+        assert!(!engine.supports_formal_proofs(VerificationLanguage::FStarLang));
+
+        for system in registry.systems() {
+            let language = system.verification_language();
+            assert_eq!(language.verification_system(), system);
+            assert_eq!(
+                VerificationLanguage::from_spec_file_extension(language.spec_file_extension()),
+                Some(language)
+            );
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod backend_tests {
+    use crate::config::ExternalToolsConfig;
+    use crate::implementations::backends::{ BackendRegistry, Z3Backend };
+    use crate::models::common::{ Domain, Language, VerificationLanguage, VerificationSystem };
+    use crate::models::implementation::Implementation;
+    use crate::models::specification::{ FormalSpecification, Specification, SpecificationMetadata };
+    use crate::models::verification::{ VerificationOptions, VerificationResult, VerificationStatus };
+    use crate::tests::stub_tools::{ scratch_dir, write_stub_tool };
+    use crate::traits::verification_engine::VerificationEngine;
+
+    fn smt_spec(code: &str) -> Specification {
+        Specification {
+            id: "positive".to_string(),
+            source_requirements: vec![],
+            formal_properties: vec![],
+            formal_spec: FormalSpecification {
+                verification_language: VerificationLanguage::Z3SMT,
+                spec_code: code.to_string(),
+                components: std::collections::HashMap::new(),
+                dependencies: vec![],
+                candidates: vec![],
+            },
+            metadata: SpecificationMetadata {
+                created_at: chrono::Utc::now(),
+                verification_system: VerificationSystem::Z3,
+                domain: Domain::Custom("verification".to_string()),
+                confidence_score: 1.0,
+                is_formally_validated: false,
+            },
+        }
+    }
+
+    fn smt_implementation(code: &str) -> Implementation {
+        Implementation {
+            id: "positive_impl".to_string(),
+            specification_id: "positive".to_string(),
+            language: Language::Custom("smt2".to_string()),
+            source_code: code.to_string(),
+            verification_result: VerificationResult {
+                status: VerificationStatus::Unverified,
+                proof_artifacts: vec![],
+                verification_time: std::time::Duration::ZERO,
+                resource_usage: Default::default(),
+                goal_results: vec![],
+                counterexamples: vec![],
+            },
+        }
+    }
+
+    #[test]
+    fn test_available_when_tool_runs() {
+        let dir = scratch_dir("registry_available");
+        let stub = write_stub_tool(&dir, "z3", "echo \"Z3 version 4.12.2 - 64 bit\"");
+
+        let mut tools = ExternalToolsConfig::default();
+        tools.tool_paths.insert("z3".to_string(), stub.to_string_lossy().to_string());
+        let custom = VerificationSystem::Custom("z3-custom".to_string());
+        let registry = BackendRegistry::from_config(&tools).with_backend(
+            custom.clone(),
+            Z3Backend::new(&tools)
+        );
+
+        assert!(registry.is_available(&VerificationSystem::Z3).unwrap());
+        assert!(registry.is_available(&custom).unwrap());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_engine_runs_the_registered_backend() {
+        let dir = scratch_dir("registry_engine");
+        // Stub z3: `sat` with a model when the query allows a negative x, `unsat` otherwise
+        let stub = write_stub_tool(
+            &dir,
+            "z3",
+            r#"for arg in "$@"; do file="$arg"; done
+if grep -q "(< x 0)" "$file"; then
+  echo "sat"
+  echo "((define-fun x () Int (- 1)))"
+else
+  echo "unsat"
+fi"#
+        );
+
+        let mut tools = ExternalToolsConfig::default();
+        tools.tool_paths.insert("z3".to_string(), stub.to_string_lossy().to_string());
+        let registry = BackendRegistry::new().with_backend(
+            VerificationSystem::Z3,
+            Z3Backend::new(&tools).with_work_dir(dir.join("runs"))
+        );
+        let engine = registry.engine(&VerificationSystem::Z3).unwrap();
+        let spec = smt_spec("(declare-const x Int)\n(assert (> x 0))");
+        assert!(engine.can_verify(&spec, &Language::Custom("smt2".to_string())));
+        assert!(!engine.can_verify(&spec, &Language::Rust));
+
+        let options = VerificationOptions::default();
+        let verified = engine.verify(&smt_implementation("(check-sat)"), &spec, &options).unwrap();
+        assert_eq!(verified.status, VerificationStatus::Verified);
+        assert!(!verified.proof_artifacts.is_empty());
+
+        let failed = engine
+            .verify(&smt_implementation("(assert (< x 0))\n(check-sat)"), &spec, &options)
+            .unwrap();
+        assert!(matches!(failed.status, VerificationStatus::Failed(_)));
+        assert_eq!(engine.extract_counterexamples(&failed).unwrap().len(), 1);
+
+        // Code the backend cannot check is rejected instead of being left out
+        let mut rust = smt_implementation("pub fn f() { panic!() }");
+        rust.language = Language::Rust;
+        assert!(engine.verify(&rust, &spec, &options).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
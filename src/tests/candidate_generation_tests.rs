@@ -0,0 +1,269 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::{ Arc, Mutex };
+
+    use async_trait::async_trait;
+
+    use crate::config::ExternalToolsConfig;
+    use crate::errors::AxiomError;
+    use crate::implementations::backends::{ BackendRegistry, FStarBackend, Z3Backend };
+    use crate::implementations::config::{ ApiConfig, GeneratorConfig };
+    use crate::implementations::llm::{ LlmClient, LlmRequest, LlmResponse, ResponseCache };
+    use crate::implementations::specification_generator::{ LLMSpecificationGenerator, SpecGenError };
+    use crate::models::common::{ Domain, VerificationLanguage, VerificationSystem };
+    use crate::models::specification::SpecificationOptions;
+    use crate::tests::stub_http::{ StubHttpServer, StubResponse };
+    use crate::tests::stub_tools::{ scratch_dir, write_stub_tool };
+    use crate::traits::specification_generator::SpecificationGenerator;
+
+    // Stub fstar.exe reporting one error for every line that mentions BROKEN
+    const STUB_FSTAR: &str = r#"
+if [ "$1" = "--version" ]; then
+  echo "F* 2025.03.25"
+  exit 0
+fi
+for arg in "$@"; do
+  case "$arg" in *.fst) file="$arg" ;; esac
+done
+if grep -q "BROKEN" "$file"; then
+  grep -n "BROKEN" "$file" | cut -d: -f1 | while read line; do
+    echo "$(basename "$file")($line,8-$line,14): (Error 72) Identifier not found: [BROKEN]" >&2
+  done
+  exit 1
+fi
+echo "All verification conditions discharged successfully"
+exit 0
+"#;
+
+    // Stub z3 finding a model for every script that allows a negative x
+    const STUB_Z3: &str = r#"
+if [ "$1" = "--version" ]; then
+  echo "Z3 version 4.13.0"
+  exit 0
+fi
+for arg in "$@"; do file="$arg"; done
+if grep -q "(< x 0)" "$file"; then
+  echo "sat"
+  echo "((define-fun x () Int (- 3)))"
+else
+  echo "unsat"
+fi
+"#;
+
+    /// Model that always answers with the same specification and remembers the samples it drew
+    struct FixedModel {
+        model: String,
+        answer: Result<String, String>,
+        samples: Mutex<Vec<usize>>,
+    }
+
+    impl FixedModel {
+        fn new(model: &str, spec_code: &str) -> Arc<Self> {
+            Arc::new(Self {
+                model: model.to_string(),
+                answer: Ok(format!("```fstar\n{}\n```", spec_code)),
+                samples: Mutex::new(Vec::new()),
+            })
+        }
+
+        fn failing(model: &str, message: &str) -> Arc<Self> {
+            Arc::new(Self {
+                model: model.to_string(),
+                answer: Err(message.to_string()),
+                samples: Mutex::new(Vec::new()),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl LlmClient for FixedModel {
+        fn provider(&self) -> &str {
+            "fixed"
+        }
+
+        fn model(&self) -> &str {
+            &self.model
+        }
+
+        async fn complete(&self, request: &LlmRequest) -> Result<LlmResponse, SpecGenError> {
+            self.samples.lock().unwrap().push(request.sample);
+            match &self.answer {
+                Ok(answer) => Ok(LlmResponse::text(answer.clone())),
+                Err(message) => Err(SpecGenError::ApiError(message.clone())),
+            }
+        }
+    }
+
+    fn stub_fstar(name: &str) -> (FStarBackend, PathBuf) {
+        let dir = scratch_dir(name);
+        let fstar = write_stub_tool(&dir, "fstar.exe", STUB_FSTAR);
+        let mut tools = ExternalToolsConfig::default();
+        tools.tool_paths.insert("fstar".to_string(), fstar.display().to_string());
+        (FStarBackend::new(&tools).with_work_dir(dir.join("runs")), dir)
+    }
+
+    fn candidate_options(candidates: usize) -> SpecificationOptions {
+        SpecificationOptions {
+            verification_language: VerificationLanguage::FStarLang,
+            candidates,
+            ..SpecificationOptions::default()
+        }
+    }
+
+    fn requirements() -> Vec<String> {
+        vec!["Incrementing must increase the value".to_string()]
+    }
+
+    #[tokio::test]
+    async fn test_candidate_with_fewest_errors_is_selected() {
+        let (backend, _dir) = stub_fstar("candidates_selected");
+        let sloppy = FixedModel::new("sloppy", "module Incr\nlet a = BROKEN\nlet b = BROKEN");
+        let careful = FixedModel::new("careful", "module Incr\nlet incr (x:int) = x + 1");
+        let close = FixedModel::new("close", "module Incr\nlet incr (x:int) = BROKEN");
+        let generator = LLMSpecificationGenerator::new_with_defaults()
+            .with_fstar_backend(backend)
+            .with_candidate_clients(vec![sloppy.clone(), careful.clone(), close.clone()]);
+
+        let spec = generator
+            .generate_specification(&requirements(), Domain::Cryptography, &candidate_options(3)).await
+            .unwrap();
+
+        assert!(spec.formal_spec.spec_code.contains("x + 1"));
+        assert_eq!(spec.metadata.confidence_score, 1.0);
+
+        let candidates = &spec.formal_spec.candidates;
+        let scores: Vec<_> = candidates
+            .iter()
+            .map(|c| (c.index, c.model.as_str(), c.errors, c.tool_validated, c.selected))
+            .collect();
+        assert_eq!(
+            scores,
+            vec![(0, "sloppy", 2, true, false), (1, "careful", 0, true, true), (2, "close", 1, true, false)]
+        );
+        assert!(candidates[0].spec_code.contains("let b = BROKEN"));
+
+        // Each candidate is a separate sample, so it gets its own cache entry
+        assert_eq!(*sloppy.samples.lock().unwrap(), vec![0]);
+        assert_eq!(*careful.samples.lock().unwrap(), vec![1]);
+        assert_eq!(*close.samples.lock().unwrap(), vec![2]);
+        let request = LlmRequest::new("Specify incr", 0.2, 256);
+        assert_eq!(
+            ResponseCache::key("fixed", "sloppy", &request),
+            ResponseCache::key("fixed", "sloppy", &request.clone().with_sample(0))
+        );
+        assert_ne!(
+            ResponseCache::key("fixed", "sloppy", &request),
+            ResponseCache::key("fixed", "sloppy", &request.clone().with_sample(1))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_errors_lower_confidence_and_failed_candidates_are_recorded() {
+        let (backend, _dir) = stub_fstar("candidates_failed");
+        let close = FixedModel::new("close", "module Incr\nlet incr (x:int) = BROKEN");
+        let down = FixedModel::failing("down", "model is overloaded");
+        let generator = LLMSpecificationGenerator::new_with_defaults()
+            .with_fstar_backend(backend)
+            .with_candidate_clients(vec![down.clone(), close.clone()]);
+
+        let spec = generator
+            .generate_specification(&requirements(), Domain::Cryptography, &candidate_options(4)).await
+            .unwrap();
+
+        assert!(spec.formal_spec.spec_code.contains("BROKEN"));
+        assert!(spec.metadata.confidence_score < 1.0 && spec.metadata.confidence_score > 0.0);
+        let candidates = &spec.formal_spec.candidates;
+        assert_eq!(candidates.len(), 4);
+        assert!(candidates[0].failure.as_deref().unwrap().contains("model is overloaded"));
+        assert!(candidates[1].selected);
+        assert_eq!(*down.samples.lock().unwrap(), vec![0, 2]);
+
+        // When every candidate fails there is nothing to select
+        let generator = LLMSpecificationGenerator::new_with_defaults().with_candidate_clients(vec![down]);
+        let result = generator.generate_specification(
+            &requirements(),
+            Domain::Cryptography,
+            &candidate_options(2)
+        ).await;
+        assert!(matches!(result, Err(AxiomError::SpecTranslationError(_))), "{:?}", result.map(|s| s.id));
+    }
+
+    #[tokio::test]
+    async fn test_candidates_cycle_through_configured_models() {
+        let (backend, _dir) = stub_fstar("candidates_models");
+        let server = StubHttpServer::start(
+            vec![
+                (
+                    "/api/chat",
+                    vec![
+                        StubResponse::json(
+                            200,
+                            r#"{"message":{"role":"assistant","content":"```fstar\nmodule Incr\n```"},"done":true}"#
+                        )
+                    ],
+                )
+            ]
+        );
+        let local_model = |model: &str| ApiConfig {
+            provider: Some("local".to_string()),
+            api_key: None,
+            api_endpoint: Some(format!("{}/api/chat", server.base_url)),
+            model: Some(model.to_string()),
+            organization_id: None,
+            parameters: None,
+        };
+        let config = GeneratorConfig {
+            candidate_models: vec![local_model("llama3:8b"), local_model("mistral:7b")],
+            ..GeneratorConfig::default()
+        };
+        let generator = LLMSpecificationGenerator::new(config).with_fstar_backend(backend);
+
+        let spec = generator
+            .generate_specification(&requirements(), Domain::Cryptography, &candidate_options(2)).await
+            .unwrap();
+
+        let mut models: Vec<String> = server
+            .requests()
+            .iter()
+            .filter(|request| request.path == "/api/chat")
+            .map(|request| {
+                let body: serde_json::Value = serde_json::from_str(&request.body).unwrap();
+                body["model"].as_str().unwrap().to_string()
+            })
+            .collect();
+        models.sort();
+        assert_eq!(models, vec!["llama3:8b", "mistral:7b"]);
+        assert_eq!(spec.formal_spec.candidates.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_candidates_are_checked_with_the_backend_for_their_language() {
+        let dir = scratch_dir("candidates_z3");
+        let z3 = write_stub_tool(&dir, "z3", STUB_Z3);
+        let mut tools = ExternalToolsConfig::default();
+        tools.tool_paths.insert("z3".to_string(), z3.display().to_string());
+        let backends = BackendRegistry::new().with_backend(
+            VerificationSystem::Z3,
+            Z3Backend::new(&tools).with_work_dir(dir.join("runs"))
+        );
+        let loose = FixedModel::new("loose", "(declare-const x Int)\n(assert (< x 0))\n(check-sat)");
+        let tight = FixedModel::new("tight", "(declare-const x Int)\n(assert (> x 0))\n(check-sat)");
+        let generator = LLMSpecificationGenerator::new_with_defaults()
+            .with_backend_registry(Arc::new(backends), tools.timeout)
+            .with_candidate_clients(vec![loose, tight]);
+        let options = SpecificationOptions {
+            verification_language: VerificationLanguage::Z3SMT,
+            ..candidate_options(2)
+        };
+
+        let spec = generator.generate_specification(&requirements(), Domain::Cryptography, &options).await.unwrap();
+
+        assert!(spec.formal_spec.spec_code.contains("(> x 0)"));
+        let scores: Vec<_> = spec.formal_spec.candidates
+            .iter()
+            .map(|c| (c.model.clone(), c.errors, c.tool_validated, c.selected))
+            .collect();
+        assert_eq!(scores, vec![("loose".to_string(), 1, true, false), ("tight".to_string(), 0, true, true)]);
+    }
+}
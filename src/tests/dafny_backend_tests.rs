@@ -0,0 +1,219 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::implementations::backends::dafny::parse_csv_log;
+    use crate::models::verification::GoalOutcome;
+
+    #[test]
+    fn test_parse_csv_log() {
+        let csv =
+            "TestResult.DisplayName,TestResult.Outcome,TestResult.Duration,TestResult.ResourceCount\n\
+            Impl$$_module.__default.Abs (correctness),Passed,00:00:00.2500000,8531\n\
+            CheckWellFormed$$_module.__default.Max (well-formedness),Passed,00:00:00.0100000,120\n\
+            Impl$$_module.__default.Max (correctness),Failed,00:00:01.5000000,40213\n\
+            Impl$$Crypto.Keys.Rotate (correctness),TimedOut,00:00:30.0000000,900000";
+
+        let goals = parse_csv_log(csv);
+
+        assert_eq!(goals.len(), 4);
+        assert_eq!(goals[0].name, "Abs (correctness)");
+        assert_eq!(goals[0].outcome, GoalOutcome::Valid);
+        assert_eq!(goals[0].duration, Duration::from_millis(250));
+        assert_eq!(goals[0].resource_count, Some(8531));
+        assert_eq!(goals[1].name, "Max (well-formedness)");
+        assert_eq!(goals[2].outcome, GoalOutcome::Invalid);
+        assert_eq!(goals[3].name, "Crypto.Keys.Rotate (correctness)");
+        assert_eq!(goals[3].outcome, GoalOutcome::Timeout);
+        assert_eq!(goals[3].duration, Duration::from_secs(30));
+    }
+}
+
+#[cfg(all(test, unix))]
+mod backend_tests {
+    use std::path::{ Path, PathBuf };
+    use std::sync::Arc;
+
+    use crate::config::ExternalToolsConfig;
+    use crate::implementations::backends::{ BackendRegistry, DafnyBackend };
+    use crate::implementations::specification_generator::LLMSpecificationGenerator;
+    use crate::models::common::{ Domain, ResourceLimits, VerificationLanguage, VerificationSystem };
+    use crate::models::specification::{ FormalSpecification, Specification, SpecificationMetadata };
+    use crate::models::verification::{
+        ArtifactType,
+        GoalOutcome,
+        VerificationOptions,
+        VerificationStatus,
+    };
+    use crate::tests::stub_tools::{ scratch_dir, write_stub_tool };
+    use crate::traits::specification_generator::{ SpecificationGenerator, ValidationDepth };
+    use crate::traits::verification_engine::VerificationBackendAdapter;
+
+    // Stub dafny: writes a per-method CSV log, fails methods containing `FAIL`,
+    // and times out methods containing `SLOWPROOF`
+    const STUB_DAFNY: &str = r#"
+if [ "$1" = "--version" ]; then
+  echo "4.8.0"
+  exit 0
+fi
+echo "$@" > args.txt
+prev=""
+for arg in "$@"; do
+  if [ "$prev" = "--log-format" ]; then log="${arg#*LogFileName=}"; fi
+  case "$arg" in *.dfy) file="$arg" ;; esac
+  prev="$arg"
+done
+echo "TestResult.DisplayName,TestResult.Outcome,TestResult.Duration,TestResult.ResourceCount" > "$log"
+echo "Impl\$\$_module.__default.Abs (correctness),Passed,00:00:00.1000000,4200" >> "$log"
+if grep -q "FAIL" "$file"; then
+  echo "Impl\$\$_module.__default.Max (correctness),Failed,00:00:00.3000000,9100" >> "$log"
+  echo "$(basename "$file")(7,4): Error: a postcondition could not be proved on this return path"
+  echo ""
+  echo "Dafny program verifier finished with 1 verified, 1 error"
+  exit 4
+fi
+if grep -q "SLOWPROOF" "$file"; then
+  echo "Impl\$\$_module.__default.Max (correctness),TimedOut,00:00:10.0000000,90000" >> "$log"
+  echo "Dafny program verifier finished with 1 verified, 0 errors, 1 time out"
+  exit 4
+fi
+echo "Dafny program verifier finished with 1 verified, 0 errors"
+exit 0
+"#;
+
+    fn stub_backend(name: &str) -> (DafnyBackend, PathBuf) {
+        let dir = scratch_dir(name);
+        let dafny = write_stub_tool(&dir, "dafny", STUB_DAFNY);
+
+        let mut tools = ExternalToolsConfig::default();
+        tools.tool_paths.insert("dafny".to_string(), dafny.display().to_string());
+
+        let backend = DafnyBackend::new(&tools).with_work_dir(dir.join("runs"));
+        (backend, dir)
+    }
+
+    fn run_dir_of(result_log: &str) -> PathBuf {
+        Path::new(result_log).parent().unwrap().to_path_buf()
+    }
+
+    #[test]
+    fn test_backend_version_and_availability() {
+        let (backend, _dir) = stub_backend("dafny_version");
+
+        assert!(backend.check_backend_availability().unwrap());
+        assert_eq!(backend.get_backend_version().unwrap(), "4.8.0");
+
+        let mut tools = ExternalToolsConfig::default();
+        tools.tool_paths.insert("dafny".to_string(), "/nonexistent/dafny".to_string());
+        assert!(DafnyBackend::new(&tools).install_dependencies().is_err());
+    }
+
+    #[test]
+    fn test_verified_program_reports_methods_and_artifacts() {
+        let (backend, _dir) = stub_backend("dafny_verified");
+        let options = VerificationOptions {
+            resource_limits: ResourceLimits {
+                parallel_jobs: Some(2),
+                ..ResourceLimits::default()
+            },
+            ..VerificationOptions::default()
+        };
+
+        let result = backend
+            .execute_verification("method Abs(x: int) returns (y: int) ensures y >= 0 { y := if x < 0 then -x else x; }", "", &options)
+            .unwrap();
+
+        assert!(matches!(result.status, VerificationStatus::Verified));
+        assert_eq!(result.resource_usage.lemmas_proven, 1);
+        assert_eq!(result.goal_results.len(), 1);
+        assert_eq!(result.goal_results[0].name, "Abs (correctness)");
+        assert_eq!(result.goal_results[0].resource_count, Some(4200));
+
+        let log = &result.proof_artifacts[0];
+        assert!(matches!(log.artifact_type, ArtifactType::Log));
+        assert!(result.proof_artifacts.iter().any(|a| a.path.ends_with("verification.csv")));
+
+        let args = std::fs::read_to_string(run_dir_of(&log.path).join("args.txt")).unwrap();
+        assert!(args.starts_with("verify --log-format csv;LogFileName="));
+        assert!(args.contains("--verification-time-limit 30"));
+        assert!(args.contains("--cores 2"));
+    }
+
+    #[test]
+    fn test_failed_method_is_reported() {
+        let (backend, _dir) = stub_backend("dafny_failed");
+
+        let result = backend
+            .execute_verification("// FAIL\nmethod Max(a: int, b: int) returns (m: int)", "", &VerificationOptions::default())
+            .unwrap();
+
+        match &result.status {
+            VerificationStatus::Failed(reasons) => {
+                assert_eq!(reasons.len(), 1);
+                assert!(reasons[0].starts_with("spec.dfy(7,4): Error: a postcondition"));
+            }
+            other => panic!("Expected failure, got {:?}", other),
+        }
+        assert_eq!(result.resource_usage.lemmas_proven, 1);
+        assert_eq!(result.goal_results[1].outcome, GoalOutcome::Invalid);
+    }
+
+    #[test]
+    fn test_method_timeouts_report_timeout() {
+        let (backend, _dir) = stub_backend("dafny_timeout");
+
+        let result = backend
+            .execute_verification("// SLOWPROOF\nmethod Max(a: int, b: int) returns (m: int)", "", &VerificationOptions::default())
+            .unwrap();
+
+        assert!(matches!(result.status, VerificationStatus::Timeout));
+        assert_eq!(result.goal_results[1].outcome, GoalOutcome::Timeout);
+    }
+
+    fn dafny_spec(code: &str) -> Specification {
+        Specification {
+            id: "dafny_spec".to_string(),
+            source_requirements: vec!["Abs returns a non-negative number".to_string()],
+            formal_properties: vec![],
+            formal_spec: FormalSpecification {
+                verification_language: VerificationLanguage::DafnyLang,
+                spec_code: code.to_string(),
+                components: std::collections::HashMap::new(),
+                dependencies: vec![],
+                candidates: vec![],
+            },
+            metadata: SpecificationMetadata {
+                created_at: chrono::Utc::now(),
+                verification_system: VerificationSystem::Dafny,
+                domain: Domain::SystemsSoftware,
+                confidence_score: 0.9,
+                is_formally_validated: false,
+            },
+        }
+    }
+
+    /// Arguments of the single Dafny run made when validating at the given depth
+    async fn validation_args(name: &str, depth: ValidationDepth) -> String {
+        let (backend, dir) = stub_backend(name);
+        let backends = BackendRegistry::new().with_backend(VerificationSystem::Dafny, backend);
+        let generator = LLMSpecificationGenerator::new_with_defaults()
+            .with_backend_registry(Arc::new(backends), ExternalToolsConfig::default().timeout);
+
+        let report = generator
+            .validate_specification(&dafny_spec("method Abs(x: int) returns (y: int)"), depth).await
+            .unwrap();
+        assert!(report.is_valid && report.tool_validated);
+
+        let run = std::fs::read_dir(dir.join("runs")).unwrap().next().unwrap().unwrap().path();
+        std::fs::read_to_string(run.join("args.txt")).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_type_check_validation_runs_at_the_quick_proof_level() {
+        let quick = validation_args("dafny_validation_quick", ValidationDepth::TypeCheck).await;
+        assert!(quick.contains("--verification-time-limit 10"), "{}", quick);
+
+        let full = validation_args("dafny_validation_full", ValidationDepth::FormalVerification).await;
+        assert!(full.contains("--verification-time-limit 30"), "{}", full);
+    }
+}
@@ -0,0 +1,176 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::implementations::backends::frama_c::parse_wp_output;
+    use crate::models::verification::GoalOutcome;
+
+    #[test]
+    fn test_parse_goal_layout_and_summary() {
+        let output =
+            "[kernel] Parsing axiom_impl.c (with preprocessing)\n\
+            [wp] Warning: Missing RTE guards\n\
+            [wp] [Alt-Ergo 2.5.2] Goal typed_incr_ensures_p2 : Valid (Qed:2ms) (12ms) (24)\n\
+            [wp] [Alt-Ergo 2.5.2] Goal typed_incr_assert_rte_unsigned_overflow : Unknown (1.5s)\n\
+            [wp] [Z3 4.12.2] Goal typed_clamp_ensures : Stepout\n\
+            [wp] Proved goals:    1 / 3";
+
+        let (goals, summary) = parse_wp_output(output);
+
+        assert_eq!(goals.len(), 3);
+        assert_eq!(goals[0].name, "typed_incr_ensures_p2");
+        assert_eq!(goals[0].outcome, GoalOutcome::Valid);
+        assert_eq!(goals[0].duration, Duration::from_millis(12));
+        assert_eq!(goals[0].prover.as_deref(), Some("Alt-Ergo 2.5.2"));
+        assert_eq!(goals[1].outcome, GoalOutcome::Unknown);
+        assert_eq!(goals[1].duration, Duration::from_millis(1500));
+        assert_eq!(goals[2].outcome, GoalOutcome::OutOfResource);
+        assert_eq!(summary, Some((1, 3)));
+    }
+
+    #[test]
+    fn test_parse_status_first_layout() {
+        let output =
+            "[wp] [Timeout] typed_incr_assert_rte_unsigned_overflow (Qed 1ms) (Alt-Ergo)\n\
+            [wp] [Failed] typed_clamp_ensures (Qed 2ms)\n\
+            [wp] Proved goals:   10 / 12";
+
+        let (goals, summary) = parse_wp_output(output);
+
+        assert_eq!(goals.len(), 2);
+        assert_eq!(goals[0].name, "typed_incr_assert_rte_unsigned_overflow");
+        assert_eq!(goals[0].outcome, GoalOutcome::Timeout);
+        assert_eq!(goals[0].prover.as_deref(), Some("Alt-Ergo"));
+        assert!(matches!(goals[1].outcome, GoalOutcome::Error(_)));
+        assert_eq!(goals[1].prover, None);
+        assert_eq!(summary, Some((10, 12)));
+    }
+}
+
+#[cfg(all(test, unix))]
+mod backend_tests {
+    use crate::config::ExternalToolsConfig;
+    use crate::implementations::backends::FramaCBackend;
+    use crate::models::common::ResourceLimits;
+    use crate::models::verification::{ GoalOutcome, VerificationOptions, VerificationStatus };
+    use crate::tests::stub_tools::{ scratch_dir, write_stub_tool };
+    use crate::traits::verification_engine::VerificationBackendAdapter;
+
+    const SPEC: &str =
+        "#include <stdint.h>\n\n/*@ requires p1: x < UINT64_MAX;\n  @ ensures p2: \\result > x;\n  @*/\nuint64_t incr(uint64_t x);\n";
+
+    // Stub frama-c: proves every goal, leaves a goal unknown for implementations
+    // mentioning `BROKEN`, generates no goal for those mentioning `NO_GOALS`, and
+    // rejects specifications mentioning `SYNTAX`
+    const STUB_FRAMA_C: &str = r#"
+if [ "$1" = "-version" ]; then
+  echo "30.0 (Zinc)"
+  exit 0
+fi
+echo "$@" > args.txt
+for arg in "$@"; do file="$arg"; done
+if grep -q "SYNTAX" axiom_spec.h; then
+  echo "[kernel:annot-error] axiom_spec.h:3: Warning: unexpected token 'SYNTAX'"
+  echo "[kernel] User Error: warning annot-error treated as fatal error."
+  exit 1
+fi
+echo "[kernel] Parsing $file (with preprocessing)"
+echo "[wp] Running WP plugin..."
+if grep -q "NO_GOALS" "$file"; then
+  echo "[wp] Proved goals:    0 / 0"
+  exit 0
+fi
+echo "[wp] [Alt-Ergo 2.5.2] Goal typed_incr_ensures_p2 : Valid (8ms) (14)"
+if grep -q "BROKEN" "$file"; then
+  echo "[wp] [Alt-Ergo 2.5.2] Goal typed_incr_assert_rte_unsigned_overflow : Unknown (40ms)"
+  echo "[wp] Proved goals:    1 / 2"
+else
+  echo "[wp] [Alt-Ergo 2.5.2] Goal typed_incr_assert_rte_unsigned_overflow : Valid (5ms) (9)"
+  echo "[wp] Proved goals:    2 / 2"
+fi
+"#;
+
+    fn stub_backend(name: &str) -> (FramaCBackend, std::path::PathBuf) {
+        let dir = scratch_dir(name);
+        let frama_c = write_stub_tool(&dir, "frama-c", STUB_FRAMA_C);
+
+        let mut tools = ExternalToolsConfig::default();
+        tools.tool_paths.insert("frama-c".to_string(), frama_c.display().to_string());
+
+        let backend = FramaCBackend::new(&tools).with_work_dir(dir.join("runs"));
+        (backend, dir)
+    }
+
+    fn run_file(result_log: &str, name: &str) -> String {
+        let run_dir = std::path::Path::new(result_log).parent().unwrap();
+        std::fs::read_to_string(run_dir.join(name)).unwrap()
+    }
+
+    #[test]
+    fn test_proved_goals_verify_the_implementation() {
+        let (backend, _dir) = stub_backend("frama_c_verified");
+        assert!(backend.check_backend_availability().unwrap());
+        assert_eq!(backend.get_backend_version().unwrap(), "30.0 (Zinc)");
+
+        let options = VerificationOptions {
+            resource_limits: ResourceLimits {
+                provers: vec!["alt-ergo".to_string(), "z3".to_string()],
+                ..ResourceLimits::default()
+            },
+            ..VerificationOptions::default()
+        };
+        let result = backend
+            .execute_verification(SPEC, "uint64_t incr(uint64_t x) { return x + 1; }", &options)
+            .unwrap();
+
+        assert!(matches!(result.status, VerificationStatus::Verified), "{:?}", result.status);
+        assert_eq!(result.goal_results.len(), 2);
+        assert_eq!(result.resource_usage.lemmas_proven, 2);
+
+        let log = &result.proof_artifacts[0].path;
+        assert!(run_file(log, "axiom_impl.c").starts_with("#include \"axiom_spec.h\"\n"));
+        let args = run_file(log, "args.txt");
+        assert!(args.contains(" -wp -wp-rte -wp-timeout 10 -wp-prover alt-ergo,z3 "), "{}", args);
+        assert!(args.trim_end().ends_with("axiom_impl.c"));
+    }
+
+    #[test]
+    fn test_unknown_goal_fails_and_annotation_error_is_reported() {
+        let (backend, _dir) = stub_backend("frama_c_failed");
+
+        let unproven = backend
+            .execute_verification(SPEC, "uint64_t incr(uint64_t x) { return x + 2; } // BROKEN", &VerificationOptions::default())
+            .unwrap();
+        match unproven.status {
+            VerificationStatus::Failed(reasons) => {
+                assert_eq!(reasons, vec!["typed_incr_assert_rte_unsigned_overflow: Unknown (Alt-Ergo 2.5.2)".to_string()]);
+            }
+            other => panic!("Expected failure, got {:?}", other),
+        }
+        assert_eq!(unproven.goal_results[1].outcome, GoalOutcome::Unknown);
+        assert_eq!(unproven.resource_usage.lemmas_proven, 1);
+
+        let broken = backend
+            .execute_verification("/*@ requires SYNTAX; */\nvoid f(void);", "", &VerificationOptions::default())
+            .unwrap();
+        match broken.status {
+            VerificationStatus::Failed(reasons) => {
+                assert_eq!(reasons.len(), 2, "{:?}", reasons);
+                assert!(reasons[0].contains("unexpected token 'SYNTAX'"));
+            }
+            other => panic!("Expected failure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_implementation_without_goals_is_unverified() {
+        let (backend, _dir) = stub_backend("frama_c_no_goals");
+
+        let result = backend
+            .execute_verification(SPEC, "// NO_GOALS\nint unrelated(void) { return 0; }", &VerificationOptions::default())
+            .unwrap();
+
+        assert_eq!(result.status, VerificationStatus::Unverified);
+        assert_eq!(result.resource_usage.lemmas_proven, 0);
+    }
+}
@@ -0,0 +1,247 @@
+#[cfg(all(test, unix))]
+mod tests {
+    use std::path::{ Path, PathBuf };
+    use std::time::Duration;
+
+    use crate::config::ExternalToolsConfig;
+    use crate::implementations::backends::FStarBackend;
+    use crate::implementations::specification_generator::LLMSpecificationGenerator;
+    use crate::errors::AxiomError;
+    use crate::models::common::{ Domain, Language, ProofLevel, VerificationLanguage, VerificationSystem };
+    use crate::models::implementation::Implementation;
+    use crate::models::specification::{ FormalSpecification, Specification, SpecificationMetadata };
+    use crate::models::verification::{
+        ArtifactType,
+        VerificationOptions,
+        VerificationResult,
+        VerificationStatus,
+    };
+    use crate::tests::stub_tools::{ scratch_dir, write_stub_tool };
+    use crate::traits::specification_generator::{ SpecificationGenerator, ValidationDepth };
+    use crate::traits::verification_engine::VerificationBackendAdapter;
+
+    // Stub fstar.exe: records its arguments, fails on `assert False`, hangs on `SLOW`
+    const STUB_FSTAR: &str = r#"
+if [ "$1" = "--version" ]; then
+  echo "F* 2025.03.25"
+  echo "platform=Linux_x86_64"
+  exit 0
+fi
+for arg in "$@"; do
+  case "$arg" in *.fst) file="$arg" ;; esac
+done
+echo "$@" > args.txt
+if grep -q "SLOW" "$file"; then
+  sleep 5
+fi
+if grep -q "assert False" "$file"; then
+  echo "$(basename "$file")(4,2-4,14): (Error 19) Assertion failed; The SMT solver could not prove the query" >&2
+  echo "1 error was reported (see above)" >&2
+  exit 1
+fi
+echo "Verified module: $(basename "$file" .fst)"
+echo "All verification conditions discharged successfully"
+exit 0
+"#;
+
+    fn stub_backend(name: &str) -> (FStarBackend, PathBuf) {
+        let dir = scratch_dir(name);
+        let fstar = write_stub_tool(&dir, "fstar.exe", STUB_FSTAR);
+
+        let mut tools = ExternalToolsConfig::default();
+        tools.tool_paths.insert("fstar".to_string(), fstar.display().to_string());
+
+        let backend = FStarBackend::new(&tools).with_work_dir(dir.join("runs"));
+        (backend, dir)
+    }
+
+    fn run_dir_of(result_log: &str) -> PathBuf {
+        Path::new(result_log).parent().unwrap().to_path_buf()
+    }
+
+    fn fstar_spec(code: &str) -> Specification {
+        Specification {
+            id: "fstar_spec".to_string(),
+            source_requirements: vec!["Increment returns a larger number".to_string()],
+            formal_properties: vec![],
+            formal_spec: FormalSpecification {
+                verification_language: VerificationLanguage::FStarLang,
+                spec_code: code.to_string(),
+                components: std::collections::HashMap::new(),
+                dependencies: vec![],
+                candidates: vec![],
+            },
+            metadata: SpecificationMetadata {
+                created_at: chrono::Utc::now(),
+                verification_system: VerificationSystem::FStar,
+                domain: Domain::Cryptography,
+                confidence_score: 0.9,
+                is_formally_validated: false,
+            },
+        }
+    }
+
+    #[test]
+    fn test_backend_version_and_availability() {
+        let (backend, _dir) = stub_backend("fstar_version");
+
+        assert!(backend.check_backend_availability().unwrap());
+        assert_eq!(backend.get_backend_version().unwrap(), "F* 2025.03.25");
+        assert!(backend.install_dependencies().is_ok());
+    }
+
+    #[test]
+    fn test_missing_executable_is_unavailable() {
+        let mut tools = ExternalToolsConfig::default();
+        tools.tool_paths.insert("fstar".to_string(), "/nonexistent/fstar.exe".to_string());
+        let backend = FStarBackend::new(&tools);
+
+        assert!(!backend.check_backend_availability().unwrap());
+        assert!(backend.get_backend_version().is_err());
+        assert!(backend.install_dependencies().is_err());
+    }
+
+    #[test]
+    fn test_verified_specification_produces_log_artifact() {
+        let (backend, _dir) = stub_backend("fstar_verified");
+
+        let result = backend
+            .execute_verification(
+                "let incr (x:int) : y:int{y > x} = x + 1",
+                "",
+                &VerificationOptions::default()
+            )
+            .unwrap();
+
+        assert!(matches!(result.status, VerificationStatus::Verified));
+        assert_eq!(result.resource_usage.lemmas_proven, 1);
+
+        let log = result.proof_artifacts
+            .iter()
+            .find(|a| matches!(a.artifact_type, ArtifactType::Log))
+            .expect("F* log artifact");
+        let log_contents = std::fs::read_to_string(&log.path).unwrap();
+        assert!(log_contents.contains("Verified module: AxiomSpec"));
+
+        // Specs without a module declaration get a default module header
+        let module = std::fs::read_to_string(run_dir_of(&log.path).join("AxiomSpec.fst")).unwrap();
+        assert!(module.starts_with("module AxiomSpec"));
+    }
+
+    #[test]
+    fn test_failed_verification_reports_fstar_errors() {
+        let (backend, _dir) = stub_backend("fstar_failed");
+
+        let result = backend
+            .execute_verification(
+                "module Broken\n\nlet bad () : unit =\n  assert False",
+                "",
+                &VerificationOptions::default()
+            )
+            .unwrap();
+
+        match result.status {
+            VerificationStatus::Failed(reasons) => {
+                assert_eq!(reasons.len(), 1);
+                assert!(reasons[0].starts_with("Broken.fst(4,2-4,14): (Error 19)"));
+            }
+            other => panic!("Expected failure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_verification_timeout() {
+        let (backend, _dir) = stub_backend("fstar_timeout");
+        let options = VerificationOptions {
+            timeout: Duration::from_millis(300),
+            ..VerificationOptions::default()
+        };
+
+        let result = backend.execute_verification("module Slow\n// SLOW", "", &options).unwrap();
+
+        assert!(matches!(result.status, VerificationStatus::Timeout));
+    }
+
+    #[test]
+    fn test_command_flags_follow_options() {
+        let (backend, _dir) = stub_backend("fstar_flags");
+        let options = VerificationOptions {
+            proof_level: ProofLevel::Quick,
+            ..VerificationOptions::default()
+        };
+
+        let result = backend
+            .with_lax(true)
+            .execute_verification("module Flags", "", &options)
+            .unwrap();
+
+        let log = &result.proof_artifacts[0].path;
+        let args = std::fs::read_to_string(run_dir_of(log).join("args.txt")).unwrap();
+        assert!(args.contains("--lax"));
+        assert!(args.contains("--z3rlimit 5"));
+        assert!(args.trim_end().ends_with("Flags.fst"));
+    }
+
+    #[tokio::test]
+    async fn test_type_check_validation_uses_fstar() {
+        let (backend, _dir) = stub_backend("fstar_validation");
+        let generator = LLMSpecificationGenerator::new_with_defaults().with_fstar_backend(backend);
+
+        let report = generator
+            .validate_specification(
+                &fstar_spec("module Incr\n\nlet incr (x:int) : y:int{y > x} = x + 1"),
+                ValidationDepth::TypeCheck
+            ).await
+            .unwrap();
+
+        assert!(report.is_valid);
+        assert!(report.tool_validated);
+        assert!(report.tool_output.unwrap().contains("All verification conditions discharged"));
+    }
+
+    #[test]
+    fn test_implementation_in_other_language_is_rejected() {
+        let (backend, _dir) = stub_backend("fstar_other_language");
+        let implementation = Implementation {
+            id: "incr_impl".to_string(),
+            specification_id: "fstar_spec".to_string(),
+            language: Language::Rust,
+            source_code: "pub fn incr(x: u64) -> u64 { panic!() }".to_string(),
+            verification_result: VerificationResult {
+                status: VerificationStatus::Unverified,
+                proof_artifacts: vec![],
+                verification_time: Duration::ZERO,
+                resource_usage: Default::default(),
+                goal_results: vec![],
+                counterexamples: vec![],
+            },
+        };
+
+        match backend.convert_implementation(&implementation) {
+            Err(AxiomError::LanguageCompatibilityError { source_lang, target_lang }) => {
+                assert_eq!(source_lang, "Rust");
+                assert_eq!(target_lang, "F*");
+            }
+            other => panic!("Expected a language compatibility error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validation_uses_configured_tool_timeout() {
+        let dir = scratch_dir("fstar_validation_timeout");
+        let fstar = write_stub_tool(&dir, "fstar.exe", STUB_FSTAR);
+        let mut tools = ExternalToolsConfig::default();
+        tools.tool_paths.insert("fstar".to_string(), fstar.display().to_string());
+        tools.timeout = Duration::from_millis(300);
+        let backend = FStarBackend::new(&tools).with_work_dir(dir.join("runs"));
+        let generator = LLMSpecificationGenerator::new_with_defaults().with_fstar_backend(backend);
+
+        let report = generator
+            .validate_specification(&fstar_spec("module Slow\n// SLOW"), ValidationDepth::TypeCheck).await
+            .unwrap();
+
+        assert!(!report.is_valid);
+        assert!(report.tool_validated);
+        assert!(report.issues[0].message.contains("timed out"), "{:?}", report.issues);
+    }
+}
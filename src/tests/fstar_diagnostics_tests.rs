@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod tests {
+    use crate::implementations::backends::fstar_diagnostics::{
+        describe_issue,
+        missing_identifier,
+        parse_fstar_diagnostics,
+        FStarErrorCategory,
+    };
+    use crate::models::specification::{ IssueSeverity, SourceSpan };
+
+    #[test]
+    fn test_parse_classic_error() {
+        let output =
+            "Crypto.fst(12,4-12,20): (Error 189) Expected expression of type int; got expression true of type bool\n\
+            1 error was reported (see above)";
+
+        let issues = parse_fstar_diagnostics(output);
+
+        assert_eq!(issues.len(), 1);
+        let issue = &issues[0];
+        assert_eq!(issue.severity, IssueSeverity::Error);
+        assert_eq!(issue.error_code, Some(189));
+        assert_eq!(issue.line_number, Some(12));
+        assert_eq!(
+            issue.span,
+            Some(SourceSpan {
+                file: Some("Crypto.fst".to_string()),
+                start_line: 12,
+                start_column: 4,
+                end_line: 12,
+                end_column: 20,
+            })
+        );
+        assert!(issue.message.starts_with("Expected expression of type int"));
+        assert_eq!(FStarErrorCategory::of(issue), FStarErrorCategory::Type);
+    }
+
+    #[test]
+    fn test_parse_bulleted_diagnostics() {
+        let output =
+            "* Error 19 at Crypto.fst(4,2-6,14):\n\
+              - Assertion failed\n\
+              - The SMT solver could not prove the query.\n\
+            \n\
+            * Warning 241 at Crypto.fst(1,0-1,0):\n\
+              - Unable to load cached module\n\
+            \n\
+            1 error was reported (see above)";
+
+        let issues = parse_fstar_diagnostics(output);
+
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].severity, IssueSeverity::Error);
+        assert_eq!(issues[0].error_code, Some(19));
+        assert_eq!(issues[0].message, "Assertion failed; The SMT solver could not prove the query.");
+        assert_eq!(issues[0].span.as_ref().map(|s| (s.end_line, s.end_column)), Some((6, 14)));
+        assert_eq!(FStarErrorCategory::of(&issues[0]), FStarErrorCategory::Proof);
+
+        assert_eq!(issues[1].severity, IssueSeverity::Warning);
+        assert_eq!(issues[1].error_code, Some(241));
+    }
+
+    #[test]
+    fn test_missing_identifier_and_syntax_errors() {
+        let output =
+            "Keys.fst(8,10-8,17): (Error 72) Identifier not found: [Keys.rotate]\n\
+            Keys.fst(15,0-15,3): (Error 168) Syntax error";
+
+        let issues = parse_fstar_diagnostics(output);
+
+        assert_eq!(issues.len(), 2);
+        assert_eq!(FStarErrorCategory::of(&issues[0]), FStarErrorCategory::MissingDefinition);
+        assert_eq!(missing_identifier(&issues[0]), Some("rotate".to_string()));
+        assert_eq!(FStarErrorCategory::of(&issues[1]), FStarErrorCategory::Syntax);
+        assert_eq!(
+            describe_issue(&issues[1]),
+            "Keys.fst(15,0-15,3): (Error 168) Syntax error"
+        );
+    }
+
+    #[test]
+    fn test_success_output_has_no_issues() {
+        let output = "Verified module: Crypto\nAll verification conditions discharged successfully";
+
+        assert!(parse_fstar_diagnostics(output).is_empty());
+    }
+}
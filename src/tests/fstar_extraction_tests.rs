@@ -0,0 +1,173 @@
+#[cfg(all(test, unix))]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::config::ExternalToolsConfig;
+    use crate::implementations::fstar_extraction::{
+        has_definitions,
+        FStarExtractor,
+        EXTRACTED_CODE_ARTIFACT,
+    };
+    use crate::models::common::{ Domain, Language, OptimizationLevel, VerificationLanguage, VerificationSystem };
+    use crate::models::implementation::ImplementationOptions;
+    use crate::models::specification::{ FormalSpecification, Specification, SpecificationMetadata };
+    use crate::models::verification::{ ArtifactType, VerificationStatus };
+    use crate::tests::stub_tools::{ scratch_dir, write_stub_tool };
+    use crate::traits::implementation_generator::ImplementationGenerator;
+
+    // Stub fstar.exe: records its arguments, fails on `assert False`, and otherwise writes
+    // `<Module>.ml` or `<Module>.krml` to the --odir directory
+    const STUB_FSTAR: &str = r#"
+echo "$@" > args.txt
+while [ $# -gt 0 ]; do
+  case "$1" in
+    --odir) odir="$2"; shift ;;
+    --codegen) codegen="$2"; shift ;;
+    --extract) module="$2"; shift ;;
+    *.fst) file="$1" ;;
+  esac
+  shift
+done
+if grep -q "assert False" "$file"; then
+  echo "$(basename "$file")(4,2-4,14): (Error 19) Assertion failed; The SMT solver could not prove the query" >&2
+  exit 1
+fi
+if [ "$codegen" = "OCaml" ]; then
+  echo "let incr x = Prims.op_Addition x Prims.int_one" > "$odir/$module.ml"
+else
+  echo "krml-ast" > "$odir/$module.krml"
+fi
+echo "Verified module: $module"
+exit 0
+"#;
+
+    // Stub krml: writes a header and a C file for each .krml input to the -tmpdir directory
+    const STUB_KRML: &str = r#"
+while [ $# -gt 0 ]; do
+  case "$1" in
+    -tmpdir) tmpdir="$2"; shift ;;
+    *.krml) name="$(basename "$1" .krml)"
+      echo "uint32_t $name""_incr(uint32_t x);" > "$tmpdir/$name.h"
+      echo "uint32_t $name""_incr(uint32_t x) { return x + 1U; }" > "$tmpdir/$name.c" ;;
+  esac
+  shift
+done
+"#;
+
+    const INCR: &str = "module Incr\n\nval incr: x:nat -> y:nat{y > x}\nlet incr x = x + 1\n";
+
+    fn stub_extractor(name: &str) -> (FStarExtractor, PathBuf) {
+        let dir = scratch_dir(name);
+        let fstar = write_stub_tool(&dir, "fstar.exe", STUB_FSTAR);
+        let krml = write_stub_tool(&dir, "krml", STUB_KRML);
+
+        let mut tools = ExternalToolsConfig::default();
+        tools.tool_paths.insert("fstar".to_string(), fstar.display().to_string());
+        tools.tool_paths.insert("krml".to_string(), krml.display().to_string());
+
+        (FStarExtractor::new(&tools).with_work_dir(dir.join("runs")), dir)
+    }
+
+    fn fstar_spec(code: &str) -> Specification {
+        Specification {
+            id: "incr".to_string(),
+            source_requirements: vec!["Increment returns a larger number".to_string()],
+            formal_properties: vec![],
+            formal_spec: FormalSpecification {
+                verification_language: VerificationLanguage::FStarLang,
+                spec_code: code.to_string(),
+                components: std::collections::HashMap::new(),
+                dependencies: vec![],
+                candidates: vec![],
+            },
+            metadata: SpecificationMetadata {
+                created_at: chrono::Utc::now(),
+                verification_system: VerificationSystem::FStar,
+                domain: Domain::Cryptography,
+                confidence_score: 0.9,
+                is_formally_validated: false,
+            },
+        }
+    }
+
+    fn options() -> ImplementationOptions {
+        ImplementationOptions {
+            optimization_level: OptimizationLevel::Security,
+            include_comments: true,
+            style_guide: None,
+        }
+    }
+
+    #[test]
+    fn test_only_specs_with_definitions_are_extracted() {
+        assert!(has_definitions(INCR));
+        assert!(has_definitions("inline_for_extraction noextract\nlet x = 1"));
+        assert!(!has_definitions("module Spec\n\nval incr: x:nat -> y:nat{y > x}\n"));
+        assert!(!has_definitions("val f: nat -> nat\n  let helper = 1"));
+
+        assert!(FStarExtractor::can_extract(&fstar_spec(INCR), &Language::OCaml));
+        assert!(FStarExtractor::can_extract(&fstar_spec(INCR), &Language::C));
+        assert!(!FStarExtractor::can_extract(&fstar_spec(INCR), &Language::Rust));
+    }
+
+    #[tokio::test]
+    async fn test_ocaml_extraction_inherits_the_fstar_result() {
+        let (extractor, _dir) = stub_extractor("fstar_extract_ocaml");
+
+        let implementation = extractor
+            .generate_implementation(&fstar_spec(INCR), Language::OCaml, &options()).await
+            .unwrap();
+
+        assert_eq!(implementation.language, Language::OCaml);
+        assert_eq!(implementation.source_code, "let incr x = Prims.op_Addition x Prims.int_one\n");
+        assert_eq!(implementation.verification_result.status, VerificationStatus::Verified);
+        assert_eq!(implementation.verification_result.resource_usage.lemmas_proven, 1);
+
+        let artifacts = &implementation.verification_result.proof_artifacts;
+        let log = artifacts
+            .iter()
+            .find(|a| a.artifact_type == ArtifactType::Log)
+            .unwrap();
+        let run_dir = PathBuf::from(&log.path).parent().unwrap().to_path_buf();
+        let args = std::fs::read_to_string(run_dir.join("args.txt")).unwrap();
+        assert!(args.contains("--codegen OCaml --extract Incr"), "{}", args);
+        assert!(
+            artifacts
+                .iter()
+                .any(
+                    |a|
+                        a.artifact_type == ArtifactType::Custom(EXTRACTED_CODE_ARTIFACT.to_string()) &&
+                        a.path.ends_with("Incr.ml")
+                )
+        );
+        assert!(extractor.validate_implementation(&implementation, &fstar_spec(INCR)).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_c_extraction_runs_karamel() {
+        let (extractor, _dir) = stub_extractor("fstar_extract_c");
+
+        let implementation = extractor
+            .generate_implementation(&fstar_spec(INCR), Language::C, &options()).await
+            .unwrap();
+
+        assert_eq!(
+            implementation.source_code,
+            "/* Incr.h */\nuint32_t Incr_incr(uint32_t x);\n/* Incr.c */\nuint32_t Incr_incr(uint32_t x) { return x + 1U; }\n"
+        );
+        assert_eq!(implementation.verification_result.status, VerificationStatus::Verified);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_spec_is_not_extracted() {
+        let (extractor, _dir) = stub_extractor("fstar_extract_rejected");
+        let spec = fstar_spec("module Incr\n\nlet incr x =\n  assert False;\n  x + 1\n");
+
+        let error = extractor
+            .generate_implementation(&spec, Language::OCaml, &options()).await
+            .unwrap_err();
+
+        assert!(error.to_string().contains("F* rejected module Incr"), "{}", error);
+        assert!(error.to_string().contains("Assertion failed"), "{}", error);
+    }
+}
@@ -0,0 +1,244 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::sync::{ Arc, Mutex };
+
+    use async_trait::async_trait;
+
+    use crate::implementations::config::GeneratorConfig;
+    use crate::implementations::implementation_generator::{
+        LLMImplementationGenerator,
+        IMPLEMENTATION_SYSTEM_PROMPT,
+    };
+    use crate::implementations::llm::{ LlmClient, LlmRequest, LlmResponse };
+    use crate::implementations::specification_generator::SpecGenError;
+    use crate::models::common::{ Domain, Language, OptimizationLevel, VerificationLanguage, VerificationSystem };
+    use crate::models::implementation::ImplementationOptions;
+    use crate::models::specification::{ FormalSpecification, Specification, SpecificationMetadata };
+    use crate::traits::implementation_generator::ImplementationGenerator;
+
+    /// Client that answers with the scripted responses in turn, repeating the last one,
+    /// and records every request
+    struct ScriptedClient {
+        answers: Mutex<VecDeque<String>>,
+        requests: Mutex<Vec<LlmRequest>>,
+    }
+
+    impl ScriptedClient {
+        fn new(answers: &[&str]) -> Arc<Self> {
+            Arc::new(Self {
+                answers: Mutex::new(answers.iter().map(|answer| answer.to_string()).collect()),
+                requests: Mutex::new(Vec::new()),
+            })
+        }
+
+        fn prompts(&self) -> Vec<String> {
+            self.requests
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|request| request.prompt.clone())
+                .collect()
+        }
+    }
+
+    #[async_trait]
+    impl LlmClient for ScriptedClient {
+        fn provider(&self) -> &str {
+            "scripted"
+        }
+
+        fn model(&self) -> &str {
+            "scripted-1"
+        }
+
+        async fn complete(&self, request: &LlmRequest) -> Result<LlmResponse, SpecGenError> {
+            self.requests.lock().unwrap().push(request.clone());
+            let mut answers = self.answers.lock().unwrap();
+            let answer = if answers.len() > 1 {
+                answers.pop_front().unwrap()
+            } else {
+                answers.front().cloned().unwrap_or_default()
+            };
+            Ok(LlmResponse::text(answer))
+        }
+    }
+
+    fn spec(language: VerificationLanguage, code: &str) -> Specification {
+        Specification {
+            id: "counter".to_string(),
+            source_requirements: vec!["The counter is positive".to_string()],
+            formal_properties: vec![],
+            formal_spec: FormalSpecification {
+                verification_language: language.clone(),
+                spec_code: code.to_string(),
+                components: std::collections::HashMap::new(),
+                dependencies: vec![],
+                candidates: vec![],
+            },
+            metadata: SpecificationMetadata {
+                created_at: chrono::Utc::now(),
+                verification_system: language.verification_system(),
+                domain: Domain::HighAssuranceSoftware,
+                confidence_score: 0.9,
+                is_formally_validated: false,
+            },
+        }
+    }
+
+    fn generator(client: Arc<ScriptedClient>) -> LLMImplementationGenerator {
+        LLMImplementationGenerator::new(GeneratorConfig::default()).with_llm_client(client)
+    }
+
+    #[tokio::test]
+    async fn test_generate_implementation_extracts_code_and_honors_options() {
+        let client = ScriptedClient::new(&[
+            "Here is the code:\n```rust\nfn increment(x: u32) -> u32 {\n    x + 1\n}\n```\nIt satisfies the spec.",
+        ]);
+        let spec = spec(VerificationLanguage::FStarLang, "val increment: x:nat -> y:nat{y > x}");
+        let options = ImplementationOptions {
+            optimization_level: OptimizationLevel::Security,
+            include_comments: false,
+            style_guide: Some("Use snake_case".to_string()),
+        };
+
+        let generator = generator(client.clone());
+        let implementation = generator
+            .generate_implementation(&spec, Language::Rust, &options).await
+            .unwrap();
+
+        assert_eq!(implementation.source_code, "fn increment(x: u32) -> u32 {\n    x + 1\n}\n");
+        assert_eq!(implementation.language, Language::Rust);
+        assert_eq!(implementation.specification_id, "counter");
+        assert!(generator.validate_implementation(&implementation, &spec).await.unwrap());
+
+        let request = client.requests.lock().unwrap()[0].clone();
+        assert_eq!(request.system, IMPLEMENTATION_SYSTEM_PROMPT);
+        assert!(request.prompt.contains("val increment: x:nat -> y:nat{y > x}"));
+        assert!(request.prompt.contains("Write a Rust implementation of this F* specification"));
+        assert!(request.prompt.contains("Optimization goal: security"));
+        assert!(request.prompt.contains("Do not include comments."));
+        assert!(request.prompt.contains("Follow this style guide:\nUse snake_case"));
+        assert_eq!(generator.usage_summary().calls, 1);
+    }
+
+    #[tokio::test]
+    async fn test_answer_without_code_is_an_error() {
+        let client = ScriptedClient::new(&["```rust\n```"]);
+        let spec = spec(VerificationLanguage::DafnyLang, "method Inc(x: int) returns (y: int) ensures y > x");
+
+        let result = generator(client).generate_implementation(
+            &spec,
+            Language::Go,
+            &ImplementationOptions {
+                optimization_level: OptimizationLevel::None,
+                include_comments: true,
+                style_guide: None,
+            }
+        ).await;
+
+        assert!(result.is_err());
+    }
+
+    #[cfg(unix)]
+    mod refinement {
+        use super::*;
+        use crate::config::ExternalToolsConfig;
+        use crate::implementations::backends::{ BackendRegistry, Z3Backend };
+        use crate::models::verification::{ VerificationOptions, VerificationStatus };
+        use crate::tests::stub_tools::{ scratch_dir, write_stub_tool };
+
+        // Stub z3: `sat` with a model when the query allows a negative x, `unsat` otherwise
+        const STUB_Z3: &str = r#"for arg in "$@"; do file="$arg"; done
+if grep -q "(< x 0)" "$file"; then
+  echo "sat"
+  echo "((define-fun x () Int (- 3)))"
+else
+  echo "unsat"
+fi"#;
+
+        fn z3_registry(name: &str) -> BackendRegistry {
+            let dir = scratch_dir(name);
+            let stub = write_stub_tool(&dir, "z3", STUB_Z3);
+            let mut tools = ExternalToolsConfig::default();
+            tools.tool_paths.insert("z3".to_string(), stub.to_string_lossy().to_string());
+            BackendRegistry::new().with_backend(
+                VerificationSystem::Z3,
+                Z3Backend::new(&tools).with_work_dir(dir.join("runs"))
+            )
+        }
+
+        fn smt_options() -> ImplementationOptions {
+            ImplementationOptions {
+                optimization_level: OptimizationLevel::None,
+                include_comments: true,
+                style_guide: None,
+            }
+        }
+
+        #[tokio::test]
+        async fn test_verifier_feedback_is_fed_back_until_verified() {
+            let registry = z3_registry("impl_refine_verified");
+            let engine = registry.engine(&VerificationSystem::Z3).unwrap();
+            let client = ScriptedClient::new(
+                &["```smt2\n(assert (< x 0))\n```", "```smt2\n(assert (> x 1))\n```"]
+            );
+            let spec = spec(VerificationLanguage::Z3SMT, "(declare-const x Int)\n(assert (> x 0))");
+            let generator = generator(client.clone()).with_max_refinement_rounds(3);
+
+            let implementation = generator
+                .generate_implementation(&spec, Language::Custom("smt2".to_string()), &smt_options()).await
+                .unwrap();
+            let refined = generator
+                .refine_until_verified(&implementation, &spec, &engine, &VerificationOptions::default()).await
+                .unwrap();
+
+            assert_eq!(refined.verification_result.status, VerificationStatus::Verified);
+            assert_eq!(refined.source_code, "(assert (> x 1))\n");
+            let prompts = client.prompts();
+            assert_eq!(prompts.len(), 2);
+            assert!(prompts[1].contains("(assert (< x 0))"));
+            assert!(prompts[1].contains("Verification result: Failed"));
+            assert!(prompts[1].contains("- check-sat #1: x = -3"), "{}", prompts[1]);
+        }
+
+        #[tokio::test]
+        async fn test_refinement_stops_after_the_last_round() {
+            let registry = z3_registry("impl_refine_bounded");
+            let engine = registry.engine(&VerificationSystem::Z3).unwrap();
+            let client = ScriptedClient::new(&["```smt2\n(assert (< x 0))\n```"]);
+            let spec = spec(VerificationLanguage::Z3SMT, "(declare-const x Int)\n(assert (> x 0))");
+            let generator = generator(client.clone()).with_max_refinement_rounds(2);
+
+            let implementation = generator
+                .generate_implementation(&spec, Language::Custom("smt2".to_string()), &smt_options()).await
+                .unwrap();
+            let refined = generator
+                .refine_until_verified(&implementation, &spec, &engine, &VerificationOptions::default()).await
+                .unwrap();
+
+            assert!(matches!(refined.verification_result.status, VerificationStatus::Failed(_)));
+            assert_eq!(client.prompts().len(), 3);
+        }
+
+        #[tokio::test]
+        async fn test_code_the_backend_cannot_check_is_not_refined() {
+            let registry = z3_registry("impl_refine_unsupported");
+            let engine = registry.engine(&VerificationSystem::Z3).unwrap();
+            let client = ScriptedClient::new(&["```rust\nfn counter() -> u32 {\n    1\n}\n```"]);
+            let spec = spec(VerificationLanguage::Z3SMT, "(declare-const x Int)\n(assert (> x 0))");
+            let generator = generator(client.clone()).with_max_refinement_rounds(3);
+
+            let implementation = generator
+                .generate_implementation(&spec, Language::Rust, &smt_options()).await
+                .unwrap();
+            let refined = generator
+                .refine_until_verified(&implementation, &spec, &engine, &VerificationOptions::default()).await
+                .unwrap();
+
+            assert_eq!(refined.source_code, implementation.source_code);
+            assert_eq!(refined.verification_result.status, VerificationStatus::Unverified);
+            assert_eq!(client.prompts().len(), 1);
+        }
+    }
+}
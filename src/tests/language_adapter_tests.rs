@@ -0,0 +1,255 @@
+#[cfg(test)]
+mod tests {
+    use crate::implementations::adapters::{
+        spec_functions,
+        CAdapter,
+        FunctionAdapter,
+        RustAdapter,
+        SpecFunction,
+        SpecParam,
+        SpecType,
+    };
+    use crate::models::common::VerificationLanguage;
+    use crate::models::property::{ Property, PropertyKind };
+    use crate::models::specification::FormalSpecification;
+    use crate::traits::language_adapter::LanguageAdapter;
+
+    const FSTAR_SPEC: &str =
+        "module Counter\n\nval incr: x:nat -> y:nat{y > x}\n\nval clamp: lo:int -> hi:int{lo <= hi}\n  -> v:int -> Tot (r:int{lo <= r /\\ r <= hi})\n\nval incr_lemma: x:nat -> Lemma (x + 1 > x)\n";
+
+    fn formal_spec(language: VerificationLanguage, code: &str) -> FormalSpecification {
+        FormalSpecification {
+            verification_language: language,
+            spec_code: code.to_string(),
+            components: std::collections::HashMap::new(),
+            dependencies: vec![],
+            candidates: vec![],
+        }
+    }
+
+    fn property(id: &str, kind: PropertyKind, definition: &str) -> Property {
+        Property {
+            id: id.to_string(),
+            description: format!("Property {}", id),
+            formal_definition: definition.to_string(),
+            kind,
+        }
+    }
+
+    fn incr_adapter() -> RustAdapter {
+        RustAdapter::new().with_function(SpecFunction {
+            name: "incr".to_string(),
+            params: vec![SpecParam { name: "x".to_string(), ty: SpecType::Nat }],
+            returns: SpecType::Nat,
+        })
+    }
+
+    fn sum_function() -> SpecFunction {
+        SpecFunction {
+            name: "sum".to_string(),
+            params: vec![SpecParam { name: "a".to_string(), ty: SpecType::Sequence(Box::new(SpecType::Int)) }],
+            returns: SpecType::Int,
+        }
+    }
+
+    #[test]
+    fn test_spec_functions_are_read_from_fstar_and_dafny() {
+        let functions = spec_functions(&formal_spec(VerificationLanguage::FStarLang, FSTAR_SPEC));
+        let names: Vec<&str> = functions
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["incr", "clamp"]);
+        assert_eq!(functions[0].params, vec![SpecParam { name: "x".to_string(), ty: SpecType::Nat }]);
+        assert_eq!(functions[0].returns, SpecType::Nat);
+        assert_eq!(functions[1].params.len(), 3);
+        assert_eq!(functions[1].params[1].ty, SpecType::Int);
+        assert_eq!(functions[1].returns, SpecType::Int);
+
+        let dafny = formal_spec(
+            VerificationLanguage::DafnyLang,
+            "method Sum(a: seq<int>, n: bv32) returns (s: int)\n  ensures s >= 0\n\nfunction Double(x: nat): nat\n\npredicate Sorted(a: seq<int>)\n\nlemma SumLemma(a: seq<int>)\n"
+        );
+        let functions = spec_functions(&dafny);
+        assert_eq!(functions.len(), 3, "{:?}", functions);
+        assert_eq!(functions[0].params[0].ty, SpecType::Sequence(Box::new(SpecType::Int)));
+        assert_eq!(functions[0].params[1].ty, SpecType::Unsigned(32));
+        assert_eq!(functions[0].returns, SpecType::Int);
+        assert_eq!(functions[1].returns, SpecType::Nat);
+        assert_eq!(functions[2].returns, SpecType::Bool);
+    }
+
+    #[test]
+    fn test_rust_contracts_and_kani_harnesses() {
+        let properties = vec![
+            property("P1", PropertyKind::Safety, "requires x < u64::MAX"),
+            property("P2", PropertyKind::Functional, "incr: ensures result > x"),
+            property("P3", PropertyKind::Liveness, "eventually done"),
+            property("P4", PropertyKind::Safety, "loop invariant i <= x"),
+        ];
+
+        let contracts = incr_adapter().convert_properties(&properties).unwrap();
+
+        assert!(contracts.starts_with("use prusti_contracts::*;\n"), "{}", contracts);
+        assert!(
+            contracts.contains(
+                "// Contracts for `incr`\n// P1: Property P1\n#[requires(x < u64::MAX)]\n// P2: Property P2\n#[ensures(result > x)]\npub fn incr(x: u64) -> u64 {\n"
+            ),
+            "{}",
+            contracts
+        );
+        assert!(contracts.contains("//   body_invariant!(i <= x);\n"));
+        assert!(syn::parse_file(&contracts).is_ok());
+        assert!(contracts.contains("// P3: cannot be stated as a contract"));
+        assert!(contracts.contains("#[cfg(kani)]\nmod verification {"));
+        assert!(contracts.contains("#[kani::proof]\n    fn check_p2() {"));
+        assert!(contracts.contains("let x: u64 = kani::any();"));
+        assert!(contracts.contains("kani::assume(x < u64::MAX);"));
+        assert!(contracts.contains("let result = incr(x);"));
+        assert!(contracts.contains("assert!(result > x, \"P2: Property P2\");"));
+    }
+
+    #[test]
+    fn test_rust_proptest_generation() {
+        let properties = vec![
+            property("P1", PropertyKind::Safety, "requires x < u64::MAX"),
+            property("P2", PropertyKind::Functional, "result > x")
+        ];
+
+        let tests = incr_adapter().generate_tests(&properties).unwrap();
+
+        assert!(tests.contains("use proptest::prelude::*;"), "{}", tests);
+        assert!(tests.contains("proptest! {"));
+        assert!(tests.contains("fn p2_incr(x in any::<u64>()) {"));
+        assert!(tests.contains("prop_assume!(x < u64::MAX);"));
+        assert!(tests.contains("prop_assert!(result > x);"));
+        assert!(syn::parse_file(&tests).is_ok());
+
+        let only_preconditions = vec![property("P1", PropertyKind::Safety, "requires x > 0")];
+        assert!(incr_adapter().generate_tests(&only_preconditions).is_err());
+    }
+
+    #[test]
+    fn test_rust_source_is_checked_against_the_spec_functions() {
+        let adapter = RustAdapter::new().with_specification(
+            &formal_spec(VerificationLanguage::FStarLang, FSTAR_SPEC)
+        );
+
+        let good =
+            "pub fn incr(x: u64) -> u64 { x + 1 }\n\npub struct Range;\nimpl Range {\n    pub fn clamp(&self, lo: i64, hi: i64, v: i64) -> Result<i64, String> { Ok(v.max(lo).min(hi)) }\n}\n";
+        assert!(adapter.validate_source(good).unwrap());
+
+        let problems = adapter.check_source("pub fn incr(x: u64, y: u64) -> u64 { x + y }\n");
+        assert_eq!(problems.len(), 2, "{:?}", problems);
+        assert!(problems[0].contains("`incr` takes 2 parameter(s), the specification declares 1"));
+        assert!(problems[1].contains("Function `clamp` is missing"));
+
+        let problems = adapter.check_source(
+            "fn incr(x: u64) -> bool { true }\nfn clamp(lo: u32, hi: i64, v: i64) -> i64 { v }\n"
+        );
+        assert_eq!(problems.len(), 2, "{:?}", problems);
+        assert!(problems[0].contains("`incr` should return a Rust type for nat"));
+        assert!(problems[1].contains("Parameter `lo` of `clamp`"));
+
+        // With several declarations of a name, the one that fits best is reported
+        let problems = adapter.check_source(
+            "mod old {\n    pub fn incr(x: bool) -> bool { x }\n}\npub fn incr(x: u64) -> bool { true }\npub fn clamp(lo: i64, hi: i64, v: i64) -> i64 { v }\n"
+        );
+        assert_eq!(problems.len(), 1, "{:?}", problems);
+        assert!(problems[0].contains("`incr` should return a Rust type for nat"));
+
+        assert!(!adapter.validate_source("fn incr(x: u64 -> u64 {").unwrap());
+    }
+
+    #[test]
+    fn test_c_acsl_contracts_on_prototypes() {
+        let adapter = CAdapter::new()
+            .with_specification(&formal_spec(VerificationLanguage::FStarLang, FSTAR_SPEC))
+            .with_function(sum_function());
+        let properties = vec![
+            property("P1", PropertyKind::Safety, "incr: requires x < UINT64_MAX"),
+            property("P2", PropertyKind::Functional, "incr: ensures result > x && s.result != my_result"),
+            property("P3", PropertyKind::Safety, "sum: loop invariant 0 <= i <= a_len"),
+            property("P4", PropertyKind::Functional, "sum: ensures \\result >= 0"),
+            property("P5", PropertyKind::Liveness, "eventually done"),
+            property("P6", PropertyKind::Functional, "ensures ok"),
+            property("P7", PropertyKind::Functional, "decr: ensures \\result < x")
+        ];
+
+        let header = adapter.convert_properties(&properties).unwrap();
+
+        assert!(header.starts_with("#include <stdbool.h>\n#include <stddef.h>\n#include <stdint.h>\n"), "{}", header);
+        assert!(
+            header.contains(
+                "// Contracts for `incr`\n/*@ // P1: Property P1\n  @ requires p1: x < UINT64_MAX;\n  @ // P2: Property P2\n  @ ensures p2: \\result > x && s.result != my_result;\n  @ assigns \\nothing;\n  @*/\nuint64_t incr(uint64_t x);\n"
+            ),
+            "{}",
+            header
+        );
+        assert!(header.contains("/*@ requires \\valid_read(a + (0 .. a_len - 1));\n"));
+        assert!(header.contains("  @ ensures p4: \\result >= 0;\n"));
+        assert!(header.contains("int64_t sum(const int64_t *a, size_t a_len);\n"));
+        assert!(header.contains("//   /*@ loop invariant p3: 0 <= i <= a_len; */\n"));
+        assert!(header.contains("// P5: cannot be stated as a contract"));
+        assert!(header.contains("// P6: no function of the specification to attach `ok` to"));
+        assert!(header.contains("// P7: cannot be stated as a contract"));
+        assert!(!header.contains("result < x"));
+    }
+
+    #[test]
+    fn test_c_assert_tests_generation() {
+        let adapter = CAdapter::new()
+            .with_function(SpecFunction {
+                name: "incr".to_string(),
+                params: vec![SpecParam { name: "x".to_string(), ty: SpecType::Unsigned(32) }],
+                returns: SpecType::Unsigned(32),
+            })
+            .with_function(sum_function());
+        let properties = vec![
+            property("P1", PropertyKind::Safety, "incr: requires x < UINT32_MAX"),
+            property("P2", PropertyKind::Functional, "incr: result > x"),
+            property("P3", PropertyKind::Functional, "sum: ensures result >= 0"),
+            property("P4", PropertyKind::Functional, "incr: ensures \\result == x + 1")
+        ];
+
+        let tests = adapter.generate_tests(&properties).unwrap();
+
+        assert!(tests.starts_with("#include <assert.h>\n"), "{}", tests);
+        assert!(tests.contains("uint32_t incr(uint32_t x);\n"));
+        assert!(tests.contains("static void test_p2_incr(void)\n{\n"));
+        assert!(tests.contains("static const uint32_t x_values[] = {0, 1, 2, 100, UINT32_MAX - 1, UINT32_MAX};"));
+        assert!(tests.contains("        if (!(x < UINT32_MAX)) {\n            continue;\n        }\n"));
+        assert!(tests.contains("        uint32_t result = incr(x);\n        assert((result > x) && \"P2: Property P2\");\n    }\n}\n"));
+        // Sequences and ACSL-only conditions are not tested
+        assert!(!tests.contains("sum("));
+        assert!(!tests.contains("test_p4"));
+        assert!(tests.contains("int main(void)\n{\n    test_p2_incr();\n"));
+
+        let only_preconditions = vec![property("P1", PropertyKind::Safety, "requires x > 0")];
+        assert!(CAdapter::new().with_function(sum_function()).generate_tests(&only_preconditions).is_err());
+    }
+
+    #[test]
+    fn test_c_source_is_checked_against_the_spec_functions() {
+        let adapter = CAdapter::new()
+            .with_specification(&formal_spec(VerificationLanguage::FStarLang, FSTAR_SPEC))
+            .with_function(sum_function());
+
+        let good =
+            "#include <stdint.h>\n\n/*@ ensures \\result > x; */\nuint64_t incr(uint64_t x);\n\nstatic inline int64_t clamp(int64_t lo, int64_t hi, int64_t v)\n{\n    // clamp(v)\n    if (v < lo) { return lo; }\n    return v > hi ? hi : v;\n}\n\nlong sum(const long *a, size_t a_len) { long s = 0; for (size_t i = 0; i < a_len; i++) { s += a[i]; } return s; }\n\nuint64_t incr(uint64_t x) { return x + 1; }\n";
+        assert!(adapter.validate_source(good).unwrap());
+
+        let problems = adapter.check_source(
+            "uint64_t incr(uint64_t x, uint64_t y) { return x + y; }\nbool clamp(uint32_t lo, int hi, int v);\nint64_t sum(int64_t a, size_t a_len);\n"
+        );
+        assert_eq!(problems.len(), 4, "{:?}", problems);
+        assert!(problems[0].contains("`incr` takes 2 parameter(s), the specification declares 1"));
+        assert!(problems[1].contains("Parameter `lo` of `clamp` should have a C type for int"));
+        assert!(problems[2].contains("`clamp` should return a C type for int"));
+        assert!(problems[3].contains("Parameter `a` of `sum`"));
+
+        let problems = adapter.check_source("/*@ requires x > 0;\nuint64_t incr(uint64_t x);\n");
+        assert_eq!(problems, vec!["Unterminated ACSL annotation starting on line 1".to_string()]);
+        assert!(!adapter.validate_source("uint64_t incr(uint64_t x) { return x + 1;\n").unwrap());
+    }
+}
@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::{ Arc, Mutex };
+
+    use async_trait::async_trait;
+
+    use crate::implementations::config::{ ApiConfig, GeneratorConfig };
+    use crate::implementations::llm::anthropic::parse_messages_response;
+    use crate::implementations::llm::fixtures::{ fixture_path, prompt_hash, LlmFixture };
+    use crate::implementations::llm::openai::parse_chat_response;
+    use crate::implementations::llm::{
+        client_from_config,
+        default_http_client,
+        AnthropicClient,
+        LlmClient,
+        LlmRequest,
+        LlmResponse,
+        RecordingClient,
+        ReplayClient,
+    };
+    use crate::implementations::specification_generator::{ LLMSpecificationGenerator, SpecGenError };
+    use crate::models::common::{ Domain, VerificationLanguage };
+    use crate::models::specification::SpecificationOptions;
+    use crate::tests::stub_tools::scratch_dir;
+    use crate::traits::specification_generator::SpecificationGenerator;
+
+    /// Client that answers every request with a canned response and records the prompts
+    struct FakeClient {
+        response: String,
+        prompts: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl LlmClient for FakeClient {
+        fn provider(&self) -> &str {
+            "fake"
+        }
+
+        fn model(&self) -> &str {
+            "fake-model"
+        }
+
+        async fn complete(&self, request: &LlmRequest) -> Result<LlmResponse, SpecGenError> {
+            self.prompts.lock().unwrap().push(request.prompt.clone());
+            Ok(LlmResponse::text(self.response.clone()))
+        }
+    }
+
+    #[test]
+    fn test_parse_messages_response() {
+        let body = r#"{"id":"msg_1","content":[{"type":"text","text":"module Incr"}]}"#;
+        assert_eq!(parse_messages_response(body).unwrap(), "module Incr");
+
+        let empty = r#"{"id":"msg_2","content":[]}"#;
+        assert!(matches!(parse_messages_response(empty), Err(SpecGenError::ParseError(_))));
+    }
+
+    #[test]
+    fn test_parse_chat_response() {
+        let body =
+            r#"{"id":"c1","choices":[{"index":0,"message":{"role":"assistant","content":"module Incr"}}]}"#;
+        assert_eq!(parse_chat_response(body).unwrap(), "module Incr");
+
+        assert!(matches!(parse_chat_response(r#"{"choices":[]}"#), Err(SpecGenError::ApiError(_))));
+        assert!(matches!(parse_chat_response("not json"), Err(SpecGenError::ParseError(_))));
+    }
+
+    #[test]
+    fn test_anthropic_request_body() {
+        let client = AnthropicClient::new(default_http_client(), "key").with_model("claude-test");
+        let body = client.request_body(&LlmRequest::new("Specify incr", 0.5, 100));
+
+        assert_eq!(body["model"], "claude-test");
+        assert_eq!(body["max_tokens"], 100);
+        assert_eq!(body["messages"][0]["content"], "Specify incr");
+        assert!(body["system"].as_str().unwrap().contains("formal verification"));
+    }
+
+    #[test]
+    fn test_config_api_key_selects_preferred_provider() {
+        let defaults = GeneratorConfig::default();
+        let config = GeneratorConfig {
+            llm_api: ApiConfig {
+                api_key: Some("key".to_string()),
+                ..defaults.llm_api.clone()
+            },
+            ..defaults
+        };
+
+        let client = client_from_config(&config, default_http_client()).unwrap();
+
+        assert_eq!(client.provider(), "anthropic");
+    }
+
+    #[tokio::test]
+    async fn test_generator_uses_injected_client() {
+        let fake = Arc::new(FakeClient {
+            response: "Here it is:\n```fstar\nmodule Incr\n\nlet incr (x:int) = x + 1\n```".to_string(),
+            prompts: Mutex::new(vec![]),
+        });
+        let generator = LLMSpecificationGenerator::new_with_defaults().with_llm_client(fake.clone());
+
+        let options = SpecificationOptions {
+            verification_language: VerificationLanguage::FStarLang,
+            ..SpecificationOptions::default()
+        };
+        let spec = generator
+            .generate_specification(
+                &["Incrementing must increase the value".to_string()],
+                Domain::Cryptography,
+                &options
+            ).await
+            .unwrap();
+
+        assert!(spec.formal_spec.spec_code.contains("let incr (x:int) = x + 1"));
+        let prompts = fake.prompts.lock().unwrap();
+        assert_eq!(prompts.len(), 1);
+        assert!(prompts[0].contains("Incrementing must increase the value"));
+    }
+
+    #[test]
+    fn test_prompt_hash_ignores_sampling_parameters_but_not_the_sample() {
+        let request = LlmRequest::new("Specify incr", 0.2, 4096);
+
+        // Pinned so that a change to the hash, which would orphan every fixture, is deliberate
+        assert_eq!(prompt_hash(&request), "d09dbde61846821b");
+        assert_eq!(prompt_hash(&request), prompt_hash(&LlmRequest::new("Specify incr", 0.9, 10)));
+        assert_ne!(prompt_hash(&request), prompt_hash(&LlmRequest::new("Specify decr", 0.2, 4096)));
+
+        // Candidates drawn for the same prompt are recorded separately
+        assert_eq!(prompt_hash(&request), prompt_hash(&request.clone().with_sample(0)));
+        assert_ne!(prompt_hash(&request.clone().with_sample(1)), prompt_hash(&request.clone().with_sample(2)));
+        assert_ne!(prompt_hash(&request), prompt_hash(&request.clone().with_sample(1)));
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay() {
+        let dir = scratch_dir("llm_record_replay");
+        let fake = Arc::new(FakeClient {
+            response: "module Incr".to_string(),
+            prompts: Mutex::new(vec![]),
+        });
+        let request = LlmRequest::new("Specify incr", 0.2, 4096);
+
+        let recorder = RecordingClient::new(fake.clone(), &dir);
+        assert_eq!(recorder.complete(&request).await.unwrap().content, "module Incr");
+
+        let fixture: LlmFixture = serde_json
+            ::from_str(&std::fs::read_to_string(fixture_path(&dir, &request)).unwrap())
+            .unwrap();
+        assert_eq!(fixture.key, prompt_hash(&request));
+        assert_eq!(fixture.provider, "fake");
+        assert_eq!(fixture.prompt, "Specify incr");
+
+        let replay = ReplayClient::new(&dir);
+        assert_eq!(replay.complete(&request).await.unwrap().content, "module Incr");
+        assert!(replay.complete(&LlmRequest::new("Specify decr", 0.2, 4096)).await.is_err());
+        assert_eq!(fake.prompts.lock().unwrap().len(), 1);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
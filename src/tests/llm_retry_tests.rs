@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::errors::RecoverableError;
+    use crate::implementations::llm::retry::parse_retry_after;
+    use crate::implementations::llm::{
+        default_http_client,
+        LlmClient,
+        LlmRequest,
+        OpenAiClient,
+        RetryPolicy,
+        RetryingClient,
+    };
+    use crate::implementations::specification_generator::SpecGenError;
+    use crate::tests::stub_http::{ StubHttpServer, StubResponse };
+
+    const OK_BODY: &str =
+        r#"{"choices":[{"index":0,"message":{"role":"assistant","content":"module Retried"}}]}"#;
+
+    fn fast_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 5,
+            max_total_wait_ms: 1_000,
+        }
+    }
+
+    fn retrying_client(server: &StubHttpServer, policy: RetryPolicy) -> RetryingClient {
+        let inner = OpenAiClient::new(default_http_client(), "key").with_endpoint(
+            format!("{}/v1/chat/completions", server.base_url)
+        );
+        RetryingClient::new(Arc::new(inner), policy)
+    }
+
+    fn request() -> LlmRequest {
+        LlmRequest::new("Specify incr", 0.2, 128)
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("2"), Some(Duration::from_secs(2)));
+        assert_eq!(parse_retry_after(" 0.5 "), Some(Duration::from_millis(500)));
+        assert_eq!(parse_retry_after("-1"), None);
+        assert_eq!(parse_retry_after("soon"), None);
+
+        let in_a_minute = (chrono::Utc::now() + chrono::Duration::seconds(60)).to_rfc2822();
+        let wait = parse_retry_after(&in_a_minute).unwrap();
+        assert!(wait > Duration::from_secs(50) && wait <= Duration::from_secs(60));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_status_classification() {
+        let rate_limited = SpecGenError::from_status(429, Some(Duration::from_secs(3)), "slow down".into());
+        assert!(matches!(rate_limited, SpecGenError::RateLimited { status: 429, .. }));
+        assert!(rate_limited.is_recoverable());
+        assert_eq!(rate_limited.retry_after(), Some(Duration::from_secs(3)));
+        assert!(rate_limited.recovery_strategy().unwrap().contains("3s"));
+
+        let overloaded = SpecGenError::from_status(529, None, "overloaded".into());
+        assert!(matches!(overloaded, SpecGenError::RateLimited { status: 529, .. }));
+
+        let unavailable = SpecGenError::from_status(503, None, "unavailable".into());
+        assert!(matches!(unavailable, SpecGenError::ServerError { .. }));
+        assert!(unavailable.is_recoverable());
+
+        let unauthorized = SpecGenError::from_status(401, None, "bad key".into());
+        assert!(matches!(unauthorized, SpecGenError::HttpError { status: 401, .. }));
+        assert!(!unauthorized.is_recoverable());
+        assert_eq!(unauthorized.status(), Some(401));
+
+        assert!(SpecGenError::Timeout("slow".into()).is_recoverable());
+        assert!(!SpecGenError::ParseError("bad json".into()).is_recoverable());
+    }
+
+    #[test]
+    fn test_backoff_grows_with_jitter_and_is_capped() {
+        let policy = RetryPolicy {
+            initial_backoff_ms: 100,
+            max_backoff_ms: 1_000,
+            ..RetryPolicy::default()
+        };
+
+        for _ in 0..20 {
+            let first = policy.backoff(1);
+            assert!(first >= Duration::from_millis(50) && first <= Duration::from_millis(100));
+            let third = policy.backoff(3);
+            assert!(third >= Duration::from_millis(200) && third <= Duration::from_millis(400));
+            let capped = policy.backoff(30);
+            assert!(capped >= Duration::from_millis(500) && capped <= Duration::from_millis(1_000));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_rate_limits_and_server_errors() {
+        let server = StubHttpServer::start(
+            vec![
+                (
+                    "/v1/chat/completions",
+                    vec![
+                        StubResponse::json(429, r#"{"error":"rate limited"}"#).with_header("retry-after", "0"),
+                        StubResponse::json(503, r#"{"error":"unavailable"}"#),
+                        StubResponse::json(200, OK_BODY)
+                    ],
+                )
+            ]
+        );
+
+        let response = retrying_client(&server, fast_policy(4)).complete(&request()).await.unwrap();
+
+        assert_eq!(response.content, "module Retried");
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_fatal_errors_are_not_retried() {
+        let server = StubHttpServer::start(
+            vec![("/v1/chat/completions", vec![StubResponse::json(401, r#"{"error":"bad key"}"#)])]
+        );
+
+        let error = retrying_client(&server, fast_policy(4)).complete(&request()).await.unwrap_err();
+
+        assert!(matches!(error, SpecGenError::HttpError { status: 401, .. }));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let server = StubHttpServer::start(
+            vec![("/v1/chat/completions", vec![StubResponse::json(500, r#"{"error":"boom"}"#)])]
+        );
+
+        let error = retrying_client(&server, fast_policy(2)).complete(&request()).await.unwrap_err();
+
+        assert!(matches!(error, SpecGenError::ServerError { status: 500, .. }));
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_beyond_budget_gives_up() {
+        let server = StubHttpServer::start(
+            vec![
+                (
+                    "/v1/chat/completions",
+                    vec![
+                        StubResponse::json(429, r#"{"error":"rate limited"}"#).with_header("retry-after", "30"),
+                        StubResponse::json(200, OK_BODY)
+                    ],
+                )
+            ]
+        );
+
+        let error = retrying_client(&server, fast_policy(4)).complete(&request()).await.unwrap_err();
+
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(30)));
+        assert_eq!(server.requests().len(), 1);
+    }
+}
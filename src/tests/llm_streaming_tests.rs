@@ -0,0 +1,245 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::{ Arc, Mutex };
+    use std::time::{ Duration, Instant };
+
+    use async_trait::async_trait;
+
+    use crate::errors::AxiomError;
+    use crate::implementations::llm::anthropic::parse_stream_event;
+    use crate::implementations::llm::openai::parse_chat_stream_event;
+    use crate::implementations::llm::stream::{ SseEvent, SseParser, StreamCollector };
+    use crate::implementations::llm::{
+        default_http_client,
+        AnthropicClient,
+        CancelFlag,
+        LlmClient,
+        LlmRequest,
+        LlmResponse,
+        OpenAiClient,
+        StreamContext,
+        StreamObserver,
+        TokenUsage,
+    };
+    use crate::implementations::specification_generator::{ LLMSpecificationGenerator, SpecGenError };
+    use crate::models::common::{ Domain, VerificationLanguage };
+    use crate::models::specification::SpecificationOptions;
+    use crate::tests::stub_http::{ StubHttpServer, StubResponse };
+    use crate::traits::specification_generator::SpecificationGenerator;
+
+    const ANTHROPIC_STREAM: &str =
+        "event: message_start\n\
+         data: {\"type\":\"message_start\",\"message\":{\"usage\":{\"input_tokens\":42,\"output_tokens\":1}}}\n\n\
+         event: ping\n\
+         data: {\"type\":\"ping\"}\n\n\
+         event: content_block_delta\n\
+         data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"module \"}}\n\n\
+         event: content_block_delta\n\
+         data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Incr\"}}\n\n\
+         event: message_delta\n\
+         data: {\"type\":\"message_delta\",\"delta\":{\"stop_reason\":\"end_turn\"},\"usage\":{\"output_tokens\":7}}\n\n\
+         event: message_stop\n\
+         data: {\"type\":\"message_stop\"}\n\n";
+
+    const OPENAI_STREAM: &str =
+        "data: {\"choices\":[{\"delta\":{\"role\":\"assistant\",\"content\":\"\"},\"finish_reason\":null}]}\n\n\
+         data: {\"choices\":[{\"delta\":{\"content\":\"module \"},\"finish_reason\":null}]}\n\n\
+         data: {\"choices\":[{\"delta\":{\"content\":\"Incr\"},\"finish_reason\":null}]}\n\n\
+         data: {\"choices\":[{\"delta\":{},\"finish_reason\":\"stop\"}]}\n\n\
+         data: {\"choices\":[],\"usage\":{\"prompt_tokens\":30,\"completion_tokens\":5}}\n\n\
+         data: [DONE]\n\n";
+
+    /// Observer that keeps every piece of text it is given
+    #[derive(Default)]
+    struct CollectingObserver {
+        texts: Mutex<Vec<String>>,
+        starts: Mutex<usize>,
+    }
+
+    impl StreamObserver for CollectingObserver {
+        fn on_start(&self, _provider: &str, _model: &str) {
+            *self.starts.lock().unwrap() += 1;
+        }
+
+        fn on_text(&self, text: &str) {
+            self.texts.lock().unwrap().push(text.to_string());
+        }
+    }
+
+    /// Client that takes far longer than any test should wait
+    struct HangingClient;
+
+    #[async_trait]
+    impl LlmClient for HangingClient {
+        fn provider(&self) -> &str {
+            "hanging"
+        }
+
+        fn model(&self) -> &str {
+            "hanging-1"
+        }
+
+        async fn complete(&self, _request: &LlmRequest) -> Result<LlmResponse, SpecGenError> {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            Ok(LlmResponse::text("too late"))
+        }
+    }
+
+    fn event_stream(body: &str) -> StubResponse {
+        StubResponse {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), "text/event-stream".to_string())],
+            body: body.to_string(),
+        }
+    }
+
+    fn observed_stream() -> (Arc<CollectingObserver>, StreamContext) {
+        let observer = Arc::new(CollectingObserver::default());
+        let stream = StreamContext::new().with_observer(observer.clone());
+        (observer, stream)
+    }
+
+    #[test]
+    fn test_sse_parser_handles_split_chunks() {
+        let body = "event: delta\r\ndata: {\"text\":\"h\u{e9}llo\"}\r\n\r\n: keep-alive\n\ndata: line one\ndata: line two\n\ndata: tail";
+        let bytes = body.as_bytes();
+        let mut parser = SseParser::new();
+
+        // Feed one byte at a time so that lines and the two-byte character are split
+        let mut events: Vec<SseEvent> = bytes
+            .iter()
+            .flat_map(|byte| parser.feed(std::slice::from_ref(byte)))
+            .collect();
+        events.extend(parser.finish());
+
+        assert_eq!(
+            events,
+            vec![
+                SseEvent { event: Some("delta".to_string()), data: "{\"text\":\"h\u{e9}llo\"}".to_string() },
+                SseEvent { event: None, data: "line one\nline two".to_string() },
+                SseEvent { event: None, data: "tail".to_string() }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_provider_stream_events_assemble_response() {
+        let (observer, stream) = observed_stream();
+        let mut collector = StreamCollector::new();
+        for event in SseParser::new().feed(ANTHROPIC_STREAM.as_bytes()) {
+            collector.apply(parse_stream_event(&event).unwrap(), &stream);
+        }
+        assert!(collector.is_done());
+        let response = collector.into_response();
+        assert_eq!(response.content, "module Incr");
+        assert_eq!(response.usage, Some(TokenUsage::new(42, 7)));
+        assert_eq!(*observer.texts.lock().unwrap(), vec!["module ", "Incr"]);
+
+        let mut collector = StreamCollector::new();
+        for event in SseParser::new().feed(OPENAI_STREAM.as_bytes()) {
+            collector.apply(parse_chat_stream_event(&event).unwrap(), &StreamContext::new());
+        }
+        let response = collector.into_response();
+        assert_eq!(response.content, "module Incr");
+        assert_eq!(response.usage, Some(TokenUsage::new(30, 5)));
+
+        let overloaded = SseEvent {
+            event: Some("error".to_string()),
+            data: r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#.to_string(),
+        };
+        assert!(matches!(parse_stream_event(&overloaded), Err(SpecGenError::RateLimited { status: 529, .. })));
+    }
+
+    #[tokio::test]
+    async fn test_anthropic_streams_over_http() {
+        let server = StubHttpServer::start(vec![("/v1/messages", vec![event_stream(ANTHROPIC_STREAM)])]);
+        let client = AnthropicClient::new(default_http_client(), "key").with_endpoint(
+            format!("{}/v1/messages", server.base_url)
+        );
+        let (observer, stream) = observed_stream();
+
+        let response = client
+            .complete_streaming(&LlmRequest::new("Specify incr", 0.2, 256), &stream).await
+            .unwrap();
+
+        assert_eq!(response.content, "module Incr");
+        assert_eq!(response.usage, Some(TokenUsage::new(42, 7)));
+        assert_eq!(observer.texts.lock().unwrap().concat(), "module Incr");
+        let request = &server.requests()[0];
+        assert_eq!(request.headers.get("accept").map(String::as_str), Some("text/event-stream"));
+        let body: serde_json::Value = serde_json::from_str(&request.body).unwrap();
+        assert_eq!(body["stream"], true);
+    }
+
+    #[tokio::test]
+    async fn test_openai_streams_over_http() {
+        let server = StubHttpServer::start(vec![("/v1/chat/completions", vec![event_stream(OPENAI_STREAM)])]);
+        let client = OpenAiClient::new(default_http_client(), "key").with_endpoint(
+            format!("{}/v1/chat/completions", server.base_url)
+        );
+
+        let response = client
+            .complete_streaming(&LlmRequest::new("Specify incr", 0.2, 256), &StreamContext::new()).await
+            .unwrap();
+
+        assert_eq!(response.content, "module Incr");
+        assert_eq!(response.usage, Some(TokenUsage::new(30, 5)));
+        let body: serde_json::Value = serde_json::from_str(&server.requests()[0].body).unwrap();
+        assert_eq!(body["stream"], true);
+        assert_eq!(body["stream_options"]["include_usage"], true);
+    }
+
+    #[tokio::test]
+    async fn test_truncated_stream_is_a_recoverable_error() {
+        let truncated = &ANTHROPIC_STREAM[..ANTHROPIC_STREAM.find("event: message_delta").unwrap()];
+        let server = StubHttpServer::start(vec![("/v1/messages", vec![event_stream(truncated)])]);
+        let client = AnthropicClient::new(default_http_client(), "key").with_endpoint(
+            format!("{}/v1/messages", server.base_url)
+        );
+
+        let result = client.complete_streaming(
+            &LlmRequest::new("Specify incr", 0.2, 256),
+            &StreamContext::new()
+        ).await;
+
+        assert!(matches!(result, Err(SpecGenError::NetworkError(_))), "{:?}", result);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_flag_stops_call_in_flight() {
+        let cancel = CancelFlag::new();
+        let observer = Arc::new(CollectingObserver::default());
+        let generator = LLMSpecificationGenerator::new_with_defaults()
+            .with_llm_client(Arc::new(HangingClient))
+            .with_stream_observer(observer.clone())
+            .with_cancel_flag(cancel.clone());
+        let options = SpecificationOptions {
+            verification_language: VerificationLanguage::FStarLang,
+            ..SpecificationOptions::default()
+        };
+
+        let canceller = cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            canceller.cancel();
+        });
+        let started = Instant::now();
+        let result = generator.generate_specification(
+            &["Incrementing must increase the value".to_string()],
+            Domain::Cryptography,
+            &options
+        ).await;
+
+        assert!(matches!(result, Err(AxiomError::Cancelled)));
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(*observer.starts.lock().unwrap(), 1);
+
+        // Later calls are refused straight away
+        let again = generator.generate_specification(
+            &["Another".to_string()],
+            Domain::Cryptography,
+            &options
+        ).await;
+        assert!(matches!(again, Err(AxiomError::Cancelled)));
+    }
+}
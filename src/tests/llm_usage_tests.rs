@@ -0,0 +1,237 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::{ AtomicUsize, Ordering };
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+
+    use crate::errors::AxiomError;
+    use crate::implementations::config::GeneratorConfig;
+    use crate::implementations::llm::anthropic::parse_messages_usage;
+    use crate::implementations::llm::local::parse_ollama_usage;
+    use crate::implementations::llm::openai::parse_chat_usage;
+    use crate::implementations::llm::usage::price_for;
+    use crate::implementations::llm::{
+        default_http_client,
+        AnthropicClient,
+        LlmCallRecord,
+        LlmClient,
+        LlmRequest,
+        LlmResponse,
+        ModelPrice,
+        TokenUsage,
+        UsageBudget,
+        UsageSummary,
+    };
+    use crate::implementations::specification_generator::{ LLMSpecificationGenerator, SpecGenError };
+    use crate::models::common::{ Domain, VerificationLanguage, VerificationSystem };
+    use crate::models::specification::{
+        FormalSpecification,
+        Specification,
+        SpecificationMetadata,
+        SpecificationOptions,
+    };
+    use crate::tests::stub_http::{ StubHttpServer, StubResponse };
+    use crate::traits::specification_generator::{ SpecificationGenerator, ValidationDepth };
+
+    /// Client that always gives the same answer and reports a fixed usage
+    struct MeteredClient {
+        response: String,
+        usage: Option<TokenUsage>,
+        calls: AtomicUsize,
+    }
+
+    impl MeteredClient {
+        fn new(response: &str, usage: Option<TokenUsage>) -> Arc<Self> {
+            Arc::new(Self {
+                response: response.to_string(),
+                usage,
+                calls: AtomicUsize::new(0),
+            })
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl LlmClient for MeteredClient {
+        fn provider(&self) -> &str {
+            "metered"
+        }
+
+        fn model(&self) -> &str {
+            "gpt-4o"
+        }
+
+        async fn complete(&self, _request: &LlmRequest) -> Result<LlmResponse, SpecGenError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(LlmResponse::text(self.response.clone()).with_usage(self.usage))
+        }
+    }
+
+    fn generator_with_budget(budget: UsageBudget, client: Arc<MeteredClient>) -> LLMSpecificationGenerator {
+        let config = GeneratorConfig {
+            budget,
+            ..GeneratorConfig::default()
+        };
+        LLMSpecificationGenerator::new(config).with_llm_client(client)
+    }
+
+    fn record(model: &str, usage: TokenUsage, cost_usd: Option<f64>, cached: bool) -> LlmCallRecord {
+        LlmCallRecord {
+            provider: "test".to_string(),
+            model: model.to_string(),
+            usage,
+            estimated: false,
+            cached,
+            latency_ms: 250,
+            cost_usd,
+        }
+    }
+
+    fn fstar_options() -> SpecificationOptions {
+        SpecificationOptions {
+            verification_language: VerificationLanguage::FStarLang,
+            ..SpecificationOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_provider_usage() {
+        let anthropic =
+            r#"{"content":[{"type":"text","text":"ok"}],"usage":{"input_tokens":120,"output_tokens":45}}"#;
+        assert_eq!(parse_messages_usage(anthropic), Some(TokenUsage::new(120, 45)));
+
+        let chat =
+            r#"{"choices":[],"usage":{"prompt_tokens":80,"completion_tokens":20,"total_tokens":100}}"#;
+        assert_eq!(parse_chat_usage(chat), Some(TokenUsage::new(80, 20)));
+
+        let ollama = r#"{"message":{"content":"ok"},"prompt_eval_count":30,"eval_count":12}"#;
+        assert_eq!(parse_ollama_usage(ollama), Some(TokenUsage::new(30, 12)));
+
+        assert_eq!(parse_chat_usage(r#"{"choices":[]}"#), None);
+    }
+
+    #[test]
+    fn test_price_lookup_prefers_overrides_and_longest_prefix() {
+        let mut overrides = HashMap::new();
+        assert_eq!(price_for("gpt-4o-mini-2024-07-18", &overrides), Some(ModelPrice::new(0.15, 0.6)));
+        assert_eq!(price_for("gpt-4-0613", &overrides), Some(ModelPrice::new(30.0, 60.0)));
+        assert_eq!(price_for("llama3:8b", &overrides), None);
+
+        overrides.insert("llama3:8b".to_string(), ModelPrice::new(1.0, 2.0));
+        let price = price_for("llama3:8b", &overrides).unwrap();
+        assert!((price.cost(&TokenUsage::new(1_000_000, 500_000)) - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_summary_totals_and_budget() {
+        let summary = UsageSummary::from_records(
+            vec![
+                record("gpt-4o", TokenUsage::new(1000, 200), Some(0.0045), false),
+                record("gpt-4o", TokenUsage::default(), Some(0.0), true),
+                record("custom", TokenUsage::new(300, 100), None, false)
+            ]
+        );
+
+        assert_eq!(summary.calls, 3);
+        assert_eq!(summary.cached_calls, 1);
+        assert_eq!(summary.unpriced_calls, 1);
+        assert_eq!(summary.total_tokens, 1600);
+        assert_eq!(summary.latency_ms, 750);
+        assert!(summary.to_string().starts_with("3 LLM calls (1 cached), 1600 tokens"));
+
+        assert!(summary.check_budget(&UsageBudget::default()).is_ok());
+        let tokens = UsageBudget { max_tokens_total: Some(1500), ..UsageBudget::default() };
+        assert!(matches!(summary.check_budget(&tokens), Err(SpecGenError::BudgetExceeded(_))));
+        let cost = UsageBudget { max_cost_usd: Some(0.001), ..UsageBudget::default() };
+        let message = summary.check_budget(&cost).unwrap_err().to_string();
+        assert!(message.contains("max_cost_usd"), "{}", message);
+    }
+
+    #[tokio::test]
+    async fn test_generator_records_reported_usage_and_cost() {
+        let body =
+            r#"{"content":[{"type":"text","text":"```fstar\nmodule Incr\n```"}],"usage":{"input_tokens":1000,"output_tokens":500}}"#;
+        let server = StubHttpServer::start(vec![("/v1/messages", vec![StubResponse::json(200, body)])]);
+        let client = AnthropicClient::new(default_http_client(), "key")
+            .with_endpoint(format!("{}/v1/messages", server.base_url))
+            .with_model("claude-3-sonnet-20240229");
+        let generator = LLMSpecificationGenerator::new_with_defaults().with_llm_client(Arc::new(client));
+
+        generator
+            .generate_specification(
+                &["Incrementing must increase the value".to_string()],
+                Domain::Cryptography,
+                &fstar_options()
+            ).await
+            .unwrap();
+
+        // Clones share the tracker, like the copies the CLI hands to worker threads
+        let summary = generator.clone().usage_summary();
+        assert_eq!(summary.calls, 1);
+        assert_eq!(summary.prompt_tokens, 1000);
+        assert_eq!(summary.completion_tokens, 500);
+        assert!(!summary.records[0].estimated);
+        assert_eq!(summary.records[0].model, "claude-3-sonnet-20240229");
+        assert!((summary.cost_usd - 0.0105).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_unreported_usage_is_estimated() {
+        let client = MeteredClient::new("```fstar\nmodule Incr\n```", None);
+        let generator = generator_with_budget(UsageBudget::default(), client);
+
+        generator
+            .generate_specification(
+                &["Incrementing must increase the value".to_string()],
+                Domain::Cryptography,
+                &fstar_options()
+            ).await
+            .unwrap();
+
+        let summary = generator.usage_summary();
+        assert!(summary.records[0].estimated);
+        assert!(summary.prompt_tokens > 0);
+        assert_eq!(summary.completion_tokens, 6); // 24 characters
+    }
+
+    #[tokio::test]
+    async fn test_budget_stops_auto_fix_loop() {
+        // Every syntax check fails, so without a budget the auto-fix loop would make seven calls
+        let client = MeteredClient::new(
+            r#"{"is_valid":false,"issues":[{"line_number":3,"message":"Identifier not found: `x`","severity":"error"}]}"#,
+            Some(TokenUsage::new(150, 50))
+        );
+        let budget = UsageBudget { max_tokens_total: Some(500), ..UsageBudget::default() };
+        let generator = generator_with_budget(budget, client.clone());
+        let spec = Specification {
+            id: "incr".to_string(),
+            source_requirements: vec!["Incrementing must increase the value".to_string()],
+            formal_properties: vec![],
+            formal_spec: FormalSpecification {
+                verification_language: VerificationLanguage::FStarLang,
+                spec_code: "module Incr\n\nlet incr (n:int) = x + 1".to_string(),
+                components: HashMap::new(),
+                dependencies: vec![],
+                candidates: vec![],
+            },
+            metadata: SpecificationMetadata {
+                created_at: chrono::Utc::now(),
+                verification_system: VerificationSystem::FStar,
+                domain: Domain::Cryptography,
+                confidence_score: 0.5,
+                is_formally_validated: false,
+            },
+        };
+
+        let result = generator.validate_specification(&spec, ValidationDepth::Basic).await;
+
+        assert!(matches!(result, Err(AxiomError::BudgetExceeded(_))), "{:?}", result.map(|r| r.is_valid));
+        assert_eq!(client.calls(), 3);
+        assert_eq!(generator.usage_summary().total_tokens, 600);
+    }
+}
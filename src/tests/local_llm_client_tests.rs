@@ -0,0 +1,141 @@
+#[cfg(test)]
+mod tests {
+    use crate::implementations::config::{ ApiConfig, GeneratorConfig };
+    use crate::implementations::llm::local::{ is_local_endpoint, parse_model_list, LocalApi };
+    use crate::implementations::llm::{
+        default_http_client,
+        uses_local_provider,
+        LlmClient,
+        LlmRequest,
+        LocalClient,
+    };
+    use crate::implementations::specification_generator::LLMSpecificationGenerator;
+    use crate::models::common::{ Domain, VerificationLanguage };
+    use crate::models::specification::SpecificationOptions;
+    use crate::tests::stub_http::{ StubHttpServer, StubResponse };
+    use crate::traits::specification_generator::SpecificationGenerator;
+
+    fn config_with(provider: Option<&str>, endpoint: &str, model: Option<&str>) -> GeneratorConfig {
+        let defaults = GeneratorConfig::default();
+        GeneratorConfig {
+            llm_api: ApiConfig {
+                provider: provider.map(|p| p.to_string()),
+                api_endpoint: Some(endpoint.to_string()),
+                model: model.map(|m| m.to_string()),
+                ..defaults.llm_api.clone()
+            },
+            ..defaults
+        }
+    }
+
+    #[test]
+    fn test_local_provider_selection() {
+        assert!(is_local_endpoint("http://localhost:11434/api/chat"));
+        assert!(is_local_endpoint("http://127.0.0.1:8080"));
+        assert!(!is_local_endpoint("https://api.openai.com/v1/chat/completions"));
+
+        assert!(uses_local_provider(&config_with(None, "http://localhost:8080", None)));
+        assert!(uses_local_provider(&config_with(Some("local"), "http://gpu-box:8080", None)));
+        assert!(!uses_local_provider(&config_with(Some("openai"), "http://localhost:8080", None)));
+        assert!(!uses_local_provider(&GeneratorConfig::default()));
+    }
+
+    #[test]
+    fn test_endpoint_detection_and_model_urls() {
+        let ollama = LocalClient::new(default_http_client(), "http://localhost:11434/api/chat").unwrap();
+        assert_eq!(ollama.api(), LocalApi::Ollama);
+        assert_eq!(ollama.models_url().as_str(), "http://localhost:11434/api/tags");
+
+        let llama_cpp = LocalClient::new(default_http_client(), "http://localhost:8080").unwrap();
+        assert_eq!(llama_cpp.api(), LocalApi::OpenAiCompatible);
+        assert_eq!(llama_cpp.models_url().as_str(), "http://localhost:8080/v1/models");
+
+        assert!(LocalClient::new(default_http_client(), "not a url").is_err());
+    }
+
+    #[test]
+    fn test_parse_model_lists() {
+        let openai = r#"{"object":"list","data":[{"id":"qwen2.5-coder:7b","object":"model"}]}"#;
+        assert_eq!(parse_model_list(openai).unwrap(), vec!["qwen2.5-coder:7b"]);
+
+        let ollama = r#"{"models":[{"name":"llama3:8b"},{"name":"mistral:7b"}]}"#;
+        assert_eq!(parse_model_list(ollama).unwrap(), vec!["llama3:8b", "mistral:7b"]);
+    }
+
+    #[tokio::test]
+    async fn test_openai_compatible_server_without_key() {
+        let server = StubHttpServer::start(
+            vec![
+                (
+                    "/v1/models",
+                    vec![StubResponse::json(200, r#"{"data":[{"id":"llama-3-8b"},{"id":"qwen"}]}"#)],
+                ),
+                (
+                    "/v1/chat/completions",
+                    vec![
+                        StubResponse::json(
+                            200,
+                            r#"{"choices":[{"index":0,"message":{"role":"assistant","content":"```fstar\nmodule Local\n```"}}]}"#
+                        )
+                    ],
+                )
+            ]
+        );
+        // The default "gpt-4o" is not served, so the first discovered model is used
+        let config = GeneratorConfig {
+            temperature: Some(0.7),
+            max_tokens: Some(512),
+            ..config_with(None, &server.base_url, Some("gpt-4o"))
+        };
+        let generator = LLMSpecificationGenerator::new(config);
+        let options = SpecificationOptions {
+            verification_language: VerificationLanguage::FStarLang,
+            ..SpecificationOptions::default()
+        };
+
+        let spec = generator
+            .generate_specification(&["Increment".to_string()], Domain::Cryptography, &options).await
+            .unwrap();
+
+        assert!(spec.formal_spec.spec_code.contains("module Local"));
+
+        let requests = server.requests();
+        let chat = requests.iter().find(|r| r.path == "/v1/chat/completions").unwrap();
+        let body: serde_json::Value = serde_json::from_str(&chat.body).unwrap();
+        assert_eq!(body["model"], "llama-3-8b");
+        assert_eq!(body["max_tokens"], 512);
+        assert!((body["temperature"].as_f64().unwrap() - 0.7).abs() < 1e-6);
+        assert!(!chat.headers.contains_key("authorization"));
+    }
+
+    #[tokio::test]
+    async fn test_ollama_native_api() {
+        let server = StubHttpServer::start(
+            vec![
+                ("/api/tags", vec![StubResponse::json(200, r#"{"models":[{"name":"llama3:8b"},{"name":"mistral:7b"}]}"#)]),
+                (
+                    "/api/chat",
+                    vec![
+                        StubResponse::json(
+                            200,
+                            r#"{"model":"mistral:7b","message":{"role":"assistant","content":"module Ollama"},"done":true}"#
+                        )
+                    ],
+                )
+            ]
+        );
+        let client = LocalClient::new(default_http_client(), &format!("{}/api/chat", server.base_url))
+            .unwrap()
+            .with_model("mistral:7b");
+
+        let response = client.complete(&LlmRequest::new("Specify incr", 0.1, 256)).await.unwrap();
+
+        assert_eq!(response.content, "module Ollama");
+        let requests = server.requests();
+        let chat = requests.iter().find(|r| r.path == "/api/chat").unwrap();
+        let body: serde_json::Value = serde_json::from_str(&chat.body).unwrap();
+        assert_eq!(body["model"], "mistral:7b");
+        assert_eq!(body["stream"], false);
+        assert_eq!(body["options"]["num_predict"], 256);
+    }
+}
@@ -1,2 +1,30 @@
 // Test modules
-pub mod specification_generator_tests;
\ No newline at end of file
+pub mod specification_generator_tests;
+pub mod fstar_backend_tests;
+pub mod fstar_diagnostics_tests;
+pub mod dafny_backend_tests;
+pub mod smt_output_tests;
+pub mod z3_backend_tests;
+pub mod tlc_backend_tests;
+pub mod proof_checker_backend_tests;
+pub mod why3_backend_tests;
+pub mod frama_c_backend_tests;
+pub mod backend_registry_tests;
+pub mod llm_client_tests;
+pub mod local_llm_client_tests;
+pub mod llm_retry_tests;
+pub mod response_cache_tests;
+pub mod llm_usage_tests;
+pub mod llm_streaming_tests;
+pub mod structured_output_tests;
+pub mod prompt_template_tests;
+pub mod candidate_generation_tests;
+pub mod model_serde_tests;
+pub mod project_tests;
+pub mod implementation_generator_tests;
+pub mod fstar_extraction_tests;
+pub mod language_adapter_tests;
+
+// Shared helpers
+pub mod stub_http;
+pub mod stub_tools;
@@ -0,0 +1,377 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::{ BTreeMap, HashMap };
+    use std::time::Duration;
+
+    use chrono::TimeZone;
+    use serde_json::{ json, Value };
+
+    use crate::models::artifact::{ Documentation, VerifiedArtifact };
+    use crate::models::common::{ Domain, Language, ResourceUsage, VerificationLanguage, VerificationSystem };
+    use crate::models::implementation::Implementation;
+    use crate::models::property::{ Property, PropertyKind };
+    use crate::models::schema::{
+        from_document_json,
+        to_document_json,
+        DocumentError,
+        ModelDocument,
+        JSON_SCHEMA,
+        SCHEMA_VERSION,
+    };
+    use crate::models::specification::{
+        FormalSpecification,
+        IssueSeverity,
+        SourceSpan,
+        Specification,
+        SpecificationCandidate,
+        SpecificationMetadata,
+        ValidationIssue,
+        ValidationReport,
+    };
+    use crate::models::verification::{
+        ArtifactType,
+        Counterexample,
+        GoalOutcome,
+        GoalResult,
+        ProofArtifact,
+        TraceStep,
+        VerificationResult,
+        VerificationStatus,
+    };
+
+    /// An artifact using every optional field and a `Custom` variant of every open enum
+    fn sample_artifact() -> VerifiedArtifact {
+        let verification_result = VerificationResult {
+            status: VerificationStatus::Failed(vec!["assertion might not hold".to_string()]),
+            proof_artifacts: vec![
+                ProofArtifact {
+                    artifact_type: ArtifactType::Log,
+                    path: "runs/1/fstar.log".to_string(),
+                    description: "F* output".to_string(),
+                },
+                ProofArtifact {
+                    artifact_type: ArtifactType::Custom("hints".to_string()),
+                    path: "runs/1/Incr.fst.hints".to_string(),
+                    description: "Z3 hints".to_string(),
+                }
+            ],
+            verification_time: Duration::new(3, 250_000_001),
+            resource_usage: ResourceUsage {
+                memory_kb: 2048,
+                cpu_seconds: 2.5,
+                peak_memory_kb: 4096,
+                lemmas_proven: 3,
+                states_explored: Some(17),
+            },
+            goal_results: vec![
+                GoalResult {
+                    name: "incr_post".to_string(),
+                    outcome: GoalOutcome::OutOfResource,
+                    duration: Duration::from_millis(1500),
+                    resource_count: Some(120_000),
+                    prover: Some("z3".to_string()),
+                },
+                GoalResult {
+                    name: "incr_pre".to_string(),
+                    outcome: GoalOutcome::Error("prover crashed".to_string()),
+                    duration: Duration::ZERO,
+                    resource_count: None,
+                    prover: None,
+                }
+            ],
+            counterexamples: vec![Counterexample {
+                property: Some("Inv".to_string()),
+                assignments: BTreeMap::from([("x".to_string(), "-1".to_string())]),
+                trace: vec![TraceStep {
+                    action: "Init".to_string(),
+                    state: BTreeMap::from([("x".to_string(), "0".to_string())]),
+                }],
+            }],
+        };
+
+        let specification = Specification {
+            id: "spec-1".to_string(),
+            source_requirements: vec!["Incrementing must increase the value".to_string()],
+            formal_properties: vec![
+                Property {
+                    id: "p1".to_string(),
+                    description: "Result is larger".to_string(),
+                    formal_definition: "incr x > x".to_string(),
+                    kind: PropertyKind::Functional,
+                },
+                Property {
+                    id: "p2".to_string(),
+                    description: "Constant time".to_string(),
+                    formal_definition: "ct incr".to_string(),
+                    kind: PropertyKind::Custom("side_channel".to_string()),
+                }
+            ],
+            formal_spec: FormalSpecification {
+                verification_language: VerificationLanguage::Custom("sail".to_string()),
+                spec_code: "module Incr\nval incr: x:int -> y:int{y > x}".to_string(),
+                components: HashMap::from([("description".to_string(), "Increments".to_string())]),
+                dependencies: vec!["FStar.Int".to_string()],
+                candidates: vec![SpecificationCandidate {
+                    index: 0,
+                    provider: "local".to_string(),
+                    model: "llama3:8b".to_string(),
+                    spec_code: "module Incr".to_string(),
+                    errors: 1,
+                    warnings: 0,
+                    tool_validated: false,
+                    failure: Some("timed out".to_string()),
+                    selected: true,
+                }],
+            },
+            metadata: SpecificationMetadata {
+                created_at: chrono::Utc.with_ymd_and_hms(2025, 3, 25, 12, 30, 5).unwrap(),
+                verification_system: VerificationSystem::Custom("sail-check".to_string()),
+                domain: Domain::Custom("avionics".to_string()),
+                confidence_score: 0.85,
+                is_formally_validated: true,
+            },
+        };
+
+        VerifiedArtifact {
+            requirements: specification.source_requirements.clone(),
+            implementation: Implementation {
+                id: "impl-1".to_string(),
+                specification_id: specification.id.clone(),
+                language: Language::Custom("zig".to_string()),
+                source_code: "fn incr(x: i32) i32 { return x + 1; }".to_string(),
+                verification_result: verification_result.clone(),
+            },
+            specification,
+            verification_result,
+            documentation: Documentation {
+                spec_explanation: "incr returns a larger value".to_string(),
+                impl_explanation: "adds one".to_string(),
+                verification_summary: "one goal ran out of resources".to_string(),
+                usage_examples: vec!["incr(1) == 2".to_string()],
+            },
+        }
+    }
+
+    fn sample_report() -> ValidationReport {
+        ValidationReport {
+            is_valid: false,
+            issues: vec![ValidationIssue {
+                severity: IssueSeverity::Error,
+                message: "Identifier not found: [BROKEN]".to_string(),
+                related_property: Some("p1".to_string()),
+                line_number: Some(2),
+                span: Some(SourceSpan {
+                    file: Some("Incr.fst".to_string()),
+                    start_line: 2,
+                    start_column: 8,
+                    end_line: 2,
+                    end_column: 14,
+                }),
+                error_code: Some(72),
+                suggested_fix: None,
+            }],
+            tool_validated: true,
+            tool_output: Some("(Error 72) Identifier not found".to_string()),
+        }
+    }
+
+    fn round_trip<T: ModelDocument + PartialEq + std::fmt::Debug>(value: &T) {
+        let json = to_document_json(value).unwrap();
+        assert_eq!(&from_document_json::<T>(&json).unwrap(), value);
+    }
+
+    #[test]
+    fn test_every_document_kind_round_trips() {
+        let artifact = sample_artifact();
+        round_trip(&artifact);
+        round_trip(&artifact.specification);
+        round_trip(&artifact.specification.formal_spec);
+        round_trip(&artifact.implementation);
+        round_trip(&artifact.verification_result);
+        round_trip(&sample_report());
+    }
+
+    #[test]
+    fn test_enum_and_duration_representations_are_stable() {
+        assert_eq!(serde_json::to_value(VerificationLanguage::FStarLang).unwrap(), json!("fstar"));
+        assert_eq!(serde_json::to_value(VerificationLanguage::TLAPlus).unwrap(), json!("tlaplus"));
+        assert_eq!(serde_json::to_value(VerificationLanguage::Z3SMT).unwrap(), json!("smt"));
+        assert_eq!(serde_json::to_value(VerificationSystem::FStar).unwrap(), json!("fstar"));
+        assert_eq!(serde_json::to_value(Domain::DistributedSystems).unwrap(), json!("distributed_systems"));
+        assert_eq!(serde_json::to_value(Language::Rust).unwrap(), json!("rust"));
+        assert_eq!(serde_json::to_value(PropertyKind::ResourceUsage).unwrap(), json!("resource_usage"));
+        assert_eq!(serde_json::to_value(GoalOutcome::OutOfResource).unwrap(), json!("out_of_resource"));
+        assert_eq!(
+            serde_json::to_value(Domain::Custom("avionics".to_string())).unwrap(),
+            json!({ "custom": "avionics" })
+        );
+        assert_eq!(
+            serde_json::to_value(VerificationStatus::Failed(vec!["no".to_string()])).unwrap(),
+            json!({ "failed": ["no"] })
+        );
+        assert_eq!(serde_json::to_value(VerificationStatus::Verified).unwrap(), json!("verified"));
+        assert_eq!(
+            serde_json::to_value(Duration::new(3, 250_000_001)).unwrap(),
+            json!({ "secs": 3, "nanos": 250_000_001 })
+        );
+
+        let document: Value = serde_json::from_str(&to_document_json(&sample_report()).unwrap()).unwrap();
+        assert_eq!(document["schema_version"], json!(SCHEMA_VERSION));
+        assert_eq!(document["kind"], json!("validation_report"));
+        assert_eq!(document["data"]["issues"][0]["severity"], json!("error"));
+    }
+
+    #[test]
+    fn test_documents_from_other_versions_or_kinds_are_rejected() {
+        let json = to_document_json(&sample_report()).unwrap();
+
+        let newer = json.replacen(
+            &format!("\"schema_version\": {}", SCHEMA_VERSION),
+            &format!("\"schema_version\": {}", SCHEMA_VERSION + 1),
+            1
+        );
+        match from_document_json::<ValidationReport>(&newer) {
+            Err(DocumentError::UnsupportedVersion { found, supported }) => {
+                assert_eq!((found, supported), (SCHEMA_VERSION + 1, SCHEMA_VERSION));
+            }
+            other => panic!("expected UnsupportedVersion, got {:?}", other),
+        }
+
+        match from_document_json::<VerificationResult>(&json) {
+            Err(DocumentError::WrongKind { expected, found }) => {
+                assert_eq!((expected, found.as_str()), ("verification_result", "validation_report"));
+            }
+            other => panic!("expected WrongKind, got {:?}", other),
+        }
+
+        assert!(matches!(from_document_json::<ValidationReport>("{}"), Err(DocumentError::Json(_))));
+    }
+
+    #[test]
+    fn test_documents_conform_to_published_schema() {
+        let schema: Value = serde_json::from_str(JSON_SCHEMA).unwrap();
+        assert_eq!(schema["properties"]["schema_version"]["const"], json!(SCHEMA_VERSION));
+
+        let artifact = sample_artifact();
+        let documents = vec![
+            to_document_json(&artifact).unwrap(),
+            to_document_json(&artifact.specification).unwrap(),
+            to_document_json(&artifact.specification.formal_spec).unwrap(),
+            to_document_json(&artifact.implementation).unwrap(),
+            to_document_json(&artifact.verification_result).unwrap(),
+            to_document_json(&sample_report()).unwrap()
+        ];
+        for document in &documents {
+            let document: Value = serde_json::from_str(document).unwrap();
+            let errors = validate(&schema, &schema, &document, "");
+            assert!(errors.is_empty(), "{} document: {:#?}", document["kind"], errors);
+        }
+
+        // The schema is strict enough to notice a changed representation
+        let mut document: Value = serde_json::from_str(&documents[5]).unwrap();
+        document["data"]["issues"][0]["severity"] = json!("Error");
+        document["data"]["tool_validated"] = json!("yes");
+        document["data"]["extra"] = json!(1);
+        let errors = validate(&schema, &schema, &document, "");
+        assert_eq!(errors.len(), 3, "{:#?}", errors);
+    }
+
+    /// Check a value against the subset of JSON Schema the model schema uses
+    fn validate(root: &Value, schema: &Value, value: &Value, path: &str) -> Vec<String> {
+        let mut errors = Vec::new();
+        let fail = |errors: &mut Vec<String>, message: String| errors.push(format!("{}: {}", path, message));
+
+        if let Some(reference) = schema["$ref"].as_str() {
+            let target = root.pointer(reference.trim_start_matches('#')).unwrap_or_else(|| panic!("{}", reference));
+            errors.extend(validate(root, target, value, path));
+        }
+        if let Some(expected) = schema.get("type") {
+            let types: Vec<&str> = match expected {
+                Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+                other => vec![other.as_str().unwrap()],
+            };
+            let matches = |ty: &&str| {
+                match *ty {
+                    "null" => value.is_null(),
+                    "boolean" => value.is_boolean(),
+                    "integer" => value.is_i64() || value.is_u64(),
+                    "number" => value.is_number(),
+                    "string" => value.is_string(),
+                    "array" => value.is_array(),
+                    "object" => value.is_object(),
+                    other => panic!("unknown type {}", other),
+                }
+            };
+            if !types.iter().any(matches) {
+                fail(&mut errors, format!("expected {:?}, found {}", types, value));
+                return errors;
+            }
+        }
+        if let Some(allowed) = schema["enum"].as_array() {
+            if !allowed.contains(value) {
+                fail(&mut errors, format!("{} is not one of {:?}", value, allowed));
+            }
+        }
+        if let Some(constant) = schema.get("const") {
+            if constant != value {
+                fail(&mut errors, format!("expected {}, found {}", constant, value));
+            }
+        }
+        if let (Some(minimum), Some(number)) = (schema["minimum"].as_f64(), value.as_f64()) {
+            if number < minimum {
+                fail(&mut errors, format!("{} is below {}", number, minimum));
+            }
+        }
+        if let (Some(maximum), Some(number)) = (schema["maximum"].as_f64(), value.as_f64()) {
+            if number > maximum {
+                fail(&mut errors, format!("{} is above {}", number, maximum));
+            }
+        }
+        if let Some(object) = value.as_object() {
+            for required in schema["required"].as_array().into_iter().flatten() {
+                if !object.contains_key(required.as_str().unwrap()) {
+                    fail(&mut errors, format!("missing {}", required));
+                }
+            }
+            for (key, field) in object {
+                let field_path = format!("{}/{}", path, key);
+                match (schema["properties"].get(key), schema.get("additionalProperties")) {
+                    (Some(property), _) => errors.extend(validate(root, property, field, &field_path)),
+                    (None, Some(Value::Bool(false))) => fail(&mut errors, format!("unexpected field {}", key)),
+                    (None, Some(additional @ Value::Object(_))) => {
+                        errors.extend(validate(root, additional, field, &field_path));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if let (Some(items), Some(array)) = (schema.get("items"), value.as_array()) {
+            for (i, item) in array.iter().enumerate() {
+                errors.extend(validate(root, items, item, &format!("{}/{}", path, i)));
+            }
+        }
+        if let Some(options) = schema["anyOf"].as_array() {
+            if !options.iter().any(|option| validate(root, option, value, path).is_empty()) {
+                fail(&mut errors, format!("{} matches none of anyOf", value));
+            }
+        }
+        if let Some(options) = schema["oneOf"].as_array() {
+            let matching = options
+                .iter()
+                .filter(|option| validate(root, option, value, path).is_empty())
+                .count();
+            if matching != 1 {
+                fail(&mut errors, format!("{} matches {} of oneOf", value, matching));
+            }
+        }
+        for part in schema["allOf"].as_array().into_iter().flatten() {
+            errors.extend(validate(root, part, value, path));
+        }
+        if let Some(condition) = schema.get("if") {
+            if validate(root, condition, value, path).is_empty() {
+                errors.extend(validate(root, &schema["then"], value, path));
+            }
+        }
+        errors
+    }
+}
@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::implementations::config::GeneratorConfig;
+    use crate::implementations::llm::cache::ResponseCacheConfig;
+    use crate::models::common::{ Domain, Language, VerificationLanguage, VerificationSystem };
+    use crate::project::{ Project, ProjectError, ProjectManifest, MANIFEST_FILE };
+    use crate::tests::stub_tools::scratch_dir;
+
+    #[test]
+    fn test_init_scaffolds_project_and_refuses_to_run_twice() {
+        let root = scratch_dir("project_init").join("incr");
+        let manifest = ProjectManifest::new("incr", Language::Rust, Domain::DistributedSystems, None);
+        assert_eq!(manifest.project.verification_system, VerificationSystem::TLA);
+
+        let project = Project::init(&root, manifest).unwrap();
+
+        for dir in ["requirements", "specs", "impl", "proofs"] {
+            assert!(root.join(dir).is_dir(), "{} was not created", dir);
+        }
+        assert_eq!(Project::load(&root).unwrap(), project);
+        assert!(project.requirements().unwrap().is_empty());
+        assert_eq!(project.spec_path(&project.manifest.verification_language()), root.join("specs/incr.tla"));
+
+        let again = ProjectManifest::new("incr", Language::C, Domain::Cryptography, None);
+        assert!(matches!(Project::init(&root, again), Err(ProjectError::AlreadyInitialized(_))));
+        assert_eq!(Project::load(&root).unwrap().manifest.project.language, Language::Rust);
+    }
+
+    #[test]
+    fn test_discover_walks_up_from_nested_directories() {
+        let dir = scratch_dir("project_discover");
+        let root = dir.join("incr");
+        Project::init(&root, ProjectManifest::new("incr", Language::Rust, Domain::Cryptography, None)).unwrap();
+        let nested = root.join("impl").join("src");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        let found = Project::discover(&nested).unwrap().unwrap();
+        assert_eq!(found.root(), root.as_path());
+        assert_eq!(found.manifest.project.name, "incr");
+        assert!(Project::discover(&dir).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_manifest_settings_are_applied() {
+        let root = scratch_dir("project_settings");
+        std::fs::write(
+            root.join(MANIFEST_FILE),
+            r#"
+[project]
+name = "ledger"
+language = { custom = "zig" }
+verification_system = "fstar"
+domain = { custom = "payments" }
+
+[llm]
+config = "config/axiom.yaml"
+provider = "openai"
+model = "gpt-4o"
+temperature = 0.1
+
+[tools]
+timeout_secs = 60
+
+[tools.paths]
+fstar = "tools/fstar/bin/fstar.exe"
+z3 = "z3-4.13"
+
+[tools.constants]
+N = "3"
+"#
+        ).unwrap();
+        std::fs::create_dir_all(root.join("requirements")).unwrap();
+        std::fs::write(root.join("requirements/b.md"), "# Transfers\nBalances never go negative\n").unwrap();
+        std::fs::write(root.join("requirements/a.txt"), "Transfers preserve the total\n\n").unwrap();
+        std::fs::write(root.join("requirements/notes.json"), "{}").unwrap();
+
+        let project = Project::load(&root).unwrap();
+        assert_eq!(project.manifest.project.language, Language::Custom("zig".to_string()));
+        assert_eq!(project.manifest.project.domain, Domain::Custom("payments".to_string()));
+        assert_eq!(project.manifest.verification_language(), VerificationLanguage::FStarLang);
+        assert_eq!(
+            project.requirements().unwrap(),
+            vec!["Transfers preserve the total", "Balances never go negative"]
+        );
+        assert_eq!(project.llm_config_path(), Some(root.join("config/axiom.yaml")));
+        let written = toml::to_string(&project.manifest).unwrap();
+        assert_eq!(toml::from_str::<ProjectManifest>(&written).unwrap(), project.manifest);
+
+        let mut config = GeneratorConfig::default();
+        project.manifest.llm.apply(&mut config);
+        assert_eq!(config.llm_api.provider.as_deref(), Some("openai"));
+        assert_eq!(config.llm_api.model.as_deref(), Some("gpt-4o"));
+        assert_eq!(config.temperature, Some(0.1));
+
+        let tools = project.tools_config();
+        assert_eq!(tools.tool_path("fstar", "fstar.exe"), root.join("tools/fstar/bin/fstar.exe").display().to_string());
+        assert_eq!(tools.tool_path("z3", "z3"), "z3-4.13");
+        assert_eq!(tools.timeout, Duration::from_secs(60));
+        assert_eq!(tools.model_constants.get("N").map(String::as_str), Some("3"));
+    }
+
+    #[test]
+    fn test_generator_config_paths_are_resolved_from_the_project_root() {
+        let root = scratch_dir("project_config_paths");
+        std::fs::create_dir_all(root.join("config")).unwrap();
+        std::fs::create_dir_all(root.join("prompts")).unwrap();
+        let config = GeneratorConfig {
+            templates_dir: Some("prompts".to_string()),
+            response_cache: Some(ResponseCacheConfig::default()),
+            ..GeneratorConfig::default()
+        };
+        let path = root.join("config/axiom.yaml");
+        std::fs::write(&path, serde_yaml::to_string(&config).unwrap()).unwrap();
+
+        let loaded = GeneratorConfig::from_file_in(&path, &root).unwrap();
+        assert_eq!(loaded.templates_dir, Some(root.join("prompts").to_string_lossy().to_string()));
+        assert_eq!(loaded.response_cache.unwrap().dir, root.join(".axiom/cache/llm"));
+
+        let mut defaults = GeneratorConfig {
+            response_cache: Some(ResponseCacheConfig::default()),
+            ..GeneratorConfig::default()
+        };
+        defaults.resolve_paths(&root);
+        assert_eq!(defaults.response_cache.unwrap().dir, root.join(".axiom/cache/llm"));
+    }
+
+    #[test]
+    fn test_invalid_manifest_names_the_file() {
+        let root = scratch_dir("project_invalid");
+        std::fs::write(
+            root.join(MANIFEST_FILE),
+            "[project]\nname = \"x\"\nlanguage = \"rust\"\nverification_system = \"fstar\"\ndomain = \"cryptography\"\n\n[llm]\nmodle = \"gpt-4o\"\n"
+        ).unwrap();
+
+        match Project::load(&root) {
+            Err(ProjectError::InvalidManifest { path, message }) => {
+                assert_eq!(path, root.join(MANIFEST_FILE));
+                assert!(message.contains("modle"), "{}", message);
+            }
+            other => panic!("expected InvalidManifest, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,192 @@
+#[cfg(test)]
+mod tests {
+    use crate::implementations::backends::coq::{ self, parse_coq_errors };
+    use crate::implementations::backends::lean;
+    use crate::models::verification::GoalOutcome;
+
+    #[test]
+    fn test_coq_admitted_proofs_are_incomplete() {
+        let source =
+            "Theorem add_0_r : forall n, n + 0 = n.\nProof. induction n; simpl; auto. Qed.\n\
+            (* Lemma commented : True. Admitted. *)\n\
+            Lemma hard : forall n, n * 1 = n.\nProof. intros. admit. Admitted.\n\
+            Lemma aborted : False.\nProof. Abort.";
+
+        let goals = coq::proof_goals(source);
+
+        assert_eq!(goals.len(), 2);
+        assert_eq!(goals[0].name, "add_0_r");
+        assert_eq!(goals[0].outcome, GoalOutcome::Valid);
+        assert_eq!(goals[1].name, "hard");
+        assert_eq!(goals[1].outcome, GoalOutcome::Incomplete);
+    }
+
+    #[test]
+    fn test_parse_coq_errors() {
+        let output =
+            "File \"./Spec.v\", line 3, characters 2-7:\n\
+            Warning: Notation \"_ + _\" was already used.\n\
+            File \"./Spec.v\", line 12, characters 4-20:\n\
+            Error: In environment\n\
+            n : nat\n\
+            Unable to unify \"n\" with \"n + 0\".";
+
+        assert_eq!(parse_coq_errors(output), vec![
+            "Spec.v:12:4-20: In environment n : nat Unable to unify \"n\" with \"n + 0\".".to_string()
+        ]);
+    }
+
+    #[test]
+    fn test_lean_sorry_marks_declaration_incomplete() {
+        let source =
+            "import Mathlib.Tactic\n\n\
+            theorem add_zero' (n : Nat) : n + 0 = n := by simp\n\n\
+            -- theorem commented : False := sorry\n\
+            private theorem hard (n : Nat) : n * 1 = n := by\n  sorry\n\n\
+            def double (n : Nat) : Nat := 2 * n";
+
+        let goals = lean::proof_goals(source);
+
+        let outcomes: Vec<(&str, GoalOutcome)> = goals
+            .iter()
+            .map(|g| (g.name.as_str(), g.outcome.clone()))
+            .collect();
+        assert_eq!(outcomes, vec![
+            ("add_zero'", GoalOutcome::Valid),
+            ("hard", GoalOutcome::Incomplete),
+            ("double", GoalOutcome::Valid)
+        ]);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod backend_tests {
+    use crate::config::ExternalToolsConfig;
+    use crate::implementations::backends::{ CoqBackend, LeanBackend };
+    use crate::models::verification::{ GoalOutcome, VerificationOptions, VerificationStatus };
+    use crate::tests::stub_tools::{ scratch_dir, write_stub_tool };
+    use crate::traits::verification_engine::VerificationBackendAdapter;
+
+    // Stub coqc: fails on files containing `BROKEN`, otherwise writes a .vo next to each file
+    const STUB_COQC: &str = r#"
+if [ "$1" = "--version" ]; then
+  echo "The Coq Proof Assistant, version 8.18.0"
+  exit 0
+fi
+for arg in "$@"; do
+  case "$arg" in
+    *.v)
+      if grep -q "BROKEN" "$arg"; then
+        echo "File \"./$(basename "$arg")\", line 2, characters 0-6:"
+        echo "Error: The reference BROKEN was not found in the current environment."
+        exit 1
+      fi
+      touch "${arg}o"
+      ;;
+  esac
+done
+"#;
+
+    // Stub lake: warns about `sorry` like Lean does and records its arguments
+    const STUB_LAKE: &str = r#"
+if [ "$1" = "--version" ]; then
+  echo "Lake version 5.0.0 (Lean version 4.9.0)"
+  exit 0
+fi
+for arg in "$@"; do file="$arg"; done
+echo "$@" > args.txt
+if grep -q "sorry" "$file"; then
+  echo "$(basename "$file"):3:8: warning: declaration uses 'sorry'"
+fi
+"#;
+
+    fn tools_with(name: &str, key: &str, tool: &str, script: &str) -> (ExternalToolsConfig, std::path::PathBuf) {
+        let dir = scratch_dir(name);
+        let path = write_stub_tool(&dir, tool, script);
+        let mut tools = ExternalToolsConfig::default();
+        tools.tool_paths.insert(key.to_string(), path.display().to_string());
+        (tools, dir)
+    }
+
+    #[test]
+    fn test_coq_complete_proof_is_verified() {
+        let (tools, dir) = tools_with("coq_verified", "coqc", "coqc", STUB_COQC);
+        let backend = CoqBackend::new(&tools).with_work_dir(dir.join("runs"));
+
+        assert_eq!(backend.get_backend_version().unwrap(), "The Coq Proof Assistant, version 8.18.0");
+
+        let result = backend
+            .execute_verification("Lemma triv : True.\nProof. exact I. Qed.", "", &VerificationOptions::default())
+            .unwrap();
+
+        assert!(matches!(result.status, VerificationStatus::Verified));
+        assert_eq!(result.resource_usage.lemmas_proven, 1);
+        assert!(result.proof_artifacts.iter().any(|a| a.path.ends_with("Spec.vo")));
+    }
+
+    #[test]
+    fn test_coq_admitted_proof_is_unverified() {
+        let (tools, dir) = tools_with("coq_admitted", "coqc", "coqc", STUB_COQC);
+        let backend = CoqBackend::new(&tools).with_work_dir(dir.join("runs"));
+
+        let result = backend
+            .execute_verification(
+                "Lemma triv : True.\nProof. exact I. Qed.\nLemma hole : 1 = 2.\nAdmitted.",
+                "",
+                &VerificationOptions::default()
+            )
+            .unwrap();
+
+        assert!(matches!(result.status, VerificationStatus::Unverified));
+        assert_eq!(result.goal_results[1].name, "hole");
+        assert_eq!(result.goal_results[1].outcome, GoalOutcome::Incomplete);
+    }
+
+    #[test]
+    fn test_coq_error_fails_verification() {
+        let (tools, dir) = tools_with("coq_failed", "coqc", "coqc", STUB_COQC);
+        let backend = CoqBackend::new(&tools).with_work_dir(dir.join("runs"));
+
+        let result = backend
+            .execute_verification("Lemma bad : True.\nBROKEN.", "", &VerificationOptions::default())
+            .unwrap();
+
+        match result.status {
+            VerificationStatus::Failed(reasons) => {
+                assert_eq!(reasons, vec![
+                    "Spec.v:2:0-6: The reference BROKEN was not found in the current environment.".to_string()
+                ]);
+            }
+            other => panic!("Expected failure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_lean_sorry_is_unverified() {
+        let (tools, dir) = tools_with("lean_sorry", "lake", "lake", STUB_LAKE);
+        let backend = LeanBackend::new(&tools).with_work_dir(dir.join("runs"));
+
+        let proven = backend
+            .execute_verification("theorem t : 1 + 1 = 2 := rfl", "", &VerificationOptions::default())
+            .unwrap();
+        assert!(matches!(proven.status, VerificationStatus::Verified));
+
+        let result = backend
+            .execute_verification(
+                "import Std\ntheorem t : 1 + 1 = 2 := rfl",
+                "import Std\ntheorem hole (n : Nat) : n = n + 0 := by\n  sorry",
+                &VerificationOptions::default()
+            )
+            .unwrap();
+
+        assert!(matches!(result.status, VerificationStatus::Unverified));
+        assert_eq!(result.resource_usage.lemmas_proven, 1);
+
+        let run_dir = std::path::Path::new(&result.proof_artifacts[0].path).parent().unwrap().to_path_buf();
+        let source = std::fs::read_to_string(run_dir.join("AxiomSpec.lean")).unwrap();
+        assert!(source.starts_with("import Std\n\ntheorem t"));
+        assert_eq!(source.matches("import Std").count(), 1);
+        let args = std::fs::read_to_string(run_dir.join("args.txt")).unwrap();
+        assert!(args.starts_with("env lean -D maxHeartbeats=200000"));
+    }
+}
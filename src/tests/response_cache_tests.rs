@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{ AtomicUsize, Ordering };
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use chrono::Utc;
+
+    use crate::implementations::llm::cache::{ CacheEntry, CachingClient, ResponseCache };
+    use crate::implementations::llm::{ LlmClient, LlmRequest, LlmResponse };
+    use crate::implementations::specification_generator::SpecGenError;
+    use crate::tests::stub_tools::scratch_dir;
+
+    /// Client counting its calls; fails the first `failures` calls
+    struct CountingClient {
+        calls: AtomicUsize,
+        failures: usize,
+    }
+
+    impl CountingClient {
+        fn new(failures: usize) -> Arc<Self> {
+            Arc::new(Self { calls: AtomicUsize::new(0), failures })
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl LlmClient for CountingClient {
+        fn provider(&self) -> &str {
+            "counting"
+        }
+
+        fn model(&self) -> &str {
+            "counting-1"
+        }
+
+        async fn complete(&self, request: &LlmRequest) -> Result<LlmResponse, SpecGenError> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            if call < self.failures {
+                return Err(SpecGenError::HttpError { status: 500, message: "overloaded".to_string() });
+            }
+            Ok(LlmResponse::text(format!("response {} to {}", call, request.prompt)))
+        }
+    }
+
+    fn entry(key: &str, age: chrono::Duration) -> CacheEntry {
+        CacheEntry {
+            key: key.to_string(),
+            provider: "counting".to_string(),
+            model: "counting-1".to_string(),
+            temperature: 0.2,
+            created_at: Utc::now() - age,
+            response: format!("cached {}", key),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_repeated_request_is_served_from_cache() {
+        let dir = scratch_dir("llm_cache_hit");
+        let inner = CountingClient::new(0);
+        let client = CachingClient::new(inner.clone(), ResponseCache::new(&dir, None));
+        let request = LlmRequest::new("Validate spec", 0.2, 4096);
+
+        let first = client.complete(&request).await.unwrap();
+        let second = client.complete(&request).await.unwrap();
+        assert_eq!(first.content, second.content);
+        assert!(!first.cached && second.cached);
+        assert_eq!(inner.calls(), 1);
+
+        // Temperature is part of the key
+        client.complete(&LlmRequest::new("Validate spec", 0.9, 4096)).await.unwrap();
+        assert_eq!(inner.calls(), 2);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_failed_responses_are_not_cached() {
+        let dir = scratch_dir("llm_cache_failure");
+        let inner = CountingClient::new(1);
+        let client = CachingClient::new(inner.clone(), ResponseCache::new(&dir, None));
+        let request = LlmRequest::new("Validate spec", 0.2, 4096);
+
+        assert!(client.complete(&request).await.is_err());
+        assert_eq!(ResponseCache::new(&dir, None).stats().entries, 0);
+
+        assert_eq!(client.complete(&request).await.unwrap().content, "response 1 to Validate spec");
+        assert_eq!(inner.calls(), 2);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_key_covers_provider_and_model() {
+        let request = LlmRequest::new("Validate spec", 0.2, 4096);
+
+        let key = ResponseCache::key("anthropic", "claude", &request);
+        assert_ne!(key, ResponseCache::key("openai", "claude", &request));
+        assert_ne!(key, ResponseCache::key("anthropic", "claude-2", &request));
+        assert_eq!(key, ResponseCache::key("anthropic", "claude", &LlmRequest::new("Validate spec", 0.2, 10)));
+    }
+
+    #[test]
+    fn test_ttl_stats_and_clear() {
+        let dir = scratch_dir("llm_cache_ttl");
+        let cache = ResponseCache::new(&dir, Some(Duration::from_secs(3600)));
+        cache.put(&entry("fresh", chrono::Duration::minutes(5))).unwrap();
+        cache.put(&entry("stale", chrono::Duration::hours(2))).unwrap();
+        std::fs::write(dir.join("corrupt.json"), "{").unwrap();
+
+        assert_eq!(cache.get("fresh").as_deref(), Some("cached fresh"));
+        assert_eq!(cache.get("stale"), None);
+        assert_eq!(ResponseCache::new(&dir, None).get("stale").as_deref(), Some("cached stale"));
+
+        let stats = cache.stats();
+        assert_eq!(stats.entries, 3);
+        assert_eq!(stats.expired, 2);
+        assert!(stats.total_bytes > 0);
+        assert!(stats.oldest < stats.newest);
+
+        assert_eq!(cache.clear(true).unwrap(), 2);
+        assert_eq!(cache.stats().entries, 1);
+        assert_eq!(cache.clear(false).unwrap(), 1);
+        assert_eq!(cache.stats(), Default::default());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod tests {
+    use crate::implementations::backends::smt_output::{ parse_smt_output, SatResult };
+
+    #[test]
+    fn test_parse_sat_with_model() {
+        let output =
+            "sat\n\
+            (\n\
+            \x20 (define-fun x () Int\n\
+            \x20   (- 3))\n\
+            \x20 (define-fun ok () Bool\n\
+            \x20   false)\n\
+            \x20 (define-fun f ((x!0 Int)) Int\n\
+            \x20   (ite (= x!0 1) 2 3))\n\
+            )";
+
+        let parsed = parse_smt_output(output);
+
+        assert_eq!(parsed.checks.len(), 1);
+        assert_eq!(parsed.checks[0].result, SatResult::Sat);
+        let model = parsed.checks[0].model.as_ref().expect("model");
+        assert_eq!(model["x"], "-3");
+        assert_eq!(model["ok"], "false");
+        assert_eq!(model["f"], "(lambda ((x!0 Int)) (ite (= x!0 1) 2 3))");
+        assert!(parsed.errors.is_empty());
+    }
+
+    #[test]
+    fn test_parse_labels_and_errors() {
+        let output =
+            "no_overflow\n\
+            unsat\n\
+            (error \"line 6 column 10: model is not available\")\n\
+            balance_positive\n\
+            unknown\n\
+            timeout";
+
+        let parsed = parse_smt_output(output);
+
+        let results: Vec<SatResult> = parsed.checks.iter().map(|c| c.result).collect();
+        assert_eq!(results, vec![SatResult::Unsat, SatResult::Unknown, SatResult::Timeout]);
+        assert_eq!(parsed.checks[0].label.as_deref(), Some("no_overflow"));
+        assert_eq!(parsed.checks[1].label.as_deref(), Some("balance_positive"));
+        assert_eq!(parsed.checks[2].label, None);
+        assert_eq!(parsed.errors, vec!["line 6 column 10: model is not available".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_legacy_model_wrapper() {
+        let output = "sat\n(model\n  (define-fun |weird name| () Real (/ 1.0 3.0))\n)";
+
+        let parsed = parse_smt_output(output);
+
+        let model = parsed.checks[0].model.as_ref().expect("model");
+        assert_eq!(model["weird name"], "(/ 1.0 3.0)");
+    }
+}
@@ -6,11 +6,14 @@ mod tests {
 
     use tokio::test;
 
-    use crate::errors::{ AxiomError, AxiomResult };
+    use crate::errors::AxiomResult;
     use crate::implementations::config::GeneratorConfig;
+    use crate::implementations::llm::{ LlmFixtureConfig, LlmFixtureMode };
     use crate::implementations::specification_generator::LLMSpecificationGenerator;
-    use crate::models::common::{ Domain, VerificationLanguage };
+    use crate::models::common::{ Domain, Language, VerificationLanguage, VerificationSystem };
     use crate::models::specification::SpecificationOptions;
+    use crate::project::{ Project, ProjectManifest };
+    use crate::tests::stub_tools::scratch_dir;
     use crate::traits::specification_generator::{ SpecificationGenerator, ValidationDepth };
 
     // Setup function to initialize logging and environment
@@ -41,7 +44,7 @@ mod tests {
     fn should_skip_api_tests() -> bool {
         setup(); // Ensure environment is loaded
 
-        let api_keys = vec![
+        let api_keys = [
             "ANTHROPIC_API_KEY",
             "OPENAI_API_KEY",
             "MISTRAL_API_KEY",
@@ -69,12 +72,123 @@ mod tests {
         ]
     }
 
+    // Set AXIOM_LLM_FIXTURES=record and AXIOM_LLM_FIXTURE_DIR to capture live responses as fixtures
     fn create_test_generator() -> LLMSpecificationGenerator {
         info!("Creating test generator with default configuration");
-        let config = GeneratorConfig::default();
+        let config = GeneratorConfig {
+            llm_fixtures: LlmFixtureConfig::from_env(),
+            ..GeneratorConfig::default()
+        };
+        LLMSpecificationGenerator::new(config)
+    }
+
+    // Generator answering from the frozen responses in src/tests/fixtures/llm/specification_pipeline
+    fn create_replay_generator() -> LLMSpecificationGenerator {
+        let config = GeneratorConfig {
+            llm_fixtures: Some(LlmFixtureConfig {
+                mode: LlmFixtureMode::Replay,
+                dir: concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/src/tests/fixtures/llm/specification_pipeline"
+                ).into(),
+            }),
+            ..GeneratorConfig::default()
+        };
         LLMSpecificationGenerator::new(config)
     }
 
+    fn fstar_options() -> SpecificationOptions {
+        SpecificationOptions {
+            verification_language: VerificationLanguage::FStarLang,
+            ..SpecificationOptions::default()
+        }
+    }
+
+    #[test]
+    async fn test_replay_generate_specification() {
+        setup();
+        let generator = create_replay_generator();
+
+        let spec = generator
+            .generate_specification(
+                &get_test_requirements(),
+                Domain::Cryptography,
+                &fstar_options()
+            ).await
+            .unwrap();
+
+        assert_eq!(spec.source_requirements, get_test_requirements());
+        assert!(spec.formal_spec.spec_code.starts_with("module KeyStore"));
+        assert!(spec.formal_spec.spec_code.contains("val rotate_key"));
+        assert!(!spec.formal_spec.spec_code.contains("```"));
+    }
+
+    #[test]
+    async fn test_replay_validate_auto_fixes_missing_definition() {
+        setup();
+        let generator = create_replay_generator();
+        let spec = generator
+            .generate_specification(
+                &get_test_requirements(),
+                Domain::Cryptography,
+                &fstar_options()
+            ).await
+            .unwrap();
+
+        // The recorded syntax check flags `is_fresh` as undefined, the recorded fix defines it,
+        // and the recorded re-check accepts the result
+        let report = generator.validate_specification(&spec, ValidationDepth::Basic).await.unwrap();
+
+        assert!(report.is_valid);
+        assert!(!report.tool_validated);
+        let fix = report.issues
+            .iter()
+            .find(|issue| issue.message.contains("automatically fixed after 1 attempts"))
+            .expect("auto-fix note");
+        assert!(fix.suggested_fix.as_ref().unwrap().contains("let is_fresh (k:key)"));
+    }
+
+    #[test]
+    async fn test_replay_without_fixture_fails() {
+        setup();
+        let generator = create_replay_generator();
+
+        let result = generator.generate_specification(
+            &["An unrecorded requirement".to_string()],
+            Domain::Cryptography,
+            &fstar_options()
+        ).await;
+
+        let message = result.unwrap_err().to_string();
+        assert!(message.contains("No recorded response"), "{}", message);
+    }
+
+    #[test]
+    async fn test_replay_generate_and_save_uses_project_specs_dir() {
+        setup();
+        let generator = create_replay_generator();
+        let manifest = ProjectManifest::new(
+            "key_store",
+            Language::Rust,
+            Domain::Cryptography,
+            Some(VerificationSystem::FStar)
+        );
+        let project = Project::init(&scratch_dir("generate_and_save"), manifest).unwrap();
+
+        let (spec, spec_path) = generator
+            .generate_and_save(
+                &project,
+                &get_test_requirements(),
+                Domain::Cryptography,
+                VerificationLanguage::FStarLang
+            ).await
+            .unwrap();
+
+        assert_eq!(spec_path, project.specs_dir().join("key_store.fst"));
+        assert_eq!(std::fs::read_to_string(&spec_path).unwrap(), spec.formal_spec.spec_code);
+        assert!(project.specs_dir().join("key_store.json").is_file());
+    }
+
     // This is our main test for specification generation
     #[test]
     #[ignore = "Requires API key"]
@@ -95,8 +209,7 @@ mod tests {
         info!("Using domain: {:?}", domain);
 
         info!("Setting up specification options");
-        let mut options = SpecificationOptions::default();
-        options.verification_language = VerificationLanguage::FStarLang;
+        let options = fstar_options();
         debug!("Verification language: {:?}", options.verification_language);
 
         info!("Calling generate_specification");
@@ -184,8 +297,7 @@ mod tests {
         info!("Using domain: {:?}", domain);
 
         info!("Setting up specification options");
-        let mut options = SpecificationOptions::default();
-        options.verification_language = VerificationLanguage::FStarLang;
+        let options = fstar_options();
 
         info!("Generating specification for validation");
         let spec = match generator.generate_specification(&requirements, domain, &options).await {
@@ -251,6 +363,7 @@ mod tests {
                 spec_code: "module Test\nlet test (x:int) : int = x + 1".to_string(),
                 components: std::collections::HashMap::new(),
                 dependencies: vec![],
+                candidates: vec![],
             },
             metadata: crate::models::specification::SpecificationMetadata {
                 created_at: chrono::Utc::now(),
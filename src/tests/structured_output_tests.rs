@@ -0,0 +1,213 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::{ Arc, Mutex };
+
+    use async_trait::async_trait;
+
+    use crate::errors::AxiomError;
+    use crate::implementations::llm::anthropic::parse_stream_event;
+    use crate::implementations::llm::stream::SseEvent;
+    use crate::implementations::llm::structured::{
+        parse_structured,
+        TemplateList,
+        TranslationList,
+        ValidationVerdict,
+    };
+    use crate::implementations::llm::{
+        default_http_client,
+        AnthropicClient,
+        LlmClient,
+        LlmRequest,
+        LlmResponse,
+        LocalClient,
+        OpenAiClient,
+        StructuredResponse,
+    };
+    use crate::implementations::specification_generator::{ LLMSpecificationGenerator, SpecGenError };
+    use crate::models::common::{ Domain, VerificationLanguage };
+    use crate::models::specification::IssueSeverity;
+    use crate::tests::stub_http::{ StubHttpServer, StubResponse };
+    use crate::traits::specification_generator::SpecificationGenerator;
+
+    /// Client that gives its answers in order, repeating the last one, and keeps the requests
+    struct ScriptedClient {
+        answers: Vec<String>,
+        requests: Mutex<Vec<LlmRequest>>,
+    }
+
+    impl ScriptedClient {
+        fn new(answers: &[&str]) -> Arc<Self> {
+            Arc::new(Self {
+                answers: answers
+                    .iter()
+                    .map(|answer| answer.to_string())
+                    .collect(),
+                requests: Mutex::new(vec![]),
+            })
+        }
+
+        fn requests(&self) -> Vec<LlmRequest> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl LlmClient for ScriptedClient {
+        fn provider(&self) -> &str {
+            "scripted"
+        }
+
+        fn model(&self) -> &str {
+            "scripted-1"
+        }
+
+        async fn complete(&self, request: &LlmRequest) -> Result<LlmResponse, SpecGenError> {
+            let mut requests = self.requests.lock().unwrap();
+            requests.push(request.clone());
+            let answer = self.answers.get(requests.len() - 1).or(self.answers.last()).unwrap();
+            Ok(LlmResponse::text(answer.clone()))
+        }
+    }
+
+    const TRANSLATIONS: &str =
+        r#"{"translations":[{"requirement":"Keys are rotated every 90 days","interpreted_properties":["age k >= 90 ==> rotated k"],"formal_representation":"forall k. age k >= 90 -> rotated k","translation_confidence":0.65}]}"#;
+
+    fn structured_request() -> LlmRequest {
+        LlmRequest::new("Validate this", 0.2, 256).with_response_schema(ValidationVerdict::response_schema())
+    }
+
+    #[test]
+    fn test_parse_structured_responses() {
+        let fenced =
+            "Here is my report:\n```json\n{\"is_valid\": false, \"issues\": [{\"line_number\": 4, \"message\": \"Missing `}`\", \"severity\": \"Error\", \"suggested_fix\": \"Close the refinement\"}, {\"message\": \"Unused open\", \"severity\": \"warning\"}]}\n```";
+        let report = parse_structured::<ValidationVerdict>(fenced).unwrap().into_report();
+        assert!(!report.is_valid);
+        assert!(!report.tool_validated);
+        assert_eq!(report.issues.len(), 2);
+        assert!(matches!(report.issues[0].severity, IssueSeverity::Error));
+        assert_eq!(report.issues[0].line_number, Some(4));
+        assert_eq!(report.issues[0].suggested_fix.as_deref(), Some("Close the refinement"));
+        assert!(matches!(report.issues[1].severity, IssueSeverity::Warning));
+        assert_eq!(report.issues[1].line_number, None);
+
+        let translation = parse_structured::<TranslationList>(TRANSLATIONS)
+            .unwrap()
+            .translations.remove(0)
+            .into_translation(VerificationLanguage::FStarLang);
+        assert_eq!(translation.interpreted_properties, vec!["age k >= 90 ==> rotated k"]);
+        assert!(translation.requires_human_review);
+
+        // Schema violations are described so that the re-ask can quote them
+        assert!(parse_structured::<ValidationVerdict>("The specification is valid.").is_err());
+        let missing = parse_structured::<TemplateList>(r#"{"templates":[{"template_name":"t"}]}"#).unwrap_err();
+        assert!(missing.contains("missing field"), "{}", missing);
+        let severity = parse_structured::<ValidationVerdict>(
+            r#"{"is_valid":false,"issues":[{"message":"m","severity":"fatal"}]}"#
+        ).unwrap_err();
+        assert!(severity.contains("unknown variant"), "{}", severity);
+        let contradiction = parse_structured::<ValidationVerdict>(
+            r#"{"is_valid":true,"issues":[{"message":"m","severity":"error"}]}"#
+        ).unwrap_err();
+        assert!(contradiction.contains("is_valid"), "{}", contradiction);
+        let confidence = TRANSLATIONS.replace("0.65", "65");
+        assert!(parse_structured::<TranslationList>(&confidence).unwrap_err().contains("between 0 and 1"));
+    }
+
+    #[tokio::test]
+    async fn test_anthropic_forces_tool_call() {
+        let body =
+            r#"{"content":[{"type":"tool_use","id":"toolu_1","name":"report_validation","input":{"is_valid":true,"issues":[]}}],"usage":{"input_tokens":10,"output_tokens":5}}"#;
+        let server = StubHttpServer::start(vec![("/v1/messages", vec![StubResponse::json(200, body)])]);
+        let client = AnthropicClient::new(default_http_client(), "key").with_endpoint(
+            format!("{}/v1/messages", server.base_url)
+        );
+
+        let response = client.complete(&structured_request()).await.unwrap();
+
+        assert!(parse_structured::<ValidationVerdict>(&response.content).unwrap().is_valid);
+        let sent: serde_json::Value = serde_json::from_str(&server.requests()[0].body).unwrap();
+        assert_eq!(sent["tools"][0]["name"], "report_validation");
+        assert_eq!(sent["tools"][0]["input_schema"]["required"][0], "is_valid");
+        assert_eq!(sent["tool_choice"]["type"], "tool");
+        assert_eq!(sent["tool_choice"]["name"], "report_validation");
+
+        // Streamed tool input arrives as partial JSON
+        let event = SseEvent {
+            event: Some("content_block_delta".to_string()),
+            data: r#"{"type":"content_block_delta","index":0,"delta":{"type":"input_json_delta","partial_json":"{\"is_valid\":"}}"#.to_string(),
+        };
+        assert_eq!(parse_stream_event(&event).unwrap().text.as_deref(), Some("{\"is_valid\":"));
+
+        // Plain requests carry no tools
+        let plain = client.request_body(&LlmRequest::new("Specify incr", 0.2, 256));
+        assert!(plain.get("tools").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_openai_function_call_and_json_mode() {
+        let body =
+            r#"{"choices":[{"index":0,"message":{"role":"assistant","content":null,"tool_calls":[{"id":"call_1","type":"function","function":{"name":"report_validation","arguments":"{\"is_valid\":true,\"issues\":[]}"}}]},"finish_reason":"tool_calls"}]}"#;
+        let server = StubHttpServer::start(vec![("/v1/chat/completions", vec![StubResponse::json(200, body)])]);
+        let client = OpenAiClient::new(default_http_client(), "key").with_endpoint(
+            format!("{}/v1/chat/completions", server.base_url)
+        );
+
+        let response = client.complete(&structured_request()).await.unwrap();
+
+        assert_eq!(response.content, r#"{"is_valid":true,"issues":[]}"#);
+        let sent: serde_json::Value = serde_json::from_str(&server.requests()[0].body).unwrap();
+        assert_eq!(sent["tools"][0]["function"]["name"], "report_validation");
+        assert_eq!(sent["tools"][0]["function"]["parameters"]["type"], "object");
+        assert_eq!(sent["tool_choice"]["function"]["name"], "report_validation");
+        assert!(sent.get("response_format").is_none());
+
+        // Servers without function calling get JSON mode, or Ollama's schema-constrained format
+        let local = LocalClient::new(default_http_client(), "http://localhost:8080/v1/chat/completions").unwrap();
+        let sent = local.request_body("llama3", &structured_request());
+        assert_eq!(sent["response_format"]["type"], "json_object");
+        assert!(sent.get("tools").is_none());
+        let ollama = LocalClient::new(default_http_client(), "http://localhost:11434/api/chat").unwrap();
+        let sent = ollama.request_body("llama3", &structured_request());
+        assert_eq!(sent["format"]["required"][0], "is_valid");
+    }
+
+    #[tokio::test]
+    async fn test_schema_violation_is_asked_again_once() {
+        let client = ScriptedClient::new(&["Requirement: Keys are rotated\nConfidence: 0.9", TRANSLATIONS]);
+        let generator = LLMSpecificationGenerator::new_with_defaults().with_llm_client(client.clone());
+
+        let translations = generator
+            .translate_to_properties(&["Keys are rotated every 90 days".to_string()], Domain::Cryptography).await
+            .unwrap();
+
+        assert_eq!(translations.len(), 1);
+        assert_eq!(translations[0].formal_representation, "forall k. age k >= 90 -> rotated k");
+        let requests = client.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].response_schema.as_ref().unwrap().name, "record_translations");
+        assert!(requests[0].prompt.contains("\"translation_confidence\""));
+        assert!(requests[1].prompt.contains("Your previous answer was:\nRequirement: Keys are rotated"));
+        assert!(requests[1].prompt.contains("no JSON object found"));
+        assert_eq!(requests[1].response_schema, requests[0].response_schema);
+    }
+
+    #[tokio::test]
+    async fn test_second_schema_violation_fails() {
+        let client = ScriptedClient::new(&[r#"{"templates":"none"}"#]);
+        let generator = LLMSpecificationGenerator::new_with_defaults().with_llm_client(client.clone());
+
+        let result = generator.get_specification_templates(
+            Domain::Cryptography,
+            VerificationLanguage::FStarLang
+        ).await;
+
+        match result {
+            Err(AxiomError::SpecTranslationError(message)) => {
+                assert!(message.contains("record_templates"), "{}", message);
+                assert!(message.contains("after a retry"), "{}", message);
+            }
+            other => panic!("expected a translation error, got {:?}", other.map(|t| t.len())),
+        }
+        assert_eq!(client.requests().len(), 2);
+    }
+}
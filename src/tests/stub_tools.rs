@@ -0,0 +1,31 @@
+//! Helpers for tests that run verification backends against stub executables
+
+use std::path::{ Path, PathBuf };
+
+/// Create an empty scratch directory for a test
+pub fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("axiom_test_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("Failed to create scratch directory");
+    dir
+}
+
+/// Write an executable shell script standing in for an external verification tool
+#[cfg(unix)]
+pub fn write_stub_tool(dir: &Path, name: &str, script: &str) -> PathBuf {
+    use std::io::Write;
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = dir.join(name);
+    {
+        let mut file = std::fs::File::create(&path).expect("Failed to create stub tool");
+        file.write_all(format!("#!/bin/sh\n{}\n", script).as_bytes()).expect(
+            "Failed to write stub tool"
+        );
+        file.sync_all().expect("Failed to sync stub tool");
+    }
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).expect(
+        "Failed to make stub tool executable"
+    );
+    path
+}
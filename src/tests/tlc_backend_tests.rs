@@ -0,0 +1,302 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::implementations::backends::tlc::{ parse_tlc_output, TlcBackend };
+
+    const VIOLATION_OUTPUT: &str =
+        "TLC2 Version 2.18 of 20 March 2023 (rev: 3ea3222)\n\
+        Computing initial states...\n\
+        Finished computing initial states: 1 distinct state generated at 2024-05-01 10:00:00.\n\
+        Error: Invariant AxiomInv_no_overdraft is violated.\n\
+        Error: The behavior up to this point is:\n\
+        State 1: <Initial predicate>\n\
+        /\\ balance = 10\n\
+        /\\ pending = <<>>\n\
+        \n\
+        State 2: <Withdraw line 12, col 3 to line 14, col 20 of module Bank>\n\
+        /\\ balance = -5\n\
+        /\\ pending = << [amount |-> 15,\n\
+        \x20                  to |-> \"alice\"] >>\n\
+        \n\
+        7 states generated, 5 distinct states found, 2 states left on queue.\n\
+        The depth of the complete state graph search is 2.\n\
+        Finished in 01s at (2024-05-01 10:00:01)";
+
+    #[test]
+    fn test_parse_invariant_violation_with_trace() {
+        let parsed = parse_tlc_output(VIOLATION_OUTPUT);
+
+        assert!(!parsed.completed);
+        assert_eq!(parsed.states_generated, Some(7));
+        assert_eq!(parsed.distinct_states, Some(5));
+        assert_eq!(parsed.violations, vec!["Invariant AxiomInv_no_overdraft is violated.".to_string()]);
+        assert!(parsed.errors.is_empty());
+
+        assert_eq!(parsed.traces.len(), 1);
+        let (violation, steps) = &parsed.traces[0];
+        assert_eq!(violation, "Invariant AxiomInv_no_overdraft is violated.");
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].action, "Initial predicate");
+        assert_eq!(steps[0].state["balance"], "10");
+        assert!(steps[1].action.starts_with("Withdraw line 12"));
+        assert_eq!(steps[1].state["balance"], "-5");
+        assert_eq!(steps[1].state["pending"], "<< [amount |-> 15, to |-> \"alice\"] >>");
+    }
+
+    #[test]
+    fn test_parse_successful_run() {
+        let output =
+            "Progress(2) at 2024-05-01 10:00:00: 120 states generated (7,200 s/min), 40 distinct states found, 3 states left on queue.\n\
+            Model checking completed. No error has been found.\n\
+            1,204 states generated, 410 distinct states found, 0 states left on queue.";
+
+        let parsed = parse_tlc_output(output);
+
+        assert!(parsed.completed);
+        assert_eq!(parsed.states_generated, Some(1204));
+        assert_eq!(parsed.distinct_states, Some(410));
+        assert!(parsed.violations.is_empty());
+    }
+
+    #[test]
+    fn test_generate_config() {
+        let module =
+            "---- MODULE Bank ----\nVARIABLE balance\nInit == balance = 0\nNext == balance' = balance + 1\n\
+            Spec == Init /\\ [][Next]_balance\nTypeOK == balance \\in Nat\n\
+            AxiomInv_positive == balance >= 0\nAxiomProp_grows == <>(balance > 3)\n====";
+
+        assert_eq!(
+            TlcBackend::generate_config(module, &BTreeMap::new()),
+            "SPECIFICATION Spec\nINVARIANT TypeOK\nINVARIANT AxiomInv_positive\nPROPERTY AxiomProp_grows\n"
+        );
+        assert_eq!(
+            TlcBackend::generate_config("Init == TRUE\nNext == TRUE", &BTreeMap::new()),
+            "INIT Init\nNEXT Next\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_config_binds_declared_constants() {
+        let module =
+            "---- MODULE Bank ----\nCONSTANTS Accounts, \\* who can hold money\n    Limit\nCONSTANT Nobody\n\
+            VARIABLE balance\nInit == balance = 0\nNext == balance' = balance + 1\n====";
+        let values = BTreeMap::from([("Limit".to_string(), "3".to_string())]);
+
+        assert_eq!(
+            TlcBackend::generate_config(module, &values),
+            "CONSTANTS\n    Accounts = Accounts\n    Limit = 3\n    Nobody = Nobody\nINIT Init\nNEXT Next\n"
+        );
+    }
+}
+
+#[cfg(all(test, unix))]
+mod backend_tests {
+    use std::path::{ Path, PathBuf };
+
+    use crate::config::ExternalToolsConfig;
+    use crate::implementations::backends::TlcBackend;
+    use crate::models::common::{ Domain, VerificationLanguage, VerificationSystem };
+    use crate::models::property::{ Property, PropertyKind };
+    use crate::models::specification::{ FormalSpecification, Specification, SpecificationMetadata };
+    use crate::models::verification::{ ArtifactType, VerificationOptions, VerificationStatus };
+    use crate::tests::stub_tools::{ scratch_dir, write_stub_tool };
+    use crate::traits::verification_engine::VerificationBackendAdapter;
+
+    // Stub java running "TLC": reports a violation with a trace for modules mentioning BUGGY
+    const STUB_JAVA: &str = r#"
+if [ "$1" = "-version" ]; then
+  echo 'openjdk version "17.0.2"' >&2
+  exit 0
+fi
+for arg in "$@"; do file="$arg"; done
+echo "TLC2 Version 2.18 of 20 March 2023 (rev: 3ea3222)"
+case "$file" in *.tla) ;; *) echo "Error: Missing input TLA+ module."; exit 1 ;; esac
+echo "$@" > args.txt
+if grep -q "BUGGY" "$file"; then
+  echo "Error: Invariant AxiomInv_bounded is violated."
+  echo "Error: The behavior up to this point is:"
+  echo "State 1: <Initial predicate>"
+  echo "x = 0"
+  echo ""
+  echo "State 2: <Next line 5, col 9 to line 5, col 19 of module Counter>"
+  echo "x = 4"
+  echo ""
+  echo "3 states generated, 2 distinct states found, 0 states left on queue."
+  exit 12
+fi
+echo "Model checking completed. No error has been found."
+echo "4 states generated, 4 distinct states found, 0 states left on queue."
+"#;
+
+    fn stub_backend(name: &str) -> (TlcBackend, PathBuf) {
+        let dir = scratch_dir(name);
+        let java = write_stub_tool(&dir, "java", STUB_JAVA);
+        let jar = dir.join("tla2tools.jar");
+        std::fs::write(&jar, "").unwrap();
+
+        let mut tools = ExternalToolsConfig::default();
+        tools.tool_paths.insert("java".to_string(), java.display().to_string());
+        tools.tool_paths.insert("tla2tools".to_string(), jar.display().to_string());
+
+        let backend = TlcBackend::new(&tools).with_work_dir(dir.join("runs"));
+        (backend, dir)
+    }
+
+    fn run_dir_of(result_log: &str) -> PathBuf {
+        Path::new(result_log).parent().unwrap().to_path_buf()
+    }
+
+    fn counter_spec(body: &str) -> Specification {
+        Specification {
+            id: "counter".to_string(),
+            source_requirements: vec!["The counter never exceeds 3".to_string()],
+            formal_properties: vec![Property {
+                id: "bounded".to_string(),
+                description: "x stays at most 3".to_string(),
+                formal_definition: "x <= 3".to_string(),
+                kind: PropertyKind::Safety,
+            }],
+            formal_spec: FormalSpecification {
+                verification_language: VerificationLanguage::TLAPlus,
+                spec_code: format!(
+                    "---- MODULE Counter ----\nEXTENDS Naturals\nVARIABLE x\nInit == x = 0\n{}\n====\n",
+                    body
+                ),
+                components: std::collections::HashMap::new(),
+                dependencies: vec![],
+                candidates: vec![],
+            },
+            metadata: SpecificationMetadata {
+                created_at: chrono::Utc::now(),
+                verification_system: VerificationSystem::TLA,
+                domain: Domain::DistributedSystems,
+                confidence_score: 0.9,
+                is_formally_validated: false,
+            },
+        }
+    }
+
+    #[test]
+    fn test_backend_availability() {
+        let (backend, _dir) = stub_backend("tlc_version");
+
+        assert!(backend.check_backend_availability().unwrap());
+        assert!(backend.get_backend_version().unwrap().starts_with("TLC2 Version 2.18"));
+
+        let mut tools = ExternalToolsConfig::default();
+        tools.tool_paths.insert("tla2tools".to_string(), "/nonexistent/tla2tools.jar".to_string());
+        assert!(TlcBackend::new(&tools).install_dependencies().is_err());
+    }
+
+    #[test]
+    fn test_model_checking_passes_with_generated_config() {
+        let (backend, _dir) = stub_backend("tlc_passed");
+        let spec = counter_spec("Next == x' = (x + 1) % 4");
+
+        let converted = backend.convert_specification(&spec).unwrap();
+        let result = backend.execute_verification(&converted, "", &VerificationOptions::default()).unwrap();
+
+        assert!(matches!(result.status, VerificationStatus::Verified));
+        assert_eq!(result.resource_usage.states_explored, Some(4));
+
+        let run_dir = run_dir_of(&result.proof_artifacts[0].path);
+        let config = std::fs::read_to_string(run_dir.join("Counter.cfg")).unwrap();
+        assert_eq!(config, "INIT Init\nNEXT Next\nINVARIANT AxiomInv_bounded\n");
+        let module = std::fs::read_to_string(run_dir.join("Counter.tla")).unwrap();
+        assert!(module.contains("AxiomInv_bounded == x <= 3\n\n===="));
+        let args = std::fs::read_to_string(run_dir.join("args.txt")).unwrap();
+        assert!(args.contains("tlc2.TLC"));
+        assert!(args.contains("-config Counter.cfg"));
+    }
+
+    #[test]
+    fn test_temporal_properties_are_checked_as_properties() {
+        let (backend, _dir) = stub_backend("tlc_temporal");
+        let mut spec = counter_spec("Next == x' = (x + 1) % 4\nSpec == Init /\\ [][Next]_x /\\ WF_x(Next)");
+        spec.formal_properties.push(Property {
+            id: "reaches-top".to_string(),
+            description: "x eventually reaches 3".to_string(),
+            formal_definition: "<>(x = 3)".to_string(),
+            kind: PropertyKind::Safety,
+        });
+        spec.formal_properties.push(Property {
+            id: "no_queue".to_string(),
+            description: "No tuple is ever built".to_string(),
+            formal_definition: "x /= <<>>".to_string(),
+            kind: PropertyKind::Liveness,
+        });
+
+        let converted = backend.convert_specification(&spec).unwrap();
+        let result = backend.execute_verification(&converted, "", &VerificationOptions::default()).unwrap();
+
+        let run_dir = run_dir_of(&result.proof_artifacts[0].path);
+        let config = std::fs::read_to_string(run_dir.join("Counter.cfg")).unwrap();
+        assert_eq!(
+            config,
+            "SPECIFICATION Spec\nINVARIANT AxiomInv_bounded\nINVARIANT AxiomInv_no_queue\nPROPERTY AxiomProp_reaches_top\n"
+        );
+    }
+
+    #[test]
+    fn test_violation_produces_trace_artifact() {
+        let (backend, _dir) = stub_backend("tlc_violation");
+        let spec = counter_spec("\\* BUGGY\nNext == x' = x + 4");
+
+        let converted = backend.convert_specification(&spec).unwrap();
+        let result = backend.execute_verification(&converted, "", &VerificationOptions::default()).unwrap();
+
+        match &result.status {
+            VerificationStatus::Failed(reasons) => {
+                assert_eq!(reasons, &vec!["Invariant AxiomInv_bounded is violated.".to_string()]);
+            }
+            other => panic!("Expected failure, got {:?}", other),
+        }
+        assert_eq!(result.counterexamples.len(), 1);
+        assert_eq!(result.counterexamples[0].trace.len(), 2);
+        assert_eq!(result.counterexamples[0].assignments["x"], "4");
+
+        let trace = result.proof_artifacts
+            .iter()
+            .find(|a| matches!(a.artifact_type, ArtifactType::Counterexample))
+            .expect("trace artifact");
+        let json: serde_json::Value = serde_json
+            ::from_str(&std::fs::read_to_string(&trace.path).unwrap())
+            .unwrap();
+        assert_eq!(json["states"][1]["state"]["x"], "4");
+        assert_eq!(json["states"][0]["action"], "Initial predicate");
+    }
+
+    #[test]
+    fn test_implementation_is_checked_against_the_spec_properties() {
+        let dir = scratch_dir("tlc_implementation");
+        let java = write_stub_tool(&dir, "java", STUB_JAVA);
+        let jar = dir.join("tla2tools.jar");
+        std::fs::write(&jar, "").unwrap();
+        let mut tools = ExternalToolsConfig::default();
+        tools.tool_paths.insert("java".to_string(), java.display().to_string());
+        tools.tool_paths.insert("tla2tools".to_string(), jar.display().to_string());
+        tools.model_constants.insert("Max".to_string(), "3".to_string());
+        let backend = TlcBackend::new(&tools).with_work_dir(dir.join("runs"));
+
+        let spec = counter_spec("Next == x' = (x + 1) % 4");
+        let implementation =
+            "---- MODULE CounterImpl ----\nEXTENDS Naturals\nCONSTANT Max\nVARIABLE x\nInit == x = 0\nNext == x' = IF x < Max THEN x + 1 ELSE 0\n====\n";
+
+        let converted = backend.convert_specification(&spec).unwrap();
+        let result = backend
+            .execute_verification(&converted, implementation, &VerificationOptions::default())
+            .unwrap();
+
+        assert!(matches!(result.status, VerificationStatus::Verified));
+        let run_dir = run_dir_of(&result.proof_artifacts[0].path);
+        let check = std::fs::read_to_string(run_dir.join("CounterImplCheck.tla")).unwrap();
+        assert!(check.starts_with("---- MODULE CounterImplCheck ----\nEXTENDS CounterImpl\n"), "{}", check);
+        assert!(check.contains("AxiomInv_bounded == x <= 3\n====\n"), "{}", check);
+        let config = std::fs::read_to_string(run_dir.join("CounterImplCheck.cfg")).unwrap();
+        assert_eq!(config, "CONSTANTS\n    Max = 3\nINIT Init\nNEXT Next\nINVARIANT AxiomInv_bounded\n");
+        let args = std::fs::read_to_string(run_dir.join("args.txt")).unwrap();
+        assert!(args.contains("-config CounterImplCheck.cfg"), "{}", args);
+        assert!(args.trim_end().ends_with("CounterImplCheck.tla"), "{}", args);
+    }
+}
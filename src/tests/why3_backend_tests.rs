@@ -0,0 +1,224 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::implementations::backends::why3::parse_why3_output;
+    use crate::models::verification::GoalOutcome;
+
+    #[test]
+    fn test_parse_classic_layout() {
+        let output =
+            "spec.mlw Incr incr'vc: Valid (0.02s, 15 steps).\n\
+            spec.mlw Incr bounded'vc: Timeout (10.00s).\n\
+            spec.mlw Incr wrong'vc: Invalid (0.10s, 300 steps).\n\
+            spec.mlw Incr odd'vc: Unknown (unknown) (0.05s).";
+
+        let goals = parse_why3_output(output, "z3");
+
+        assert_eq!(goals.len(), 4);
+        assert_eq!(goals[0].name, "Incr.incr'vc");
+        assert_eq!(goals[0].outcome, GoalOutcome::Valid);
+        assert_eq!(goals[0].duration, Duration::from_millis(20));
+        assert_eq!(goals[0].resource_count, Some(15));
+        assert_eq!(goals[0].prover.as_deref(), Some("z3"));
+        assert_eq!(goals[1].outcome, GoalOutcome::Timeout);
+        assert_eq!(goals[1].duration, Duration::from_secs(10));
+        assert_eq!(goals[2].outcome, GoalOutcome::Invalid);
+        assert_eq!(goals[3].outcome, GoalOutcome::Unknown);
+    }
+
+    #[test]
+    fn test_parse_verification_condition_layout() {
+        let output =
+            "File \"spec.mlw\", line 5, characters 6-10:\n\
+            Verification condition incr'vc.\n\
+            Prover result is: Valid (0.01s, 12 steps).\n\
+            \n\
+            File \"spec.mlw\", line 9, characters 6-12:\n\
+            Goal crash'vc.\n\
+            Prover result is: Failure (prover exited with code 2).";
+
+        let goals = parse_why3_output(output, "alt-ergo");
+
+        assert_eq!(goals.len(), 2);
+        assert_eq!(goals[0].name, "incr'vc");
+        assert_eq!(goals[0].resource_count, Some(12));
+        assert_eq!(goals[1].name, "crash'vc");
+        assert!(matches!(goals[1].outcome, GoalOutcome::Error(_)));
+    }
+}
+
+#[cfg(all(test, unix))]
+mod backend_tests {
+    use crate::config::ExternalToolsConfig;
+    use crate::implementations::backends::Why3Backend;
+    use crate::models::common::ResourceLimits;
+    use crate::models::verification::{ GoalOutcome, VerificationOptions, VerificationStatus };
+    use crate::tests::stub_tools::{ scratch_dir, write_stub_tool };
+    use crate::traits::verification_engine::VerificationBackendAdapter;
+
+    // Stub why3: alt-ergo proves `easy` only, z3 proves everything except goals in files
+    // mentioning `FALSE`, which it refutes; syntax errors for files mentioning `SYNTAX`.
+    // Only the goals selected with `-G` are reported, `slow` never finishes, and any other
+    // prover is unknown.
+    const STUB_WHY3: &str = r#"
+if [ "$1" = "--version" ]; then
+  echo "Why3 platform, version 1.7.2"
+  exit 0
+fi
+for arg in "$@"; do
+  case "$arg" in *.mlw) file="$arg" ;; esac
+done
+prover="$3"
+echo "$prover" >> provers.txt
+echo "$*" >> args.txt
+goal() {
+  case " $ARGS " in
+    *" -G "*) case " $ARGS " in *" -G $1 "*) ;; *) return ;; esac ;;
+  esac
+  echo "spec.mlw M $1: $2"
+}
+ARGS="$*"
+if grep -q "SYNTAX" "$file"; then
+  echo "File \"spec.mlw\", line 2, characters 4-6:"
+  echo "syntax error"
+  exit 1
+fi
+if [ "$prover" = "alt-ergo" ]; then
+  goal "easy'vc" "Valid (0.01s, 5 steps)."
+  goal "hard'vc" "Timeout (10.00s)."
+elif [ "$prover" = "slow" ]; then
+  sleep 10
+elif [ "$prover" != "z3" ]; then
+  echo "Prover '$prover' not installed or not configured"
+  exit 1
+elif grep -q "FALSE" "$file"; then
+  goal "easy'vc" "Valid (0.01s, 7 steps)."
+  goal "hard'vc" "Invalid (0.20s, 80 steps)."
+else
+  goal "easy'vc" "Valid (0.01s, 7 steps)."
+  goal "hard'vc" "Valid (1.50s, 4000 steps)."
+fi
+"#;
+
+    fn stub_backend(name: &str) -> (Why3Backend, std::path::PathBuf) {
+        let dir = scratch_dir(name);
+        let why3 = write_stub_tool(&dir, "why3", STUB_WHY3);
+
+        let mut tools = ExternalToolsConfig::default();
+        tools.tool_paths.insert("why3".to_string(), why3.display().to_string());
+        tools.tool_paths.insert("why3_provers".to_string(), "alt-ergo, z3".to_string());
+
+        let backend = Why3Backend::new(&tools).with_work_dir(dir.join("runs"));
+        (backend, dir)
+    }
+
+    fn run_file(result_log: &str, name: &str) -> String {
+        let run_dir = std::path::Path::new(result_log).parent().unwrap();
+        std::fs::read_to_string(run_dir.join(name)).unwrap()
+    }
+
+    fn provers_run(result_log: &str) -> String {
+        run_file(result_log, "provers.txt")
+    }
+
+    #[test]
+    fn test_unproven_goals_are_retried_with_next_prover() {
+        let (backend, _dir) = stub_backend("why3_retry");
+
+        let result = backend
+            .execute_verification("module M\n  goal easy: true\n  goal hard: true\nend", "", &VerificationOptions::default())
+            .unwrap();
+
+        assert!(matches!(result.status, VerificationStatus::Verified));
+        assert_eq!(result.resource_usage.lemmas_proven, 2);
+        assert_eq!(result.goal_results[0].prover.as_deref(), Some("alt-ergo"));
+        assert_eq!(result.goal_results[1].prover.as_deref(), Some("z3"));
+        assert_eq!(result.goal_results[1].resource_count, Some(4000));
+        assert_eq!(provers_run(&result.proof_artifacts[0].path), "alt-ergo\nz3\n");
+        let retry = run_file(&result.proof_artifacts[0].path, "args.txt").lines().nth(1).unwrap().to_string();
+        assert!(retry.ends_with("spec.mlw -T M -G hard'vc"), "{}", retry);
+    }
+
+    #[test]
+    fn test_prover_that_cannot_run_is_skipped() {
+        let (backend, _dir) = stub_backend("why3_unknown_prover");
+        let options = |provers: &[&str]| VerificationOptions {
+            resource_limits: ResourceLimits {
+                provers: provers.iter().map(|p| p.to_string()).collect(),
+                ..ResourceLimits::default()
+            },
+            ..VerificationOptions::default()
+        };
+        let module = "module M\n  goal easy: true\n  goal hard: true\nend";
+
+        let result = backend.execute_verification(module, "", &options(&["cvc5", "z3"])).unwrap();
+        assert!(matches!(result.status, VerificationStatus::Verified), "{:?}", result.status);
+        assert_eq!(provers_run(&result.proof_artifacts[0].path), "cvc5\nz3\n");
+
+        // A tooling problem, not a refuted goal
+        let result = backend.execute_verification(module, "", &options(&["cvc5"])).unwrap();
+        assert_eq!(result.status, VerificationStatus::Error("why3 prove -P cvc5 exited with code Some(1)".to_string()));
+    }
+
+    #[test]
+    fn test_provers_share_the_timeout() {
+        let (backend, _dir) = stub_backend("why3_deadline");
+        let options = VerificationOptions {
+            timeout: std::time::Duration::from_millis(500),
+            resource_limits: ResourceLimits {
+                provers: vec!["slow".to_string(), "z3".to_string()],
+                ..ResourceLimits::default()
+            },
+            ..VerificationOptions::default()
+        };
+
+        let started = std::time::Instant::now();
+        let result = backend.execute_verification("module M\n  goal easy: true\nend", "", &options).unwrap();
+
+        assert_eq!(result.status, VerificationStatus::Timeout);
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+        assert_eq!(provers_run(&result.proof_artifacts[0].path), "slow\n");
+    }
+
+    #[test]
+    fn test_provers_from_resource_limits_take_precedence() {
+        let (backend, _dir) = stub_backend("why3_options");
+        let options = VerificationOptions {
+            resource_limits: ResourceLimits {
+                provers: vec!["alt-ergo".to_string()],
+                ..ResourceLimits::default()
+            },
+            ..VerificationOptions::default()
+        };
+
+        let result = backend.execute_verification("module M end", "", &options).unwrap();
+
+        assert!(matches!(result.status, VerificationStatus::Timeout));
+        assert_eq!(result.goal_results[1].outcome, GoalOutcome::Timeout);
+        assert_eq!(provers_run(&result.proof_artifacts[0].path), "alt-ergo\n");
+    }
+
+    #[test]
+    fn test_invalid_goal_fails_and_syntax_error_is_reported() {
+        let (backend, _dir) = stub_backend("why3_invalid");
+
+        let refuted = backend
+            .execute_verification("module M\n  goal hard: FALSE\nend", "", &VerificationOptions::default())
+            .unwrap();
+        match refuted.status {
+            VerificationStatus::Failed(reasons) => assert_eq!(reasons, vec!["M.hard'vc: Invalid (z3)".to_string()]),
+            other => panic!("Expected failure, got {:?}", other),
+        }
+
+        let broken = backend
+            .execute_verification("module M\n  SYNTAX\nend", "", &VerificationOptions::default())
+            .unwrap();
+        match broken.status {
+            VerificationStatus::Failed(reasons) => {
+                assert_eq!(reasons, vec!["File \"spec.mlw\", line 2, characters 4-6: syntax error".to_string()]);
+            }
+            other => panic!("Expected failure, got {:?}", other),
+        }
+    }
+}
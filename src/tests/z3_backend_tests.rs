@@ -0,0 +1,194 @@
+#[cfg(all(test, unix))]
+mod tests {
+    use std::path::{ Path, PathBuf };
+
+    use crate::config::ExternalToolsConfig;
+    use crate::implementations::backends::Z3Backend;
+    use crate::models::common::Language;
+    use crate::models::implementation::Implementation;
+    use crate::models::property::{ Property, PropertyKind };
+    use crate::models::verification::{
+        ArtifactType,
+        VerificationOptions,
+        VerificationResult,
+        VerificationStatus,
+    };
+    use crate::tests::stub_tools::{ scratch_dir, write_stub_tool };
+    use crate::traits::verification_engine::{ VerificationBackendAdapter, VerificationEngine };
+
+    // Stub z3: answers `sat` with a model for queries mentioning `(> x 5)`,
+    // `unknown` for `UNKNOWN`, and `unsat` otherwise
+    const STUB_Z3: &str = r#"
+if [ "$1" = "--version" ]; then
+  echo "Z3 version 4.13.0 - 64 bit"
+  exit 0
+fi
+for arg in "$@"; do file="$arg"; done
+echo "$@" > args.txt
+label=$(sed -n 's/.*(echo "\(.*\)").*/\1/p' "$file")
+[ -n "$label" ] && echo "$label"
+if grep -q "(> x 5)" "$file"; then
+  echo "sat"
+  echo "("
+  echo "  (define-fun x () Int"
+  echo "    (- 7))"
+  echo "  (define-fun y () Bool"
+  echo "    true)"
+  echo ")"
+elif grep -q "UNKNOWN" "$file"; then
+  echo "unknown"
+  echo '(error "line 3 column 10: model is not available")'
+else
+  echo "unsat"
+  echo '(error "line 3 column 10: model is not available")'
+  exit 1
+fi
+"#;
+
+    fn stub_backend(name: &str) -> (Z3Backend, PathBuf) {
+        let dir = scratch_dir(name);
+        let z3 = write_stub_tool(&dir, "z3", STUB_Z3);
+
+        let mut tools = ExternalToolsConfig::default();
+        tools.tool_paths.insert("z3".to_string(), z3.display().to_string());
+
+        let backend = Z3Backend::new(&tools).with_work_dir(dir.join("runs"));
+        (backend, dir)
+    }
+
+    fn run_dir_of(result_log: &str) -> PathBuf {
+        Path::new(result_log).parent().unwrap().to_path_buf()
+    }
+
+    fn smt_implementation(code: &str) -> Implementation {
+        Implementation {
+            id: "abs".to_string(),
+            specification_id: "abs_spec".to_string(),
+            language: Language::Custom("smt2".to_string()),
+            source_code: code.to_string(),
+            verification_result: VerificationResult {
+                status: VerificationStatus::Unverified,
+                proof_artifacts: vec![],
+                verification_time: std::time::Duration::ZERO,
+                resource_usage: Default::default(),
+                goal_results: vec![],
+                counterexamples: vec![],
+            },
+        }
+    }
+
+    #[test]
+    fn test_unsat_query_is_verified() {
+        let (backend, _dir) = stub_backend("z3_unsat");
+
+        let result = backend
+            .execute_verification(
+                "(declare-const x Int)\n(assert (< x 0))\n(assert (> x 0))",
+                "",
+                &VerificationOptions::default()
+            )
+            .unwrap();
+
+        assert!(matches!(result.status, VerificationStatus::Verified));
+        assert_eq!(result.resource_usage.lemmas_proven, 1);
+        assert!(result.counterexamples.is_empty());
+
+        let log = &result.proof_artifacts[0];
+        let query = std::fs::read_to_string(run_dir_of(&log.path).join("query.smt2")).unwrap();
+        assert!(query.trim_end().ends_with("(check-sat)\n(get-model)"));
+
+        let args = std::fs::read_to_string(run_dir_of(&log.path).join("args.txt")).unwrap();
+        assert!(args.starts_with("-smt2 -T:300 -memory:1024"));
+    }
+
+    #[test]
+    fn test_sat_query_produces_counterexample() {
+        let (backend, _dir) = stub_backend("z3_sat");
+
+        let result = backend
+            .execute_verification(
+                "(declare-const x Int)\n(declare-const y Bool)\n(assert (> x 5))",
+                "",
+                &VerificationOptions::default()
+            )
+            .unwrap();
+
+        match &result.status {
+            VerificationStatus::Failed(reasons) => {
+                assert_eq!(reasons, &vec!["Counterexample for check-sat #1: x = -7, y = true".to_string()]);
+            }
+            other => panic!("Expected failure, got {:?}", other),
+        }
+        assert_eq!(result.counterexamples[0].assignments["x"], "-7");
+
+        let witness = result.proof_artifacts
+            .iter()
+            .find(|a| matches!(a.artifact_type, ArtifactType::Counterexample))
+            .expect("counterexample artifact");
+        let json: serde_json::Value = serde_json
+            ::from_str(&std::fs::read_to_string(&witness.path).unwrap())
+            .unwrap();
+        assert_eq!(json["y"], "true");
+
+        assert_eq!(backend.extract_counterexamples(&result).unwrap(), vec![
+            "check-sat #1: x = -7, y = true".to_string()
+        ]);
+    }
+
+    #[test]
+    fn test_unknown_query_is_unverified() {
+        let (backend, _dir) = stub_backend("z3_unknown");
+
+        let result = backend
+            .execute_verification("; UNKNOWN\n(assert true)", "", &VerificationOptions::default())
+            .unwrap();
+
+        assert!(matches!(result.status, VerificationStatus::Unverified));
+    }
+
+    #[test]
+    fn test_verify_property_uses_negation() {
+        let (backend, _dir) = stub_backend("z3_property");
+        let implementation = smt_implementation("(declare-const x Int)\n(declare-const y Bool)");
+        let property = |id: &str, definition: &str| Property {
+            id: id.to_string(),
+            description: String::new(),
+            formal_definition: definition.to_string(),
+            kind: PropertyKind::Safety,
+        };
+
+        assert!(
+            backend
+                .verify_property(&implementation, &property("tautology", "(= x x)"), &VerificationOptions::default())
+                .unwrap()
+        );
+        assert!(
+            !backend
+                .verify_property(&implementation, &property("small_x", "(not (> x 5))"), &VerificationOptions::default())
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_verify_property_drops_implementation_queries() {
+        let (backend, dir) = stub_backend("z3_property_queries");
+        let implementation = smt_implementation(
+            "(declare-const x Int)\n(assert (>= x 0))\n(echo \"impl\")\n(check-sat)\n(get-model)\n(exit)"
+        );
+        let property = Property {
+            id: "non_negative".to_string(),
+            description: String::new(),
+            formal_definition: "(>= x 0)".to_string(),
+            kind: PropertyKind::Safety,
+        };
+
+        assert!(backend.verify_property(&implementation, &property, &VerificationOptions::default()).unwrap());
+
+        let run = std::fs::read_dir(dir.join("runs")).unwrap().next().unwrap().unwrap().path();
+        let query = std::fs::read_to_string(run.join("query.smt2")).unwrap();
+        assert!(query.contains("(assert (>= x 0))"));
+        assert_eq!(query.matches("(check-sat)").count(), 1);
+        assert!(!query.contains("(exit)"));
+        assert!(!query.contains("\"impl\""));
+    }
+}
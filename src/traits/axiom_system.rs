@@ -20,11 +20,11 @@ pub trait AxiomSystem {
     ) -> AxiomResult<VerifiedArtifact>;
 
     // Method to check if a specification completely covers the requirements
-    async fn verify_specification_completeness(
+    fn verify_specification_completeness(
         &self,
         spec: &crate::models::specification::Specification,
         requirements: &[String]
-    ) -> crate::errors::AxiomResult<(bool, Vec<String>)>;
+    ) -> impl std::future::Future<Output = crate::errors::AxiomResult<(bool, Vec<String>)>> + Send;
 
     /// Verify an existing implementation against requirements
     fn verify_existing_implementation(
@@ -1,3 +1,5 @@
+use async_trait::async_trait;
+
 use crate::errors::AxiomResult;
 use crate::models::common::Language;
 use crate::models::implementation::{Implementation, ImplementationOptions};
@@ -5,22 +7,24 @@ use crate::models::specification::Specification;
 use crate::models::verification::VerificationResult;
 
 /// Trait for generating implementations from specifications
+#[async_trait]
 pub trait ImplementationGenerator {
     /// Generate an implementation from a specification in the target language
-    fn generate_implementation(
-        &self, 
-        spec: &Specification, 
+    async fn generate_implementation(
+        &self,
+        spec: &Specification,
         language: Language,
         options: &ImplementationOptions,
     ) -> AxiomResult<Implementation>;
-    
-    /// Refine an implementation based on verification feedback
-    fn refine_implementation(
+
+    /// Refine an implementation of `spec` based on verification feedback
+    async fn refine_implementation(
         &self,
         implementation: &Implementation,
+        spec: &Specification,
         verification_result: &VerificationResult,
     ) -> AxiomResult<Implementation>;
-    
+
     /// Check if the implementation matches the specification before formal verification
-    fn validate_implementation(&self, implementation: &Implementation, spec: &Specification) -> AxiomResult<bool>;
-}
\ No newline at end of file
+    async fn validate_implementation(&self, implementation: &Implementation, spec: &Specification) -> AxiomResult<bool>;
+}
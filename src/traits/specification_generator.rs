@@ -16,6 +16,9 @@ use crate::models::specification::{
 #[async_trait]
 pub trait SpecificationGenerator {
     /// Generate a formal specification from natural language requirements
+    ///
+    /// With `options.candidates` above one, several specifications are drawn and checked,
+    /// and the one with the fewest errors is returned.
     async fn generate_specification(
         &self, 
         requirements: &[String], 
@@ -94,6 +94,10 @@ pub trait VerificationBackendAdapter {
     /// Convert an Axiom implementation to the format required by the backend
     fn convert_implementation(&self, implementation: &Implementation) -> AxiomResult<String>;
     
+    /// Check if implementations in the language can be checked against a specification;
+    /// `convert_implementation` rejects all others
+    fn supports_implementation_language(&self, language: &Language) -> bool;
+    
     /// Execute the verification backend and interpret the results
     fn execute_verification(
         &self, 